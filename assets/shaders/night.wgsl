@@ -0,0 +1,23 @@
+// Night mode: darkens the court except for soft pools of light.
+// Each light is (x, y, radius, unused) in world space.
+
+#import bevy_sprite::mesh2d_vertex_output::VertexOutput
+
+struct NightSettings {
+    lights: array<vec4<f32>, 6>,
+    light_count: u32,
+    darkness: f32,
+};
+
+@group(2) @binding(0) var<uniform> settings: NightSettings;
+
+@fragment
+fn fragment(mesh: VertexOutput) -> @location(0) vec4<f32> {
+    var light = 0.0;
+    for (var i = 0u; i < settings.light_count; i++) {
+        let source = settings.lights[i];
+        let distance = length(mesh.world_position.xy - source.xy);
+        light = max(light, 1.0 - smoothstep(source.z * 0.5, source.z, distance));
+    }
+    return vec4<f32>(0.0, 0.0, 0.0, settings.darkness * (1.0 - light));
+}
@@ -0,0 +1,58 @@
+//! Fixed-tick benchmarks for crowded courts.
+//!
+//! Each iteration advances a headless stress scene by one fixed tick:
+//! collision response for every ball, the speed clamp, and the wall bounces
+//! fanned out on the event bus. Run with `cargo bench`, and compare against
+//! a baseline with `cargo bench -- --save-baseline <name>` before a change
+//! and `--baseline <name>` after.
+
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use paddlegeddon::stress::{StressScene, headless_app};
+
+/// Ticks to run before timing, so the balls have spread out and are bouncing
+const WARM_UP_TICKS: usize = 32;
+
+fn step(scene: StressScene) -> impl FnMut() {
+    let mut app = headless_app(scene);
+    for _ in 0..WARM_UP_TICKS {
+        app.update();
+    }
+    move || app.update()
+}
+
+fn many_balls(c: &mut Criterion) {
+    let mut group = c.benchmark_group("many_balls");
+    for balls in [50, 200, 500] {
+        let scene = StressScene {
+            balls,
+            ..StressScene::default()
+        };
+        group.bench_with_input(BenchmarkId::from_parameter(balls), &scene, |b, &scene| {
+            let mut tick = step(scene);
+            b.iter(&mut tick);
+        });
+    }
+    group.finish();
+}
+
+fn many_obstacles(c: &mut Criterion) {
+    let mut group = c.benchmark_group("many_obstacles");
+    for obstacles in [0, 40, 160] {
+        let scene = StressScene {
+            obstacles,
+            ..StressScene::default()
+        };
+        group.bench_with_input(
+            BenchmarkId::from_parameter(obstacles),
+            &scene,
+            |b, &scene| {
+                let mut tick = step(scene);
+                b.iter(&mut tick);
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, many_balls, many_obstacles);
+criterion_main!(benches);
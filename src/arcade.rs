@@ -0,0 +1,768 @@
+//! Arcade mode, for cabinets and show floors.
+//!
+//! An attract demo of two CPU paddles plays until someone "inserts a coin"
+//! by pressing anything. They then take on CPU opponents one stage after
+//! another, each a little sharper, under fixed rules. Losing a match offers
+//! a continue; when the run ends, a score good enough for the high-score
+//! table gets the player's initials.
+//!
+//! Start it from the modes menu, or boot straight into it by setting
+//! `PADDLEGEDDON_ARCADE`. Quitting to the title from the pause menu leaves
+//! it.
+
+use bevy::{prelude::*, ui::Val::*};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    asset_tracking::ResourceHandles,
+    game::{
+        GamePhase,
+        cpu::{CpuPlayer, ServeStyle},
+        mutators::Mutators,
+        player::{MirroredCourt, Player},
+        rules::MatchRules,
+        scoring::Score,
+    },
+    screens::Screen,
+    storage,
+    theme::widget,
+};
+
+/// Set to boot into arcade mode instead of the title menu
+const ARCADE_ENV: &str = "PADDLEGEDDON_ARCADE";
+const HIGH_SCORES_KEY: &str = "arcade_high_scores";
+const HIGH_SCORE_ENTRIES: usize = 10;
+pub const INITIALS_LENGTH: usize = 3;
+
+// Rules and opponents
+const ARCADE_TARGET_SCORE: u32 = 5;
+const DEMO_SKILL: f32 = 0.8;
+const FIRST_STAGE_SKILL: f32 = 0.2;
+const SKILL_PER_STAGE: f32 = 0.15;
+/// Bonus for clearing a stage, times the stage number
+const STAGE_BONUS: u32 = 10;
+
+// Timings, in seconds
+const RESULT_DURATION: f32 = 3.0;
+const DEMO_RESTART_DELAY: f32 = 4.0;
+const CONTINUE_SECONDS: f32 = 10.0;
+const INITIALS_TIMEOUT: f32 = 30.0;
+const BLINK_PERIOD: f32 = 1.0;
+
+// Overlays
+const TITLE_FONT_SIZE: f32 = 48.0;
+const TEXT_FONT_SIZE: f32 = 24.0;
+const INITIAL_FONT_SIZE: f32 = 64.0;
+const OVERLAY_BACKGROUND: Color = Color::srgba(0.0, 0.0, 0.0, 0.6);
+const HIGHLIGHT_COLOR: Color = Color::srgb(1.0, 0.85, 0.3);
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_state::<Arcade>();
+    app.register_type::<ArcadeRun>();
+    app.register_type::<ContinueCountdown>();
+    app.register_type::<InitialsEntry>();
+    app.register_type::<Blink>();
+    app.register_type::<ArcadeResult>();
+    app.register_type::<ContinueLabel>();
+    app.register_type::<InitialSlot>();
+    app.init_resource::<ArcadeRun>();
+    app.insert_resource(ArcadeHighScores::load());
+
+    app.add_systems(OnEnter(Screen::Title), (leave_arcade, boot_into_arcade));
+    app.add_systems(
+        Update,
+        assign_cpu_players.run_if(in_state(Screen::Gameplay).and(not(in_state(Arcade::Off)))),
+    );
+
+    // Attract demo
+    app.add_systems(OnEnter(Arcade::Attract), spawn_attract_overlay);
+    app.add_systems(
+        Update,
+        (
+            blink,
+            insert_coin.run_if(any_button_just_pressed),
+            restart_demo.run_if(in_state(GamePhase::GameOver)),
+        )
+            .run_if(in_state(Arcade::Attract)),
+    );
+
+    // A run
+    app.add_systems(
+        OnEnter(GamePhase::GameOver),
+        finish_match.run_if(in_state(Arcade::Playing)),
+    );
+    app.add_systems(
+        Update,
+        after_match.run_if(in_state(Arcade::Playing).and(in_state(GamePhase::GameOver))),
+    );
+
+    // Continue?
+    app.add_systems(OnEnter(Arcade::Continue), spawn_continue_overlay);
+    app.add_systems(
+        Update,
+        (
+            count_down_continue,
+            continue_run.run_if(any_button_just_pressed),
+        )
+            .chain()
+            .run_if(in_state(Arcade::Continue)),
+    );
+
+    // High score initials
+    app.add_systems(OnEnter(Arcade::Initials), spawn_initials_overlay);
+    app.add_systems(
+        Update,
+        (
+            (type_initials, pick_initials_with_gamepad),
+            save_initials,
+            update_initial_slots,
+        )
+            .chain()
+            .run_if(in_state(Arcade::Initials)),
+    );
+}
+
+/// Where the arcade flow is, layered over the regular screens
+#[derive(States, Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+#[states(scoped_entities)]
+pub enum Arcade {
+    /// Not in arcade mode
+    #[default]
+    Off,
+    /// CPU paddles playing each other until a coin goes in
+    Attract,
+    /// A player's run against the CPU
+    Playing,
+    /// The player lost a match and can carry on
+    Continue,
+    /// Entering initials for a new high score
+    Initials,
+}
+
+/// The player's own settings, put back when arcade mode ends
+#[derive(Resource, Debug)]
+pub struct ArcadeSession {
+    previous_rules: MatchRules,
+    previous_mutators: Mutators,
+}
+
+impl ArcadeSession {
+    /// Swaps in arcade mode's fixed rules. Call before entering the attract
+    /// demo.
+    pub fn start(rules: &mut MatchRules, mutators: &mut Mutators) -> Self {
+        let arcade_rules = MatchRules {
+            target_score: ARCADE_TARGET_SCORE,
+            undo_limit: 0,
+            ..default()
+        };
+        Self {
+            previous_rules: std::mem::replace(rules, arcade_rules),
+            previous_mutators: std::mem::take(mutators),
+        }
+    }
+}
+
+/// The current run
+#[derive(Resource, Debug, Clone, Default, Reflect)]
+#[reflect(Resource)]
+pub struct ArcadeRun {
+    /// Starting from 1
+    pub stage: u32,
+    pub score: u32,
+    pub continues: u32,
+    /// Whether the last match was won
+    won_last: bool,
+}
+
+impl ArcadeRun {
+    fn new() -> Self {
+        Self {
+            stage: 1,
+            ..default()
+        }
+    }
+
+    /// How sharp this stage's opponent is
+    fn opponent_skill(&self) -> f32 {
+        FIRST_STAGE_SKILL + SKILL_PER_STAGE * self.stage.saturating_sub(1) as f32
+    }
+
+    /// How this stage's opponent serves, taking each style in turn
+    fn opponent_serve_style(&self) -> ServeStyle {
+        let index = self.stage.saturating_sub(1) as usize % ServeStyle::ALL.len();
+        ServeStyle::ALL[index]
+    }
+}
+
+/// The best arcade runs on this machine
+#[derive(Resource, Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ArcadeHighScores {
+    /// Best first
+    pub entries: Vec<HighScore>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HighScore {
+    pub initials: String,
+    pub score: u32,
+}
+
+impl ArcadeHighScores {
+    fn load() -> Self {
+        storage::load(HIGH_SCORES_KEY).unwrap_or_default()
+    }
+
+    fn save(&self) {
+        if let Err(error) = storage::save(HIGH_SCORES_KEY, self) {
+            warn!("Couldn't save arcade high scores: {error}");
+        }
+    }
+
+    /// Whether `score` would make the table
+    pub fn qualifies(&self, score: u32) -> bool {
+        score > 0
+            && (self.entries.len() < HIGH_SCORE_ENTRIES
+                || self.entries.last().is_some_and(|last| score > last.score))
+    }
+
+    /// Adds a score in its place and saves the table
+    pub fn insert(&mut self, initials: String, score: u32) {
+        let index = self.entries.partition_point(|entry| entry.score >= score);
+        self.entries.insert(index, HighScore { initials, score });
+        self.entries.truncate(HIGH_SCORE_ENTRIES);
+        self.save();
+    }
+}
+
+/// Flashes on and off, like an "INSERT COIN" prompt
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct Blink;
+
+/// How the last match went, shown until the run moves on
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct ArcadeResult;
+
+/// Counts down until the continue offer runs out
+#[derive(Resource, Debug, Default, Reflect)]
+#[reflect(Resource)]
+struct ContinueCountdown(Timer);
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct ContinueLabel;
+
+/// Initials being entered for a new high score
+#[derive(Resource, Debug, Reflect)]
+#[reflect(Resource)]
+pub struct InitialsEntry {
+    pub letters: [char; INITIALS_LENGTH],
+    /// The letter being changed
+    pub cursor: usize,
+    /// Set once the player is happy with their initials
+    pub confirmed: bool,
+    /// Saves whatever was entered if the player walks away
+    timeout: Timer,
+}
+
+impl Default for InitialsEntry {
+    fn default() -> Self {
+        Self {
+            letters: ['A'; INITIALS_LENGTH],
+            cursor: 0,
+            confirmed: false,
+            timeout: Timer::from_seconds(INITIALS_TIMEOUT, TimerMode::Once),
+        }
+    }
+}
+
+impl InitialsEntry {
+    /// Steps the current letter through the alphabet by `step`, wrapping
+    pub fn cycle(&mut self, step: i8) {
+        let letter = &mut self.letters[self.cursor];
+        let index = (*letter as u8 - b'A') as i8;
+        *letter = (b'A' + (index + step).rem_euclid(26) as u8) as char;
+    }
+
+    /// Sets the current letter and moves on
+    pub fn type_letter(&mut self, letter: char) {
+        self.letters[self.cursor] = letter.to_ascii_uppercase();
+        self.advance();
+    }
+
+    /// Moves on to the next letter, confirming after the last one
+    pub fn advance(&mut self) {
+        if self.cursor + 1 >= INITIALS_LENGTH {
+            self.confirmed = true;
+        } else {
+            self.cursor += 1;
+        }
+    }
+
+    pub fn back(&mut self) {
+        self.cursor = self.cursor.saturating_sub(1);
+    }
+
+    pub fn initials(&self) -> String {
+        self.letters.iter().collect()
+    }
+}
+
+/// One letter of the initials being entered
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct InitialSlot(usize);
+
+/// Any key (bar Escape, which pauses), mouse button, gamepad button or touch
+fn any_button_just_pressed(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    touches: Res<Touches>,
+    gamepads: Query<&Gamepad>,
+) -> bool {
+    keyboard
+        .get_just_pressed()
+        .any(|key| *key != KeyCode::Escape)
+        || mouse.get_just_pressed().next().is_some()
+        || touches.any_just_pressed()
+        || gamepads
+            .iter()
+            .any(|gamepad| gamepad.get_just_pressed().next().is_some())
+}
+
+/// Starts arcade mode from the attract demo
+pub fn enter_arcade(
+    commands: &mut Commands,
+    rules: &mut MatchRules,
+    mutators: &mut Mutators,
+    resource_handles: &ResourceHandles,
+    next_arcade: &mut NextState<Arcade>,
+    next_screen: &mut NextState<Screen>,
+) {
+    commands.insert_resource(ArcadeSession::start(rules, mutators));
+    next_arcade.set(Arcade::Attract);
+    next_screen.set(if resource_handles.is_all_done() {
+        Screen::Gameplay
+    } else {
+        Screen::Loading
+    });
+}
+
+fn boot_into_arcade(
+    mut commands: Commands,
+    mut booted: Local<bool>,
+    mut rules: ResMut<MatchRules>,
+    mut mutators: ResMut<Mutators>,
+    resource_handles: Res<ResourceHandles>,
+    mut next_arcade: ResMut<NextState<Arcade>>,
+    mut next_screen: ResMut<NextState<Screen>>,
+) {
+    if *booted || std::env::var_os(ARCADE_ENV).is_none() {
+        return;
+    }
+    *booted = true;
+    enter_arcade(
+        &mut commands,
+        &mut rules,
+        &mut mutators,
+        &resource_handles,
+        &mut next_arcade,
+        &mut next_screen,
+    );
+}
+
+/// Quitting to the title ends arcade mode and restores the player's settings
+fn leave_arcade(
+    mut commands: Commands,
+    session: Option<Res<ArcadeSession>>,
+    mut rules: ResMut<MatchRules>,
+    mut mutators: ResMut<Mutators>,
+    mut next_arcade: ResMut<NextState<Arcade>>,
+) {
+    let Some(session) = session else {
+        return;
+    };
+    *rules = session.previous_rules.clone();
+    *mutators = session.previous_mutators.clone();
+    commands.remove_resource::<ArcadeSession>();
+    next_arcade.set(Arcade::Off);
+}
+
+/// Hands both paddles to the CPU in the demo, and the opponent's during a run
+fn assign_cpu_players(
+    mut commands: Commands,
+    arcade: Res<State<Arcade>>,
+    run: Res<ArcadeRun>,
+    mirrored: Res<MirroredCourt>,
+    paddles: Query<(Entity, &Player), Added<Player>>,
+) {
+    for (entity, player) in &paddles {
+        let cpu = match arcade.get() {
+            Arcade::Attract => CpuPlayer::new(DEMO_SKILL),
+            _ if player.takes_keyboard_input(&mirrored) => continue,
+            _ => CpuPlayer::new(run.opponent_skill()).with_serve_style(run.opponent_serve_style()),
+        };
+        commands.entity(entity).insert(cpu);
+    }
+}
+
+fn spawn_attract_overlay(mut commands: Commands, high_scores: Res<ArcadeHighScores>) {
+    let table = if high_scores.entries.is_empty() {
+        "No high scores yet".to_string()
+    } else {
+        high_scores
+            .entries
+            .iter()
+            .enumerate()
+            .map(|(rank, entry)| format!("{:>2}. {}  {:>5}", rank + 1, entry.initials, entry.score))
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    commands.spawn((
+        overlay("Attract Overlay"),
+        StateScoped(Arcade::Attract),
+        children![
+            overlay_text("PADDLEGEDDON", TITLE_FONT_SIZE),
+            (overlay_text("INSERT COIN", TEXT_FONT_SIZE), Blink),
+            overlay_text("press any button", TEXT_FONT_SIZE),
+            overlay_text(table, TEXT_FONT_SIZE),
+        ],
+    ));
+}
+
+fn blink(time: Res<Time<Real>>, mut prompts: Query<&mut Visibility, With<Blink>>) {
+    let visible = time.elapsed_secs() % BLINK_PERIOD < BLINK_PERIOD / 2.0;
+    for mut visibility in &mut prompts {
+        *visibility = if visible {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+    }
+}
+
+fn insert_coin(
+    mut run: ResMut<ArcadeRun>,
+    mut next_arcade: ResMut<NextState<Arcade>>,
+    mut next_screen: ResMut<NextState<Screen>>,
+) {
+    *run = ArcadeRun::new();
+    next_arcade.set(Arcade::Playing);
+    next_screen.set(Screen::Gameplay);
+}
+
+/// Starts another demo match a little after the last one ends
+fn restart_demo(
+    time: Res<Time<Real>>,
+    mut waited: Local<f32>,
+    mut next_screen: ResMut<NextState<Screen>>,
+) {
+    *waited += time.delta_secs();
+    if *waited >= DEMO_RESTART_DELAY {
+        *waited = 0.0;
+        next_screen.set(Screen::Gameplay);
+    }
+}
+
+/// Scores the match for the run and shows how it went
+fn finish_match(
+    mut commands: Commands,
+    score: Res<Score>,
+    rules: Res<MatchRules>,
+    mirrored: Res<MirroredCourt>,
+    mut run: ResMut<ArcadeRun>,
+) {
+    let home = mirrored.home_side();
+    run.score += score.get(home);
+    run.won_last = score.winner(&rules) == Some(home);
+    let headline = if run.won_last {
+        run.score += STAGE_BONUS * run.stage;
+        format!("STAGE {} CLEAR", run.stage)
+    } else {
+        "GAME OVER".to_string()
+    };
+
+    commands.spawn((
+        overlay("Arcade Result"),
+        ArcadeResult,
+        StateScoped(GamePhase::GameOver),
+        children![
+            overlay_text(headline, TITLE_FONT_SIZE),
+            overlay_text(format!("SCORE {}", run.score), TEXT_FONT_SIZE),
+        ],
+    ));
+}
+
+/// On to the next stage after a win, or to the continue offer after a loss
+fn after_match(
+    time: Res<Time<Real>>,
+    mut waited: Local<f32>,
+    mut run: ResMut<ArcadeRun>,
+    mut next_arcade: ResMut<NextState<Arcade>>,
+    mut next_screen: ResMut<NextState<Screen>>,
+) {
+    *waited += time.delta_secs();
+    if *waited < RESULT_DURATION {
+        return;
+    }
+    *waited = 0.0;
+    if run.won_last {
+        run.stage += 1;
+        next_screen.set(Screen::Gameplay);
+    } else {
+        next_arcade.set(Arcade::Continue);
+    }
+}
+
+fn spawn_continue_overlay(mut commands: Commands, results: Query<Entity, With<ArcadeResult>>) {
+    for result in &results {
+        commands.entity(result).despawn();
+    }
+    commands.insert_resource(ContinueCountdown(Timer::from_seconds(
+        CONTINUE_SECONDS,
+        TimerMode::Once,
+    )));
+    commands.spawn((
+        overlay("Continue Overlay"),
+        StateScoped(Arcade::Continue),
+        children![
+            overlay_text("CONTINUE?", TITLE_FONT_SIZE),
+            (overlay_text("", TITLE_FONT_SIZE), ContinueLabel),
+            (overlay_text("press any button", TEXT_FONT_SIZE), Blink),
+        ],
+    ));
+}
+
+/// Ends the run once the offer runs out, onto the high-score table if the
+/// score made it
+fn count_down_continue(
+    time: Res<Time<Real>>,
+    mut countdown: ResMut<ContinueCountdown>,
+    mut label: Single<&mut Text, With<ContinueLabel>>,
+    run: Res<ArcadeRun>,
+    high_scores: Res<ArcadeHighScores>,
+    mut next_arcade: ResMut<NextState<Arcade>>,
+    mut next_screen: ResMut<NextState<Screen>>,
+) {
+    countdown.0.tick(time.delta());
+    label.0 = (countdown.0.remaining_secs().ceil() as u32).to_string();
+    if !countdown.0.just_finished() {
+        return;
+    }
+    if high_scores.qualifies(run.score) {
+        next_arcade.set(Arcade::Initials);
+    } else {
+        next_arcade.set(Arcade::Attract);
+        next_screen.set(Screen::Gameplay);
+    }
+}
+
+/// Replays the stage that was lost, keeping the score so far
+fn continue_run(
+    countdown: Res<ContinueCountdown>,
+    mut run: ResMut<ArcadeRun>,
+    mut next_arcade: ResMut<NextState<Arcade>>,
+    mut next_screen: ResMut<NextState<Screen>>,
+) {
+    if countdown.0.finished() {
+        return;
+    }
+    run.continues += 1;
+    next_arcade.set(Arcade::Playing);
+    next_screen.set(Screen::Gameplay);
+}
+
+fn spawn_initials_overlay(mut commands: Commands, run: Res<ArcadeRun>) {
+    commands.insert_resource(InitialsEntry::default());
+    // Buttons above and below each letter, for touch screens and mice
+    let slot = |index: usize| {
+        let step = move |step: i8| {
+            move |_: Trigger<Pointer<Click>>, mut entry: ResMut<InitialsEntry>| {
+                entry.cursor = index;
+                entry.cycle(step);
+            }
+        };
+        (
+            Name::new("Initial"),
+            Node {
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                row_gap: Px(8.0),
+                ..default()
+            },
+            children![
+                widget::button_small("+", step(1)),
+                (
+                    Name::new("Letter"),
+                    InitialSlot(index),
+                    Text::new("A"),
+                    TextFont {
+                        font_size: INITIAL_FONT_SIZE,
+                        ..default()
+                    },
+                ),
+                widget::button_small("-", step(-1)),
+            ],
+        )
+    };
+    commands.spawn((
+        overlay("Initials Overlay"),
+        StateScoped(Arcade::Initials),
+        children![
+            overlay_text("NEW HIGH SCORE", TITLE_FONT_SIZE),
+            overlay_text(format!("{}", run.score), TEXT_FONT_SIZE),
+            (
+                Name::new("Initial Slots"),
+                Node {
+                    column_gap: Px(24.0),
+                    ..default()
+                },
+                children![slot(0), slot(1), slot(2)],
+            ),
+            overlay_text(
+                "type, or pick letters with the arrows or d-pad, then press Enter or A",
+                TEXT_FONT_SIZE
+            ),
+            widget::button("Done", confirm_initials),
+        ],
+    ));
+}
+
+fn confirm_initials(_: Trigger<Pointer<Click>>, mut entry: ResMut<InitialsEntry>) {
+    entry.confirmed = true;
+}
+
+fn type_initials(keyboard: Res<ButtonInput<KeyCode>>, mut entry: ResMut<InitialsEntry>) {
+    for key in keyboard.get_just_pressed() {
+        match key {
+            KeyCode::ArrowUp => entry.cycle(1),
+            KeyCode::ArrowDown => entry.cycle(-1),
+            KeyCode::ArrowLeft | KeyCode::Backspace => entry.back(),
+            KeyCode::ArrowRight | KeyCode::Enter | KeyCode::Space => entry.advance(),
+            key => {
+                if let Some(letter) = letter_key(*key) {
+                    entry.type_letter(letter);
+                }
+            }
+        }
+    }
+}
+
+/// Up and down on the d-pad pick a letter, A moves on and B goes back
+fn pick_initials_with_gamepad(gamepads: Query<&Gamepad>, mut entry: ResMut<InitialsEntry>) {
+    for gamepad in &gamepads {
+        for button in gamepad.get_just_pressed() {
+            match button {
+                GamepadButton::DPadUp => entry.cycle(1),
+                GamepadButton::DPadDown => entry.cycle(-1),
+                GamepadButton::DPadLeft | GamepadButton::East => entry.back(),
+                GamepadButton::DPadRight | GamepadButton::South => entry.advance(),
+                GamepadButton::Start => entry.confirmed = true,
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Adds the initials to the table once confirmed, or when time runs out
+fn save_initials(
+    time: Res<Time<Real>>,
+    mut entry: ResMut<InitialsEntry>,
+    run: Res<ArcadeRun>,
+    mut high_scores: ResMut<ArcadeHighScores>,
+    mut next_arcade: ResMut<NextState<Arcade>>,
+    mut next_screen: ResMut<NextState<Screen>>,
+) {
+    let timed_out = entry.timeout.tick(time.delta()).just_finished();
+    if !entry.confirmed && !timed_out {
+        return;
+    }
+
+    high_scores.insert(entry.initials(), run.score);
+    next_arcade.set(Arcade::Attract);
+    next_screen.set(Screen::Gameplay);
+}
+
+/// The letter a key types, if it's one of A to Z
+fn letter_key(key: KeyCode) -> Option<char> {
+    const LETTERS: [KeyCode; 26] = [
+        KeyCode::KeyA,
+        KeyCode::KeyB,
+        KeyCode::KeyC,
+        KeyCode::KeyD,
+        KeyCode::KeyE,
+        KeyCode::KeyF,
+        KeyCode::KeyG,
+        KeyCode::KeyH,
+        KeyCode::KeyI,
+        KeyCode::KeyJ,
+        KeyCode::KeyK,
+        KeyCode::KeyL,
+        KeyCode::KeyM,
+        KeyCode::KeyN,
+        KeyCode::KeyO,
+        KeyCode::KeyP,
+        KeyCode::KeyQ,
+        KeyCode::KeyR,
+        KeyCode::KeyS,
+        KeyCode::KeyT,
+        KeyCode::KeyU,
+        KeyCode::KeyV,
+        KeyCode::KeyW,
+        KeyCode::KeyX,
+        KeyCode::KeyY,
+        KeyCode::KeyZ,
+    ];
+    LETTERS
+        .iter()
+        .position(|letter| *letter == key)
+        .map(|index| (b'A' + index as u8) as char)
+}
+
+fn update_initial_slots(
+    entry: Res<InitialsEntry>,
+    mut slots: Query<(&InitialSlot, &mut Text, &mut TextColor)>,
+) {
+    for (slot, mut text, mut color) in &mut slots {
+        text.0 = entry.letters[slot.0].to_string();
+        color.0 = if slot.0 == entry.cursor {
+            HIGHLIGHT_COLOR
+        } else {
+            Color::WHITE
+        };
+    }
+}
+
+/// A dimmed, centred column over the court
+fn overlay(name: &'static str) -> impl Bundle {
+    (
+        Name::new(name),
+        Node {
+            position_type: PositionType::Absolute,
+            width: Percent(100.0),
+            height: Percent(100.0),
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::Center,
+            row_gap: Px(16.0),
+            ..default()
+        },
+        BackgroundColor(OVERLAY_BACKGROUND),
+        GlobalZIndex(1),
+        Pickable::IGNORE,
+    )
+}
+
+fn overlay_text(text: impl Into<String>, font_size: f32) -> impl Bundle {
+    (
+        Name::new("Arcade Text"),
+        Text::new(text),
+        TextFont {
+            font_size,
+            ..default()
+        },
+        TextLayout::new_with_justify(JustifyText::Center),
+    )
+}
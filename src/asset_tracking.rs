@@ -1,8 +1,12 @@
 //! A high-level way to load collections of asset handles as resources.
 
-use std::collections::VecDeque;
+use std::{collections::VecDeque, marker::PhantomData};
 
-use bevy::prelude::*;
+use bevy::{
+    asset::{AssetLoader, LoadContext, io::Reader},
+    prelude::*,
+};
+use serde::de::DeserializeOwned;
 
 pub(super) fn plugin(app: &mut App) {
     app.init_resource::<ResourceHandles>();
@@ -36,6 +40,42 @@ impl LoadResource for App {
     }
 }
 
+/// An [`Asset`] read straight from a JSON file.
+pub trait JsonAsset: Asset + DeserializeOwned {
+    /// File extensions to load it from, e.g. `"theme.json"`.
+    const EXTENSIONS: &'static [&'static str];
+}
+
+/// Loads a [`JsonAsset`] from any file with one of its extensions.
+pub struct JsonAssetLoader<A>(PhantomData<fn() -> A>);
+
+impl<A> Default for JsonAssetLoader<A> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<A: JsonAsset> AssetLoader for JsonAssetLoader<A> {
+    type Asset = A;
+    type Settings = ();
+    type Error = Box<dyn std::error::Error + Send + Sync>;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<A, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        Ok(serde_json::from_slice(&bytes)?)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        A::EXTENSIONS
+    }
+}
+
 /// A function that inserts a loaded resource.
 type InsertLoadedResource = fn(&mut World, &UntypedHandle);
 
@@ -1,13 +1,82 @@
-use bevy::prelude::*;
+//! Sound categories and the mixer they play through.
+//!
+//! Every sound is tagged with the bus it belongs on ([`Music`], [`SoundEffect`],
+//! [`Stinger`] or [`Announcer`]), and [`AudioMixer`] sets each bus's level.
+//! While a stinger or an announcer line is playing the music bus ducks under
+//! it, then ramps back up once it's done, so nothing that plays has to
+//! lower the music itself.
+//!
+//! Sound effects that happen somewhere on the court can be panned to where
+//! they happened with [`positional_sound_effect`]. The [`MusicController`]
+//! sequences music changes, fading the music bus out before a stinger.
+
+use bevy::{
+    audio::{SpatialScale, Volume},
+    prelude::*,
+};
+
+use crate::game::court::CourtSize;
+
+/// Music bus level while it's ducked
+const DUCKED_LEVEL: f32 = 0.3;
+/// Time to duck the music all the way down
+const DUCK_ATTACK: f32 = 0.15; // seconds
+/// Time to bring it back up afterward
+const DUCK_RELEASE: f32 = 0.8; // seconds
+
+/// Time to fade the music out before a stinger
+const MUSIC_FADE_OUT: f32 = 0.6; // seconds
 
 pub(super) fn plugin(app: &mut App) {
     app.register_type::<Music>();
     app.register_type::<SoundEffect>();
+    app.register_type::<Stinger>();
+    app.register_type::<Announcer>();
+    app.register_type::<AudioMixer>();
+    app.init_resource::<AudioMixer>();
+    app.init_resource::<MusicController>();
+
+    app.add_systems(Startup, spawn_listener);
+    app.add_systems(Update, (sequence_music, duck_music, mix_buses).chain());
+}
 
-    app.add_systems(
-        Update,
-        apply_global_volume.run_if(resource_changed::<GlobalVolume>),
-    );
+/// Hears positional sounds from the middle of the court. Its ears sit at
+/// either end of the court, so a sound's place between the end lines is its
+/// place across the stereo field.
+fn spawn_listener(mut commands: Commands, court: Res<CourtSize>) {
+    commands.spawn((
+        Name::new("Audio Listener"),
+        SpatialListener::new(court.width),
+        Transform::default(),
+    ));
+}
+
+/// Levels for each bus, on top of [`GlobalVolume`]
+#[derive(Resource, Debug, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct AudioMixer {
+    pub music: f32,
+    pub effects: f32,
+    pub stingers: f32,
+    pub announcer: f32,
+    /// How far the music is ducked right now, from 1 (not at all) down to
+    /// [`DUCKED_LEVEL`]
+    music_duck: f32,
+    /// How far a [`MusicController`] fade-out has got, from 1 down to 0
+    music_fade: f32,
+}
+
+impl Default for AudioMixer {
+    fn default() -> Self {
+        Self {
+            music: 1.0,
+            effects: 1.0,
+            stingers: 1.0,
+            announcer: 1.0,
+            music_duck: 1.0,
+            music_fade: 1.0,
+        }
+    }
 }
 
 /// An organizational marker component that should be added to a spawned [`AudioPlayer`] if it's in the
@@ -36,12 +105,146 @@ pub fn sound_effect(handle: Handle<AudioSource>) -> impl Bundle {
     (AudioPlayer(handle), PlaybackSettings::DESPAWN, SoundEffect)
 }
 
-/// [`GlobalVolume`] doesn't apply to already-running audio entities, so this system will update them.
-fn apply_global_volume(
+/// A sound effect audio instance panned to where it happened on the court.
+/// Only the X position matters; height on the court doesn't change the
+/// sound.
+pub fn positional_sound_effect(
+    handle: Handle<AudioSource>,
+    position: Vec2,
+    court: CourtSize,
+) -> impl Bundle {
+    (
+        AudioPlayer(handle),
+        PlaybackSettings::DESPAWN
+            .with_spatial(true)
+            // One unit of audio space per court width, so nothing on the
+            // court is far enough away to fade
+            .with_spatial_scale(SpatialScale::new_2d(1.0 / court.width)),
+        SoundEffect,
+        Transform::from_xyz(position.x, 0.0, 0.0),
+    )
+}
+
+/// A short musical cue for a big moment, like a goal fanfare. The music
+/// ducks under it.
+#[derive(Component, Reflect, Default)]
+#[reflect(Component)]
+pub struct Stinger;
+
+/// A stinger audio instance.
+pub fn stinger(handle: Handle<AudioSource>) -> impl Bundle {
+    (AudioPlayer(handle), PlaybackSettings::DESPAWN, Stinger)
+}
+
+/// A spoken announcer line. The music ducks under it.
+#[derive(Component, Reflect, Default)]
+#[reflect(Component)]
+pub struct Announcer;
+
+/// An announcer line audio instance.
+pub fn announcer_line(handle: Handle<AudioSource>) -> impl Bundle {
+    (AudioPlayer(handle), PlaybackSettings::DESPAWN, Announcer)
+}
+
+/// Sequences changes to the music, so they don't cut each other off
+#[derive(Resource, Debug, Default)]
+pub struct MusicController {
+    /// Playing once the music has faded out
+    queued: Option<Handle<AudioSource>>,
+    fading: bool,
+}
+
+impl MusicController {
+    /// Fades out whatever music is playing and stops it, then plays
+    /// `stinger`
+    pub fn fade_out_then(&mut self, stinger: Handle<AudioSource>) {
+        self.queued = Some(stinger);
+        self.fading = true;
+    }
+}
+
+/// Fades the music bus down for the controller, then stops the music, puts
+/// the bus back and plays whatever was queued
+fn sequence_music(
+    mut commands: Commands,
+    time: Res<Time<Real>>,
+    mut controller: ResMut<MusicController>,
+    mut mixer: ResMut<AudioMixer>,
+    music: Query<Entity, With<Music>>,
+) {
+    if !controller.fading {
+        return;
+    }
+
+    if !music.is_empty() && mixer.music_fade > 0.0 {
+        mixer.music_fade = (mixer.music_fade - time.delta_secs() / MUSIC_FADE_OUT).max(0.0);
+        return;
+    }
+
+    for entity in &music {
+        commands.entity(entity).despawn();
+    }
+    mixer.music_fade = 1.0;
+    controller.fading = false;
+    if let Some(stinger_sound) = controller.queued.take() {
+        commands.spawn((Name::new("Stinger"), stinger(stinger_sound)));
+    }
+}
+
+/// Ducks the music while any stinger or announcer line is playing. Runs on
+/// real time, so a pause doesn't leave it stuck halfway.
+fn duck_music(
+    time: Res<Time<Real>>,
+    mut mixer: ResMut<AudioMixer>,
+    ducking: Query<&AudioSink, Or<(With<Stinger>, With<Announcer>)>>,
+) {
+    let ducked = ducking
+        .iter()
+        .any(|sink| !sink.is_paused() && !sink.empty());
+    let (target, duration) = if ducked {
+        (DUCKED_LEVEL, DUCK_ATTACK)
+    } else {
+        (1.0, DUCK_RELEASE)
+    };
+    let step = (1.0 - DUCKED_LEVEL) / duration * time.delta_secs();
+    let music_duck = if mixer.music_duck < target {
+        (mixer.music_duck + step).min(target)
+    } else {
+        (mixer.music_duck - step).max(target)
+    };
+    if mixer.music_duck != music_duck {
+        mixer.music_duck = music_duck;
+    }
+}
+
+/// Sets every sound's volume from its own, its bus's and the global level.
+/// Bevy only applies [`GlobalVolume`] as sounds start, so this also keeps
+/// sounds already playing in step with it.
+fn mix_buses(
     global_volume: Res<GlobalVolume>,
-    mut audio_query: Query<(&PlaybackSettings, &mut AudioSink)>,
+    mixer: Res<AudioMixer>,
+    mut sinks: Query<(
+        &PlaybackSettings,
+        &mut AudioSink,
+        Has<Music>,
+        Has<Stinger>,
+        Has<Announcer>,
+    )>,
 ) {
-    for (playback, mut sink) in &mut audio_query {
-        sink.set_volume(global_volume.volume * playback.volume);
+    let remix = global_volume.is_changed() || mixer.is_changed();
+    for (playback, mut sink, music, stinger, announcer) in &mut sinks {
+        if !remix && !sink.is_added() {
+            continue;
+        }
+        let bus = if music {
+            mixer.music * mixer.music_duck * mixer.music_fade
+        } else if stinger {
+            mixer.stingers
+        } else if announcer {
+            mixer.announcer
+        } else {
+            mixer.effects
+        };
+        sink.set_volume(global_volume.volume * playback.volume * Volume::Linear(bus));
     }
 }
@@ -0,0 +1,209 @@
+//! Screenshot and highlight capture.
+//!
+//! Press F12 to save a screenshot. With highlight capture switched on (F9),
+//! the last few seconds of gameplay are kept in memory and written out as an
+//! animated GIF whenever a goal is scored. Files are encoded and written on the
+//! IO task pool so the game never stalls on disk access.
+
+use std::{
+    collections::VecDeque,
+    fs,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use bevy::{
+    input::common_conditions::input_just_pressed,
+    prelude::*,
+    render::view::screenshot::{Screenshot, ScreenshotCaptured},
+    tasks::IoTaskPool,
+};
+use image::{
+    Delay, Frame, ImageError, RgbaImage,
+    codecs::gif::{GifEncoder, Repeat},
+    imageops::FilterType,
+};
+
+use crate::{AppSystems, PausableSystems, game::scoring::GoalScored, screens::Screen};
+
+const CAPTURE_DIR: &str = "captures";
+const SCREENSHOT_KEY: KeyCode = KeyCode::F12;
+const HIGHLIGHT_TOGGLE_KEY: KeyCode = KeyCode::F9;
+
+// Highlight recording
+const HIGHLIGHT_SECONDS: f32 = 3.0;
+const HIGHLIGHT_FPS: f32 = 10.0;
+const HIGHLIGHT_WIDTH: u32 = 400; // pixels, height keeps the aspect ratio
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<CaptureSettings>();
+    app.init_resource::<CaptureSettings>();
+    app.init_resource::<HighlightBuffer>();
+
+    app.add_systems(
+        Update,
+        (
+            take_screenshot.run_if(input_just_pressed(SCREENSHOT_KEY)),
+            toggle_highlights.run_if(input_just_pressed(HIGHLIGHT_TOGGLE_KEY)),
+            sample_highlight_frame
+                .in_set(AppSystems::TickTimers)
+                .in_set(PausableSystems)
+                .run_if(in_state(Screen::Gameplay).and(highlights_enabled)),
+        ),
+    );
+    app.add_systems(OnExit(Screen::Gameplay), clear_highlight_buffer);
+    app.add_observer(save_highlight_on_goal);
+}
+
+/// User-facing capture options
+#[derive(Resource, Default, Reflect)]
+#[reflect(Resource)]
+pub struct CaptureSettings {
+    /// Record a GIF of the lead-up to every goal
+    pub highlights: bool,
+}
+
+/// Rolling window of recent downscaled frames
+#[derive(Resource)]
+struct HighlightBuffer {
+    frames: VecDeque<RgbaImage>,
+    timer: Timer,
+}
+
+impl Default for HighlightBuffer {
+    fn default() -> Self {
+        Self {
+            frames: VecDeque::new(),
+            timer: Timer::from_seconds(1.0 / HIGHLIGHT_FPS, TimerMode::Repeating),
+        }
+    }
+}
+
+impl HighlightBuffer {
+    fn capacity() -> usize {
+        (HIGHLIGHT_SECONDS * HIGHLIGHT_FPS).ceil() as usize
+    }
+
+    fn push(&mut self, frame: RgbaImage) {
+        self.frames.push_back(frame);
+        while self.frames.len() > Self::capacity() {
+            self.frames.pop_front();
+        }
+    }
+}
+
+fn highlights_enabled(settings: Res<CaptureSettings>) -> bool {
+    settings.highlights
+}
+
+fn toggle_highlights(mut settings: ResMut<CaptureSettings>, mut buffer: ResMut<HighlightBuffer>) {
+    settings.highlights = !settings.highlights;
+    buffer.frames.clear();
+    info!(
+        "Highlight capture {}",
+        if settings.highlights { "on" } else { "off" }
+    );
+}
+
+fn take_screenshot(mut commands: Commands) {
+    commands
+        .spawn(Screenshot::primary_window())
+        .observe(save_screenshot);
+}
+
+fn save_screenshot(trigger: Trigger<ScreenshotCaptured>) {
+    let image = match trigger.event().0.clone().try_into_dynamic() {
+        Ok(image) => image,
+        Err(error) => {
+            warn!("Couldn't convert screenshot: {error}");
+            return;
+        }
+    };
+
+    let path = capture_path("screenshot", "png");
+    IoTaskPool::get()
+        .spawn(async move {
+            let result = fs::create_dir_all(CAPTURE_DIR)
+                .map_err(ImageError::IoError)
+                .and_then(|_| image.to_rgb8().save(&path));
+            match result {
+                Ok(()) => info!("Saved screenshot to {}", path.display()),
+                Err(error) => warn!("Couldn't save screenshot: {error}"),
+            }
+        })
+        .detach();
+}
+
+fn sample_highlight_frame(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut buffer: ResMut<HighlightBuffer>,
+) {
+    if buffer.timer.tick(time.delta()).just_finished() {
+        commands
+            .spawn(Screenshot::primary_window())
+            .observe(record_highlight_frame);
+    }
+}
+
+fn record_highlight_frame(
+    trigger: Trigger<ScreenshotCaptured>,
+    mut buffer: ResMut<HighlightBuffer>,
+) {
+    let Ok(image) = trigger.event().0.clone().try_into_dynamic() else {
+        return;
+    };
+
+    let height = image.height() * HIGHLIGHT_WIDTH / image.width().max(1);
+    buffer.push(
+        image
+            .resize_exact(HIGHLIGHT_WIDTH, height, FilterType::Nearest)
+            .to_rgba8(),
+    );
+}
+
+fn save_highlight_on_goal(
+    _: Trigger<GoalScored>,
+    settings: Res<CaptureSettings>,
+    mut buffer: ResMut<HighlightBuffer>,
+) {
+    if !settings.highlights || buffer.frames.is_empty() {
+        return;
+    }
+
+    let frames: Vec<RgbaImage> = buffer.frames.drain(..).collect();
+    let path = capture_path("highlight", "gif");
+    IoTaskPool::get()
+        .spawn(async move {
+            match write_gif(&path, frames) {
+                Ok(()) => info!("Saved highlight to {}", path.display()),
+                Err(error) => warn!("Couldn't save highlight: {error}"),
+            }
+        })
+        .detach();
+}
+
+fn clear_highlight_buffer(mut buffer: ResMut<HighlightBuffer>) {
+    buffer.frames.clear();
+}
+
+fn write_gif(path: &Path, frames: Vec<RgbaImage>) -> Result<(), ImageError> {
+    fs::create_dir_all(CAPTURE_DIR)?;
+    let mut encoder = GifEncoder::new(fs::File::create(path)?);
+    encoder.set_repeat(Repeat::Infinite)?;
+
+    let delay = Delay::from_numer_denom_ms(1000, HIGHLIGHT_FPS as u32);
+    encoder.encode_frames(
+        frames
+            .into_iter()
+            .map(|frame| Frame::from_parts(frame, 0, 0, delay)),
+    )
+}
+
+/// A unique file name in the capture directory, e.g. `captures/highlight-1712345678901.gif`
+fn capture_path(prefix: &str, extension: &str) -> PathBuf {
+    let millis = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_millis());
+    PathBuf::from(CAPTURE_DIR).join(format!("{prefix}-{millis}.{extension}"))
+}
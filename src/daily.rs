@@ -0,0 +1,308 @@
+//! The daily challenge: one match setup per day, the same for everyone.
+//!
+//! The day number (days since the Unix epoch, UTC) is turned into a seed that
+//! picks the day's mutators, court, ball and CPU opponent, and also seeds the
+//! serve angles. A [`DailyChallenge`] resource holds the setup while the
+//! challenge is being played. The challenger plays their usual paddle against
+//! the CPU and is scored on point difference; the best result of the day is
+//! kept locally.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use bevy::prelude::*;
+use rand::{prelude::*, rngs::StdRng};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    game::{
+        GamePhase,
+        ball::ServeRng,
+        ball_kind::BallKind,
+        cpu::{CpuPlayer, ServeStyle},
+        mutators::{Mutator, Mutators},
+        player::{MirroredCourt, Player},
+        rules::{CourtLayout, MatchRules},
+        scoring::Score,
+    },
+    screens::Screen,
+    storage,
+};
+
+const SAVE_KEY: &str = "daily_best";
+const SECONDS_PER_DAY: u64 = 60 * 60 * 24;
+
+/// Mutators drawn for each day
+const DAILY_MUTATORS: usize = 2;
+const BALL_KINDS: [BallKind; 4] = [
+    BallKind::Classic,
+    BallKind::Puck,
+    BallKind::BeachBall,
+    BallKind::Bullet,
+];
+/// Range the day's opponent skill is drawn from
+const OPPONENT_SKILL: (f32, f32) = (0.4, 0.9);
+
+const RESULT_FONT_SIZE: f32 = 28.0;
+const RESULT_Y_OFFSET: f32 = 40.0; // Distance from bottom
+const NEW_BEST_COLOR: Color = Color::srgb(1.0, 0.85, 0.3);
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        OnEnter(Screen::Gameplay),
+        seed_daily_match.run_if(resource_exists::<DailyChallenge>),
+    );
+    app.add_systems(
+        Update,
+        assign_daily_opponent
+            .run_if(in_state(Screen::Gameplay).and(resource_exists::<DailyChallenge>)),
+    );
+    app.add_systems(
+        OnEnter(GamePhase::GameOver),
+        record_daily_result.run_if(resource_exists::<DailyChallenge>),
+    );
+    // Quitting to the title ends the challenge and puts the player's own
+    // rules back
+    app.add_systems(OnEnter(Screen::Title), end_daily_challenge);
+}
+
+/// Today's challenge while it's being played
+#[derive(Resource, Clone, Debug)]
+pub struct DailyChallenge {
+    pub setup: DailySetup,
+    /// Settings to put back once the challenge is over
+    previous_rules: MatchRules,
+    previous_mutators: Mutators,
+}
+
+impl DailyChallenge {
+    /// Swaps in the day's setup. Call before entering gameplay.
+    pub fn start(setup: DailySetup, rules: &mut MatchRules, mutators: &mut Mutators) -> Self {
+        let previous_rules = std::mem::replace(rules, setup.rules());
+        let previous_mutators = std::mem::replace(mutators, setup.mutators.clone());
+        Self {
+            setup,
+            previous_rules,
+            previous_mutators,
+        }
+    }
+}
+
+/// Everything that sets one day's challenge apart
+#[derive(Clone, Debug)]
+pub struct DailySetup {
+    /// Days since the Unix epoch
+    pub day: u64,
+    /// Seeds the setup and the serve angles
+    pub seed: u64,
+    pub mutators: Mutators,
+    pub court: CourtLayout,
+    pub moving_goals: bool,
+    pub bricks: bool,
+    pub ball: BallKind,
+    /// The CPU's skill, from 0 (easy) to 1 (hard)
+    pub opponent_skill: f32,
+    /// How the CPU serves
+    pub opponent_serve_style: ServeStyle,
+}
+
+impl DailySetup {
+    /// The challenge for the current (UTC) day
+    pub fn today() -> Self {
+        let day = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs() / SECONDS_PER_DAY);
+        Self::for_day(day)
+    }
+
+    pub fn for_day(day: u64) -> Self {
+        let seed = day_seed(day);
+        let mut rng = StdRng::seed_from_u64(seed);
+
+        let mutators = Mutators::new(
+            Mutator::ALL
+                .choose_multiple(&mut rng, DAILY_MUTATORS)
+                .copied(),
+        );
+        let court = if rng.random_bool(0.3) {
+            CourtLayout::Open
+        } else {
+            CourtLayout::Walled
+        };
+        let moving_goals = rng.random_bool(0.3);
+        let bricks = rng.random_bool(0.3);
+        let ball = *BALL_KINDS.choose(&mut rng).unwrap_or(&BallKind::Classic);
+        let (low, high) = OPPONENT_SKILL;
+        let opponent_skill = rng.random_range(low..=high);
+        let opponent_serve_style = *ServeStyle::ALL
+            .choose(&mut rng)
+            .unwrap_or(&ServeStyle::STEADY);
+
+        Self {
+            day,
+            seed,
+            mutators,
+            court,
+            moving_goals,
+            bricks,
+            ball,
+            opponent_skill,
+            opponent_serve_style,
+        }
+    }
+
+    /// The day's rules, built on the default match rules so handicaps and
+    /// house rules don't carry over
+    fn rules(&self) -> MatchRules {
+        MatchRules {
+            court: self.court,
+            moving_goals: self.moving_goals,
+            bricks: self.bricks,
+            ball: self.ball,
+            ..default()
+        }
+    }
+
+    /// The day's CPU opponent
+    pub fn opponent(&self) -> CpuPlayer {
+        CpuPlayer::new(self.opponent_skill).with_serve_style(self.opponent_serve_style)
+    }
+
+    /// One-line summary for the menu, e.g. "Tricky CPU, Open court, Puck,
+    /// Fog + Big Ball"
+    pub fn describe(&self) -> String {
+        let court = match self.court {
+            CourtLayout::Walled => "Walled court",
+            CourtLayout::Open => "Open court",
+        };
+        let mut parts = vec![
+            format!("{} CPU", self.opponent_serve_style.name),
+            court.to_string(),
+            self.ball.name().to_string(),
+        ];
+        if self.moving_goals {
+            parts.push("Moving goals".into());
+        }
+        if self.bricks {
+            parts.push("Bricks".into());
+        }
+        let mutators = self
+            .mutators
+            .iter()
+            .map(Mutator::name)
+            .collect::<Vec<_>>()
+            .join(" + ");
+        if !mutators.is_empty() {
+            parts.push(mutators);
+        }
+        parts.join(", ")
+    }
+}
+
+/// Spreads consecutive day numbers across the seed space (SplitMix64)
+fn day_seed(day: u64) -> u64 {
+    let mut z = day.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+/// Serves the same angles for everyone, from the first serve of each attempt
+pub(crate) fn seed_daily_match(daily: Res<DailyChallenge>, mut rng: ResMut<ServeRng>) {
+    rng.reseed(daily.setup.seed);
+}
+
+/// Hands the challenger's opponent to the day's CPU
+fn assign_daily_opponent(
+    mut commands: Commands,
+    daily: Res<DailyChallenge>,
+    mirrored: Res<MirroredCourt>,
+    paddles: Query<(Entity, &Player), Added<Player>>,
+) {
+    for (entity, player) in &paddles {
+        if !player.takes_keyboard_input(&mirrored) {
+            commands.entity(entity).insert(daily.setup.opponent());
+        }
+    }
+}
+
+/// The best local result for a day
+#[derive(Serialize, Deserialize, Clone, Copy, Debug)]
+pub struct DailyBest {
+    pub day: u64,
+    /// The player's points minus their opponent's
+    pub score: i32,
+}
+
+impl DailyBest {
+    /// The saved best for `day`, if one was set that day
+    pub fn load(day: u64) -> Option<Self> {
+        storage::load::<Self>(SAVE_KEY).filter(|best| best.day == day)
+    }
+
+    fn save(&self) {
+        if let Err(error) = storage::save(SAVE_KEY, self) {
+            warn!("Couldn't save daily best: {error}");
+        }
+    }
+}
+
+/// Scores the finished match and saves it if it beats the day's best
+fn record_daily_result(
+    mut commands: Commands,
+    daily: Res<DailyChallenge>,
+    score: Res<Score>,
+    mirrored: Res<MirroredCourt>,
+) {
+    let day = daily.setup.day;
+    let side = mirrored.home_side();
+    let result = score.get(side) as i32 - score.get(side.opponent()) as i32;
+    let previous = DailyBest::load(day);
+    let new_best = previous.is_none_or(|best| result > best.score);
+    if new_best {
+        DailyBest { day, score: result }.save();
+    }
+
+    let text = if new_best {
+        format!("Daily challenge: {result:+} - new best!")
+    } else {
+        let best = previous.map_or(result, |best| best.score);
+        format!("Daily challenge: {result:+} (today's best {best:+})")
+    };
+    commands.spawn((
+        Name::new("Daily Result"),
+        Text::new(text),
+        TextFont {
+            font_size: RESULT_FONT_SIZE,
+            ..default()
+        },
+        TextColor(if new_best {
+            NEW_BEST_COLOR
+        } else {
+            Color::WHITE
+        }),
+        TextLayout::new_with_justify(JustifyText::Center),
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            bottom: Val::Px(RESULT_Y_OFFSET),
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        GlobalZIndex(1),
+        StateScoped(GamePhase::GameOver),
+    ));
+}
+
+fn end_daily_challenge(
+    mut commands: Commands,
+    daily: Option<Res<DailyChallenge>>,
+    mut rules: ResMut<MatchRules>,
+    mut mutators: ResMut<Mutators>,
+) {
+    let Some(daily) = daily else {
+        return;
+    };
+    *rules = daily.previous_rules.clone();
+    *mutators = daily.previous_mutators.clone();
+    commands.remove_resource::<DailyChallenge>();
+}
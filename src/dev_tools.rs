@@ -4,6 +4,7 @@ use bevy::{
     dev_tools::states::log_transitions, input::common_conditions::input_just_pressed, prelude::*,
     ui::UiDebugOptions,
 };
+use bevy_inspector_egui::{bevy_egui::EguiPlugin, quick::WorldInspectorPlugin};
 
 use crate::game::GamePhase;
 use crate::menus::Menu;
@@ -20,10 +21,35 @@ pub(super) fn plugin(app: &mut App) {
         Update,
         toggle_debug_ui.run_if(input_just_pressed(TOGGLE_KEY)),
     );
+
+    // Live-edit every `Reflect`-registered type. The inspector isn't part of
+    // `PausableSystems`, so it keeps working while the game is paused.
+    if !app.is_plugin_added::<EguiPlugin>() {
+        app.add_plugins(EguiPlugin {
+            enable_multipass_for_primary_context: true,
+        });
+    }
+    app.init_resource::<InspectorVisible>();
+    app.add_plugins(WorldInspectorPlugin::new().run_if(resource_equals(InspectorVisible(true))));
+    app.add_systems(
+        Update,
+        toggle_inspector.run_if(input_just_pressed(INSPECTOR_KEY)),
+    );
 }
 
-const TOGGLE_KEY: KeyCode = KeyCode::Backquote;
+// Backquote is taken by the debug console.
+const TOGGLE_KEY: KeyCode = KeyCode::F1;
 
 fn toggle_debug_ui(mut options: ResMut<UiDebugOptions>) {
     options.toggle();
 }
+
+const INSPECTOR_KEY: KeyCode = KeyCode::F2;
+
+/// Whether the world inspector panel is shown.
+#[derive(Resource, Default, PartialEq)]
+struct InspectorVisible(bool);
+
+fn toggle_inspector(mut visible: ResMut<InspectorVisible>) {
+    visible.0 = !visible.0;
+}
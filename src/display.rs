@@ -0,0 +1,104 @@
+//! Display options: vsync and a frame rate cap.
+//!
+//! Physics and paddle movement run on the fixed timestep, so neither option
+//! changes how the game plays, only how smoothly it's drawn.
+
+use bevy::{prelude::*, window::PresentMode};
+
+/// Frame rate caps to choose from, `None` being uncapped
+pub const FPS_CAPS: [Option<u32>; 6] = [None, Some(30), Some(60), Some(120), Some(144), Some(240)];
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<DisplaySettings>();
+    app.init_resource::<DisplaySettings>();
+
+    app.add_systems(
+        Update,
+        apply_vsync.run_if(resource_changed::<DisplaySettings>),
+    );
+
+    // Browsers pace frames themselves, so web builds ignore the cap
+    #[cfg(not(target_family = "wasm"))]
+    {
+        app.init_resource::<FrameLimiter>();
+        app.add_systems(Last, limit_frame_rate);
+    }
+}
+
+/// Player-facing display options
+#[derive(Resource, Debug, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct DisplaySettings {
+    pub vsync: bool,
+    /// Most frames per second to draw, if capped
+    pub fps_cap: Option<u32>,
+}
+
+impl Default for DisplaySettings {
+    fn default() -> Self {
+        Self {
+            vsync: true,
+            fps_cap: None,
+        }
+    }
+}
+
+impl DisplaySettings {
+    /// The next cap in [`FPS_CAPS`], for cycling through them in the settings menu
+    pub fn next_fps_cap(&self) -> Option<u32> {
+        let index = FPS_CAPS
+            .iter()
+            .position(|&cap| cap == self.fps_cap)
+            .unwrap_or_default();
+        FPS_CAPS[(index + 1) % FPS_CAPS.len()]
+    }
+
+    /// The previous cap in [`FPS_CAPS`], for cycling back through them
+    pub fn previous_fps_cap(&self) -> Option<u32> {
+        let index = FPS_CAPS
+            .iter()
+            .position(|&cap| cap == self.fps_cap)
+            .unwrap_or_default();
+        FPS_CAPS[(index + FPS_CAPS.len() - 1) % FPS_CAPS.len()]
+    }
+}
+
+/// When the last frame finished, for pacing the next one
+#[cfg(not(target_family = "wasm"))]
+#[derive(Resource)]
+struct FrameLimiter {
+    last_frame: std::time::Instant,
+}
+
+#[cfg(not(target_family = "wasm"))]
+impl Default for FrameLimiter {
+    fn default() -> Self {
+        Self {
+            last_frame: std::time::Instant::now(),
+        }
+    }
+}
+
+fn apply_vsync(settings: Res<DisplaySettings>, mut windows: Query<&mut Window>) {
+    let present_mode = if settings.vsync {
+        PresentMode::AutoVsync
+    } else {
+        PresentMode::AutoNoVsync
+    };
+    for mut window in &mut windows {
+        window.present_mode = present_mode;
+    }
+}
+
+/// Sleeps off whatever is left of the frame budget
+#[cfg(not(target_family = "wasm"))]
+fn limit_frame_rate(settings: Res<DisplaySettings>, mut limiter: ResMut<FrameLimiter>) {
+    if let Some(cap) = settings.fps_cap {
+        let frame_budget = std::time::Duration::from_secs_f64(1.0 / f64::from(cap));
+        let remaining = frame_budget.saturating_sub(limiter.last_frame.elapsed());
+        if !remaining.is_zero() {
+            std::thread::sleep(remaining);
+        }
+    }
+    limiter.last_frame = std::time::Instant::now();
+}
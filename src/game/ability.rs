@@ -0,0 +1,348 @@
+//! Characters' special abilities, paid for out of the momentum meter.
+//!
+//! The ability press goes through the input buffer like a smash, so replays
+//! record it. What it does depends on the paddle's [`Character`], and each
+//! use starts a cooldown shown as a ring beside that player's score.
+
+use std::f32::consts::TAU;
+
+use avian2d::prelude::*;
+use bevy::{prelude::*, ui::Val::*};
+
+use super::{
+    ball::Ball,
+    hud::InCourtFrame,
+    input_buffer::{BufferedAction, InputBuffer},
+    momentum::Momentum,
+    player::{MirroredCourt, PaddleHit, PaddleIntent, Player, PlayerSide},
+    profile::PlayerProfiles,
+    roster::Character,
+};
+use crate::{AppSystems, screens::Screen};
+
+/// Share of the momentum meter each use costs
+const ABILITY_COST: f32 = 0.25;
+
+// Teleport
+const TELEPORT_DISTANCE: f32 = 120.0; // pixels at most
+
+// Curve shot
+const CURVE_RATE: f32 = 1.2; // radians per second
+const CURVE_DURATION: f32 = 1.0; // seconds
+
+// Spin shot: a gentler curve anyone can put on a return by holding a
+// direction as the ball arrives
+const SPIN_RATE: f32 = 0.6; // radians per second
+const SPIN_DURATION: f32 = 0.7; // seconds
+/// Share of the momentum meter each spin shot costs
+const SPIN_COST: f32 = 0.05;
+
+// Cooldown rings sit beside the scores
+const RING_SIZE: f32 = 32.0;
+const RING_SEGMENTS: usize = 12;
+const RING_DOT_SIZE: f32 = 6.0;
+const RING_X_OFFSET: f32 = 60.0; // px out from the score
+/// Distance from the top of the court, as a percentage of its height
+const RING_TOP: f32 = 5.0;
+const RING_UNLIT_COLOR: Color = Color::srgba(1.0, 1.0, 1.0, 0.15);
+const RING_CHARGING_COLOR: Color = Color::srgb(0.5, 0.5, 0.5);
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<AbilityCooldown>();
+    app.register_type::<CurveReady>();
+    app.register_type::<Curve>();
+    app.register_type::<CooldownSegment>();
+    app.register_required_components::<Character, AbilityCooldown>();
+
+    app.add_observer(curve_on_hit);
+    app.add_systems(OnEnter(Screen::Gameplay), spawn_cooldown_rings);
+    app.add_systems(
+        FixedUpdate,
+        (tick_cooldowns, use_abilities, bend_curving_balls)
+            .chain()
+            .run_if(in_state(Screen::Gameplay)),
+    );
+    app.add_systems(
+        Update,
+        update_cooldown_rings
+            .in_set(AppSystems::Update)
+            .run_if(in_state(Screen::Gameplay)),
+    );
+}
+
+/// A character's special move
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum Ability {
+    /// Blinks the paddle a short way toward the ball
+    Teleport,
+    /// The next return swerves as it flies
+    CurveShot,
+}
+
+impl Ability {
+    pub fn name(self) -> &'static str {
+        match self {
+            Ability::Teleport => "Teleport",
+            Ability::CurveShot => "Curve Shot",
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            Ability::Teleport => "blink a short way toward the ball",
+            Ability::CurveShot => "your next return swerves in flight",
+        }
+    }
+
+    /// Seconds before it can be used again
+    pub fn cooldown(self) -> f32 {
+        match self {
+            Ability::Teleport => 3.0,
+            Ability::CurveShot => 5.0,
+        }
+    }
+}
+
+/// Time left before a paddle's ability can be used again
+#[derive(Component, Debug, Clone, Copy, Default, Reflect)]
+#[reflect(Component)]
+pub struct AbilityCooldown {
+    pub remaining: f32,
+    pub duration: f32,
+}
+
+impl AbilityCooldown {
+    pub fn is_ready(&self) -> bool {
+        self.remaining <= 0.0
+    }
+
+    /// How far through the cooldown it is, from 0.0 (just used) to 1.0 (ready)
+    pub fn fraction(&self) -> f32 {
+        if self.duration <= 0.0 {
+            1.0
+        } else {
+            1.0 - self.remaining / self.duration
+        }
+    }
+
+    fn start(&mut self, duration: f32) {
+        self.remaining = duration;
+        self.duration = duration;
+    }
+}
+
+/// The paddle's next return will be a curve shot
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct CurveReady;
+
+/// A ball swerving in flight
+#[derive(Component, Debug, Clone, Copy, Reflect)]
+#[reflect(Component)]
+pub struct Curve {
+    /// Radians per second, counterclockwise
+    pub rate: f32,
+    /// Seconds left before it flies straight again
+    pub remaining: f32,
+}
+
+/// A marker for one dot of a cooldown ring, lit in order as it recharges
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct CooldownSegment {
+    side: PlayerSide,
+    index: usize,
+}
+
+fn tick_cooldowns(time: Res<Time>, mut cooldowns: Query<&mut AbilityCooldown>) {
+    for mut cooldown in &mut cooldowns {
+        cooldown.remaining = (cooldown.remaining - time.delta_secs()).max(0.0);
+    }
+}
+
+fn use_abilities(
+    mut commands: Commands,
+    time: Res<Time<Virtual>>,
+    mut buffer: ResMut<InputBuffer>,
+    mut momentum: ResMut<Momentum>,
+    mirrored: Res<MirroredCourt>,
+    mut paddles: Query<
+        (
+            Entity,
+            &Player,
+            &Character,
+            &mut AbilityCooldown,
+            &mut Position,
+        ),
+        Without<Ball>,
+    >,
+    balls: Query<&Position, With<Ball>>,
+) {
+    if !buffer.consume(BufferedAction::Ability, time.elapsed_secs()) {
+        return;
+    }
+
+    for (entity, player, character, mut cooldown, mut position) in &mut paddles {
+        if !player.takes_keyboard_input(&mirrored) || !cooldown.is_ready() {
+            continue;
+        }
+        let Some(ability) = character.stats().ability else {
+            continue;
+        };
+
+        match ability {
+            Ability::Teleport => {
+                let paddle = position.0;
+                let Some(ball) = balls.iter().min_by(|a, b| {
+                    a.distance_squared(paddle)
+                        .total_cmp(&b.distance_squared(paddle))
+                }) else {
+                    continue;
+                };
+                if !momentum.spend(player.side, ABILITY_COST) {
+                    continue;
+                }
+                // Paddles are kept inside the court before the next step
+                position.y += (ball.y - paddle.y).clamp(-TELEPORT_DISTANCE, TELEPORT_DISTANCE);
+            }
+            Ability::CurveShot => {
+                if !momentum.spend(player.side, ABILITY_COST) {
+                    continue;
+                }
+                commands.entity(entity).insert(CurveReady);
+            }
+        }
+        cooldown.start(ability.cooldown());
+    }
+}
+
+/// Curves a return from a primed paddle, or spins one hit while holding up
+/// or down; any other return straightens the ball out again
+fn curve_on_hit(
+    trigger: Trigger<PaddleHit>,
+    mut commands: Commands,
+    mut momentum: ResMut<Momentum>,
+    ready: Query<(), With<CurveReady>>,
+    paddles: Query<(&LinearVelocity, &PaddleIntent), (With<Player>, Without<Ball>)>,
+    balls: Query<&LinearVelocity, With<Ball>>,
+) {
+    let hit = trigger.event();
+    let (Ok((paddle_velocity, intent)), Ok(ball_velocity)) =
+        (paddles.get(hit.paddle), balls.get(hit.ball))
+    else {
+        return;
+    };
+
+    let (bend, rate, duration) = if ready.contains(hit.paddle) {
+        commands.entity(hit.paddle).remove::<CurveReady>();
+        // Bends the way the paddle was moving, or back toward the middle
+        let bend = if paddle_velocity.y != 0.0 {
+            paddle_velocity.y.signum()
+        } else {
+            -hit.contact_point.y.signum()
+        };
+        (bend, CURVE_RATE, CURVE_DURATION)
+    } else if intent.0 != 0.0 && momentum.spend(hit.side, SPIN_COST) {
+        (intent.0.signum(), SPIN_RATE, SPIN_DURATION)
+    } else {
+        commands.entity(hit.ball).remove::<Curve>();
+        return;
+    };
+    commands.entity(hit.ball).insert(Curve {
+        rate: rate * bend * ball_velocity.x.signum(),
+        remaining: duration,
+    });
+}
+
+fn bend_curving_balls(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut balls: Query<(Entity, &mut Curve, &mut LinearVelocity), With<Ball>>,
+) {
+    let dt = time.delta_secs();
+    for (entity, mut curve, mut velocity) in &mut balls {
+        velocity.0 = Vec2::from_angle(curve.rate * dt).rotate(velocity.0);
+        curve.remaining -= dt;
+        if curve.remaining <= 0.0 {
+            commands.entity(entity).remove::<Curve>();
+        }
+    }
+}
+
+/// A ring of dots beside the score of each player whose character has an
+/// ability
+fn spawn_cooldown_rings(mut commands: Commands, profiles: Res<PlayerProfiles>) {
+    for (side, center, offset) in [
+        (PlayerSide::Left, 25.0, -RING_X_OFFSET),
+        (PlayerSide::Right, 75.0, RING_X_OFFSET),
+    ] {
+        if profiles.get(side).character.stats().ability.is_none() {
+            continue;
+        }
+
+        let radius = (RING_SIZE - RING_DOT_SIZE) / 2.0;
+        commands
+            .spawn((
+                Name::new(format!("{side:?} Cooldown Ring")),
+                Node {
+                    position_type: PositionType::Absolute,
+                    left: Percent(center),
+                    top: Percent(RING_TOP),
+                    width: Px(RING_SIZE),
+                    height: Px(RING_SIZE),
+                    margin: UiRect::left(Px(offset - RING_SIZE / 2.0)),
+                    ..default()
+                },
+                Pickable::IGNORE,
+                InCourtFrame,
+                StateScoped(Screen::Gameplay),
+            ))
+            .with_children(|ring| {
+                for index in 0..RING_SEGMENTS {
+                    // Clockwise from the top
+                    let angle = index as f32 / RING_SEGMENTS as f32 * TAU;
+                    let position = Vec2::new(angle.sin(), -angle.cos()) * radius + radius;
+                    ring.spawn((
+                        Name::new("Cooldown Segment"),
+                        CooldownSegment { side, index },
+                        Node {
+                            position_type: PositionType::Absolute,
+                            left: Px(position.x),
+                            top: Px(position.y),
+                            width: Px(RING_DOT_SIZE),
+                            height: Px(RING_DOT_SIZE),
+                            ..default()
+                        },
+                        BorderRadius::MAX,
+                        BackgroundColor(RING_UNLIT_COLOR),
+                    ));
+                }
+            });
+    }
+}
+
+/// Lights each ring as its cooldown runs down, in the player's color once ready
+fn update_cooldown_rings(
+    profiles: Res<PlayerProfiles>,
+    paddles: Query<(&Player, &AbilityCooldown)>,
+    mut segments: Query<(&CooldownSegment, &mut BackgroundColor)>,
+) {
+    for (player, cooldown) in &paddles {
+        let lit = (cooldown.fraction() * RING_SEGMENTS as f32).floor() as usize;
+        let color = if cooldown.is_ready() {
+            profiles.color(player.side)
+        } else {
+            RING_CHARGING_COLOR
+        };
+        for (segment, mut background) in &mut segments {
+            if segment.side != player.side {
+                continue;
+            }
+            background.0 = if segment.index < lit {
+                color
+            } else {
+                RING_UNLIT_COLOR
+            };
+        }
+    }
+}
@@ -0,0 +1,26 @@
+//! Accessibility options that change presentation but never gameplay.
+
+use bevy::prelude::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<ReducedMotion>();
+    app.init_resource::<ReducedMotion>();
+    app.register_type::<AlwaysShowBall>();
+    app.init_resource::<AlwaysShowBall>();
+}
+
+/// Turns off camera moves, flashing and other purely decorative motion.
+/// Effects should check [`motion_allowed`] before they start.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Resource)]
+pub struct ReducedMotion(pub bool);
+
+/// Keeps the ball in view when a mutator would hide it
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Resource)]
+pub struct AlwaysShowBall(pub bool);
+
+/// Run condition for decorative motion
+pub fn motion_allowed(reduced_motion: Res<ReducedMotion>) -> bool {
+    !reduced_motion.0
+}
@@ -5,14 +5,21 @@
 //! - [Timers](https://github.com/bevyengine/bevy/blob/latest/examples/time/timers.rs)
 
 use bevy::prelude::*;
+use bevy_enhanced_input::prelude::*;
 use rand::prelude::*;
 use std::time::Duration;
 
-use crate::{AppSystems, PausableSystems, audio::sound_effect, game::player::PlayerAssets};
+use crate::{
+    AppSystems, PausableSystems,
+    audio::sound_effect,
+    game::player::{MirroredCourt, Move, PaddleIntent, Player, PlayerAssets, PlayerSide},
+};
 
 pub(super) fn plugin(app: &mut App) {
     // Animate and play sound effects based on controls.
     app.register_type::<PlayerAnimation>();
+    app.add_observer(walk_on_move);
+    app.add_observer(idle_on_stop);
     app.add_systems(
         Update,
         (
@@ -30,10 +37,71 @@ pub(super) fn plugin(app: &mut App) {
     );
 }
 
-/// Update the sprite direction and animation state (idling/walking).
-/// TODO: Re-implement this using action events from bevy_enhanced_input
-fn update_animation_movement(mut _player_query: Query<(&mut Sprite, &mut PlayerAnimation)>) {
-    // Temporarily disabled - needs to be reimplemented with action events
+/// Walk while the Move action is held, turning to face any sideways push.
+fn walk_on_move(
+    trigger: Trigger<Fired<Move>>,
+    mirrored: Res<MirroredCourt>,
+    mut player_query: Query<(&Player, &mut Sprite, &mut PlayerAnimation)>,
+) {
+    let Ok((player, mut sprite, mut animation)) = player_query.get_mut(trigger.target()) else {
+        return;
+    };
+    if !player.takes_keyboard_input(&mirrored) {
+        return;
+    }
+
+    let direction = trigger.value;
+    if direction.x != 0.0 {
+        sprite.flip_x = direction.x < 0.0;
+    }
+    if direction.y != 0.0 {
+        animation.update_state(PlayerAnimationState::Walking);
+    }
+}
+
+/// Idle once the Move action is released, facing back into the court.
+fn idle_on_stop(
+    trigger: Trigger<Completed<Move>>,
+    mirrored: Res<MirroredCourt>,
+    mut player_query: Query<(&Player, &mut Sprite, &mut PlayerAnimation)>,
+) {
+    let Ok((player, mut sprite, mut animation)) = player_query.get_mut(trigger.target()) else {
+        return;
+    };
+    if !player.takes_keyboard_input(&mirrored) {
+        return;
+    }
+
+    sprite.flip_x = facing_court_flipped(player.side);
+    animation.update_state(PlayerAnimationState::Idling);
+}
+
+/// Update the animation state (idling/walking) of paddles driven by anything
+/// other than the keyboard: touch, tilt, an AI or a replay.
+fn update_animation_movement(
+    mirrored: Res<MirroredCourt>,
+    mut player_query: Query<
+        (&Player, &PaddleIntent, &mut PlayerAnimation),
+        Or<(Changed<PaddleIntent>, Added<PlayerAnimation>)>,
+    >,
+) {
+    for (player, intent, mut animation) in &mut player_query {
+        if player.takes_keyboard_input(&mirrored) && intent.0 != 0.0 {
+            // Already walking from the action events
+            continue;
+        }
+        animation.update_state(if intent.0 == 0.0 {
+            PlayerAnimationState::Idling
+        } else {
+            PlayerAnimationState::Walking
+        });
+    }
+}
+
+/// Whether `side`'s sprite needs flipping to face into the court. The sheet
+/// faces right.
+pub fn facing_court_flipped(side: PlayerSide) -> bool {
+    side == PlayerSide::Right
 }
 
 /// Update the animation timer.
@@ -90,7 +158,6 @@ pub enum PlayerAnimationState {
     Walking,
 }
 
-#[allow(dead_code)] // TODO: Remove when animation is reimplemented with input actions
 impl PlayerAnimation {
     /// The number of idle frames.
     const IDLE_FRAMES: usize = 2;
@@ -101,6 +168,11 @@ impl PlayerAnimation {
     /// The duration of each walking frame.
     const WALKING_INTERVAL: Duration = Duration::from_millis(50);
 
+    /// A fresh animation, starting from the first idle frame
+    pub fn new() -> Self {
+        Self::idling()
+    }
+
     fn idling() -> Self {
         Self {
             timer: Timer::new(Self::IDLE_INTERVAL, TimerMode::Repeating),
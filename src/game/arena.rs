@@ -0,0 +1,352 @@
+//! Decorative arena backdrop drawn behind the court: a scrolling starfield and
+//! an animated crowd, each with a little parallax against the camera.
+//!
+//! The backdrop follows the court layout: walled courts sit in a stadium with
+//! spectators along the walls, open courts float in space. How many stars,
+//! how fast, and the colors come from the [`CourtTheme`], and the backdrop is
+//! respawned whenever the theme is edited or swapped. The low-detail
+//! setting thins the starfield and stops all background animation, as does
+//! reduced motion.
+
+use bevy::{ecs::spawn::SpawnIter, prelude::*};
+use rand::prelude::*;
+
+use super::{
+    accessibility::motion_allowed,
+    camera::SplitCamera,
+    court::{COURT_Z, CourtSize},
+    court_theme::{CourtTheme, CourtThemeAssets},
+    rules::{CourtLayout, MatchRules},
+    scoring::GoalScored,
+};
+use crate::{AppSystems, PausableSystems, screens::Screen};
+
+// Layers, back to front (all behind the court)
+const STARFIELD_Z: f32 = COURT_Z - 2.0;
+const CROWD_Z: f32 = COURT_Z - 1.0;
+
+// How much of the camera's movement each layer follows (0 = pinned to the
+// world, 1 = pinned to the screen)
+const STARFIELD_PARALLAX: f32 = 0.8;
+const CROWD_PARALLAX: f32 = 0.3;
+
+// Starfield, sized in court widths and heights to cover a zoomed-out view
+const STARFIELD_SCALE: Vec2 = Vec2::new(2.25, 2.0);
+const STAR_SIZE: f32 = 2.0;
+const LOW_DETAIL_STAR_DIVISOR: usize = 4;
+
+// Crowd
+const SPECTATOR_RADIUS: f32 = 6.0;
+const SPECTATOR_SPACING: f32 = 18.0;
+const CROWD_WIDTH_SCALE: f32 = 1.5; // court widths
+const CROWD_ROW_OFFSET: f32 = 30.0; // Beyond the top and bottom walls
+const CROWD_IDLE_AMPLITUDE: f32 = 1.5;
+const CROWD_CHEER_AMPLITUDE: f32 = 6.0;
+const CROWD_BOB_SPEED: f32 = 8.0; // radians per second
+const CROWD_CHEER_DURATION: f32 = 2.0; // seconds
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<BackgroundDetail>();
+    app.register_type::<Parallax>();
+    app.register_type::<Star>();
+    app.register_type::<Spectator>();
+    app.init_resource::<BackgroundDetail>();
+    app.init_resource::<ArenaAssets>();
+
+    app.add_systems(OnEnter(Screen::Gameplay), spawn_arena);
+    app.add_systems(
+        Update,
+        respawn_arena_on_theme_edit.run_if(in_state(Screen::Gameplay)),
+    );
+    app.add_observer(start_cheering);
+    app.add_systems(
+        Update,
+        (scroll_stars, animate_crowd)
+            .in_set(AppSystems::Update)
+            .in_set(PausableSystems)
+            .run_if(
+                in_state(Screen::Gameplay)
+                    .and(resource_equals(BackgroundDetail::Full))
+                    .and(motion_allowed),
+            ),
+    );
+    // After the camera has moved for this frame
+    app.add_systems(
+        PostUpdate,
+        apply_parallax
+            .before(TransformSystem::TransformPropagate)
+            .run_if(in_state(Screen::Gameplay)),
+    );
+}
+
+/// How much of the arena backdrop is drawn and animated
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Resource)]
+pub enum BackgroundDetail {
+    #[default]
+    Full,
+    /// Fewer stars and no background animation, for weaker machines
+    Low,
+}
+
+impl BackgroundDetail {
+    pub fn name(self) -> &'static str {
+        match self {
+            BackgroundDetail::Full => "Full",
+            BackgroundDetail::Low => "Low",
+        }
+    }
+
+    /// The other setting, for toggling in the settings menu
+    pub fn next(self) -> Self {
+        match self {
+            BackgroundDetail::Full => BackgroundDetail::Low,
+            BackgroundDetail::Low => BackgroundDetail::Full,
+        }
+    }
+}
+
+/// Shared meshes for the backdrop
+#[derive(Resource)]
+struct ArenaAssets {
+    star_mesh: Handle<Mesh>,
+    spectator_mesh: Handle<Mesh>,
+}
+
+impl FromWorld for ArenaAssets {
+    fn from_world(world: &mut World) -> Self {
+        let mut meshes = world.resource_mut::<Assets<Mesh>>();
+        Self {
+            star_mesh: meshes.add(Rectangle::from_length(STAR_SIZE)),
+            spectator_mesh: meshes.add(Circle::new(SPECTATOR_RADIUS)),
+        }
+    }
+}
+
+/// A background layer that follows part of the camera's movement
+#[derive(Component, Debug, Clone, Copy, Reflect)]
+#[reflect(Component)]
+struct Parallax(f32);
+
+/// A star drifting across the starfield
+#[derive(Component, Debug, Clone, Copy, Reflect)]
+#[reflect(Component)]
+struct Star {
+    speed: f32,
+}
+
+/// A spectator bobbing in the crowd
+#[derive(Component, Debug, Clone, Copy, Reflect)]
+#[reflect(Component)]
+struct Spectator {
+    base_y: f32,
+    phase: f32,
+}
+
+/// The crowd gets excited for a moment after each goal
+#[derive(Resource)]
+struct CrowdCheer(Timer);
+
+fn spawn_arena(
+    mut commands: Commands,
+    rules: Res<MatchRules>,
+    court: Res<CourtSize>,
+    detail: Res<BackgroundDetail>,
+    assets: Res<ArenaAssets>,
+    theme_assets: Option<Res<CourtThemeAssets>>,
+    themes: Res<Assets<CourtTheme>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    let theme = theme_assets.map_or_else(CourtTheme::default, |assets| assets.get(&themes));
+    spawn_backdrop(
+        &mut commands,
+        &mut materials,
+        &assets,
+        &theme,
+        rules.court,
+        *court,
+        *detail,
+    );
+}
+
+/// Swaps the backdrop for a fresh one when the theme file is saved or another
+/// theme is picked
+fn respawn_arena_on_theme_edit(
+    mut commands: Commands,
+    mut theme_events: EventReader<AssetEvent<CourtTheme>>,
+    theme_assets: Option<Res<CourtThemeAssets>>,
+    themes: Res<Assets<CourtTheme>>,
+    rules: Res<MatchRules>,
+    court: Res<CourtSize>,
+    detail: Res<BackgroundDetail>,
+    assets: Res<ArenaAssets>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    layers: Query<Entity, With<Parallax>>,
+) {
+    let Some(theme_assets) = theme_assets else {
+        return;
+    };
+    let edited = theme_events
+        .read()
+        .filter(|event| event.is_modified(&theme_assets.theme))
+        .count()
+        > 0
+        || theme_assets.is_changed();
+    if !edited {
+        return;
+    }
+
+    for layer in &layers {
+        commands.entity(layer).despawn();
+    }
+    spawn_backdrop(
+        &mut commands,
+        &mut materials,
+        &assets,
+        &theme_assets.get(&themes),
+        rules.court,
+        *court,
+        *detail,
+    );
+}
+
+fn spawn_backdrop(
+    commands: &mut Commands,
+    materials: &mut Assets<ColorMaterial>,
+    assets: &ArenaAssets,
+    theme: &CourtTheme,
+    layout: CourtLayout,
+    court: CourtSize,
+    detail: BackgroundDetail,
+) {
+    let style = theme.arena(layout);
+    let rng = &mut rand::rng();
+
+    let star_material = materials.add(theme.star.0);
+    let star_count = match detail {
+        BackgroundDetail::Full => style.stars,
+        BackgroundDetail::Low => style.stars / LOW_DETAIL_STAR_DIVISOR,
+    };
+    let half_field = court.extent() * STARFIELD_SCALE / 2.0;
+    let stars: Vec<_> = (0..star_count)
+        .map(|_| {
+            // Nearer stars are faster and bigger
+            let depth = rng.random_range(0.3..=1.0);
+            (
+                Star {
+                    speed: style.star_speed * depth,
+                },
+                Mesh2d(assets.star_mesh.clone()),
+                MeshMaterial2d(star_material.clone()),
+                Transform::from_xyz(
+                    rng.random_range(-half_field.x..half_field.x),
+                    rng.random_range(-half_field.y..half_field.y),
+                    0.0,
+                )
+                .with_scale(Vec3::splat(depth)),
+            )
+        })
+        .collect();
+
+    commands.spawn((
+        Name::new("Starfield"),
+        Parallax(STARFIELD_PARALLAX),
+        Transform::from_xyz(0.0, 0.0, STARFIELD_Z),
+        Visibility::default(),
+        StateScoped(Screen::Gameplay),
+        Children::spawn(SpawnIter(stars.into_iter())),
+    ));
+
+    if !style.crowd {
+        return;
+    }
+
+    let spectator_materials: Vec<_> = theme
+        .crowd
+        .iter()
+        .map(|color| materials.add(color.0))
+        .collect();
+
+    let row_y = court.half_height() + CROWD_ROW_OFFSET;
+    let crowd_width = court.width * CROWD_WIDTH_SCALE;
+    let per_row = (crowd_width / SPECTATOR_SPACING) as usize;
+    let spectators: Vec<_> = [row_y, -row_y]
+        .into_iter()
+        .flat_map(|y| (0..per_row).map(move |i| (y, i)))
+        .map(|(y, i)| {
+            let x = -crowd_width / 2.0 + (i as f32 + 0.5) * SPECTATOR_SPACING;
+            let material = spectator_materials.choose(rng).cloned();
+            (
+                Spectator {
+                    base_y: y,
+                    phase: rng.random_range(0.0..std::f32::consts::TAU),
+                },
+                Mesh2d(assets.spectator_mesh.clone()),
+                MeshMaterial2d(material.unwrap_or_default()),
+                Transform::from_xyz(x, y, 0.0),
+            )
+        })
+        .collect();
+
+    commands.spawn((
+        Name::new("Crowd"),
+        Parallax(CROWD_PARALLAX),
+        Transform::from_xyz(0.0, 0.0, CROWD_Z),
+        Visibility::default(),
+        StateScoped(Screen::Gameplay),
+        Children::spawn(SpawnIter(spectators.into_iter())),
+    ));
+}
+
+fn scroll_stars(time: Res<Time>, court: Res<CourtSize>, mut stars: Query<(&Star, &mut Transform)>) {
+    let field_width = court.width * STARFIELD_SCALE.x;
+    let half_width = field_width / 2.0;
+    for (star, mut transform) in &mut stars {
+        transform.translation.x -= star.speed * time.delta_secs();
+        if transform.translation.x < -half_width {
+            transform.translation.x += field_width;
+        }
+    }
+}
+
+fn start_cheering(_: Trigger<GoalScored>, mut commands: Commands) {
+    commands.insert_resource(CrowdCheer(Timer::from_seconds(
+        CROWD_CHEER_DURATION,
+        TimerMode::Once,
+    )));
+}
+
+fn animate_crowd(
+    mut commands: Commands,
+    time: Res<Time>,
+    cheer: Option<ResMut<CrowdCheer>>,
+    mut spectators: Query<(&Spectator, &mut Transform)>,
+) {
+    let amplitude = match cheer {
+        Some(mut cheer) => {
+            cheer.0.tick(time.delta());
+            if cheer.0.finished() {
+                commands.remove_resource::<CrowdCheer>();
+            }
+            CROWD_CHEER_AMPLITUDE
+        }
+        None => CROWD_IDLE_AMPLITUDE,
+    };
+
+    let t = time.elapsed_secs() * CROWD_BOB_SPEED;
+    for (spectator, mut transform) in &mut spectators {
+        transform.translation.y = spectator.base_y + amplitude * (t + spectator.phase).sin().abs();
+    }
+}
+
+fn apply_parallax(
+    camera: Single<&Transform, (With<Camera2d>, Without<SplitCamera>)>,
+    mut layers: Query<(&Parallax, &mut Transform), Without<Camera2d>>,
+) {
+    let camera_position = camera.translation.truncate();
+    for (parallax, mut transform) in &mut layers {
+        let offset = camera_position * parallax.0;
+        transform.translation.x = offset.x;
+        transform.translation.y = offset.y;
+    }
+}
@@ -2,45 +2,121 @@
 
 use avian2d::prelude::*;
 use bevy::prelude::*;
-use rand::prelude::*;
+use rand::{prelude::*, rngs::StdRng};
 
-use super::{GamePhase, physics::ball_layers, player::PlayerSide};
-use crate::screens::Screen;
+use super::{
+    GamePhase,
+    ball_kind::BallKind,
+    config::{BallConfig, GameConfig},
+    court::{CourtInset, CourtSize},
+    events::GameEvent,
+    hud::InCourtFrame,
+    input_buffer::{BufferedAction, InputBuffer},
+    mutators::{MutatorTweaks, Mutators},
+    physics::ball_layers,
+    player::{Knockback, MirroredCourt, PaddleHit, Player, PlayerSide},
+    profile::PlayerProfiles,
+    rules::{CourtLayout, MatchRules, SERVES_PER_TURN, ServeRotation},
+    shots::Shot,
+    watchdog::BallWatchdog,
+};
+use crate::{
+    screens::{Screen, transition::Transition},
+    theme::tween::TweenAlpha,
+};
 
-// Ball properties
-const BALL_RADIUS: f32 = 8.0;
-const BALL_SPEED: f32 = 300.0; // pixels per second
+#[cfg(feature = "dev")]
+use super::debug::console::{ConsoleResult, RegisterConsoleCommand};
+
+// Ball properties (speeds and bounce are in the `GameConfig`)
+pub const BALL_RADIUS: f32 = 8.0;
 const BALL_COLOR: Color = Color::WHITE;
+const SERVE_UI_FADE_DURATION: f32 = 0.3; // seconds
 const BALL_FRICTION: f32 = 0.0; // No friction for perfect bounces
-const BALL_RESTITUTION: f32 = 1.0; // Perfect elastic collisions
 const BALL_Z: f32 = 0.0; // Same layer as paddles
 
-// Serve angles - avoid too steep angles for better gameplay
-const MIN_SERVE_ANGLE: f32 = 15.0; // degrees from horizontal
-const MAX_SERVE_ANGLE: f32 = 45.0; // degrees from horizontal
+// Swept CCD tuning - only sweep when the ball moves far enough per step to tunnel
+const BALL_CCD_LINEAR_THRESHOLD: f32 = BALL_RADIUS; // pixels per second
 
 pub(super) fn plugin(app: &mut App) {
     app.register_type::<Ball>()
         .register_type::<ServeDirection>()
+        .register_type::<ServedBy>()
+        .register_type::<LastTouchedBy>()
+        .register_type::<Deflected>()
+        .register_type::<Rally>()
         .init_resource::<ServeDirection>()
+        .init_resource::<Rally>()
+        .init_resource::<ServeRng>()
+        .register_type::<ChosenServe>()
+        .init_resource::<ChosenServe>()
+        .add_observer(count_rally_hits)
+        .add_observer(track_last_touch)
+        .add_observer(track_deflections)
+        .add_observer(transfer_paddle_velocity)
+        .add_observer(knock_back_paddle)
+        .add_observer(apply_smash)
+        // Taken in fixed steps, so replays serve on the recorded tick
         .add_systems(
-            Update,
-            handle_serve_input
-                .run_if(in_state(GamePhase::WaitingToServe).and(in_state(Screen::Gameplay))),
+            FixedUpdate,
+            (
+                handle_serve_input.run_if(
+                    in_state(GamePhase::WaitingToServe)
+                        .and(in_state(Screen::Gameplay))
+                        .and(in_state(Transition::Idle)),
+                ),
+                serve_this_tick.run_if(serve_pending),
+            )
+                .chain(),
         )
         .add_systems(
             OnEnter(GamePhase::WaitingToServe),
             setup_serve_ui.run_if(in_state(Screen::Gameplay)),
         )
         .add_systems(OnExit(GamePhase::WaitingToServe), despawn_serve_ui)
-        .add_systems(OnEnter(GamePhase::Playing), serve_on_play_start);
+        .add_systems(OnEnter(Screen::Gameplay), reset_serve_turn)
+        .add_systems(OnEnter(GamePhase::Playing), serve_on_play_start)
+        // Clamp before the physics step (avian runs in `FixedPostUpdate`)
+        .add_systems(
+            FixedUpdate,
+            clamp_ball_speed.run_if(in_state(GamePhase::Playing)),
+        );
+
+    #[cfg(feature = "dev")]
+    app.register_console_command("ball", "ball speed <pixels per second>", ball_command);
 }
 
 /// Marker component for the ball entity
 #[derive(Component, Reflect)]
 #[reflect(Component)]
+#[require(BallWatchdog)]
 pub struct Ball;
 
+/// The player who served this ball
+#[derive(Component, Debug, Clone, Copy, Reflect)]
+#[reflect(Component)]
+pub struct ServedBy(pub PlayerSide);
+
+/// The paddle that touched this ball most recently. Absent until the first
+/// return after a serve.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub struct LastTouchedBy(pub PlayerSide);
+
+/// Marks a ball that has bounced off a wall or obstacle since a paddle last
+/// touched it
+#[derive(Component, Debug, Clone, Copy, Reflect)]
+#[reflect(Component)]
+pub struct Deflected;
+
+/// Triggered on a ball when a different player takes possession of it
+#[derive(Event, Debug, Clone, Copy)]
+pub struct PossessionChanged {
+    /// The previous owner, if the ball had been touched since its serve
+    pub previous: Option<PlayerSide>,
+    pub side: PlayerSide,
+}
+
 /// Marker component for serve UI elements
 #[derive(Component)]
 pub struct ServeUI;
@@ -50,10 +126,120 @@ pub struct ServeUI;
 #[reflect(Resource)]
 pub struct ServeDirection {
     pub side: PlayerSide,
+    /// Points `side` has already served in a row
+    pub serves_in_turn: u32,
+}
+
+impl ServeDirection {
+    /// Picks the next server after `rally_winner` won a point
+    pub fn rotate(&mut self, rotation: ServeRotation, rally_winner: PlayerSide, deuce: bool) {
+        let next = match rotation {
+            ServeRotation::LoserServes => rally_winner.opponent(),
+            ServeRotation::WinnerServes => rally_winner,
+            ServeRotation::Alternate if deuce || self.serves_in_turn + 1 >= SERVES_PER_TURN => {
+                self.side.opponent()
+            }
+            ServeRotation::Alternate => self.side,
+        };
+
+        if next == self.side {
+            self.serves_in_turn += 1;
+        } else {
+            self.side = next;
+            self.serves_in_turn = 0;
+        }
+    }
+}
+
+/// Randomness for serve angles. Seeded per match so replays serve the same way.
+#[derive(Resource)]
+pub struct ServeRng {
+    rng: StdRng,
+    seed: u64,
+    /// Serves drawn since seeding
+    serves: u32,
+}
+
+impl Default for ServeRng {
+    fn default() -> Self {
+        Self::from_seed(rand::random())
+    }
+}
+
+impl ServeRng {
+    fn from_seed(seed: u64) -> Self {
+        Self {
+            rng: StdRng::seed_from_u64(seed),
+            seed,
+            serves: 0,
+        }
+    }
+
+    pub fn reseed(&mut self, seed: u64) {
+        *self = Self::from_seed(seed);
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn serves(&self) -> u32 {
+        self.serves
+    }
+
+    /// Puts the generator back to where it was after `serves` serves
+    pub fn restore(&mut self, seed: u64, serves: u32) {
+        self.reseed(seed);
+        // The angle range doesn't change how much randomness a serve uses
+        let config = BallConfig::default();
+        for _ in 0..serves {
+            self.draw_serve(&config);
+        }
+    }
+
+    /// Picks a random serve at the usual pace, within the configured angles
+    pub fn draw_serve(&mut self, config: &BallConfig) -> ServeShot {
+        self.serves += 1;
+
+        // Random angle within safe range
+        let angle_degrees = config.serve_angle(&mut self.rng);
+
+        // Randomly choose up or down
+        let angle_sign = if self.rng.random_bool(0.5) { 1.0 } else { -1.0 };
+
+        ServeShot {
+            angle: angle_degrees * angle_sign,
+            speed: 1.0,
+        }
+    }
+}
+
+/// How a ball is served
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+pub struct ServeShot {
+    /// Degrees from horizontal, positive going up
+    pub angle: f32,
+    /// Multiplier on the usual serve speed
+    pub speed: f32,
+}
+
+/// A serve the server picked for themselves, used for their next serve
+/// instead of a random one
+#[derive(Resource, Debug, Default, Reflect)]
+#[reflect(Resource)]
+pub struct ChosenServe(pub Option<ServeShot>);
+
+/// Counts paddle returns in the current point
+#[derive(Resource, Default, Reflect)]
+#[reflect(Resource)]
+pub struct Rally {
+    pub hits: u32,
+    /// The paddle that touched the ball most recently this point
+    pub last_hit: Option<PlayerSide>,
 }
 
 /// Spawns a ball entity at the center of the court (without serving)
-pub(super) fn spawn_ball(
+pub fn spawn_ball(
     commands: &mut Commands,
     meshes: &mut ResMut<Assets<Mesh>>,
     materials: &mut ResMut<Assets<ColorMaterial>>,
@@ -61,7 +247,7 @@ pub(super) fn spawn_ball(
     let ball_mesh = meshes.add(Circle::new(BALL_RADIUS));
     let ball_material = materials.add(ColorMaterial::from_color(BALL_COLOR));
 
-    let ball_entity = commands
+    commands
         .spawn((
             Name::new("Ball"),
             Ball,
@@ -69,33 +255,44 @@ pub(super) fn spawn_ball(
             Mesh2d(ball_mesh),
             MeshMaterial2d(ball_material),
             Transform::from_xyz(0.0, 0.0, BALL_Z),
-            // Physics
-            RigidBody::Dynamic,
-            Collider::circle(BALL_RADIUS),
-            ball_layers(),
-            Friction::new(BALL_FRICTION),
-            Restitution::new(BALL_RESTITUTION),
-            // Prevent rotation for now (can add spin later)
-            LockedAxes::ROTATION_LOCKED,
-            // Start with zero velocity - will be served later
-            LinearVelocity::ZERO,
-            // Disable gravity for top-down view
-            GravityScale(0.0),
-            // Enable transform interpolation for smooth visual movement
-            TransformInterpolation,
-            // Enable collision events for goal detection
-            CollisionEventsEnabled,
+            ball_body(),
+            StateScoped(Screen::Gameplay),
         ))
-        .id();
-
-    // Add damping components separately to avoid tuple size limit
-    commands.entity(ball_entity).insert((
-        LinearDamping(0.0),
-        AngularDamping(0.0),
-        StateScoped(Screen::Gameplay),
-    ));
+        .id()
+}
 
-    ball_entity
+/// The ball's physics: a dynamic circle that bounces without losing speed,
+/// starting at rest. It starts with the default restitution, and the physics
+/// plugin keeps it in line with the [`GameConfig`] from there.
+pub fn ball_body() -> impl Bundle {
+    (
+        RigidBody::Dynamic,
+        Collider::circle(BALL_RADIUS),
+        ball_layers(),
+        Friction::new(BALL_FRICTION),
+        Restitution::new(BallConfig::default().restitution),
+        // Prevent rotation for now (can add spin later)
+        LockedAxes::ROTATION_LOCKED,
+        // Start with zero velocity - will be served later
+        LinearVelocity::ZERO,
+        // Disable gravity for top-down view
+        GravityScale(0.0),
+        // Enable transform interpolation for smooth visual movement
+        TransformInterpolation,
+        // Enable collision events for goal detection
+        CollisionEventsEnabled,
+        (
+            LinearDamping(0.0),
+            AngularDamping(0.0),
+            // Sweep the ball between steps so fast shots can't tunnel through
+            // thin paddles or boundaries.
+            SweptCcd::LINEAR
+                .include_dynamic(true)
+                .with_linear_threshold(BALL_CCD_LINEAR_THRESHOLD),
+            // Track current contacts for debugging tools
+            CollidingEntities::default(),
+        ),
+    )
 }
 
 /// Applies initial velocity to the ball based on serve direction
@@ -103,15 +300,8 @@ pub(super) fn serve_ball(
     commands: &mut Commands,
     ball_entity: Entity,
     serve_direction: &ServeDirection,
+    shot: ServeShot,
 ) {
-    let mut rng = rand::rng();
-
-    // Random angle within safe range
-    let angle_degrees = rng.random_range(MIN_SERVE_ANGLE..=MAX_SERVE_ANGLE);
-
-    // Randomly choose up or down
-    let angle_sign = if rng.random_bool(0.5) { 1.0 } else { -1.0 };
-
     // Determine serve direction based on which player is serving
     let direction_x = match serve_direction.side {
         PlayerSide::Left => 1.0,   // Left player serves to the right
@@ -119,40 +309,94 @@ pub(super) fn serve_ball(
     };
 
     // Convert to radians and calculate velocity components
-    let angle_radians = angle_degrees.to_radians() * angle_sign;
-    let velocity_x = angle_radians.cos() * BALL_SPEED * direction_x;
-    let velocity_y = angle_radians.sin() * BALL_SPEED;
+    let angle_radians = shot.angle.to_radians();
+    let direction = Vec2::new(angle_radians.cos() * direction_x, angle_radians.sin());
+    let side = serve_direction.side;
+    let server = match side {
+        PlayerSide::Left => "Left",
+        PlayerSide::Right => "Right",
+    };
 
-    // Apply velocity to the ball
+    // Apply velocity to the ball, at the pace of its kind and any mutators
     commands
         .entity(ball_entity)
-        .insert(LinearVelocity(Vec2::new(velocity_x, velocity_y)));
-
-    info!(
-        "{} player served at angle: {:.1}° with velocity: ({:.1}, {:.1})",
-        match serve_direction.side {
-            PlayerSide::Left => "Left",
-            PlayerSide::Right => "Right",
-        },
-        angle_degrees * angle_sign,
-        velocity_x,
-        velocity_y
-    );
-}
-
-/// Handles space bar input to transition from WaitingToServe to Playing
-fn handle_serve_input(
-    keyboard: Res<ButtonInput<KeyCode>>,
+        .queue(move |mut ball: EntityWorldMut| {
+            let speed_multiplier = ball
+                .get::<BallKind>()
+                .map_or(1.0, |kind| kind.speed_multiplier())
+                * ball
+                    .world()
+                    .get_resource::<Mutators>()
+                    .map_or(1.0, Mutators::ball_speed_multiplier)
+                * ball
+                    .world()
+                    .get_resource::<MutatorTweaks>()
+                    .map_or(1.0, |tweaks| tweaks.ball_speed);
+            let ball_speed = ball
+                .world()
+                .get_resource::<GameConfig>()
+                .copied()
+                .unwrap_or_default()
+                .ball
+                .speed;
+            let velocity = direction * ball_speed * shot.speed * speed_multiplier;
+            ball.insert((LinearVelocity(velocity), ServedBy(side)))
+                .remove::<(LastTouchedBy, Deflected, Shot)>();
+            let ball_entity = ball.id();
+            ball.world_scope(|world| {
+                world.send_event(GameEvent::Serve {
+                    side,
+                    ball: ball_entity,
+                });
+            });
+
+            info!(
+                "{server} player served at angle: {:.1}° with velocity: ({:.1}, {:.1})",
+                shot.angle, velocity.x, velocity.y
+            );
+        });
+}
+
+/// Handles space bar input to transition from WaitingToServe to Playing.
+/// The press is buffered, so hitting serve just before the ball is ready works.
+pub(super) fn handle_serve_input(
+    time: Res<Time<Virtual>>,
+    mut buffer: ResMut<InputBuffer>,
     mut game_phase: ResMut<NextState<GamePhase>>,
 ) {
-    if keyboard.just_pressed(KeyCode::Space) {
+    if buffer.consume(BufferedAction::Serve, time.elapsed_secs()) {
         // Transition to playing phase - ball will be served on state entry
         game_phase.set(GamePhase::Playing);
     }
 }
 
+fn serve_pending(next_phase: Res<NextState<GamePhase>>) -> bool {
+    matches!(*next_phase, NextState::Pending(GamePhase::Playing))
+}
+
+/// Enters `Playing` before the physics step instead of at the next frame, so
+/// the ball leaves on the tick the serve was pressed however many ticks the
+/// frame runs
+fn serve_this_tick(world: &mut World) {
+    world.run_schedule(StateTransition);
+}
+
 /// Sets up the serve UI
-fn setup_serve_ui(mut commands: Commands, serve_direction: Res<ServeDirection>) {
+fn setup_serve_ui(
+    mut commands: Commands,
+    serve_direction: Res<ServeDirection>,
+    rules: Res<MatchRules>,
+    profiles: Res<PlayerProfiles>,
+) {
+    let rotation = match rules.effective_serve_rotation() {
+        ServeRotation::LoserServes => "Loser serves".to_string(),
+        ServeRotation::WinnerServes => "Winner serves".to_string(),
+        ServeRotation::Alternate => format!(
+            "Serve {} of {SERVES_PER_TURN}",
+            serve_direction.serves_in_turn + 1
+        ),
+    };
+
     // Main container
     commands
         .spawn((
@@ -168,6 +412,7 @@ fn setup_serve_ui(mut commands: Commands, serve_direction: Res<ServeDirection>)
                 ..default()
             },
             BackgroundColor(Color::NONE),
+            InCourtFrame,
         ))
         .with_children(|parent| {
             // Serve direction indicator
@@ -183,7 +428,19 @@ fn setup_serve_ui(mut commands: Commands, serve_direction: Res<ServeDirection>)
                     font_size: 32.0,
                     ..default()
                 },
-                TextColor(Color::WHITE),
+                TextColor(profiles.color(serve_direction.side)),
+                TweenAlpha::fade_in(SERVE_UI_FADE_DURATION),
+            ));
+
+            // Serve rotation
+            parent.spawn((
+                Text::new(rotation),
+                TextFont {
+                    font_size: 20.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.6, 0.6, 0.6)),
+                TweenAlpha::fade_in(SERVE_UI_FADE_DURATION),
             ));
 
             // Instructions
@@ -194,10 +451,16 @@ fn setup_serve_ui(mut commands: Commands, serve_direction: Res<ServeDirection>)
                     ..default()
                 },
                 TextColor(Color::srgb(0.8, 0.8, 0.8)),
+                TweenAlpha::fade_in(SERVE_UI_FADE_DURATION),
             ));
         });
 }
 
+/// Each match starts a fresh serve turn
+fn reset_serve_turn(mut serve_direction: ResMut<ServeDirection>) {
+    serve_direction.serves_in_turn = 0;
+}
+
 /// Despawns all serve UI elements when transitioning away from WaitingToServe
 fn despawn_serve_ui(mut commands: Commands, serve_ui_query: Query<Entity, With<ServeUI>>) {
     for entity in &serve_ui_query {
@@ -210,9 +473,313 @@ fn serve_on_play_start(
     mut commands: Commands,
     balls: Query<Entity, With<Ball>>,
     serve_direction: Res<ServeDirection>,
+    rules: Res<MatchRules>,
+    config: Res<GameConfig>,
+    mut rng: ResMut<ServeRng>,
+    mut chosen: ResMut<ChosenServe>,
+    mut rally: ResMut<Rally>,
+) {
+    rally.hits = 0;
+    rally.last_hit = None;
+
+    // Find the ball and serve it. With two balls the receiver serves the
+    // second one so both players start with a ball coming at them.
+    let receiver = ServeDirection {
+        side: serve_direction.side.opponent(),
+        serves_in_turn: 0,
+    };
+    for (index, ball_entity) in balls.iter().enumerate() {
+        let direction = if rules.two_balls && index % 2 == 1 {
+            &receiver
+        } else {
+            &serve_direction
+        };
+        // The server's own pick only goes on their own ball
+        let shot = match chosen.0.take() {
+            Some(shot) if direction.side == serve_direction.side => shot,
+            _ => rng.draw_serve(&config.ball),
+        };
+        serve_ball(&mut commands, ball_entity, direction, shot);
+    }
+}
+
+/// Counts every time a ball starts touching a paddle
+fn count_rally_hits(trigger: Trigger<PaddleHit>, mut rally: ResMut<Rally>) {
+    rally.hits += 1;
+    rally.last_hit = Some(trigger.event().side);
+}
+
+/// Records which paddle touched a ball last and announces possession changes
+fn track_last_touch(
+    trigger: Trigger<PaddleHit>,
+    mut commands: Commands,
+    balls: Query<Option<&LastTouchedBy>, With<Ball>>,
+) {
+    let &PaddleHit { side, ball, .. } = trigger.event();
+    let Ok(last_touch) = balls.get(ball) else {
+        return;
+    };
+    commands.entity(ball).remove::<Deflected>();
+
+    let previous = last_touch.map(|last_touch| last_touch.0);
+    if previous == Some(side) {
+        return;
+    }
+
+    commands.entity(ball).insert(LastTouchedBy(side));
+    commands.trigger_targets(PossessionChanged { previous, side }, ball);
+}
+
+/// Marks balls bouncing off anything solid that isn't a paddle
+fn track_deflections(
+    trigger: Trigger<OnCollisionStart>,
+    mut commands: Commands,
+    balls: Query<(), With<Ball>>,
+    obstacles: Query<(), (Without<Player>, Without<Sensor>)>,
 ) {
-    // Find the ball and serve it
-    for ball_entity in &balls {
-        serve_ball(&mut commands, ball_entity, &serve_direction);
+    let ball = trigger.target();
+    if balls.contains(ball) && obstacles.contains(trigger.event().collider) {
+        commands.entity(ball).insert(Deflected);
+    }
+}
+
+/// Kinematic paddles have infinite mass, so the solver only reflects the ball.
+/// Carry part of the paddle's movement into the ball so players can steer it.
+fn transfer_paddle_velocity(
+    trigger: Trigger<PaddleHit>,
+    config: Res<GameConfig>,
+    mut balls: Query<&mut LinearVelocity, With<Ball>>,
+    paddles: Query<&LinearVelocity, (With<Player>, Without<Ball>)>,
+) {
+    let hit = trigger.event();
+    let Ok(mut ball_velocity) = balls.get_mut(hit.ball) else {
+        return;
+    };
+    let Ok(paddle_velocity) = paddles.get(hit.paddle) else {
+        return;
+    };
+
+    ball_velocity.y += paddle_velocity.y * config.ball.paddle_velocity_transfer;
+}
+
+/// Very fast balls push the paddle that meets them back, harder the faster
+/// they go, so smashes are tougher to return cleanly
+fn knock_back_paddle(
+    trigger: Trigger<PaddleHit>,
+    config: Res<GameConfig>,
+    mut paddles: Query<&mut Knockback>,
+) {
+    let hit = trigger.event();
+    let Ok(mut knockback) = paddles.get_mut(hit.paddle) else {
+        return;
+    };
+
+    let excess_speed = hit.ball_speed - config.ball.knockback_min_speed;
+    if excess_speed > 0.0 {
+        knockback.push(excess_speed * config.ball.knockback_per_speed);
+    }
+}
+
+/// Speeds up the return when the keyboard player smashed just before contact
+fn apply_smash(
+    trigger: Trigger<PaddleHit>,
+    time: Res<Time<Virtual>>,
+    mut buffer: ResMut<InputBuffer>,
+    mirrored: Res<MirroredCourt>,
+    config: Res<GameConfig>,
+    mut balls: Query<&mut LinearVelocity, With<Ball>>,
+) {
+    let hit = trigger.event();
+    // Only one paddle takes keyboard input for now
+    if hit.side != mirrored.home_side() {
+        return;
+    }
+    let Ok(mut velocity) = balls.get_mut(hit.ball) else {
+        return;
+    };
+
+    if buffer.consume(BufferedAction::Smash, time.elapsed_secs()) {
+        velocity.0 *= config.ball.smash_speed_up;
+        info!("Smash!");
+    }
+}
+
+/// Caps the ball's speed so it never outruns swept CCD
+pub(super) fn clamp_ball_speed(
+    config: Res<GameConfig>,
+    mut balls: Query<&mut LinearVelocity, With<Ball>>,
+) {
+    for mut velocity in &mut balls {
+        velocity.0 = velocity.0.clamp_length_max(config.ball.max_speed);
+    }
+}
+
+/// How far the ball's centre gets from the middle of the court before it
+/// bounces off the top or bottom wall, or `None` on an open court
+pub fn ball_wall_y(rules: &MatchRules, inset: &CourtInset, court: CourtSize) -> Option<f32> {
+    (rules.court == CourtLayout::Walled).then_some(court.half_height() - inset.0 - BALL_RADIUS)
+}
+
+/// Predicts where a ball's centre will cross the vertical line at `x`, and
+/// how many seconds away that is. With `wall_y`, the ball bounces between
+/// `-wall_y` and `wall_y` on the way. Paddles, bricks and spin are ignored.
+pub fn predict_crossing(
+    position: Vec2,
+    velocity: Vec2,
+    x: f32,
+    wall_y: Option<f32>,
+) -> Option<(Vec2, f32)> {
+    if velocity.x == 0.0 {
+        return None;
+    }
+    let time = (x - position.x) / velocity.x;
+    if time < 0.0 {
+        return None;
+    }
+
+    let mut y = position.y + velocity.y * time;
+    if let Some(wall) = wall_y.filter(|wall| *wall > 0.0) {
+        // Fold the straight-line path back between the walls, once per bounce
+        let folded = (y + wall).rem_euclid(4.0 * wall);
+        y = if folded <= 2.0 * wall {
+            folded - wall
+        } else {
+            3.0 * wall - folded
+        };
+    }
+    Some((Vec2::new(x, y), time))
+}
+
+/// Console command for changing the ball's speed mid-rally
+#[cfg(feature = "dev")]
+fn ball_command(
+    In(args): In<Vec<String>>,
+    config: Res<GameConfig>,
+    mut balls: Query<&mut LinearVelocity, With<Ball>>,
+) -> ConsoleResult {
+    let [action, speed] = args.as_slice() else {
+        return Err("usage: ball speed <pixels per second>".into());
+    };
+    if action != "speed" {
+        return Err(format!("unknown action '{action}'"));
+    }
+
+    let speed: f32 = speed
+        .parse()
+        .map_err(|_| format!("invalid speed '{speed}'"))?;
+    let mut changed = 0;
+    for mut velocity in &mut balls {
+        // Keep the current heading, only change the magnitude
+        let direction = velocity.0.normalize_or_zero();
+        if direction != Vec2::ZERO {
+            velocity.0 = direction * speed;
+            changed += 1;
+        }
+    }
+
+    if changed == 0 {
+        Err("no ball in motion".into())
+    } else {
+        Ok(format!(
+            "Ball speed set to {speed} (capped at {})",
+            config.ball.max_speed
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::time::TimeUpdateStrategy;
+
+    use super::*;
+    use crate::game::{
+        court::BOUNDARY_THICKNESS,
+        physics::{boundary_layers, paddle_layers},
+    };
+
+    const TICK_RATE: f64 = 64.0;
+    /// Fixed steps to run, long enough for several bounces at full speed
+    const STEPS: usize = 256;
+    const WALL_X: f32 = 200.0;
+    const PADDLE_X: f32 = -200.0;
+
+    /// A ball launched at `speed` between a thin wall and a paddle, with only
+    /// the physics step and the speed clamp running
+    fn rally_app(speed: f32) -> (App, Entity) {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            AssetPlugin::default(),
+            TransformPlugin,
+            bevy::scene::ScenePlugin,
+            bevy::render::mesh::MeshPlugin,
+            PhysicsPlugins::default(),
+        ));
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(
+            1.0 / TICK_RATE,
+        )));
+        app.insert_resource(Time::<Fixed>::from_hz(TICK_RATE));
+        app.insert_resource(GameConfig::default());
+        app.add_systems(FixedUpdate, clamp_ball_speed);
+
+        let world = app.world_mut();
+        world.spawn((
+            RigidBody::Static,
+            Collider::rectangle(BOUNDARY_THICKNESS, CourtSize::default().height),
+            boundary_layers(),
+            Restitution::new(1.0),
+            Transform::from_xyz(WALL_X, 0.0, 0.0),
+        ));
+        world.spawn((
+            RigidBody::Kinematic,
+            Collider::rectangle(
+                GameConfig::default().paddle.width,
+                CourtSize::default().height,
+            ),
+            paddle_layers(),
+            Restitution::new(1.0),
+            Transform::from_xyz(PADDLE_X, 0.0, 0.0),
+        ));
+        let ball = world
+            .spawn((Ball, ball_body(), Transform::default()))
+            .insert(LinearVelocity(Vec2::new(speed, 0.0)))
+            .id();
+        app.update();
+        (app, ball)
+    }
+
+    #[test]
+    fn ball_at_max_speed_never_tunnels() {
+        let config = GameConfig::default();
+        // Launched well over the cap, so the clamp has to catch it first
+        let (mut app, ball) = rally_app(config.ball.max_speed * 2.0);
+
+        let mut bounces = 0;
+        let mut heading = 1.0;
+        for step in 0..STEPS {
+            app.update();
+            let world = app.world();
+            let position = world.get::<Position>(ball).unwrap();
+            let velocity = world.get::<LinearVelocity>(ball).unwrap();
+
+            assert!(
+                position.x > PADDLE_X && position.x < WALL_X,
+                "ball tunnelled to x = {} on step {step}",
+                position.x
+            );
+            let speed = velocity.length();
+            assert!(
+                (config.ball.speed..=config.ball.max_speed + 1.0).contains(&speed),
+                "ball speed {speed} out of range on step {step}"
+            );
+            if velocity.x.signum() != heading {
+                heading = velocity.x.signum();
+                bounces += 1;
+            }
+        }
+        // Make sure it actually met the wall and paddle at speed
+        assert!(bounces >= 4, "only {bounces} bounces");
     }
 }
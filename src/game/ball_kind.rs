@@ -0,0 +1,164 @@
+//! Ball variants with their own shape, size, weight and pace.
+//!
+//! The kind for a match comes from [`MatchRules::ball`]. Inserting a different
+//! [`BallKind`] on a live ball (e.g. from a power-up) swaps its mesh, collider
+//! and mass on the spot; the speed modifier applies from its next serve.
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::{ball::Ball, mutators::Mutators, rules::MatchRules};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<BallKind>();
+    app.add_observer(use_match_ball_kind);
+    app.add_observer(apply_ball_kind);
+}
+
+/// Which ball is in play
+#[derive(
+    Component, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect, Serialize, Deserialize,
+)]
+#[reflect(Component)]
+pub enum BallKind {
+    #[default]
+    Classic,
+    /// Square and heavy
+    Puck,
+    /// Big, light and slow
+    BeachBall,
+    /// Tiny and fast
+    Bullet,
+}
+
+/// Shape of a ball's mesh and collider
+#[derive(Debug, Clone, Copy)]
+enum BallShape {
+    Circle,
+    Square,
+}
+
+/// Everything that sets a ball kind apart
+#[derive(Debug, Clone, Copy)]
+struct BallSpec {
+    shape: BallShape,
+    /// Radius, or half the side length for squares
+    radius: f32,
+    mass: f32,
+    /// Scales the serve speed
+    speed_multiplier: f32,
+    color: Color,
+}
+
+impl BallKind {
+    fn spec(self) -> BallSpec {
+        match self {
+            BallKind::Classic => BallSpec {
+                shape: BallShape::Circle,
+                radius: 8.0,
+                mass: 1.0,
+                speed_multiplier: 1.0,
+                color: Color::WHITE,
+            },
+            BallKind::Puck => BallSpec {
+                shape: BallShape::Square,
+                radius: 9.0,
+                mass: 2.0,
+                speed_multiplier: 1.1,
+                color: Color::srgb(0.75, 0.75, 0.8),
+            },
+            BallKind::BeachBall => BallSpec {
+                shape: BallShape::Circle,
+                radius: 16.0,
+                mass: 0.5,
+                speed_multiplier: 0.75,
+                color: Color::srgb(1.0, 0.55, 0.35),
+            },
+            BallKind::Bullet => BallSpec {
+                shape: BallShape::Circle,
+                radius: 4.0,
+                mass: 0.5,
+                speed_multiplier: 1.4,
+                color: Color::srgb(1.0, 0.95, 0.5),
+            },
+        }
+    }
+
+    /// Scales the serve speed for this kind
+    pub fn speed_multiplier(self) -> f32 {
+        self.spec().speed_multiplier
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            BallKind::Classic => "Classic",
+            BallKind::Puck => "Puck",
+            BallKind::BeachBall => "Beach Ball",
+            BallKind::Bullet => "Bullet",
+        }
+    }
+
+    /// The next kind, for cycling through them in the rules menu
+    pub fn next(self) -> Self {
+        match self {
+            BallKind::Classic => BallKind::Puck,
+            BallKind::Puck => BallKind::BeachBall,
+            BallKind::BeachBall => BallKind::Bullet,
+            BallKind::Bullet => BallKind::Classic,
+        }
+    }
+
+    /// The previous kind, for cycling backwards
+    pub fn previous(self) -> Self {
+        match self {
+            BallKind::Classic => BallKind::Bullet,
+            BallKind::Puck => BallKind::Classic,
+            BallKind::BeachBall => BallKind::Puck,
+            BallKind::Bullet => BallKind::BeachBall,
+        }
+    }
+}
+
+/// New balls take the match's ball kind
+fn use_match_ball_kind(
+    trigger: Trigger<OnAdd, Ball>,
+    mut commands: Commands,
+    rules: Res<MatchRules>,
+) {
+    commands.entity(trigger.target()).insert(rules.ball);
+}
+
+/// Reshapes a ball whenever its kind is set
+fn apply_ball_kind(
+    trigger: Trigger<OnInsert, BallKind>,
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mutators: Res<Mutators>,
+    balls: Query<&BallKind>,
+) {
+    let Ok(&kind) = balls.get(trigger.target()) else {
+        return;
+    };
+    let mut spec = kind.spec();
+    spec.radius *= mutators.ball_scale();
+
+    let (mesh, collider) = match spec.shape {
+        BallShape::Circle => (
+            meshes.add(Circle::new(spec.radius)),
+            Collider::circle(spec.radius),
+        ),
+        BallShape::Square => (
+            meshes.add(Rectangle::from_length(spec.radius * 2.0)),
+            Collider::rectangle(spec.radius * 2.0, spec.radius * 2.0),
+        ),
+    };
+
+    commands.entity(trigger.target()).insert((
+        Mesh2d(mesh),
+        MeshMaterial2d(materials.add(spec.color)),
+        collider,
+        Mass(spec.mass),
+    ));
+}
@@ -0,0 +1,148 @@
+//! Brick-breaker hybrid: a wall of bricks in the middle of the court.
+//!
+//! Bricks take a few hits to break. Breaking one is worth a bonus point to
+//! whoever hit the ball last (or served it, before anyone returned it), and
+//! can win the match. Bricks stay broken for the rest of the match.
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+
+use super::{
+    GamePhase,
+    ball::{Ball, LastTouchedBy, ServedBy},
+    config::GameConfig,
+    events::GameEvent,
+    fx::{EmitParticles, ParticleKind},
+    physics::{BOUNDARY_FRICTION, brick_layers},
+    player::PlayerSide,
+    rules::MatchRules,
+    scoring::Score,
+};
+use crate::screens::Screen;
+
+// Layout: two columns of bricks either side of the center, leaving room to serve
+const BRICK_SIZE: Vec2 = Vec2::new(16.0, 48.0);
+const BRICK_COLUMNS_X: [f32; 4] = [-110.0, -80.0, 80.0, 110.0];
+const BRICK_ROWS: usize = 6;
+const BRICK_ROW_SPACING: f32 = 72.0;
+const BRICK_Z: f32 = -0.2;
+
+const BRICK_HIT_POINTS: u32 = 2;
+const BRICK_BONUS_POINTS: u32 = 1;
+const BRICK_COLOR: Color = Color::srgb(0.85, 0.45, 0.25);
+const BRICK_BREAK_PARTICLES: usize = 16;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<Brick>();
+    app.add_systems(
+        OnEnter(Screen::Gameplay),
+        spawn_bricks.run_if(|rules: Res<MatchRules>| rules.bricks),
+    );
+    app.add_observer(hit_brick);
+}
+
+/// A breakable brick
+#[derive(Component, Debug, Clone, Copy, Reflect)]
+#[reflect(Component)]
+pub struct Brick {
+    pub hit_points: u32,
+}
+
+fn spawn_bricks(
+    mut commands: Commands,
+    config: Res<GameConfig>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    let mesh = meshes.add(Rectangle::from_size(BRICK_SIZE));
+    let top_y = (BRICK_ROWS - 1) as f32 * BRICK_ROW_SPACING / 2.0;
+
+    for (column, x) in BRICK_COLUMNS_X.into_iter().enumerate() {
+        for row in 0..BRICK_ROWS {
+            commands.spawn((
+                Name::new(format!("Brick {column}-{row}")),
+                Brick {
+                    hit_points: BRICK_HIT_POINTS,
+                },
+                RigidBody::Static,
+                Collider::rectangle(BRICK_SIZE.x, BRICK_SIZE.y),
+                brick_layers(),
+                Friction::new(BOUNDARY_FRICTION),
+                Restitution::new(config.court.wall_restitution),
+                Mesh2d(mesh.clone()),
+                // Each brick fades on its own as it takes damage
+                MeshMaterial2d(materials.add(BRICK_COLOR)),
+                Transform::from_xyz(x, top_y - row as f32 * BRICK_ROW_SPACING, BRICK_Z),
+                StateScoped(Screen::Gameplay),
+            ));
+        }
+    }
+}
+
+/// Damages a brick the ball hits, awarding the bonus when it breaks
+fn hit_brick(
+    trigger: Trigger<OnCollisionStart>,
+    mut commands: Commands,
+    rules: Res<MatchRules>,
+    mut score: ResMut<Score>,
+    mut game_phase: ResMut<NextState<GamePhase>>,
+    mut emit: EventWriter<EmitParticles>,
+    mut game_events: EventWriter<GameEvent>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    balls: Query<(Option<&LastTouchedBy>, Option<&ServedBy>), With<Ball>>,
+    mut bricks: Query<(&mut Brick, &Transform, &MeshMaterial2d<ColorMaterial>)>,
+    all_balls: Query<Entity, With<Ball>>,
+) {
+    let Ok((last_touch, served_by)) = balls.get(trigger.target()) else {
+        return;
+    };
+    let brick_entity = trigger.event().collider;
+    let Ok((mut brick, transform, material)) = bricks.get_mut(brick_entity) else {
+        return;
+    };
+
+    brick.hit_points = brick.hit_points.saturating_sub(1);
+    if brick.hit_points > 0 {
+        if let Some(material) = materials.get_mut(&material.0) {
+            let strength = brick.hit_points as f32 / BRICK_HIT_POINTS as f32;
+            material.color = BRICK_COLOR.with_alpha(0.35 + 0.65 * strength);
+        }
+        return;
+    }
+
+    commands.entity(brick_entity).despawn();
+    emit.write(EmitParticles {
+        kind: ParticleKind::Spark,
+        position: transform.translation.truncate(),
+        count: BRICK_BREAK_PARTICLES,
+    });
+
+    // Credit whoever's shot it was
+    let Some(side) = last_touch
+        .map(|last_touch| last_touch.0)
+        .or(served_by.map(|served_by| served_by.0))
+    else {
+        return;
+    };
+    match side {
+        PlayerSide::Left => score.left += BRICK_BONUS_POINTS,
+        PlayerSide::Right => score.right += BRICK_BONUS_POINTS,
+    }
+    game_events.write(GameEvent::BrickBroken {
+        side,
+        points: BRICK_BONUS_POINTS,
+    });
+    info!(
+        "{side:?} player breaks a brick! Score: {} - {}",
+        score.left, score.right
+    );
+
+    // A bonus can be the winning point
+    if score.winner(&rules).is_some() {
+        info!("Brick win for {side:?}!");
+        for ball in &all_balls {
+            commands.entity(ball).despawn();
+        }
+        game_phase.set(GamePhase::GameOver);
+    }
+}
@@ -0,0 +1,309 @@
+//! Gameplay camera behaviours: a subtle zoom-out as rallies get long, a quick
+//! punch-in on goals, and an optional ball-follow mode for courts bigger than
+//! the window. Whatever the mode, the view zooms out when the window (or
+//! browser canvas) is too small to show the whole court.
+//!
+//! For oversized courts there is also a split-court view, where each half of
+//! the window has its own camera framing one player's half of the court. The
+//! main camera then only draws the HUD on top.
+
+use bevy::{
+    prelude::*,
+    render::{camera::Viewport, view::RenderLayers},
+    window::PrimaryWindow,
+};
+
+use super::{
+    accessibility::ReducedMotion,
+    ball::{Ball, Rally},
+    court::{BOUNDARY_THICKNESS, CourtSize},
+    player::PlayerSide,
+    scoring::GoalScored,
+};
+use crate::{AppSystems, screens::Screen};
+
+// Rally zoom-out
+const RALLY_ZOOM_START: u32 = 4; // Hits before the camera starts pulling back
+const RALLY_ZOOM_PER_HIT: f32 = 0.01;
+const MAX_RALLY_ZOOM: f32 = 0.12; // Extra scale at most
+
+// Goal punch-in
+const PUNCH_DURATION: f32 = 0.8; // seconds
+const PUNCH_ZOOM: f32 = 0.15; // Scale taken off at the peak of the punch
+const PUNCH_SHIFT: f32 = 0.25; // Fraction of the way towards the goal mouth
+
+/// How quickly the camera eases towards its target (per second)
+const CAMERA_SMOOTHING: f32 = 6.0;
+
+/// Render layer nothing in the world is on, so the main camera only draws UI
+/// while the split view is up
+const HUD_ONLY_LAYER: usize = 31;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<CameraMode>();
+    app.register_type::<SplitCamera>();
+    app.init_resource::<CameraMode>();
+
+    app.add_observer(punch_in_on_goal);
+    app.add_systems(
+        Update,
+        (
+            update_camera,
+            (sync_split_view, frame_split_cameras).chain(),
+        )
+            .in_set(AppSystems::Update)
+            .run_if(in_state(Screen::Gameplay)),
+    );
+    app.add_systems(OnExit(Screen::Gameplay), reset_camera);
+}
+
+/// How the camera behaves during matches
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Resource)]
+pub enum CameraMode {
+    /// The whole court, always
+    Fixed,
+    /// Zooms out on long rallies and punches in on goals
+    #[default]
+    Dynamic,
+    /// Dynamic, and also pans after the ball when the court doesn't fit
+    Follow,
+    /// Each half of the window shows one player's half of the court
+    Split,
+}
+
+impl CameraMode {
+    pub fn name(self) -> &'static str {
+        match self {
+            CameraMode::Fixed => "Fixed",
+            CameraMode::Dynamic => "Dynamic",
+            CameraMode::Follow => "Follow Ball",
+            CameraMode::Split => "Split Court",
+        }
+    }
+
+    /// The next mode, for cycling through them in the settings menu
+    pub fn next(self) -> Self {
+        match self {
+            CameraMode::Fixed => CameraMode::Dynamic,
+            CameraMode::Dynamic => CameraMode::Follow,
+            CameraMode::Follow => CameraMode::Split,
+            CameraMode::Split => CameraMode::Fixed,
+        }
+    }
+
+    /// The previous mode, for cycling back through them in the settings menu
+    pub fn previous(self) -> Self {
+        match self {
+            CameraMode::Fixed => CameraMode::Split,
+            CameraMode::Dynamic => CameraMode::Fixed,
+            CameraMode::Follow => CameraMode::Dynamic,
+            CameraMode::Split => CameraMode::Follow,
+        }
+    }
+
+    /// Whether the main camera zooms and punches in
+    fn is_dynamic(self) -> bool {
+        matches!(self, CameraMode::Dynamic | CameraMode::Follow)
+    }
+}
+
+/// One of the two cameras of the split-court view, framing a player's half
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub(super) struct SplitCamera(PlayerSide);
+
+/// A punch-in towards the goal that was just scored on
+#[derive(Resource)]
+struct GoalPunch {
+    timer: Timer,
+    goal_x: f32,
+}
+
+fn punch_in_on_goal(
+    trigger: Trigger<GoalScored>,
+    mut commands: Commands,
+    mode: Res<CameraMode>,
+    reduced_motion: Res<ReducedMotion>,
+    court: Res<CourtSize>,
+) {
+    if !mode.is_dynamic() || reduced_motion.0 {
+        return;
+    }
+
+    // The scorer put the ball into the opponent's goal
+    let goal_x = match trigger.event().side {
+        PlayerSide::Left => court.half_width(),
+        PlayerSide::Right => -court.half_width(),
+    };
+    commands.insert_resource(GoalPunch {
+        timer: Timer::from_seconds(PUNCH_DURATION, TimerMode::Once),
+        goal_x,
+    });
+}
+
+fn update_camera(
+    mut commands: Commands,
+    time: Res<Time>,
+    mode: Res<CameraMode>,
+    reduced_motion: Res<ReducedMotion>,
+    rally: Res<Rally>,
+    court: Res<CourtSize>,
+    punch: Option<ResMut<GoalPunch>>,
+    balls: Query<&Transform, (With<Ball>, Without<Camera2d>)>,
+    camera: Single<
+        (&Camera, &mut Transform, &mut Projection),
+        (With<Camera2d>, Without<SplitCamera>),
+    >,
+) {
+    let (camera, mut transform, mut projection) = camera.into_inner();
+    let Projection::Orthographic(ortho) = &mut *projection else {
+        return;
+    };
+
+    // Zoom out when the window (or browser canvas) is too small for the court
+    let court_extent = court.extent() + Vec2::splat(2.0 * BOUNDARY_THICKNESS);
+    let mut target_scale = match camera.logical_viewport_size() {
+        Some(viewport) if viewport.min_element() > 0.0 => {
+            (court_extent / viewport).max_element().max(1.0)
+        }
+        _ => 1.0,
+    };
+    let mut target = Vec2::ZERO;
+
+    // Reduced motion keeps the camera still, as in fixed mode
+    let mode = if reduced_motion.0 {
+        CameraMode::Fixed
+    } else {
+        *mode
+    };
+
+    if mode.is_dynamic() {
+        let rally_hits = rally.hits.saturating_sub(RALLY_ZOOM_START) as f32;
+        target_scale *= 1.0 + (rally_hits * RALLY_ZOOM_PER_HIT).min(MAX_RALLY_ZOOM);
+    }
+
+    if mode == CameraMode::Follow {
+        if let Some(ball) = balls.iter().next() {
+            // Only pan as far as the court extends past the view
+            let half_view = ortho.area.size() / 2.0;
+            let limit = (court_extent / 2.0 - half_view).max(Vec2::ZERO);
+            target = ball.translation.truncate().clamp(-limit, limit);
+        }
+    }
+
+    if let Some(mut punch) = punch {
+        punch.timer.tick(time.delta());
+        // Strongest right after the goal, easing back out
+        let strength = 1.0 - punch.timer.fraction();
+        target_scale *= 1.0 - PUNCH_ZOOM * strength;
+        target.x += (punch.goal_x - target.x) * PUNCH_SHIFT * strength;
+        if punch.timer.finished() {
+            commands.remove_resource::<GoalPunch>();
+        }
+    }
+
+    let t = 1.0 - (-CAMERA_SMOOTHING * time.delta_secs()).exp();
+    ortho.scale = ortho.scale.lerp(target_scale, t);
+    let position = transform.translation.truncate().lerp(target, t);
+    transform.translation = position.extend(transform.translation.z);
+}
+
+/// Brings the split view up or down to match the camera mode
+fn sync_split_view(
+    mut commands: Commands,
+    mode: Res<CameraMode>,
+    main_camera: Single<(Entity, &mut Camera), (With<Camera2d>, Without<SplitCamera>)>,
+    split_cameras: Query<Entity, With<SplitCamera>>,
+) {
+    let wants_split = *mode == CameraMode::Split;
+    if wants_split != split_cameras.is_empty() {
+        return;
+    }
+
+    let (main_entity, mut main_camera) = main_camera.into_inner();
+    if !wants_split {
+        for entity in &split_cameras {
+            commands.entity(entity).despawn();
+        }
+        show_world_on_main_camera(&mut commands, main_entity, &mut main_camera);
+        return;
+    }
+
+    // Draw the halves first, then the HUD over them without clearing
+    for (order, side) in [(-2, PlayerSide::Left), (-1, PlayerSide::Right)] {
+        commands.spawn((
+            Name::new(format!("{side:?} Split Camera")),
+            SplitCamera(side),
+            Camera2d,
+            Camera { order, ..default() },
+            StateScoped(Screen::Gameplay),
+        ));
+    }
+    commands
+        .entity(main_entity)
+        .insert(RenderLayers::layer(HUD_ONLY_LAYER));
+    main_camera.clear_color = ClearColorConfig::None;
+}
+
+fn show_world_on_main_camera(commands: &mut Commands, entity: Entity, camera: &mut Camera) {
+    commands.entity(entity).remove::<RenderLayers>();
+    camera.clear_color = ClearColorConfig::Default;
+}
+
+/// Keeps each split camera on its half of the window, framing its half of
+/// the court
+fn frame_split_cameras(
+    window: Single<&Window, With<PrimaryWindow>>,
+    court: Res<CourtSize>,
+    mut split_cameras: Query<(&SplitCamera, &mut Camera, &mut Transform, &mut Projection)>,
+) {
+    let physical = window.physical_size();
+    let half_width = physical.x / 2;
+    if half_width == 0 || physical.y == 0 {
+        return;
+    }
+
+    // Fit half the court (plus its boundary) into half the window
+    let half_court = Vec2::new(
+        court.half_width() + BOUNDARY_THICKNESS,
+        court.height + 2.0 * BOUNDARY_THICKNESS,
+    );
+    let half_window = Vec2::new(window.width() / 2.0, window.height());
+    let scale = (half_court / half_window).max_element();
+
+    for (split, mut camera, mut transform, mut projection) in &mut split_cameras {
+        let (viewport_x, center_x) = match split.0 {
+            PlayerSide::Left => (0, -court.width / 4.0),
+            PlayerSide::Right => (half_width, court.width / 4.0),
+        };
+        camera.viewport = Some(Viewport {
+            physical_position: UVec2::new(viewport_x, 0),
+            physical_size: UVec2::new(half_width, physical.y),
+            ..default()
+        });
+        transform.translation.x = center_x;
+        transform.translation.y = 0.0;
+        if let Projection::Orthographic(ortho) = &mut *projection {
+            ortho.scale = scale;
+        }
+    }
+}
+
+fn reset_camera(
+    mut commands: Commands,
+    camera: Single<
+        (Entity, &mut Camera, &mut Transform, &mut Projection),
+        (With<Camera2d>, Without<SplitCamera>),
+    >,
+) {
+    commands.remove_resource::<GoalPunch>();
+
+    // The split cameras themselves are scoped to gameplay
+    let (entity, mut camera, mut transform, mut projection) = camera.into_inner();
+    show_world_on_main_camera(&mut commands, entity, &mut camera);
+    transform.translation = Vec3::new(0.0, 0.0, transform.translation.z);
+    if let Projection::Orthographic(ortho) = &mut *projection {
+        ortho.scale = 1.0;
+    }
+}
@@ -0,0 +1,218 @@
+//! Two-ball chaos: both balls stay in play for the whole match.
+//!
+//! A goal only takes out the ball that scored. A fresh ball is served from the
+//! center after a short delay while the other keeps going, so the match never
+//! stops for a serve. Goals that land close together are called out as one.
+
+use bevy::prelude::*;
+
+use super::{
+    GamePhase,
+    ball::{ServeDirection, ServeRng, ServedBy, serve_ball, spawn_ball},
+    config::GameConfig,
+    player::PlayerSide,
+    profile::PlayerProfiles,
+    rules::{MatchRules, ScoringSystem},
+    scoring::GoalScored,
+};
+use crate::{AppSystems, PausableSystems, screens::Screen, theme::tween::Toast};
+
+/// Pause before a scoring ball is replaced by a fresh serve
+const RESERVE_DELAY: f32 = 1.0; // seconds
+
+// Goal callout
+const CALLOUT_DURATION: f32 = 1.2; // seconds since the latest goal
+const CALLOUT_MERGE_WINDOW: f32 = 0.4; // goals closer than this share a callout
+const CALLOUT_FONT_SIZE: f32 = 32.0;
+const CALLOUT_Y_OFFSET: f32 = 160.0; // Distance from top
+const CALLOUT_COLOR: Color = Color::srgb(1.0, 0.85, 0.3);
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<PendingServe>();
+    app.add_observer(call_out_goal);
+    app.add_systems(
+        Update,
+        serve_pending_balls
+            .in_set(AppSystems::Update)
+            .run_if(in_state(GamePhase::Playing))
+            .run_if(in_state(Screen::Gameplay))
+            .in_set(PausableSystems),
+    );
+}
+
+/// A ball waiting at the center to be served
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct PendingServe {
+    side: PlayerSide,
+    timer: Timer,
+}
+
+/// Replaces a ball that scored with a new one, served by `side` after a delay
+pub(super) fn reserve_ball(
+    commands: &mut Commands,
+    meshes: &mut ResMut<Assets<Mesh>>,
+    materials: &mut ResMut<Assets<ColorMaterial>>,
+    side: PlayerSide,
+) {
+    let ball = spawn_ball(commands, meshes, materials);
+    commands.entity(ball).insert(PendingServe {
+        side,
+        timer: Timer::from_seconds(RESERVE_DELAY, TimerMode::Once),
+    });
+}
+
+fn serve_pending_balls(
+    mut commands: Commands,
+    time: Res<Time>,
+    config: Res<GameConfig>,
+    mut rng: ResMut<ServeRng>,
+    mut balls: Query<(Entity, &mut PendingServe)>,
+) {
+    for (ball, mut pending) in &mut balls {
+        pending.timer.tick(time.delta());
+        if !pending.timer.finished() {
+            continue;
+        }
+
+        let serve_direction = ServeDirection {
+            side: pending.side,
+            serves_in_turn: 0,
+        };
+        serve_ball(
+            &mut commands,
+            ball,
+            &serve_direction,
+            rng.draw_serve(&config.ball),
+        );
+        commands.entity(ball).remove::<PendingServe>();
+    }
+}
+
+/// How a goal in the callout was called
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum GoalCall {
+    Goal,
+    Fault,
+    OwnGoal,
+    /// An own goal that doesn't count under the rules
+    Replayed,
+    /// The receiver won the rally under side-out scoring
+    SideOut,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct CalledGoal {
+    side: PlayerSide,
+    call: GoalCall,
+}
+
+/// Callout listing the goals scored in quick succession
+#[derive(Component)]
+struct GoalCallout {
+    goals: Vec<CalledGoal>,
+}
+
+impl GoalCallout {
+    fn text(&self) -> String {
+        let lines = self.goals.iter().map(|goal| match goal.call {
+            GoalCall::Goal => format!("+1 {:?}", goal.side),
+            GoalCall::Fault => format!("+1 {:?} (fault)", goal.side),
+            GoalCall::OwnGoal => format!("+1 {:?} (own goal)", goal.side),
+            GoalCall::Replayed => "Own goal - replayed".to_string(),
+            GoalCall::SideOut => format!("Side out - {:?} serves", goal.side),
+        });
+
+        match self.goals.as_slice() {
+            [_] => lines.collect(),
+            _ if self.one_side().is_some() => {
+                format!("DOUBLE GOAL!\n{}", lines.collect::<Vec<_>>().join("\n"))
+            }
+            _ => format!("TRADED GOALS!\n{}", lines.collect::<Vec<_>>().join("\n")),
+        }
+    }
+
+    /// The side behind every goal in the callout, if it's just one
+    fn one_side(&self) -> Option<PlayerSide> {
+        let first = self.goals.first()?.side;
+        self.goals
+            .iter()
+            .all(|goal| goal.side == first)
+            .then_some(first)
+    }
+
+    /// The callout's color: the scorer's, or neutral if both sides scored
+    fn color(&self, profiles: &PlayerProfiles) -> Color {
+        self.one_side()
+            .map_or(CALLOUT_COLOR, |side| profiles.color(side))
+    }
+}
+
+fn call_out_goal(
+    trigger: Trigger<GoalScored>,
+    mut commands: Commands,
+    rules: Res<MatchRules>,
+    profiles: Res<PlayerProfiles>,
+    served_by: Query<&ServedBy>,
+    mut callouts: Query<(&mut GoalCallout, &mut Toast, &mut Text, &mut TextColor)>,
+) {
+    if !rules.two_balls {
+        return;
+    }
+    let event = trigger.event();
+    let side_out = rules.scoring == ScoringSystem::SideOut
+        && served_by
+            .get(event.ball)
+            .is_ok_and(|served_by| served_by.0 != event.side);
+    let call = if event.own_goal && !rules.own_goals {
+        GoalCall::Replayed
+    } else if side_out {
+        GoalCall::SideOut
+    } else if event.own_goal {
+        GoalCall::OwnGoal
+    } else if event.fault {
+        GoalCall::Fault
+    } else {
+        GoalCall::Goal
+    };
+    let goal = CalledGoal {
+        side: event.side,
+        call,
+    };
+
+    // Merge with a goal that has only just been called out
+    if let Ok((mut callout, mut toast, mut text, mut color)) = callouts.single_mut() {
+        // The toast restarts with each goal, so its age is the time since the latest
+        if toast.age() < CALLOUT_MERGE_WINDOW {
+            callout.goals.push(goal);
+        } else {
+            callout.goals = vec![goal];
+        }
+        toast.restart();
+        text.0 = callout.text();
+        color.0 = callout.color(&profiles);
+        return;
+    }
+
+    let callout = GoalCallout { goals: vec![goal] };
+    commands.spawn((
+        Name::new("Goal Callout"),
+        Text::new(callout.text()),
+        TextFont {
+            font_size: CALLOUT_FONT_SIZE,
+            ..default()
+        },
+        TextColor(callout.color(&profiles)),
+        TextLayout::new_with_justify(JustifyText::Center),
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            top: Val::Px(CALLOUT_Y_OFFSET),
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        callout,
+        Toast::new(CALLOUT_DURATION),
+        StateScoped(Screen::Gameplay),
+    ));
+}
@@ -0,0 +1,168 @@
+//! Practice mode's coach: marks where the ball will reach the player's
+//! paddle, and rates each return as early, late or centred, to help new
+//! players learn where to stand.
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+
+use super::{
+    ball::{BALL_RADIUS, Ball, ball_wall_y, predict_crossing},
+    court::{CourtInset, CourtSize},
+    floating_text::floating_text,
+    player::{MirroredCourt, PaddleHit, PaddleSize, Player, PlayerSide},
+    rules::MatchRules,
+};
+use crate::{AppSystems, screens::Screen};
+
+/// Contacts this close to the middle of the paddle, as a fraction of its half
+/// height, count as centred
+const CENTER_ZONE: f32 = 0.35;
+/// Paddles slower than this (px/s) count as standing still
+const STILL_SPEED: f32 = 20.0;
+const MARKER_RADIUS: f32 = 14.0;
+const MARKER_COLOR: Color = Color::srgba(0.5, 1.0, 0.6, 0.8);
+const CENTER_COLOR: Color = Color::srgb(0.5, 1.0, 0.6);
+const MISS_COLOR: Color = Color::srgb(1.0, 0.75, 0.3);
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<Practice>();
+    app.register_type::<CoachHints>();
+    app.init_resource::<CoachHints>();
+
+    app.add_observer(rate_return);
+    app.add_systems(
+        Update,
+        draw_intercept
+            .in_set(AppSystems::Update)
+            .run_if(in_state(Screen::Gameplay).and(coaching)),
+    );
+    app.add_systems(OnEnter(Screen::Title), end_practice);
+}
+
+/// Present while a practice match is running
+#[derive(Resource, Debug, Clone, Copy, Default, Reflect)]
+#[reflect(Resource)]
+pub struct Practice;
+
+/// Whether the coach shows hints in practice matches
+#[derive(Resource, Debug, Clone, Copy, Reflect)]
+#[reflect(Resource)]
+pub struct CoachHints(pub bool);
+
+impl Default for CoachHints {
+    fn default() -> Self {
+        Self(true)
+    }
+}
+
+fn coaching(practice: Option<Res<Practice>>, hints: Res<CoachHints>) -> bool {
+    practice.is_some() && hints.0
+}
+
+/// How well a return was lined up
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ReturnRating {
+    Early,
+    Late,
+    Center,
+}
+
+impl ReturnRating {
+    /// Rates a contact `offset` from the paddle's middle (as a fraction of
+    /// its half height) while the paddle moves at `paddle_speed`. Catching
+    /// the ball on the edge the paddle is moving toward means it was still
+    /// getting there; on the trailing edge, it got there too soon.
+    fn rate(offset: f32, paddle_speed: f32) -> Self {
+        if offset.abs() <= CENTER_ZONE {
+            Self::Center
+        } else if paddle_speed.abs() < STILL_SPEED || offset * paddle_speed > 0.0 {
+            Self::Late
+        } else {
+            Self::Early
+        }
+    }
+
+    fn text(self) -> &'static str {
+        match self {
+            Self::Early => "EARLY",
+            Self::Late => "LATE",
+            Self::Center => "CENTER!",
+        }
+    }
+
+    fn color(self) -> Color {
+        match self {
+            Self::Center => CENTER_COLOR,
+            Self::Early | Self::Late => MISS_COLOR,
+        }
+    }
+}
+
+/// Circles the spot each incoming ball will reach the coached paddle
+fn draw_intercept(
+    mut gizmos: Gizmos,
+    mirrored: Res<MirroredCourt>,
+    rules: Res<MatchRules>,
+    inset: Res<CourtInset>,
+    court: Res<CourtSize>,
+    paddles: Query<(&Player, &Transform, &PaddleSize)>,
+    balls: Query<(&Transform, &LinearVelocity), With<Ball>>,
+) {
+    // The coach only watches the player on the keyboard
+    let coached = mirrored.home_side();
+    let Some((_, paddle, size)) = paddles.iter().find(|(player, ..)| player.side == coached) else {
+        return;
+    };
+    let (face_x, incoming) = match coached {
+        PlayerSide::Left => (paddle.translation.x + size.0.x / 2.0 + BALL_RADIUS, -1.0),
+        PlayerSide::Right => (paddle.translation.x - size.0.x / 2.0 - BALL_RADIUS, 1.0),
+    };
+    let wall_y = ball_wall_y(&rules, &inset, *court);
+
+    for (ball, velocity) in &balls {
+        // Only balls on their way in
+        if velocity.x * incoming <= 0.0 {
+            continue;
+        }
+        let Some((intercept, _)) =
+            predict_crossing(ball.translation.truncate(), velocity.0, face_x, wall_y)
+        else {
+            continue;
+        };
+        gizmos.circle_2d(intercept, MARKER_RADIUS, MARKER_COLOR);
+        gizmos.line_2d(
+            Vec2::new(paddle.translation.x, intercept.y),
+            intercept,
+            MARKER_COLOR,
+        );
+    }
+}
+
+fn rate_return(
+    trigger: Trigger<PaddleHit>,
+    mut commands: Commands,
+    practice: Option<Res<Practice>>,
+    hints: Res<CoachHints>,
+    mirrored: Res<MirroredCourt>,
+    paddles: Query<(&Transform, &LinearVelocity, &PaddleSize)>,
+) {
+    let hit = trigger.event();
+    if practice.is_none() || !hints.0 || hit.side != mirrored.home_side() {
+        return;
+    }
+    let Ok((paddle, velocity, size)) = paddles.get(hit.paddle) else {
+        return;
+    };
+
+    let offset = (hit.contact_point.y - paddle.translation.y) / (size.0.y / 2.0);
+    let rating = ReturnRating::rate(offset, velocity.y);
+    commands.spawn(floating_text(
+        rating.text(),
+        hit.contact_point,
+        rating.color(),
+    ));
+}
+
+fn end_practice(mut commands: Commands) {
+    commands.remove_resource::<Practice>();
+}
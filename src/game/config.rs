@@ -0,0 +1,197 @@
+//! Gameplay numbers in one place, loaded from `assets/config/game.config.json`.
+//!
+//! The defaults for every tunable number live here, in the `Default` impls
+//! below, and the file only has to list the values it changes. It's watched
+//! like any other asset, so with the `dev_native` feature, saving it
+//! mid-match takes effect straight away.
+//! Mods, settings and tests can replace the resource the same way. The
+//! court's size isn't in here: it's fixed for the app's lifetime by the
+//! plugin, since the rest of the game is laid out from it.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::asset_tracking::{JsonAsset, JsonAssetLoader};
+
+pub const CONFIG_PATH: &str = "config/game.config.json";
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_asset::<GameConfig>();
+    app.register_asset_loader(JsonAssetLoader::<GameConfig>::default());
+    app.register_type::<GameConfig>();
+    app.init_resource::<GameConfig>();
+    app.init_resource::<GameConfigHandle>();
+
+    app.add_systems(Update, apply_game_config);
+}
+
+/// Tunable gameplay values, grouped by the part of the game they shape
+#[derive(
+    Resource, Asset, Debug, Clone, Copy, PartialEq, Default, Reflect, Serialize, Deserialize,
+)]
+#[reflect(Resource)]
+#[serde(default)]
+pub struct GameConfig {
+    pub ball: BallConfig,
+    pub paddle: PaddleConfig,
+    pub court: CourtConfig,
+    pub scoring: ScoringConfig,
+}
+
+impl JsonAsset for GameConfig {
+    const EXTENSIONS: &'static [&'static str] = &["config.json"];
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Reflect, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BallConfig {
+    /// Serve speed before ball kinds and mutators, in pixels per second
+    pub speed: f32,
+    /// Hard cap on ball speed, in pixels per second
+    pub max_speed: f32,
+    pub restitution: f32,
+    /// Share of the paddle's vertical velocity passed on to the ball
+    pub paddle_velocity_transfer: f32,
+    /// Speed multiplier for a smashed return
+    pub smash_speed_up: f32,
+    /// Balls faster than this knock back the paddle they hit, in pixels per second
+    pub knockback_min_speed: f32,
+    /// Knockback speed per unit of ball speed over the minimum
+    pub knockback_per_speed: f32,
+    /// Serve angles from horizontal, in degrees
+    pub min_serve_angle: f32,
+    pub max_serve_angle: f32,
+}
+
+impl Default for BallConfig {
+    fn default() -> Self {
+        Self {
+            speed: 300.0,
+            // Keeps the ball from outrunning swept CCD
+            max_speed: 1200.0,
+            // Perfectly elastic bounces
+            restitution: 1.0,
+            paddle_velocity_transfer: 0.5,
+            smash_speed_up: 1.5,
+            knockback_min_speed: 600.0,
+            knockback_per_speed: 0.4,
+            // Nothing too steep, for better rallies
+            min_serve_angle: 15.0,
+            max_serve_angle: 45.0,
+        }
+    }
+}
+
+impl BallConfig {
+    /// A random serve angle in range, without its up or down sign
+    pub fn serve_angle(&self, rng: &mut impl rand::Rng) -> f32 {
+        let (low, high) = self.serve_angles();
+        rng.random_range(low..=high)
+    }
+
+    /// The lowest and highest serve angles, however they were entered
+    pub fn serve_angles(&self) -> (f32, f32) {
+        let (a, b) = (self.min_serve_angle, self.max_serve_angle);
+        (a.min(b), a.max(b))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Reflect, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PaddleConfig {
+    /// Top speed before handicaps and characters, in pixels per second
+    pub max_speed: f32,
+    /// Width of a paddle (not a character), in pixels
+    pub width: f32,
+    /// Height before handicaps, characters and mutators, in pixels
+    pub height: f32,
+    /// How quickly a released or reversing paddle slows down, as a
+    /// multiplier on the movement feel's deceleration
+    pub damping: f32,
+    pub restitution: f32,
+    /// How hard a knocked-back paddle springs back to its line, per second squared
+    pub knockback_stiffness: f32,
+    /// How quickly a knocked-back paddle stops moving, per second
+    pub knockback_damping: f32,
+    /// Furthest a paddle can be pushed behind its line, in pixels
+    pub max_knockback: f32,
+}
+
+impl Default for PaddleConfig {
+    fn default() -> Self {
+        Self {
+            max_speed: 400.0,
+            width: 12.0,
+            // An eighth of the court height
+            height: 75.0,
+            damping: 1.0,
+            restitution: 1.0,
+            knockback_stiffness: 120.0,
+            knockback_damping: 14.0,
+            max_knockback: 30.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Reflect, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CourtConfig {
+    pub wall_restitution: f32,
+}
+
+impl Default for CourtConfig {
+    fn default() -> Self {
+        Self {
+            wall_restitution: 1.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Reflect, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScoringConfig {
+    /// Seconds play stops for after a goal
+    pub goal_pause: f32,
+}
+
+impl Default for ScoringConfig {
+    fn default() -> Self {
+        Self { goal_pause: 1.0 }
+    }
+}
+
+/// The config file, kept loaded so edits to it are picked up
+#[derive(Resource)]
+struct GameConfigHandle(Handle<GameConfig>);
+
+impl FromWorld for GameConfigHandle {
+    fn from_world(world: &mut World) -> Self {
+        Self(world.resource::<AssetServer>().load(CONFIG_PATH))
+    }
+}
+
+/// Replaces the config with the file once it loads, and whenever it changes
+fn apply_game_config(
+    mut events: EventReader<AssetEvent<GameConfig>>,
+    handle: Res<GameConfigHandle>,
+    files: Res<Assets<GameConfig>>,
+    mut config: ResMut<GameConfig>,
+) {
+    let modified = events
+        .read()
+        .filter(|event| {
+            event.is_modified(&handle.0) || event.is_loaded_with_dependencies(&handle.0)
+        })
+        .count()
+        > 0;
+    if !modified {
+        return;
+    }
+    let Some(file) = files.get(&handle.0) else {
+        return;
+    };
+    if *file != *config {
+        *config = *file;
+        info!("Game config: loaded {CONFIG_PATH}");
+    }
+}
@@ -4,64 +4,126 @@ use avian2d::prelude::*;
 use bevy::prelude::*;
 
 use super::{
-    ball::Ball,
-    physics::{BOUNDARY_FRICTION, BOUNDARY_RESTITUTION, boundary_layers, goal_layers},
-    player::PlayerSide,
-    scoring::GoalScored,
+    config::GameConfig,
+    court_theme::CourtLine,
+    goal::{
+        EndGoal, Goal, OUT_OF_BOUNDS_HEIGHT, spawn_goal, spawn_goal_mouth, spawn_out_of_bounds,
+    },
+    physics::{BOUNDARY_FRICTION, boundary_layers, side_line_layers},
+    rules::{CourtLayout, MatchRules},
+    wall_damage::WallSegment,
 };
+use crate::screens::Screen;
 
 pub(super) fn plugin(app: &mut App) {
     app.register_type::<Court>();
-    app.register_type::<Goal>();
+    app.register_type::<Boundary>();
+    app.register_type::<CourtSize>();
+    app.init_resource::<CourtSize>();
+    app.register_type::<CourtInset>();
+    app.init_resource::<CourtInset>();
+    app.add_systems(OnEnter(Screen::Gameplay), reset_court_inset);
+    app.add_systems(
+        Update,
+        apply_court_inset.run_if(resource_changed::<CourtInset>.and(in_state(Screen::Gameplay))),
+    );
 }
 
-// Court dimensions
-pub const COURT_WIDTH: f32 = 800.0;
-pub const COURT_HEIGHT: f32 = 600.0;
 // const COURT_PADDING: f32 = 50.0;  // Reserved for future use
 
 // Boundary dimensions
-const BOUNDARY_THICKNESS: f32 = 8.0;
+pub const BOUNDARY_THICKNESS: f32 = 8.0;
 
 // Center line properties
 const CENTER_LINE_WIDTH: f32 = 4.0;
 const CENTER_LINE_DASH_HEIGHT: f32 = 20.0;
 const CENTER_LINE_GAP: f32 = 15.0;
 
-// Goal area dimensions (sensor colliders)
-const GOAL_WIDTH: f32 = 50.0;
-const GOAL_HEIGHT: f32 = COURT_HEIGHT;
+// Breakable walls are split into this many segments
+const WALL_SEGMENTS: usize = 8;
 
 // Colors
-const LINE_COLOR: Color = Color::WHITE;
+pub const LINE_COLOR: Color = Color::WHITE;
+pub const OPEN_LINE_COLOR: Color = Color::srgba(1.0, 1.0, 1.0, 0.35);
 // const COURT_BACKGROUND_COLOR: Color = Color::BLACK;  // Reserved for future use
 
 // Z-ordering
-const COURT_Z: f32 = -1.0; // Behind game objects
+pub const COURT_Z: f32 = -1.0; // Behind game objects
 
 /// Marker component for the court entity
 #[derive(Component, Reflect)]
 #[reflect(Component)]
 pub struct Court;
 
-/// Goal area sensor for detecting scoring
-#[derive(Component, Debug, Clone, Copy, Reflect)]
-#[reflect(Component)]
-pub enum Goal {
-    Left,
-    Right,
+/// The size of the court in world units, from the
+/// [`PaddlegeddonPlugin`](crate::PaddlegeddonPlugin). Everything on and
+/// around the court is laid out from it.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Resource)]
+pub struct CourtSize {
+    pub width: f32,
+    pub height: f32,
+}
+
+impl Default for CourtSize {
+    fn default() -> Self {
+        Self {
+            width: 800.0,
+            height: 600.0,
+        }
+    }
+}
+
+impl CourtSize {
+    pub fn half_width(self) -> f32 {
+        self.width / 2.0
+    }
+
+    pub fn half_height(self) -> f32 {
+        self.height / 2.0
+    }
+
+    pub fn extent(self) -> Vec2 {
+        Vec2::new(self.width, self.height)
+    }
 }
 
+/// Marker for the top and bottom walls
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct Boundary;
+
+/// How far the top and bottom of the court have closed in, in pixels from
+/// each side. Walls thicken inward and paddles stay inside them.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Default, Reflect)]
+#[reflect(Resource)]
+pub struct CourtInset(pub f32);
+
 /// Spawns the complete court with boundaries and center line
 pub fn spawn_court(
     commands: &mut Commands,
     meshes: &mut Assets<Mesh>,
     materials: &mut Assets<ColorMaterial>,
+    rules: &MatchRules,
+    config: &GameConfig,
+    size: CourtSize,
 ) -> Entity {
+    let layout = rules.court;
+    let restitution = config.court.wall_restitution;
     let line_material = materials.add(LINE_COLOR);
 
+    // Open courts draw faded side lines that don't stop the ball
+    let (side_line_material, side_line_layers, side_line) = match layout {
+        CourtLayout::Walled => (line_material.clone(), boundary_layers(), CourtLine::Solid),
+        CourtLayout::Open => (
+            materials.add(OPEN_LINE_COLOR),
+            side_line_layers(),
+            CourtLine::Faded,
+        ),
+    };
+
     // Calculate boundary positions
-    let half_height = COURT_HEIGHT / 2.0;
+    let half_height = size.half_height();
 
     // Create the main court entity
     let court_entity = commands
@@ -73,65 +135,156 @@ pub fn spawn_court(
         ))
         .id();
 
-    // Spawn top boundary
-    let top_boundary = commands
-        .spawn((
-            Name::new("Top Boundary"),
-            RigidBody::Static,
-            Collider::rectangle(COURT_WIDTH, BOUNDARY_THICKNESS),
-            boundary_layers(),
-            // Physics material properties for boundaries
-            Friction::new(BOUNDARY_FRICTION),
-            Restitution::new(BOUNDARY_RESTITUTION),
-            Mesh2d(meshes.add(Rectangle::new(COURT_WIDTH, BOUNDARY_THICKNESS))),
-            MeshMaterial2d(line_material.clone()),
-            Transform::from_xyz(0.0, half_height - BOUNDARY_THICKNESS / 2.0, 0.0),
-        ))
-        .id();
-
-    // Spawn bottom boundary
-    let bottom_boundary = commands
-        .spawn((
-            Name::new("Bottom Boundary"),
-            RigidBody::Static,
-            Collider::rectangle(COURT_WIDTH, BOUNDARY_THICKNESS),
-            boundary_layers(),
-            // Physics material properties for boundaries
-            Friction::new(BOUNDARY_FRICTION),
-            Restitution::new(BOUNDARY_RESTITUTION),
-            Mesh2d(meshes.add(Rectangle::new(COURT_WIDTH, BOUNDARY_THICKNESS))),
-            MeshMaterial2d(line_material.clone()),
-            Transform::from_xyz(0.0, -half_height + BOUNDARY_THICKNESS / 2.0, 0.0),
-        ))
-        .id();
+    // Walls only break on a walled court
+    let breakable = rules.wall_damage && layout == CourtLayout::Walled;
+
+    // Spawn top and bottom boundaries
+    let mut boundaries = Vec::new();
+    for (name, y) in [
+        ("Top", half_height - BOUNDARY_THICKNESS / 2.0),
+        ("Bottom", -half_height + BOUNDARY_THICKNESS / 2.0),
+    ] {
+        if breakable {
+            boundaries.extend(spawn_wall_segments(
+                commands,
+                meshes,
+                materials,
+                name,
+                y,
+                size.width,
+                restitution,
+            ));
+            continue;
+        }
+
+        boundaries.push(
+            commands
+                .spawn((
+                    Name::new(format!("{name} Boundary")),
+                    Boundary,
+                    RigidBody::Static,
+                    Collider::rectangle(size.width, BOUNDARY_THICKNESS),
+                    side_line_layers,
+                    // Physics material properties for boundaries
+                    Friction::new(BOUNDARY_FRICTION),
+                    Restitution::new(restitution),
+                    Mesh2d(meshes.add(Rectangle::new(size.width, BOUNDARY_THICKNESS))),
+                    MeshMaterial2d(side_line_material.clone()),
+                    side_line,
+                    Transform::from_xyz(0.0, y, 0.0),
+                ))
+                .id(),
+        );
+    }
 
     // Spawn center line
-    let center_line = spawn_center_line(commands, meshes, line_material);
+    let center_line = spawn_center_line(commands, meshes, line_material, size.height);
 
     // Spawn goal sensors
-    let left_goal = spawn_goal(commands, Goal::Left);
-    let right_goal = spawn_goal(commands, Goal::Right);
+    let (left_goal, right_goal) = if rules.moving_goals {
+        (
+            spawn_goal_mouth(commands, meshes, materials, EndGoal::Left, size),
+            spawn_goal_mouth(commands, meshes, materials, EndGoal::Right, size),
+        )
+    } else {
+        (
+            spawn_goal(commands, EndGoal::Left, size),
+            spawn_goal(commands, EndGoal::Right, size),
+        )
+    };
 
     // Make boundaries, center line, and goals children of the court
-    commands.entity(court_entity).add_children(&[
-        top_boundary,
-        bottom_boundary,
-        center_line,
-        left_goal,
-        right_goal,
-    ]);
+    commands.entity(court_entity).add_children(&boundaries);
+    commands
+        .entity(court_entity)
+        .add_children(&[center_line, left_goal, right_goal]);
+
+    // Moving goals leave the rest of the end lines to bounce off
+    if rules.moving_goals {
+        let end_line_material = materials.add(LINE_COLOR);
+        let end_line_mesh = meshes.add(Rectangle::new(BOUNDARY_THICKNESS, size.height));
+        for (name, x) in [
+            ("Left End Line", -(size.width + BOUNDARY_THICKNESS) / 2.0),
+            ("Right End Line", (size.width + BOUNDARY_THICKNESS) / 2.0),
+        ] {
+            let end_line = commands
+                .spawn((
+                    Name::new(name),
+                    Boundary,
+                    RigidBody::Static,
+                    Collider::rectangle(BOUNDARY_THICKNESS, size.height),
+                    boundary_layers(),
+                    Friction::new(BOUNDARY_FRICTION),
+                    Restitution::new(restitution),
+                    Mesh2d(end_line_mesh.clone()),
+                    MeshMaterial2d(end_line_material.clone()),
+                    CourtLine::Solid,
+                    Transform::from_xyz(x, 0.0, 0.0),
+                ))
+                .id();
+            commands.entity(court_entity).add_child(end_line);
+        }
+    }
+
+    // Balls can leave an open court, or one whose walls have broken
+    if layout == CourtLayout::Open || breakable {
+        let sensor_y = half_height + OUT_OF_BOUNDS_HEIGHT / 2.0;
+        let top_sensor = spawn_out_of_bounds(commands, sensor_y, size.width);
+        let bottom_sensor = spawn_out_of_bounds(commands, -sensor_y, size.width);
+        commands
+            .entity(court_entity)
+            .add_children(&[top_sensor, bottom_sensor]);
+    }
 
     court_entity
 }
 
+/// Spawns a top or bottom wall as a row of breakable segments
+fn spawn_wall_segments(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<ColorMaterial>,
+    name: &str,
+    y_position: f32,
+    width: f32,
+    restitution: f32,
+) -> Vec<Entity> {
+    let segment_width = width / WALL_SEGMENTS as f32;
+    let mesh = meshes.add(Rectangle::new(segment_width, BOUNDARY_THICKNESS));
+
+    (0..WALL_SEGMENTS)
+        .map(|i| {
+            let x = -width / 2.0 + segment_width * (i as f32 + 0.5);
+            commands
+                .spawn((
+                    Name::new(format!("{name} Wall Segment {i}")),
+                    Boundary,
+                    WallSegment::default(),
+                    RigidBody::Static,
+                    Collider::rectangle(segment_width, BOUNDARY_THICKNESS),
+                    boundary_layers(),
+                    Friction::new(BOUNDARY_FRICTION),
+                    Restitution::new(restitution),
+                    Mesh2d(mesh.clone()),
+                    // Each segment fades on its own as it takes damage
+                    MeshMaterial2d(materials.add(LINE_COLOR)),
+                    CourtLine::Solid,
+                    Transform::from_xyz(x, y_position, 0.0),
+                ))
+                .id()
+        })
+        .collect()
+}
+
 /// Spawns the decorative center dashed line
 fn spawn_center_line(
     commands: &mut Commands,
     meshes: &mut Assets<Mesh>,
     material: Handle<ColorMaterial>,
+    court_height: f32,
 ) -> Entity {
     // Calculate the available space between boundaries
-    let available_height = COURT_HEIGHT - (BOUNDARY_THICKNESS * 2.0);
+    let available_height = court_height - (BOUNDARY_THICKNESS * 2.0);
 
     // Calculate how many complete dash+gap patterns we can fit
     // We want to start and end with a gap for better visual balance
@@ -165,6 +318,7 @@ fn spawn_center_line(
                 Name::new(format!("Dash {i}")),
                 Mesh2d(dash_mesh.clone()),
                 MeshMaterial2d(material.clone()),
+                CourtLine::Solid,
                 Transform::from_xyz(0.0, y, 0.0),
             ))
             .id();
@@ -179,51 +333,35 @@ fn spawn_center_line(
     center_line_entity
 }
 
-/// Spawns a goal sensor area
-fn spawn_goal(commands: &mut Commands, goal: Goal) -> Entity {
-    // Position goals just inside the court edges, overlapping with the play area
-    let x_position = match goal {
-        Goal::Left => -(COURT_WIDTH / 2.0 - GOAL_WIDTH / 2.0),
-        Goal::Right => COURT_WIDTH / 2.0 - GOAL_WIDTH / 2.0,
-    };
+fn reset_court_inset(mut inset: ResMut<CourtInset>) {
+    inset.0 = 0.0;
+}
 
-    let goal_side = goal;
-    commands
-        .spawn((
-            Name::new(format!("{goal:?} Goal")),
-            goal,
-            // Sensor collider - doesn't physically block but detects overlaps
-            Sensor,
-            Collider::rectangle(GOAL_WIDTH, GOAL_HEIGHT),
-            goal_layers(),
-            Transform::from_xyz(x_position, 0.0, 0.0),
-            // Enable collision events for observer-based detection
-            CollisionEventsEnabled,
-        ))
-        .observe(
-            move |trigger: Trigger<OnCollisionStart>,
-                  mut commands: Commands,
-                  ball_query: Query<&Ball>| {
-                let other_entity = trigger.event().collider;
-
-                // Check if the colliding entity is a ball
-                if ball_query.contains(other_entity) {
-                    // Determine which side scores based on which goal was hit
-                    let scoring_side = match goal_side {
-                        Goal::Left => PlayerSide::Right, // Ball in left goal = right player scores
-                        Goal::Right => PlayerSide::Left, // Ball in right goal = left player scores
-                    };
-
-                    info!(
-                        "Goal detected! {} scores",
-                        match scoring_side {
-                            PlayerSide::Left => "Left player",
-                            PlayerSide::Right => "Right player",
-                        }
-                    );
-                    commands.trigger(GoalScored { side: scoring_side });
-                }
-            },
-        )
-        .id()
+/// Thickens the top and bottom walls inward and pulls the out-of-bounds
+/// sensors in with them
+fn apply_court_inset(
+    inset: Res<CourtInset>,
+    size: Res<CourtSize>,
+    mut walls: Query<&mut Transform, With<Boundary>>,
+    mut sensors: Query<(&Goal, &mut Transform), Without<Boundary>>,
+) {
+    let half_height = size.half_height();
+    let thickness = BOUNDARY_THICKNESS + inset.0;
+
+    for mut transform in &mut walls {
+        // End lines sit on the center line and don't move
+        if transform.translation.y == 0.0 {
+            continue;
+        }
+        let side = transform.translation.y.signum();
+        transform.translation.y = side * (half_height - thickness / 2.0);
+        transform.scale.y = thickness / BOUNDARY_THICKNESS;
+    }
+
+    for (goal, mut transform) in &mut sensors {
+        if let Goal::OutOfBounds = goal {
+            let side = transform.translation.y.signum();
+            transform.translation.y = side * (half_height - inset.0 + OUT_OF_BOUNDS_HEIGHT / 2.0);
+        }
+    }
 }
@@ -0,0 +1,180 @@
+//! Sounds for the ball coming off a paddle or a wall, for goals and for
+//! faked serves, each panned to where on the court it happened.
+//!
+//! They're either sampled, or with the retro [`SoundStyle`], square-wave
+//! blips synthesized at startup in the spirit of the original Pong. The
+//! retro blips don't need any audio files.
+
+use bevy::prelude::*;
+
+use super::{court::CourtSize, events::GameEvent, player::PlayerSide};
+use crate::{asset_tracking::LoadResource, audio::positional_sound_effect, screens::Screen};
+
+// Retro blips, pitched and timed after the original Pong
+const RETRO_PADDLE: (f32, f32) = (459.0, 0.096); // Hz, seconds
+const RETRO_WALL: (f32, f32) = (226.0, 0.016);
+const RETRO_GOAL: (f32, f32) = (490.0, 0.257);
+const SAMPLE_RATE: u32 = 44_100;
+const RETRO_AMPLITUDE: f32 = 0.25;
+/// Samples faded out at the end of each blip, so it doesn't click
+const RETRO_FADE_SAMPLES: f32 = 88.0;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<CourtSounds>();
+    app.register_type::<SoundStyle>();
+    app.load_resource::<CourtSounds>();
+    app.init_resource::<RetroSounds>();
+    app.init_resource::<SoundStyle>();
+
+    app.add_systems(Update, play_court_sounds.run_if(in_state(Screen::Gameplay)));
+}
+
+/// Whether court sounds are sampled or synthesized
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Resource)]
+pub enum SoundStyle {
+    #[default]
+    Sampled,
+    Retro,
+}
+
+impl SoundStyle {
+    pub fn name(self) -> &'static str {
+        match self {
+            SoundStyle::Sampled => "Sampled",
+            SoundStyle::Retro => "Retro",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            SoundStyle::Sampled => SoundStyle::Retro,
+            SoundStyle::Retro => SoundStyle::Sampled,
+        }
+    }
+
+    pub fn previous(self) -> Self {
+        match self {
+            SoundStyle::Sampled => SoundStyle::Retro,
+            SoundStyle::Retro => SoundStyle::Sampled,
+        }
+    }
+}
+
+#[derive(Resource, Asset, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct CourtSounds {
+    #[dependency]
+    paddle: Handle<AudioSource>,
+    #[dependency]
+    wall: Handle<AudioSource>,
+    #[dependency]
+    goal: Handle<AudioSource>,
+}
+
+/// The retro blips, synthesized at startup
+#[derive(Resource)]
+struct RetroSounds {
+    paddle: Handle<AudioSource>,
+    wall: Handle<AudioSource>,
+    goal: Handle<AudioSource>,
+}
+
+impl FromWorld for RetroSounds {
+    fn from_world(world: &mut World) -> Self {
+        let mut sources = world.resource_mut::<Assets<AudioSource>>();
+        Self {
+            paddle: sources.add(square_wave(RETRO_PADDLE)),
+            wall: sources.add(square_wave(RETRO_WALL)),
+            goal: sources.add(square_wave(RETRO_GOAL)),
+        }
+    }
+}
+
+/// A square wave at `frequency` lasting `duration` seconds, as a 16-bit mono
+/// WAV file
+fn square_wave((frequency, duration): (f32, f32)) -> AudioSource {
+    let samples = (SAMPLE_RATE as f32 * duration) as u32;
+    let data_size = samples * 2;
+
+    let mut bytes = Vec::with_capacity(44 + data_size as usize);
+    bytes.extend_from_slice(b"RIFF");
+    bytes.extend_from_slice(&(36 + data_size).to_le_bytes());
+    bytes.extend_from_slice(b"WAVEfmt ");
+    bytes.extend_from_slice(&16u32.to_le_bytes()); // Format chunk size
+    bytes.extend_from_slice(&1u16.to_le_bytes()); // PCM
+    bytes.extend_from_slice(&1u16.to_le_bytes()); // Mono
+    bytes.extend_from_slice(&SAMPLE_RATE.to_le_bytes());
+    bytes.extend_from_slice(&(SAMPLE_RATE * 2).to_le_bytes()); // Bytes per second
+    bytes.extend_from_slice(&2u16.to_le_bytes()); // Bytes per sample
+    bytes.extend_from_slice(&16u16.to_le_bytes()); // Bits per sample
+    bytes.extend_from_slice(b"data");
+    bytes.extend_from_slice(&data_size.to_le_bytes());
+
+    for index in 0..samples {
+        let time = index as f32 / SAMPLE_RATE as f32;
+        let level = if (time * frequency).fract() < 0.5 {
+            RETRO_AMPLITUDE
+        } else {
+            -RETRO_AMPLITUDE
+        };
+        let fade = ((samples - index) as f32 / RETRO_FADE_SAMPLES).min(1.0);
+        let sample = (level * fade * i16::MAX as f32) as i16;
+        bytes.extend_from_slice(&sample.to_le_bytes());
+    }
+
+    AudioSource {
+        bytes: bytes.into(),
+    }
+}
+
+impl FromWorld for CourtSounds {
+    fn from_world(world: &mut World) -> Self {
+        let assets = world.resource::<AssetServer>();
+        Self {
+            paddle: assets.load("audio/sound_effects/paddle_hit.wav"),
+            wall: assets.load("audio/sound_effects/wall_bounce.wav"),
+            goal: assets.load("audio/sound_effects/goal.wav"),
+        }
+    }
+}
+
+fn play_court_sounds(
+    mut commands: Commands,
+    mut events: EventReader<GameEvent>,
+    court: Res<CourtSize>,
+    style: Res<SoundStyle>,
+    sampled: Option<Res<CourtSounds>>,
+    retro: Res<RetroSounds>,
+) {
+    let (paddle, wall, goal) = match (*style, &sampled) {
+        (SoundStyle::Sampled, Some(sampled)) => (&sampled.paddle, &sampled.wall, &sampled.goal),
+        (SoundStyle::Sampled, None) => {
+            events.clear();
+            return;
+        }
+        (SoundStyle::Retro, _) => (&retro.paddle, &retro.wall, &retro.goal),
+    };
+
+    for event in events.read() {
+        let (sound, position) = match *event {
+            GameEvent::PaddleHit { position, .. } => (paddle, position),
+            GameEvent::WallBounce { position, .. } => (wall, position),
+            // A swing at nothing, from the server's paddle
+            GameEvent::ServeFeint { side } => (paddle, Vec2::new(side.paddle_x(*court), 0.0)),
+            // Heard from the goal it went in
+            GameEvent::Goal { side, .. } => {
+                let x = match side {
+                    PlayerSide::Left => court.half_width(),
+                    PlayerSide::Right => -court.half_width(),
+                };
+                (goal, Vec2::new(x, 0.0))
+            }
+            _ => continue,
+        };
+        commands.spawn((
+            Name::new("Court Sound"),
+            positional_sound_effect(sound.clone(), position, *court),
+        ));
+    }
+}
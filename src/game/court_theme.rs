@@ -0,0 +1,243 @@
+//! The look of the court and the arena around it, loaded from
+//! `assets/themes/court.theme.json`.
+//!
+//! The file is watched like any other asset, so with the `dev_native`
+//! feature, saving it mid-match recolors the court lines and respawns the
+//! arena backdrop to match. Mods can add more themes to pick from in
+//! [`CourtThemeChoices`].
+
+use bevy::{color::HexColorError, prelude::*};
+use serde::Deserialize;
+
+use super::{
+    court::{LINE_COLOR, OPEN_LINE_COLOR},
+    rules::CourtLayout,
+};
+use crate::{
+    asset_tracking::{JsonAsset, JsonAssetLoader, LoadResource},
+    screens::Screen,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_asset::<CourtTheme>();
+    app.register_asset_loader(JsonAssetLoader::<CourtTheme>::default());
+    app.register_type::<CourtThemeAssets>();
+    app.load_resource::<CourtThemeAssets>();
+    app.register_type::<CourtLine>();
+    app.init_resource::<CourtThemeChoices>();
+
+    app.add_systems(
+        Update,
+        (
+            apply_theme_choice,
+            recolor_court_lines.run_if(in_state(Screen::Gameplay)),
+        )
+            .chain(),
+    );
+}
+
+/// Colors and backdrop settings for the court
+#[derive(Asset, TypePath, Deserialize, Debug, Clone)]
+pub struct CourtTheme {
+    /// Walls, end lines, goal posts and the center line
+    pub line: ThemeColor,
+    /// Side lines on an open court, which don't stop the ball
+    pub open_line: ThemeColor,
+    pub star: ThemeColor,
+    /// Spectators are picked from these at random
+    pub crowd: Vec<ThemeColor>,
+    /// The backdrop around a walled court
+    pub walled: ArenaStyle,
+    /// The backdrop around an open court
+    pub open: ArenaStyle,
+}
+
+impl Default for CourtTheme {
+    fn default() -> Self {
+        Self {
+            line: ThemeColor(LINE_COLOR),
+            open_line: ThemeColor(OPEN_LINE_COLOR),
+            star: ThemeColor(Color::srgba(1.0, 1.0, 1.0, 0.6)),
+            crowd: vec![
+                ThemeColor(Color::srgb(0.85, 0.35, 0.3)),
+                ThemeColor(Color::srgb(0.3, 0.55, 0.85)),
+                ThemeColor(Color::srgb(0.9, 0.8, 0.35)),
+                ThemeColor(Color::srgb(0.5, 0.75, 0.45)),
+            ],
+            walled: ArenaStyle {
+                stars: 80,
+                star_speed: 8.0,
+                crowd: true,
+            },
+            open: ArenaStyle {
+                stars: 240,
+                star_speed: 30.0,
+                crowd: false,
+            },
+        }
+    }
+}
+
+impl CourtTheme {
+    pub fn arena(&self, layout: CourtLayout) -> ArenaStyle {
+        match layout {
+            CourtLayout::Walled => self.walled,
+            CourtLayout::Open => self.open,
+        }
+    }
+}
+
+/// What the backdrop looks like around one court layout
+#[derive(Deserialize, Debug, Clone, Copy)]
+pub struct ArenaStyle {
+    pub stars: usize,
+    /// Scroll speed of the nearest stars, in pixels per second
+    pub star_speed: f32,
+    pub crowd: bool,
+}
+
+/// A color written as a hex string, e.g. `"#ffffff"` or `"#ffffff80"`
+#[derive(Deserialize, Debug, Clone, Copy)]
+#[serde(try_from = "String")]
+pub struct ThemeColor(pub Color);
+
+impl TryFrom<String> for ThemeColor {
+    type Error = HexColorError;
+
+    fn try_from(hex: String) -> Result<Self, Self::Error> {
+        Srgba::hex(hex).map(|color| ThemeColor(color.into()))
+    }
+}
+
+impl JsonAsset for CourtTheme {
+    const EXTENSIONS: &'static [&'static str] = &["theme.json"];
+}
+
+#[derive(Resource, Asset, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct CourtThemeAssets {
+    #[dependency]
+    pub theme: Handle<CourtTheme>,
+}
+
+impl FromWorld for CourtThemeAssets {
+    fn from_world(world: &mut World) -> Self {
+        let assets = world.resource::<AssetServer>();
+        Self {
+            theme: assets.load("themes/court.theme.json"),
+        }
+    }
+}
+
+impl CourtThemeAssets {
+    /// The current theme, or the built-in one if the file couldn't be read
+    pub fn get(&self, themes: &Assets<CourtTheme>) -> CourtTheme {
+        themes.get(&self.theme).cloned().unwrap_or_default()
+    }
+}
+
+/// Every court theme to pick from, the built-in one first
+#[derive(Resource, Debug)]
+pub struct CourtThemeChoices {
+    themes: Vec<(String, Handle<CourtTheme>)>,
+    selected: usize,
+}
+
+impl FromWorld for CourtThemeChoices {
+    fn from_world(world: &mut World) -> Self {
+        let assets = world.resource::<AssetServer>();
+        Self {
+            themes: vec![("Default".into(), assets.load("themes/court.theme.json"))],
+            selected: 0,
+        }
+    }
+}
+
+impl CourtThemeChoices {
+    /// Adds a theme, unless one with the same name is already there
+    pub fn add(&mut self, name: impl Into<String>, theme: Handle<CourtTheme>) -> bool {
+        let name = name.into();
+        if self.contains(&name) {
+            return false;
+        }
+        self.themes.push((name, theme));
+        true
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.themes.iter().any(|(theme, _)| theme == name)
+    }
+
+    /// Name of the theme in use
+    pub fn selected(&self) -> &str {
+        &self.themes[self.selected].0
+    }
+
+    pub fn next(&mut self) {
+        self.selected = (self.selected + 1) % self.themes.len();
+    }
+
+    pub fn previous(&mut self) {
+        self.selected = (self.selected + self.themes.len() - 1) % self.themes.len();
+    }
+}
+
+/// Swaps the picked theme in, which recolors the court and respawns the
+/// backdrop like an edit would
+fn apply_theme_choice(
+    choices: Res<CourtThemeChoices>,
+    theme_assets: Option<ResMut<CourtThemeAssets>>,
+) {
+    let Some(mut theme_assets) = theme_assets else {
+        return;
+    };
+    let theme = &choices.themes[choices.selected].1;
+    if theme_assets.theme != *theme {
+        theme_assets.theme = theme.clone();
+    }
+}
+
+/// A court line drawn in a theme color
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub enum CourtLine {
+    Solid,
+    /// An open court's side line
+    Faded,
+}
+
+/// Colors newly spawned lines, and every line again when the theme is edited
+/// or swapped
+fn recolor_court_lines(
+    mut theme_events: EventReader<AssetEvent<CourtTheme>>,
+    theme_assets: Option<Res<CourtThemeAssets>>,
+    themes: Res<Assets<CourtTheme>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    lines: Query<(&CourtLine, &MeshMaterial2d<ColorMaterial>)>,
+    added: Query<(), Added<CourtLine>>,
+) {
+    let Some(theme_assets) = theme_assets else {
+        return;
+    };
+    let edited = theme_events
+        .read()
+        .filter(|event| event.is_modified(&theme_assets.theme))
+        .count()
+        > 0
+        || theme_assets.is_changed();
+    if !edited && added.is_empty() {
+        return;
+    }
+
+    let theme = theme_assets.get(&themes);
+    for (line, material) in &lines {
+        let Some(material) = materials.get_mut(&material.0) else {
+            continue;
+        };
+        material.color = match line {
+            // Keep any fading from wall damage
+            CourtLine::Solid => theme.line.0.with_alpha(material.color.alpha()),
+            CourtLine::Faded => theme.open_line.0,
+        };
+    }
+}
@@ -0,0 +1,364 @@
+//! CPU-controlled paddles, for arcade opponents and the attract demo.
+//!
+//! A [`CpuPlayer`] steers its paddle toward where the next ball will arrive,
+//! aiming slightly off-centre so its returns vary, and serves on its own
+//! after a short pause. Lower skill reacts later, moves slower and aims
+//! worse; only skilled CPUs read a lob or drop shot as it leaves the
+//! opponent's paddle. A faked serve draws a lunge the wrong way, longer
+//! the less skilled the CPU.
+//!
+//! Each CPU serves to its [`ServeStyle`]: how often it places the serve away
+//! from the receiver, how hard it hits it, and, when skilled and the rules
+//! allow, how often it fakes one first.
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use rand::prelude::*;
+
+use super::{
+    GamePhase,
+    ball::{
+        BALL_RADIUS, Ball, ChosenServe, ServeDirection, ServeShot, ball_wall_y, predict_crossing,
+    },
+    config::{BallConfig, GameConfig},
+    court::{CourtInset, CourtSize},
+    events::GameEvent,
+    input_buffer::{BufferedAction, InputBuffer},
+    player::{PaddleHit, PaddleIntent, PaddleSize, Player, PlayerSide},
+    rules::MatchRules,
+    shots::Shot,
+};
+use crate::{AppSystems, PausableSystems, screens::Screen};
+
+/// Seconds the CPU waits before serving
+const SERVE_DELAY: f32 = 1.0;
+/// Distance (px) from the target at which the paddle moves at full intent
+const RESPONSE_DISTANCE: f32 = 40.0;
+/// How far ahead (s) a CPU reacts to an incoming ball, at no and full skill
+const REACTION_WINDOW: (f32, f32) = (0.45, 1.4);
+/// Share of full speed a CPU uses, at no and full skill
+const MAX_INTENT: (f32, f32) = (0.55, 1.0);
+/// How far off the predicted spot (px) a CPU may aim, at no and full skill
+const AIM_ERROR: (f32, f32) = (45.0, 12.0);
+/// Skill from which a CPU sets off for a lob or drop shot straight away,
+/// rather than waiting for it to come into its reaction window
+const READS_SHOTS_SKILL: f32 = 0.6;
+/// Seconds a CPU receiver lunges after a faked serve, at no and full skill
+const FEINT_LUNGE: (f32, f32) = (0.5, 0.15);
+/// Skill from which a CPU fakes serves, where the rules allow it
+const FEINT_SKILL: f32 = 0.7;
+/// Degrees between the serve angles a CPU weighs up when placing a serve
+const SERVE_ANGLE_STEP: f32 = 5.0;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<CpuPlayer>();
+    app.register_type::<CpuServe>();
+    app.init_resource::<CpuServe>();
+
+    app.add_observer(reroll_aim);
+    app.add_systems(OnEnter(GamePhase::WaitingToServe), reset_serve_timer);
+    app.add_systems(
+        Update,
+        (
+            bite_on_feints,
+            steer_cpu_paddles,
+            serve_for_cpu.run_if(in_state(GamePhase::WaitingToServe)),
+        )
+            .chain()
+            .in_set(AppSystems::RecordInput)
+            .in_set(PausableSystems)
+            .run_if(in_state(Screen::Gameplay)),
+    );
+}
+
+/// Drives a paddle instead of a person
+#[derive(Component, Debug, Clone, Copy, PartialEq, Default, Reflect)]
+#[reflect(Component)]
+pub struct CpuPlayer {
+    /// From 0 (easy) to 1 (hard)
+    pub skill: f32,
+    /// How it likes to serve
+    pub serve_style: ServeStyle,
+    /// Where on the paddle it's trying to meet the next ball, in px from its
+    /// middle
+    aim_offset: f32,
+    /// Seconds left lunging after a faked serve, and which way
+    lunge: f32,
+    lunge_direction: f32,
+}
+
+impl CpuPlayer {
+    pub fn new(skill: f32) -> Self {
+        Self {
+            skill: skill.clamp(0.0, 1.0),
+            serve_style: ServeStyle::default(),
+            aim_offset: 0.0,
+            lunge: 0.0,
+            lunge_direction: 0.0,
+        }
+    }
+
+    pub fn with_serve_style(mut self, serve_style: ServeStyle) -> Self {
+        self.serve_style = serve_style;
+        self
+    }
+
+    fn scaled(self, (low, high): (f32, f32)) -> f32 {
+        low + (high - low) * self.skill
+    }
+}
+
+/// A CPU's serving personality. Arcade opponents use the presets; a boss
+/// can be given one of its own.
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+pub struct ServeStyle {
+    pub name: &'static str,
+    /// Chance at full skill of placing the serve as far from the receiver as
+    /// it can, rather than anywhere
+    pub placement: f32,
+    /// Serve speed multiplier, from slowest to fastest
+    pub pace: (f32, f32),
+    /// Chance at full skill of faking the serve first
+    pub feint_chance: f32,
+}
+
+impl ServeStyle {
+    /// Puts the ball in play without fuss
+    pub const STEADY: Self = Self {
+        name: "Steady",
+        placement: 0.5,
+        pace: (0.95, 1.05),
+        feint_chance: 0.0,
+    };
+    /// Hits it hard, wherever it goes
+    pub const POWER: Self = Self {
+        name: "Power",
+        placement: 0.3,
+        pace: (1.1, 1.35),
+        feint_chance: 0.1,
+    };
+    /// Picks its spot and loves a fake
+    pub const TRICKY: Self = Self {
+        name: "Tricky",
+        placement: 0.9,
+        pace: (0.8, 1.15),
+        feint_chance: 0.5,
+    };
+    pub const ALL: [Self; 3] = [Self::STEADY, Self::POWER, Self::TRICKY];
+}
+
+impl Default for ServeStyle {
+    fn default() -> Self {
+        Self::STEADY
+    }
+}
+
+/// Counts down to the CPU's serve
+#[derive(Resource, Debug, Reflect)]
+#[reflect(Resource)]
+struct CpuServe {
+    timer: Timer,
+    /// Whether it's already decided on a feint this point
+    considered_feint: bool,
+}
+
+impl Default for CpuServe {
+    fn default() -> Self {
+        Self {
+            timer: Timer::from_seconds(SERVE_DELAY, TimerMode::Once),
+            considered_feint: false,
+        }
+    }
+}
+
+fn reset_serve_timer(mut serve: ResMut<CpuServe>) {
+    *serve = CpuServe::default();
+}
+
+/// Picks a new spot on the paddle to aim for after every return
+fn reroll_aim(_: Trigger<PaddleHit>, mut cpus: Query<&mut CpuPlayer>) {
+    let rng = &mut rand::rng();
+    for mut cpu in &mut cpus {
+        let error = cpu.scaled(AIM_ERROR);
+        cpu.aim_offset = rng.random_range(-error..=error);
+    }
+}
+
+/// Sends CPU receivers lunging after a faked serve, as if it were real
+fn bite_on_feints(
+    mut game_events: EventReader<GameEvent>,
+    mut cpus: Query<(&Player, &mut CpuPlayer)>,
+) {
+    let rng = &mut rand::rng();
+    for event in game_events.read() {
+        let GameEvent::ServeFeint { side } = *event else {
+            continue;
+        };
+        for (player, mut cpu) in &mut cpus {
+            if player.side == side {
+                continue;
+            }
+            cpu.lunge = cpu.scaled(FEINT_LUNGE);
+            cpu.lunge_direction = if rng.random_bool(0.5) { 1.0 } else { -1.0 };
+        }
+    }
+}
+
+fn steer_cpu_paddles(
+    time: Res<Time>,
+    rules: Res<MatchRules>,
+    inset: Res<CourtInset>,
+    court: Res<CourtSize>,
+    balls: Query<(&Position, &LinearVelocity, Option<&Shot>), With<Ball>>,
+    mut paddles: Query<(
+        &Player,
+        &mut CpuPlayer,
+        &Transform,
+        &PaddleSize,
+        &mut PaddleIntent,
+    )>,
+) {
+    let wall_y = ball_wall_y(&rules, &inset, *court);
+
+    for (player, mut cpu, transform, size, mut intent) in &mut paddles {
+        // Committed to the lunge until it runs out
+        if cpu.lunge > 0.0 {
+            cpu.lunge -= time.delta_secs();
+            intent.0 = cpu.lunge_direction * cpu.scaled(MAX_INTENT);
+            continue;
+        }
+
+        let face_x = match player.side {
+            PlayerSide::Left => transform.translation.x + size.0.x / 2.0 + BALL_RADIUS,
+            PlayerSide::Right => transform.translation.x - size.0.x / 2.0 - BALL_RADIUS,
+        };
+        let window = cpu.scaled(REACTION_WINDOW);
+        let reads_shots = cpu.skill >= READS_SHOTS_SKILL;
+        let incoming = balls
+            .iter()
+            .filter_map(|(position, velocity, shot)| {
+                let (intercept, time) = predict_crossing(position.0, velocity.0, face_x, wall_y)?;
+                (time <= window || (reads_shots && shot.is_some())).then_some((intercept, time))
+            })
+            .min_by(|(_, a), (_, b)| a.total_cmp(b));
+
+        // Meet the ball, or drift back to the middle while waiting. The aim
+        // stays on the paddle, however worn down it is.
+        let reach = size.0.y / 2.0;
+        let aim = cpu.aim_offset.clamp(-reach, reach);
+        let (target, max_intent) = match incoming {
+            Some((intercept, _)) => (intercept.y + aim, cpu.scaled(MAX_INTENT)),
+            None => (0.0, cpu.scaled(MAX_INTENT) / 2.0),
+        };
+        intent.0 =
+            ((target - transform.translation.y) / RESPONSE_DISTANCE).clamp(-max_intent, max_intent);
+    }
+}
+
+/// Serves for a CPU server once its delay is up, maybe faking it first
+fn serve_for_cpu(
+    time: Res<Time<Virtual>>,
+    rules: Res<MatchRules>,
+    inset: Res<CourtInset>,
+    court: Res<CourtSize>,
+    serve_direction: Res<ServeDirection>,
+    config: Res<GameConfig>,
+    mut serve: ResMut<CpuServe>,
+    mut buffer: ResMut<InputBuffer>,
+    mut chosen: ResMut<ChosenServe>,
+    paddles: Query<(&Player, &Transform, Option<&CpuPlayer>)>,
+    balls: Query<&Position, With<Ball>>,
+) {
+    let server = serve_direction.side;
+    let Some(&cpu) = paddles
+        .iter()
+        .find_map(|(player, _, cpu)| cpu.filter(|_| player.side == server))
+    else {
+        return;
+    };
+    // The serve can change hands while waiting, e.g. on the serve clock
+    if serve_direction.is_changed() {
+        *serve = CpuServe::default();
+    }
+    serve.timer.tick(time.delta());
+    let rng = &mut rand::rng();
+
+    // Halfway through the wait, a sharp enough CPU may go through the motion
+    // and make the receiver wait all over again
+    if !serve.considered_feint && serve.timer.fraction() >= 0.5 {
+        serve.considered_feint = true;
+        let chance = cpu.serve_style.feint_chance * cpu.skill;
+        if rules.serve_feints && cpu.skill >= FEINT_SKILL && rng.random::<f32>() < chance {
+            buffer.press(BufferedAction::Feint, time.elapsed_secs());
+            serve.timer.reset();
+            return;
+        }
+    }
+    if !serve.timer.just_finished() {
+        return;
+    }
+
+    let receiver = paddles
+        .iter()
+        .find(|(player, ..)| player.side != server)
+        .map(|(_, transform, _)| transform.translation.truncate());
+    let ball = balls
+        .iter()
+        .next()
+        .map_or(Vec2::ZERO, |position| position.0);
+    let wall_y = ball_wall_y(&rules, &inset, *court);
+    chosen.0 = Some(choose_serve(
+        cpu,
+        &config.ball,
+        server,
+        ball,
+        receiver,
+        wall_y,
+        rng,
+    ));
+    buffer.press(BufferedAction::Serve, time.elapsed_secs());
+}
+
+/// Where and how hard `cpu` serves from `ball`. A well-placed serve arrives
+/// as far from the receiver's paddle as it can.
+fn choose_serve(
+    cpu: CpuPlayer,
+    config: &BallConfig,
+    server: PlayerSide,
+    ball: Vec2,
+    receiver: Option<Vec2>,
+    wall_y: Option<f32>,
+    rng: &mut impl Rng,
+) -> ServeShot {
+    let style = cpu.serve_style;
+    let (slow, fast) = style.pace;
+    let speed = rng.random_range(slow..=fast);
+    let direction_x = match server {
+        PlayerSide::Left => 1.0,
+        PlayerSide::Right => -1.0,
+    };
+
+    let (low, high) = config.serve_angles();
+    let placed = receiver.filter(|_| rng.random::<f32>() < style.placement * cpu.skill);
+    let angle = match placed {
+        Some(receiver) => {
+            let steps = ((high - low) / SERVE_ANGLE_STEP) as i32;
+            (0..=steps)
+                .map(|step| low + step as f32 * SERVE_ANGLE_STEP)
+                .flat_map(|angle| [angle, -angle])
+                .filter_map(|angle| {
+                    let radians = angle.to_radians();
+                    let direction = Vec2::new(radians.cos() * direction_x, radians.sin());
+                    let (arrival, _) = predict_crossing(ball, direction, receiver.x, wall_y)?;
+                    Some((angle, (arrival.y - receiver.y).abs()))
+                })
+                .max_by(|(_, a), (_, b)| a.total_cmp(b))
+                .map_or(low, |(angle, _)| angle)
+        }
+        None => {
+            let angle = config.serve_angle(rng);
+            if rng.random_bool(0.5) { angle } else { -angle }
+        }
+    };
+    ServeShot { angle, speed }
+}
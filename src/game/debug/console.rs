@@ -0,0 +1,265 @@
+//! In-game developer console with cheat commands.
+//!
+//! Toggle with backquote and type a command such as `score set 10 0`. Modules
+//! add their own commands through [`RegisterConsoleCommand`].
+
+use std::collections::BTreeMap;
+
+use bevy::{
+    ecs::system::SystemId,
+    input::{
+        ButtonState, InputSystem,
+        keyboard::{Key, KeyboardInput},
+    },
+    prelude::*,
+};
+
+use crate::game::{GamePhase, rules::MatchRules, scoring::Score};
+
+const TOGGLE_KEY: KeyCode = KeyCode::Backquote;
+const MAX_LOG_LINES: usize = 12;
+const CONSOLE_FONT_SIZE: f32 = 18.0;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<ConsoleState>();
+    app.init_resource::<ConsoleCommands>();
+
+    // Open/close the console and hide keys from gameplay while it's open.
+    app.add_systems(
+        PreUpdate,
+        (toggle_console, swallow_gameplay_input)
+            .chain()
+            .after(InputSystem),
+    );
+    app.add_systems(
+        Update,
+        (read_console_input, run_pending_commands, update_console_ui).chain(),
+    );
+
+    app.register_console_command("help", "help - list available commands", help);
+    app.register_console_command(
+        "phase",
+        "phase <WaitingToServe|Playing|GoalScored|GameOver>",
+        set_phase,
+    );
+}
+
+/// Output of a console command: a message to print, or an error
+pub type ConsoleResult = Result<String, String>;
+
+pub trait RegisterConsoleCommand {
+    /// Registers a one-shot system that runs when `name` is typed into the
+    /// console. The remaining words of the line are passed in as arguments.
+    fn register_console_command<M>(
+        &mut self,
+        name: &'static str,
+        help: &'static str,
+        system: impl IntoSystem<In<Vec<String>>, ConsoleResult, M> + 'static,
+    ) -> &mut Self;
+}
+
+impl RegisterConsoleCommand for App {
+    fn register_console_command<M>(
+        &mut self,
+        name: &'static str,
+        help: &'static str,
+        system: impl IntoSystem<In<Vec<String>>, ConsoleResult, M> + 'static,
+    ) -> &mut Self {
+        let system = self.register_system(system);
+        self.world_mut()
+            .get_resource_or_init::<ConsoleCommands>()
+            .0
+            .insert(name, ConsoleCommand { help, system });
+        self
+    }
+}
+
+struct ConsoleCommand {
+    help: &'static str,
+    system: SystemId<In<Vec<String>>, ConsoleResult>,
+}
+
+/// All registered console commands, keyed by name
+#[derive(Resource, Default)]
+pub struct ConsoleCommands(BTreeMap<&'static str, ConsoleCommand>);
+
+#[derive(Resource, Default)]
+struct ConsoleState {
+    open: bool,
+    input: String,
+    log: Vec<String>,
+    pending: Vec<String>,
+}
+
+impl ConsoleState {
+    fn push_line(&mut self, line: impl Into<String>) {
+        self.log.extend(line.into().lines().map(str::to_owned));
+        let overflow = self.log.len().saturating_sub(MAX_LOG_LINES);
+        self.log.drain(..overflow);
+    }
+}
+
+/// Marker for the console root UI node
+#[derive(Component)]
+struct ConsoleUi;
+
+/// Marker for the console's text
+#[derive(Component)]
+struct ConsoleText;
+
+fn toggle_console(
+    mut commands: Commands,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut console: ResMut<ConsoleState>,
+    ui: Query<Entity, With<ConsoleUi>>,
+) {
+    if !keyboard.just_pressed(TOGGLE_KEY) {
+        return;
+    }
+
+    console.open = !console.open;
+    if !console.open {
+        for entity in &ui {
+            commands.entity(entity).despawn();
+        }
+        return;
+    }
+
+    commands.spawn((
+        Name::new("Debug Console"),
+        ConsoleUi,
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            padding: UiRect::all(Val::Px(8.0)),
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.85)),
+        GlobalZIndex(10),
+        children![(
+            ConsoleText,
+            Text::default(),
+            TextFont {
+                font_size: CONSOLE_FONT_SIZE,
+                ..default()
+            },
+            TextColor(Color::srgb(0.6, 1.0, 0.6)),
+        )],
+    ));
+}
+
+/// Keeps typed keys from also steering paddles, serving, or pausing
+fn swallow_gameplay_input(console: Res<ConsoleState>, mut keyboard: ResMut<ButtonInput<KeyCode>>) {
+    if console.open {
+        keyboard.reset_all();
+    }
+}
+
+fn read_console_input(mut events: EventReader<KeyboardInput>, mut console: ResMut<ConsoleState>) {
+    if !console.open {
+        events.clear();
+        return;
+    }
+
+    for event in events.read() {
+        if event.state != ButtonState::Pressed || event.key_code == TOGGLE_KEY {
+            continue;
+        }
+        match &event.logical_key {
+            Key::Enter => {
+                let line = std::mem::take(&mut console.input);
+                if !line.trim().is_empty() {
+                    console.pending.push(line);
+                }
+            }
+            Key::Backspace => {
+                console.input.pop();
+            }
+            Key::Space => console.input.push(' '),
+            Key::Character(text) => console.input.push_str(text),
+            _ => {}
+        }
+    }
+}
+
+/// Runs submitted lines against the command registry
+fn run_pending_commands(world: &mut World) {
+    let pending = std::mem::take(&mut world.resource_mut::<ConsoleState>().pending);
+
+    for line in pending {
+        let mut words = line.split_whitespace().map(str::to_owned);
+        let Some(name) = words.next() else {
+            continue;
+        };
+        let args: Vec<String> = words.collect();
+
+        let system = world
+            .resource::<ConsoleCommands>()
+            .0
+            .get(name.as_str())
+            .map(|command| command.system);
+        let output = match system {
+            Some(system) => world
+                .run_system_with(system, args)
+                .unwrap_or_else(|error| Err(error.to_string())),
+            None => Err(format!("unknown command '{name}', try 'help'")),
+        };
+
+        let mut console = world.resource_mut::<ConsoleState>();
+        console.push_line(format!("> {line}"));
+        match output {
+            Ok(message) if message.is_empty() => {}
+            Ok(message) => console.push_line(message),
+            Err(error) => console.push_line(format!("error: {error}")),
+        }
+    }
+}
+
+fn update_console_ui(console: Res<ConsoleState>, mut text: Query<&mut Text, With<ConsoleText>>) {
+    if !console.is_changed() {
+        return;
+    }
+
+    for mut text in &mut text {
+        let mut lines = console.log.clone();
+        lines.push(format!("> {}_", console.input));
+        text.0 = lines.join("\n");
+    }
+}
+
+fn help(_: In<Vec<String>>, commands: Res<ConsoleCommands>) -> ConsoleResult {
+    Ok(commands
+        .0
+        .values()
+        .map(|command| command.help)
+        .collect::<Vec<_>>()
+        .join("\n"))
+}
+
+fn set_phase(
+    In(args): In<Vec<String>>,
+    state: Option<Res<State<GamePhase>>>,
+    next_phase: Option<ResMut<NextState<GamePhase>>>,
+    score: Res<Score>,
+    rules: Res<MatchRules>,
+) -> ConsoleResult {
+    let (Some(_), Some(mut next_phase)) = (state, next_phase) else {
+        return Err("not in gameplay".into());
+    };
+
+    let phase = match args.first().map(|arg| arg.to_ascii_lowercase()).as_deref() {
+        Some("waitingtoserve") => GamePhase::WaitingToServe,
+        Some("playing") => GamePhase::Playing,
+        Some("goalscored") => GamePhase::GoalScored,
+        Some("gameover") => {
+            if score.winner(&rules).is_none() {
+                return Err("no winner yet, try 'score set 11 0' first".into());
+            }
+            GamePhase::GameOver
+        }
+        _ => return Err("usage: phase <WaitingToServe|Playing|GoalScored|GameOver>".into()),
+    };
+
+    next_phase.set(phase);
+    Ok(format!("Phase set to {phase:?}"))
+}
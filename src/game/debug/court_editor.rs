@@ -0,0 +1,427 @@
+//! A court editor for trying out layouts mid-match.
+//!
+//! Press F7 during a match to freeze it and edit the court over a snapping
+//! grid. Click empty court to place a block, drag blocks around, and
+//! right-click one to remove it. Drag the top or bottom wall to move it in,
+//! and a goal post to narrow the goals. T test-plays the layout and goes
+//! back to editing; Ctrl+S saves it to `assets/courts/custom.court.json`.
+//! F7 again puts the normal court back.
+//!
+//! The layout is a [`CourtPlan`] asset, loaded like the court theme, so the
+//! saved file is picked up again the next time the editor opens.
+
+use std::fs;
+
+use avian2d::prelude::*;
+use bevy::{input::common_conditions::input_just_pressed, prelude::*, window::PrimaryWindow};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    asset_tracking::{JsonAsset, JsonAssetLoader},
+    game::{
+        camera::SplitCamera,
+        config::GameConfig,
+        court::{BOUNDARY_THICKNESS, CourtInset, CourtSize, LINE_COLOR},
+        court_theme::CourtLine,
+        physics::{BOUNDARY_FRICTION, boundary_layers},
+    },
+    screens::Screen,
+};
+
+const EDITOR_KEY: KeyCode = KeyCode::F7;
+const TEST_PLAY_KEY: KeyCode = KeyCode::KeyT;
+const SAVE_KEY: KeyCode = KeyCode::KeyS;
+
+const PLAN_PATH: &str = "courts/custom.court.json";
+
+const GRID_SIZE: f32 = 20.0;
+const BLOCK_SIZE: Vec2 = Vec2::splat(40.0);
+/// How close to a wall or post the cursor has to be to grab it
+const GRAB_DISTANCE: f32 = 12.0;
+const MAX_WALL_INSET: f32 = 200.0;
+const MIN_GOAL_OPENING: f32 = 60.0;
+// Goals reach this far in from the end lines; posts stand just in front
+const GOAL_DEPTH: f32 = 50.0;
+const POST_WIDTH: f32 = 8.0;
+const PIECE_Z: f32 = -0.2;
+
+const GRID_COLOR: Color = Color::srgba(1.0, 1.0, 1.0, 0.08);
+const OUTLINE_COLOR: Color = Color::srgb(1.0, 0.9, 0.4);
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_asset::<CourtPlan>();
+    app.register_asset_loader(JsonAssetLoader::<CourtPlan>::default());
+    app.init_resource::<CourtEditor>();
+    app.register_type::<PlanPiece>();
+
+    app.add_systems(OnExit(Screen::Gameplay), close_editor);
+    app.add_systems(
+        Update,
+        (
+            toggle_editor.run_if(input_just_pressed(EDITOR_KEY)),
+            toggle_test_play.run_if(input_just_pressed(TEST_PLAY_KEY)),
+            save_plan.run_if(input_just_pressed(SAVE_KEY)),
+            reload_plan,
+            edit_with_mouse,
+            spawn_plan,
+            draw_editor,
+        )
+            .chain()
+            .run_if(in_state(Screen::Gameplay)),
+    );
+}
+
+/// A court layout: blocks to bounce off, how far the walls have moved in,
+/// and how wide the goals are
+#[derive(Asset, TypePath, Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CourtPlan {
+    pub blocks: Vec<Block>,
+    /// Pixels the top and bottom walls have moved in
+    pub wall_inset: f32,
+    /// Height of each goal's opening
+    pub goal_opening: f32,
+}
+
+impl Default for CourtPlan {
+    fn default() -> Self {
+        Self::empty(CourtSize::default())
+    }
+}
+
+impl CourtPlan {
+    /// The normal court: no blocks, and goals open along the whole end line
+    fn empty(size: CourtSize) -> Self {
+        Self {
+            blocks: Vec::new(),
+            wall_inset: 0.0,
+            goal_opening: size.height,
+        }
+    }
+}
+
+impl JsonAsset for CourtPlan {
+    const EXTENSIONS: &'static [&'static str] = &["court.json"];
+}
+
+/// A solid block on the court
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct Block {
+    pub center: [f32; 2],
+    pub size: [f32; 2],
+}
+
+impl Block {
+    fn contains(&self, point: Vec2) -> bool {
+        let offset = (point - Vec2::from(self.center)).abs();
+        offset.x <= self.size[0] / 2.0 && offset.y <= self.size[1] / 2.0
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum EditorMode {
+    /// The normal court
+    #[default]
+    Off,
+    /// Frozen, taking mouse edits
+    Editing,
+    /// Playing on the edited court
+    TestPlay,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Drag {
+    /// A block, held at this offset from its center
+    Block {
+        index: usize,
+        offset: Vec2,
+    },
+    Wall,
+    GoalPost,
+}
+
+#[derive(Resource, Default)]
+struct CourtEditor {
+    mode: EditorMode,
+    plan: CourtPlan,
+    /// The saved layout, for picking up where we left off
+    saved: Handle<CourtPlan>,
+    drag: Option<Drag>,
+}
+
+/// Part of the edited court, respawned whenever the plan changes
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct PlanPiece;
+
+fn snap(value: f32) -> f32 {
+    (value / GRID_SIZE).round() * GRID_SIZE
+}
+
+fn toggle_editor(
+    mut editor: ResMut<CourtEditor>,
+    plans: Res<Assets<CourtPlan>>,
+    asset_server: Res<AssetServer>,
+    size: Res<CourtSize>,
+    mut time: ResMut<Time<Virtual>>,
+) {
+    match editor.mode {
+        EditorMode::Off => {
+            if editor.saved == Handle::default() {
+                editor.saved = asset_server.load(PLAN_PATH);
+            }
+            editor.plan = plans
+                .get(&editor.saved)
+                .cloned()
+                .unwrap_or_else(|| CourtPlan::empty(*size));
+            editor.mode = EditorMode::Editing;
+            time.pause();
+            info!("Court editor: editing, {TEST_PLAY_KEY:?} to test-play");
+        }
+        EditorMode::Editing | EditorMode::TestPlay => {
+            editor.mode = EditorMode::Off;
+            editor.drag = None;
+            time.unpause();
+            info!("Court editor: closed");
+        }
+    }
+}
+
+fn toggle_test_play(mut editor: ResMut<CourtEditor>, mut time: ResMut<Time<Virtual>>) {
+    match editor.mode {
+        EditorMode::Off => {}
+        EditorMode::Editing => {
+            editor.mode = EditorMode::TestPlay;
+            editor.drag = None;
+            time.unpause();
+            info!("Court editor: test-playing");
+        }
+        EditorMode::TestPlay => {
+            editor.mode = EditorMode::Editing;
+            time.pause();
+            info!("Court editor: editing");
+        }
+    }
+}
+
+fn close_editor(mut editor: ResMut<CourtEditor>, mut time: ResMut<Time<Virtual>>) {
+    if editor.mode != EditorMode::Off {
+        editor.mode = EditorMode::Off;
+        editor.drag = None;
+        time.unpause();
+    }
+}
+
+fn save_plan(keyboard: Res<ButtonInput<KeyCode>>, editor: Res<CourtEditor>) {
+    if editor.mode == EditorMode::Off || !keyboard.pressed(KeyCode::ControlLeft) {
+        return;
+    }
+
+    let path = std::path::Path::new("assets").join(PLAN_PATH);
+    let result = path
+        .parent()
+        .map_or(Ok(()), fs::create_dir_all)
+        .and_then(|_| {
+            let json = serde_json::to_string_pretty(&editor.plan).map_err(std::io::Error::other)?;
+            fs::write(&path, json)
+        });
+    match result {
+        Ok(()) => info!("Court editor: saved to {path:?}"),
+        Err(error) => warn!("Court editor: couldn't save: {error}"),
+    }
+}
+
+/// Picks up the saved layout once it loads, and outside edits to it, unless
+/// something is being dragged
+fn reload_plan(
+    mut events: EventReader<AssetEvent<CourtPlan>>,
+    plans: Res<Assets<CourtPlan>>,
+    mut editor: ResMut<CourtEditor>,
+) {
+    let modified = events
+        .read()
+        .filter(|event| {
+            event.is_modified(&editor.saved) || event.is_loaded_with_dependencies(&editor.saved)
+        })
+        .count()
+        > 0;
+    if !modified || editor.mode == EditorMode::Off || editor.drag.is_some() {
+        return;
+    }
+    let Some(plan) = plans.get(&editor.saved) else {
+        return;
+    };
+    if *plan != editor.plan {
+        editor.plan = plan.clone();
+    }
+}
+
+fn edit_with_mouse(
+    mouse: Res<ButtonInput<MouseButton>>,
+    window: Single<&Window, With<PrimaryWindow>>,
+    camera: Single<(&Camera, &GlobalTransform), (With<Camera2d>, Without<SplitCamera>)>,
+    mut editor: ResMut<CourtEditor>,
+    size: Res<CourtSize>,
+) {
+    if editor.mode != EditorMode::Editing {
+        return;
+    }
+    let (camera, camera_transform) = *camera;
+    let Some(cursor) = window
+        .cursor_position()
+        .and_then(|cursor| camera.viewport_to_world_2d(camera_transform, cursor).ok())
+    else {
+        return;
+    };
+
+    if mouse.just_pressed(MouseButton::Right) {
+        if let Some(index) = editor.plan.blocks.iter().position(|b| b.contains(cursor)) {
+            editor.plan.blocks.remove(index);
+        }
+        return;
+    }
+
+    if mouse.just_pressed(MouseButton::Left) {
+        let wall_y = size.half_height() - BOUNDARY_THICKNESS - editor.plan.wall_inset;
+        let post_x = size.half_width() - GOAL_DEPTH;
+        let post_y = editor.plan.goal_opening / 2.0;
+        editor.drag =
+            if let Some(index) = editor.plan.blocks.iter().position(|b| b.contains(cursor)) {
+                let offset = cursor - Vec2::from(editor.plan.blocks[index].center);
+                Some(Drag::Block { index, offset })
+            } else if (cursor.y.abs() - wall_y).abs() <= GRAB_DISTANCE {
+                Some(Drag::Wall)
+            } else if (cursor.x.abs() - post_x).abs() <= GRAB_DISTANCE
+                && (cursor.y.abs() - post_y).abs() <= GRAB_DISTANCE
+            {
+                Some(Drag::GoalPost)
+            } else {
+                let center = Vec2::new(snap(cursor.x), snap(cursor.y));
+                editor.plan.blocks.push(Block {
+                    center: center.to_array(),
+                    size: BLOCK_SIZE.to_array(),
+                });
+                None
+            };
+    }
+
+    if !mouse.pressed(MouseButton::Left) {
+        if editor.drag.is_some() {
+            editor.drag = None;
+        }
+        return;
+    }
+
+    match editor.drag {
+        Some(Drag::Block { index, offset }) => {
+            let target = cursor - offset;
+            let center = [snap(target.x), snap(target.y)];
+            if editor.plan.blocks[index].center != center {
+                editor.plan.blocks[index].center = center;
+            }
+        }
+        Some(Drag::Wall) => {
+            let wall_inset = snap(size.half_height() - BOUNDARY_THICKNESS - cursor.y.abs())
+                .clamp(0.0, MAX_WALL_INSET);
+            if editor.plan.wall_inset != wall_inset {
+                editor.plan.wall_inset = wall_inset;
+            }
+        }
+        Some(Drag::GoalPost) => {
+            let goal_opening = snap(cursor.y.abs() * 2.0).clamp(MIN_GOAL_OPENING, size.height);
+            if editor.plan.goal_opening != goal_opening {
+                editor.plan.goal_opening = goal_opening;
+            }
+        }
+        None => {}
+    }
+}
+
+/// Rebuilds the blocks and goal posts and moves the walls whenever the plan
+/// changes, and puts the normal court back when the editor closes
+fn spawn_plan(
+    mut commands: Commands,
+    editor: Res<CourtEditor>,
+    config: Res<GameConfig>,
+    size: Res<CourtSize>,
+    mut spawned: Local<Option<CourtPlan>>,
+    mut inset: ResMut<CourtInset>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    pieces: Query<Entity, With<PlanPiece>>,
+) {
+    let wanted = (editor.mode != EditorMode::Off).then(|| editor.plan.clone());
+    if *spawned == wanted {
+        return;
+    }
+    for piece in &pieces {
+        commands.entity(piece).despawn();
+    }
+    *spawned = wanted;
+    let Some(plan) = spawned.as_ref() else {
+        inset.0 = 0.0;
+        return;
+    };
+    inset.0 = plan.wall_inset;
+
+    let material = materials.add(LINE_COLOR);
+    let mut spawn_piece = |name: &str, center: Vec2, size: Vec2| {
+        commands.spawn((
+            Name::new(name.to_string()),
+            PlanPiece,
+            RigidBody::Static,
+            Collider::rectangle(size.x, size.y),
+            boundary_layers(),
+            Friction::new(BOUNDARY_FRICTION),
+            Restitution::new(config.court.wall_restitution),
+            Mesh2d(meshes.add(Rectangle::from_size(size))),
+            MeshMaterial2d(material.clone()),
+            CourtLine::Solid,
+            Transform::from_translation(center.extend(PIECE_Z)),
+            StateScoped(Screen::Gameplay),
+        ));
+    };
+
+    for block in &plan.blocks {
+        spawn_piece("Block", block.center.into(), block.size.into());
+    }
+
+    // Posts close off each goal above and below the opening
+    let post_height = (size.height - plan.goal_opening) / 2.0;
+    if post_height > 0.0 {
+        let x = size.half_width() - GOAL_DEPTH - POST_WIDTH / 2.0;
+        let y = plan.goal_opening / 2.0 + post_height / 2.0;
+        for center in [
+            Vec2::new(-x, y),
+            Vec2::new(-x, -y),
+            Vec2::new(x, y),
+            Vec2::new(x, -y),
+        ] {
+            spawn_piece("Goal Post", center, Vec2::new(POST_WIDTH, post_height));
+        }
+    }
+}
+
+fn draw_editor(mut gizmos: Gizmos, editor: Res<CourtEditor>, size: Res<CourtSize>) {
+    if editor.mode != EditorMode::Editing {
+        return;
+    }
+
+    let cells = UVec2::new(
+        (size.width / GRID_SIZE) as u32,
+        (size.height / GRID_SIZE) as u32,
+    );
+    gizmos.grid_2d(
+        Isometry2d::IDENTITY,
+        cells,
+        Vec2::splat(GRID_SIZE),
+        GRID_COLOR,
+    );
+
+    for block in &editor.plan.blocks {
+        gizmos.rect_2d(
+            Isometry2d::from_translation(block.center.into()),
+            block.size.into(),
+            OUTLINE_COLOR,
+        );
+    }
+}
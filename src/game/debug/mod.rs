@@ -3,10 +3,30 @@
 use avian2d::prelude::*;
 use bevy::prelude::*;
 
+#[cfg(feature = "dev")]
+pub mod console;
+#[cfg(feature = "dev")]
+mod court_editor;
+#[cfg(feature = "dev")]
+mod overlay;
+#[cfg(feature = "dev")]
+mod stepper;
+#[cfg(feature = "dev")]
+mod tuning;
+
 pub(super) fn plugin(app: &mut App) {
     // Only add debug systems in development builds
     #[cfg(feature = "dev")]
-    app.add_systems(Update, warn_default_collision_layers);
+    {
+        app.add_plugins((
+            console::plugin,
+            court_editor::plugin,
+            overlay::plugin,
+            stepper::plugin,
+            tuning::plugin,
+        ));
+        app.add_systems(Update, warn_default_collision_layers);
+    }
 }
 
 /// Warns when entities have colliders but are using the default collision layer.
@@ -18,7 +38,7 @@ fn warn_default_collision_layers(
     for (entity, name, layers) in &query {
         // For now, we'll just check if the entity has the default_layers() configuration
         // which has empty filters (doesn't collide with anything)
-        if layers == &super::physics::default_layers() {
+        if layers == &super::super::physics::default_layers() {
             warn!(
                 "Entity {:?} ({}) spawned with Default collision layer. \
                 If this entity should participate in collisions, \
@@ -0,0 +1,201 @@
+//! F3-style overlay with physics and state info, a frame-time graph, plus
+//! collider gizmos.
+
+use avian2d::prelude::*;
+use bevy::{
+    diagnostic::{DiagnosticsStore, FrameTimeDiagnosticsPlugin},
+    ecs::spawn::SpawnIter,
+    input::common_conditions::input_just_pressed,
+    prelude::*,
+};
+
+use crate::{
+    game::{
+        GamePhase,
+        ball::{Ball, Rally},
+    },
+    screens::Screen,
+};
+
+const TOGGLE_OVERLAY_KEY: KeyCode = KeyCode::F3;
+const TOGGLE_GIZMOS_KEY: KeyCode = KeyCode::F4;
+const OVERLAY_FONT_SIZE: f32 = 16.0;
+
+// Frame-time graph: one bar per recent frame, newest on the right
+const GRAPH_BARS: usize = 100;
+const GRAPH_BAR_WIDTH: f32 = 2.0;
+const GRAPH_HEIGHT: f32 = 60.0;
+const GRAPH_MAX_MS: f64 = 50.0; // Frame time at the top of the graph
+const SMOOTH_FRAME_MS: f64 = 1000.0 / 60.0;
+const SLOW_FRAME_MS: f64 = 1000.0 / 30.0;
+
+pub(super) fn plugin(app: &mut App) {
+    if !app.is_plugin_added::<FrameTimeDiagnosticsPlugin>() {
+        app.add_plugins(FrameTimeDiagnosticsPlugin::default());
+    }
+    app.add_plugins(PhysicsDebugPlugin::default());
+
+    app.add_systems(Startup, disable_physics_gizmos);
+    app.add_systems(
+        Update,
+        (
+            toggle_overlay.run_if(input_just_pressed(TOGGLE_OVERLAY_KEY)),
+            toggle_physics_gizmos.run_if(input_just_pressed(TOGGLE_GIZMOS_KEY)),
+            (update_overlay, update_frame_time_graph),
+        )
+            .chain(),
+    );
+}
+
+/// Marker for the overlay's entities
+#[derive(Component)]
+struct DebugOverlay;
+
+/// One bar of the frame-time graph, counting back from the newest frame
+#[derive(Component)]
+struct FrameTimeBar(usize);
+
+/// Collider gizmos are noisy, so start with them off
+fn disable_physics_gizmos(mut store: ResMut<GizmoConfigStore>) {
+    store.config_mut::<PhysicsGizmos>().0.enabled = false;
+}
+
+fn toggle_physics_gizmos(mut store: ResMut<GizmoConfigStore>) {
+    let config = &mut store.config_mut::<PhysicsGizmos>().0;
+    config.enabled = !config.enabled;
+}
+
+fn toggle_overlay(mut commands: Commands, overlay: Query<Entity, With<DebugOverlay>>) {
+    if !overlay.is_empty() {
+        for entity in &overlay {
+            commands.entity(entity).despawn();
+        }
+        return;
+    }
+
+    commands.spawn((
+        Name::new("Debug Overlay"),
+        DebugOverlay,
+        Text::default(),
+        TextFont {
+            font_size: OVERLAY_FONT_SIZE,
+            ..default()
+        },
+        TextColor(Color::srgb(1.0, 1.0, 0.6)),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(8.0),
+            right: Val::Px(8.0),
+            padding: UiRect::all(Val::Px(6.0)),
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.6)),
+        GlobalZIndex(9),
+    ));
+
+    let bars = (0..GRAPH_BARS).rev().map(|age| {
+        (
+            FrameTimeBar(age),
+            Node {
+                width: Val::Px(GRAPH_BAR_WIDTH),
+                height: Val::Px(0.0),
+                ..default()
+            },
+            BackgroundColor(Color::NONE),
+        )
+    });
+    commands.spawn((
+        Name::new("Frame Time Graph"),
+        DebugOverlay,
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(8.0),
+            right: Val::Px(8.0),
+            height: Val::Px(GRAPH_HEIGHT),
+            align_items: AlignItems::End,
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.6)),
+        GlobalZIndex(9),
+        Children::spawn(SpawnIter(bars)),
+    ));
+}
+
+fn update_frame_time_graph(
+    diagnostics: Res<DiagnosticsStore>,
+    mut bars: Query<(&FrameTimeBar, &mut Node, &mut BackgroundColor)>,
+) {
+    let Some(frame_times) = diagnostics.get(&FrameTimeDiagnosticsPlugin::FRAME_TIME) else {
+        return;
+    };
+    let recent: Vec<f64> = frame_times
+        .values()
+        .rev()
+        .take(GRAPH_BARS)
+        .copied()
+        .collect();
+
+    for (bar, mut node, mut background) in &mut bars {
+        let Some(&ms) = recent.get(bar.0) else {
+            node.height = Val::Px(0.0);
+            continue;
+        };
+        node.height = Val::Px((ms / GRAPH_MAX_MS).min(1.0) as f32 * GRAPH_HEIGHT);
+        background.0 = if ms <= SMOOTH_FRAME_MS {
+            Color::srgb(0.4, 0.9, 0.4)
+        } else if ms <= SLOW_FRAME_MS {
+            Color::srgb(1.0, 0.85, 0.3)
+        } else {
+            Color::srgb(1.0, 0.35, 0.3)
+        };
+    }
+}
+
+fn update_overlay(
+    mut overlay: Query<&mut Text, With<DebugOverlay>>,
+    diagnostics: Res<DiagnosticsStore>,
+    screen: Res<State<Screen>>,
+    phase: Option<Res<State<GamePhase>>>,
+    rally: Res<Rally>,
+    balls: Query<&LinearVelocity, With<Ball>>,
+    contacts: Query<(&Name, &CollidingEntities)>,
+    names: Query<&Name>,
+) {
+    let Ok(mut text) = overlay.single_mut() else {
+        return;
+    };
+
+    let fps = diagnostics
+        .get(&FrameTimeDiagnosticsPlugin::FPS)
+        .and_then(|fps| fps.smoothed())
+        .unwrap_or_default();
+
+    let mut lines = vec![
+        format!("FPS: {fps:.0}"),
+        format!("Screen: {:?}", screen.get()),
+        format!(
+            "Phase: {}",
+            phase.map_or("-".to_string(), |phase| format!("{:?}", phase.get()))
+        ),
+        format!("Rally: {}", rally.hits),
+    ];
+
+    for velocity in &balls {
+        lines.push(format!(
+            "Ball: ({:.0}, {:.0}) speed {:.0}",
+            velocity.x,
+            velocity.y,
+            velocity.length()
+        ));
+    }
+
+    for (name, colliding) in &contacts {
+        for other in colliding.iter() {
+            let other = names.get(*other).map_or("?", Name::as_str);
+            lines.push(format!("Contact: {name} <-> {other}"));
+        }
+    }
+
+    lines.push(format!("[{TOGGLE_GIZMOS_KEY:?}] collider gizmos"));
+    text.0 = lines.join("\n");
+}
@@ -0,0 +1,82 @@
+//! Frame-by-frame stepping of the fixed-timestep simulation.
+//!
+//! Freezing stops the virtual clock, which in turn starves `FixedUpdate` and
+//! the physics step. Each step then runs exactly one `FixedMain` tick by hand.
+//! Frame-based `PausableSystems` stay frozen for the whole session so timers
+//! and animations don't drift away from the stepped simulation.
+
+use bevy::{
+    app::FixedMain, input::common_conditions::input_just_pressed, prelude::*,
+    time::common_conditions::paused,
+};
+
+use crate::PausableSystems;
+
+const FREEZE_KEY: KeyCode = KeyCode::F5;
+const STEP_KEY: KeyCode = KeyCode::F6;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<DebugStepper>();
+    app.init_resource::<DebugStepper>();
+
+    app.configure_sets(
+        Update,
+        PausableSystems.run_if(|stepper: Res<DebugStepper>| !stepper.frozen),
+    );
+    app.add_systems(
+        Update,
+        (
+            toggle_freeze.run_if(input_just_pressed(FREEZE_KEY)),
+            queue_step.run_if(input_just_pressed(STEP_KEY).and(paused)),
+            run_queued_steps,
+        )
+            .chain(),
+    );
+}
+
+/// Frame stepper state
+#[derive(Resource, Default, Reflect)]
+#[reflect(Resource)]
+pub struct DebugStepper {
+    /// Whether the simulation is frozen
+    pub frozen: bool,
+    /// Fixed ticks waiting to be run
+    pending_steps: u32,
+}
+
+fn toggle_freeze(mut stepper: ResMut<DebugStepper>, mut time: ResMut<Time<Virtual>>) {
+    stepper.frozen = !stepper.frozen;
+    stepper.pending_steps = 0;
+
+    if stepper.frozen {
+        time.pause();
+        info!("Stepper: frozen, press {STEP_KEY:?} to advance one tick");
+    } else {
+        time.unpause();
+        info!("Stepper: resumed");
+    }
+}
+
+fn queue_step(mut stepper: ResMut<DebugStepper>) {
+    if stepper.frozen {
+        stepper.pending_steps += 1;
+    }
+}
+
+/// Runs one `FixedMain` tick per queued step, mirroring what the fixed main
+/// loop does: advance `Time<Fixed>` and expose it as the generic `Time`.
+fn run_queued_steps(world: &mut World) {
+    let steps = std::mem::take(&mut world.resource_mut::<DebugStepper>().pending_steps);
+
+    for _ in 0..steps {
+        let timestep = world.resource::<Time<Fixed>>().timestep();
+        world.resource_mut::<Time<Fixed>>().advance_by(timestep);
+
+        let fixed = world.resource::<Time<Fixed>>().as_generic();
+        let frame = std::mem::replace(&mut *world.resource_mut::<Time>(), fixed);
+        world.run_schedule(FixedMain);
+        *world.resource_mut::<Time>() = frame;
+
+        debug!("Stepper: advanced one fixed tick ({timestep:?})");
+    }
+}
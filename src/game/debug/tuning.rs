@@ -0,0 +1,114 @@
+//! A tuning panel for the gameplay config.
+//!
+//! Press F8 to open a window of sliders over [`GameConfig`]: ball speed
+//! and its cap, restitution, paddle speed and damping, the smash speed-up
+//! and the serve angles. Changes apply straight away. Save writes
+//! the whole config back to `assets/config/game.config.json`, so it's
+//! picked up on the next run; Reset goes back to the compiled-in values.
+
+use std::{fs, ops::RangeInclusive};
+
+use bevy::{input::common_conditions::input_just_pressed, prelude::*};
+use bevy_inspector_egui::bevy_egui::{EguiContextPass, EguiContexts, egui};
+
+use crate::game::config::{CONFIG_PATH, GameConfig};
+
+const PANEL_KEY: KeyCode = KeyCode::F8;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<TuningPanel>();
+
+    app.add_systems(Update, toggle_panel.run_if(input_just_pressed(PANEL_KEY)));
+    app.add_systems(
+        EguiContextPass,
+        draw_panel.run_if(|panel: Res<TuningPanel>| panel.open),
+    );
+}
+
+#[derive(Resource, Default)]
+struct TuningPanel {
+    open: bool,
+}
+
+fn toggle_panel(mut panel: ResMut<TuningPanel>) {
+    panel.open = !panel.open;
+}
+
+fn draw_panel(
+    mut contexts: EguiContexts,
+    mut panel: ResMut<TuningPanel>,
+    mut config: ResMut<GameConfig>,
+) {
+    let Some(ctx) = contexts.try_ctx_mut() else {
+        return;
+    };
+
+    let mut edited = *config;
+    let mut open = panel.open;
+    egui::Window::new("Game Config")
+        .open(&mut open)
+        .show(ctx, |ui| {
+            ui.heading("Ball");
+            slider(ui, &mut edited.ball.speed, 100.0..=800.0, "Serve speed");
+            slider(ui, &mut edited.ball.max_speed, 400.0..=1600.0, "Max speed");
+            slider(
+                ui,
+                &mut edited.ball.smash_speed_up,
+                1.0..=3.0,
+                "Smash speed-up",
+            );
+
+            ui.heading("Restitution");
+            slider(ui, &mut edited.ball.restitution, 0.5..=1.5, "Ball");
+            slider(ui, &mut edited.court.wall_restitution, 0.5..=1.5, "Walls");
+            slider(ui, &mut edited.paddle.restitution, 0.5..=1.5, "Paddles");
+
+            ui.heading("Paddles");
+            slider(
+                ui,
+                &mut edited.paddle.max_speed,
+                100.0..=1000.0,
+                "Max speed",
+            );
+            slider(ui, &mut edited.paddle.damping, 0.25..=4.0, "Damping");
+
+            ui.heading("Serve angles");
+            slider(ui, &mut edited.ball.min_serve_angle, 0.0..=80.0, "Min °");
+            slider(ui, &mut edited.ball.max_serve_angle, 0.0..=80.0, "Max °");
+
+            ui.separator();
+            ui.horizontal(|ui| {
+                if ui.button("Save").clicked() {
+                    save_config(&edited);
+                }
+                if ui.button("Reset").clicked() {
+                    edited = GameConfig::default();
+                }
+            });
+        });
+    panel.open = open;
+
+    // Only write back real edits, so change detection stays quiet
+    if edited != *config {
+        *config = edited;
+    }
+}
+
+fn slider(ui: &mut egui::Ui, value: &mut f32, range: RangeInclusive<f32>, text: &str) {
+    ui.add(egui::Slider::new(value, range).text(text));
+}
+
+fn save_config(config: &GameConfig) {
+    let path = std::path::Path::new("assets").join(CONFIG_PATH);
+    let result = path
+        .parent()
+        .map_or(Ok(()), fs::create_dir_all)
+        .and_then(|_| {
+            let json = serde_json::to_string_pretty(config).map_err(std::io::Error::other)?;
+            fs::write(&path, json)
+        });
+    match result {
+        Ok(()) => info!("Game config: saved to {path:?}"),
+        Err(error) => warn!("Game config: couldn't save: {error}"),
+    }
+}
@@ -0,0 +1,132 @@
+//! Decoy balls, for [`Mutator::Decoys`].
+//!
+//! Every few wall bounces the ball splits: a decoy that looks just like it
+//! peels off at an angle and fades away over a couple of seconds. Decoys
+//! aren't [`Ball`]s and only collide with the walls, so they pass straight
+//! through paddles and goals and never score. Any still around when the
+//! point ends go with it.
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use rand::prelude::*;
+
+use super::{
+    GamePhase,
+    ball::Ball,
+    events::GameEvent,
+    mutators::{Mutator, Mutators},
+    physics::decoy_layers,
+};
+use crate::{AppSystems, PausableSystems, screens::Screen};
+
+/// Wall bounces between decoys
+const BOUNCES_PER_DECOY: u32 = 3;
+/// Degrees a decoy peels off from the ball's path
+const DECOY_SPLIT_ANGLE: f32 = 25.0;
+/// Seconds a decoy lasts, fading all the while
+const DECOY_LIFETIME: f32 = 2.0;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<Decoy>();
+    app.register_type::<DecoyBounces>();
+    app.init_resource::<DecoyBounces>();
+
+    app.add_systems(OnEnter(Screen::Gameplay), reset_bounces);
+    app.add_systems(
+        Update,
+        (
+            spawn_decoys.run_if(|mutators: Res<Mutators>| mutators.is_active(Mutator::Decoys)),
+            fade_decoys,
+        )
+            .chain()
+            .in_set(AppSystems::Update)
+            .in_set(PausableSystems)
+            .run_if(in_state(GamePhase::Playing)),
+    );
+}
+
+/// A look-alike ball that fades out and can't score
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component)]
+struct Decoy(Timer);
+
+/// Wall bounces this match, counting toward the next decoy
+#[derive(Resource, Debug, Default, Reflect)]
+#[reflect(Resource)]
+struct DecoyBounces(u32);
+
+fn reset_bounces(mut bounces: ResMut<DecoyBounces>) {
+    bounces.0 = 0;
+}
+
+/// Splits a decoy off the ball on every [`BOUNCES_PER_DECOY`]th wall bounce
+fn spawn_decoys(
+    mut commands: Commands,
+    mut game_events: EventReader<GameEvent>,
+    mut bounces: ResMut<DecoyBounces>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    balls: Query<
+        (
+            &Position,
+            &LinearVelocity,
+            &Collider,
+            &Mesh2d,
+            &MeshMaterial2d<ColorMaterial>,
+        ),
+        With<Ball>,
+    >,
+) {
+    let rng = &mut rand::rng();
+    for event in game_events.read() {
+        let GameEvent::WallBounce { ball, .. } = *event else {
+            continue;
+        };
+        bounces.0 += 1;
+        if bounces.0 % BOUNCES_PER_DECOY != 0 {
+            continue;
+        }
+        let Ok((position, velocity, collider, mesh, material)) = balls.get(ball) else {
+            continue;
+        };
+
+        // Its own material, so it can fade on its own
+        let color = materials
+            .get(&material.0)
+            .map_or(Color::WHITE, |material| material.color);
+        let split = if rng.random_bool(0.5) { 1.0 } else { -1.0 } * DECOY_SPLIT_ANGLE;
+        commands.spawn((
+            Name::new("Decoy Ball"),
+            Decoy(Timer::from_seconds(DECOY_LIFETIME, TimerMode::Once)),
+            mesh.clone(),
+            MeshMaterial2d(materials.add(ColorMaterial::from_color(color))),
+            Transform::from_translation(position.0.extend(0.0)),
+            RigidBody::Dynamic,
+            collider.clone(),
+            decoy_layers(),
+            Friction::new(0.0),
+            Restitution::new(1.0),
+            LockedAxes::ROTATION_LOCKED,
+            GravityScale(0.0),
+            LinearVelocity(Vec2::from_angle(split.to_radians()).rotate(velocity.0)),
+            TransformInterpolation,
+            SweptCcd::LINEAR,
+            StateScoped(GamePhase::Playing),
+        ));
+    }
+}
+
+fn fade_decoys(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut decoys: Query<(Entity, &mut Decoy, &MeshMaterial2d<ColorMaterial>)>,
+) {
+    for (entity, mut decoy, material) in &mut decoys {
+        decoy.0.tick(time.delta());
+        if decoy.0.finished() {
+            commands.entity(entity).despawn();
+        } else if let Some(material) = materials.get_mut(&material.0) {
+            material.color.set_alpha(1.0 - decoy.0.fraction());
+        }
+    }
+}
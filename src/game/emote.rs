@@ -0,0 +1,215 @@
+//! Quick-chat emotes: a speech bubble pops up over the player's paddle for a
+//! second, with a blip.
+//!
+//! Each emote is its own input action (number keys or the d-pad), so they can
+//! be rebound. Presses become an [`EmoteSent`] trigger, which is also what a
+//! remote player's emotes would arrive as.
+
+use bevy::prelude::*;
+use bevy_enhanced_input::prelude::*;
+
+use super::player::{MirroredCourt, PaddleSize, Player, PlayerSide};
+use crate::{
+    AppSystems, PausableSystems, asset_tracking::LoadResource, audio::sound_effect, screens::Screen,
+};
+
+const BUBBLE_DURATION: f32 = 1.0; // seconds
+const BUBBLE_POP_DURATION: f32 = 0.1; // seconds to grow to full size
+const BUBBLE_SIZE: Vec2 = Vec2::new(90.0, 32.0);
+const BUBBLE_GAP: f32 = 12.0; // px above the paddle
+const BUBBLE_Z: f32 = 5.0;
+const BUBBLE_COLOR: Color = Color::srgb(0.95, 0.95, 0.95);
+const BUBBLE_TEXT_COLOR: Color = Color::srgb(0.1, 0.1, 0.15);
+const BUBBLE_FONT_SIZE: f32 = 16.0;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<EmoteAssets>();
+    app.load_resource::<EmoteAssets>();
+    app.register_type::<EmoteBubble>();
+
+    app.add_observer(send_emote::<EmoteGoodGame>)
+        .add_observer(send_emote::<EmoteNice>)
+        .add_observer(send_emote::<EmoteOops>)
+        .add_observer(send_emote::<EmoteTaunt>)
+        .add_observer(show_emote);
+
+    app.add_systems(
+        Update,
+        pop_emote_bubbles
+            .in_set(AppSystems::Update)
+            .in_set(PausableSystems)
+            .run_if(in_state(Screen::Gameplay)),
+    );
+}
+
+/// Something a player can say
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum Emote {
+    GoodGame,
+    Nice,
+    Oops,
+    Taunt,
+}
+
+impl Emote {
+    pub fn text(self) -> &'static str {
+        match self {
+            Emote::GoodGame => "GG!",
+            Emote::Nice => "Nice one!",
+            Emote::Oops => "Oops!",
+            Emote::Taunt => "Too easy!",
+        }
+    }
+
+    /// Each emote blips at its own pitch
+    fn pitch(self) -> f32 {
+        match self {
+            Emote::GoodGame => 1.0,
+            Emote::Nice => 1.25,
+            Emote::Oops => 0.8,
+            Emote::Taunt => 1.5,
+        }
+    }
+}
+
+/// An input action that sends an emote
+pub trait EmoteInput: InputAction {
+    const EMOTE: Emote;
+}
+
+macro_rules! emote_action {
+    ($name:ident, $emote:expr) => {
+        #[derive(Debug, InputAction)]
+        #[action_output(bool)]
+        pub struct $name;
+
+        impl EmoteInput for $name {
+            const EMOTE: Emote = $emote;
+        }
+    };
+}
+
+emote_action!(EmoteGoodGame, Emote::GoodGame);
+emote_action!(EmoteNice, Emote::Nice);
+emote_action!(EmoteOops, Emote::Oops);
+emote_action!(EmoteTaunt, Emote::Taunt);
+
+/// Triggered when a player emotes, locally or from across the network
+#[derive(Event, Debug, Clone, Copy)]
+pub struct EmoteSent {
+    pub side: PlayerSide,
+    pub emote: Emote,
+}
+
+#[derive(Resource, Asset, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct EmoteAssets {
+    #[dependency]
+    blip: Handle<AudioSource>,
+    bubble: Handle<Mesh>,
+    bubble_material: Handle<ColorMaterial>,
+}
+
+impl FromWorld for EmoteAssets {
+    fn from_world(world: &mut World) -> Self {
+        let blip = world
+            .resource::<AssetServer>()
+            .load("audio/sound_effects/button_click.ogg");
+        let bubble = world
+            .resource_mut::<Assets<Mesh>>()
+            .add(Ellipse::from_size(BUBBLE_SIZE));
+        let bubble_material = world
+            .resource_mut::<Assets<ColorMaterial>>()
+            .add(BUBBLE_COLOR);
+        Self {
+            blip,
+            bubble,
+            bubble_material,
+        }
+    }
+}
+
+/// A speech bubble over a paddle
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct EmoteBubble {
+    timer: Timer,
+}
+
+fn send_emote<A: EmoteInput>(
+    trigger: Trigger<Started<A>>,
+    mut commands: Commands,
+    mirrored: Res<MirroredCourt>,
+    paddles: Query<&Player>,
+) {
+    let Ok(player) = paddles.get(trigger.target()) else {
+        return;
+    };
+    if player.takes_keyboard_input(&mirrored) {
+        commands.trigger(EmoteSent {
+            side: player.side,
+            emote: A::EMOTE,
+        });
+    }
+}
+
+/// Replaces any bubble over the player's paddle with the new emote
+fn show_emote(
+    trigger: Trigger<EmoteSent>,
+    mut commands: Commands,
+    emote_assets: Option<Res<EmoteAssets>>,
+    paddles: Query<(Entity, &Player, &PaddleSize, Option<&Children>)>,
+    bubbles: Query<(), With<EmoteBubble>>,
+) {
+    let Some(emote_assets) = emote_assets else {
+        return;
+    };
+    let EmoteSent { side, emote } = *trigger.event();
+    let Some((paddle, _, size, children)) =
+        paddles.iter().find(|(_, player, ..)| player.side == side)
+    else {
+        return;
+    };
+
+    for &child in children.into_iter().flatten() {
+        if bubbles.contains(child) {
+            commands.entity(child).despawn();
+        }
+    }
+
+    let height = size.0.y / 2.0 + BUBBLE_GAP + BUBBLE_SIZE.y / 2.0;
+    commands.entity(paddle).with_child((
+        Name::new("Emote Bubble"),
+        EmoteBubble {
+            timer: Timer::from_seconds(BUBBLE_DURATION, TimerMode::Once),
+        },
+        Mesh2d(emote_assets.bubble.clone()),
+        MeshMaterial2d(emote_assets.bubble_material.clone()),
+        Transform::from_xyz(0.0, height, BUBBLE_Z).with_scale(Vec3::ZERO),
+        children![(
+            Text2d::new(emote.text()),
+            TextFont::from_font_size(BUBBLE_FONT_SIZE),
+            TextColor(BUBBLE_TEXT_COLOR),
+            Transform::from_xyz(0.0, 0.0, 1.0),
+        )],
+    ));
+    commands
+        .spawn(sound_effect(emote_assets.blip.clone()))
+        .insert(PlaybackSettings::DESPAWN.with_speed(emote.pitch()));
+}
+
+/// Pops bubbles in, then clears them once they've been up long enough
+fn pop_emote_bubbles(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut bubbles: Query<(Entity, &mut EmoteBubble, &mut Transform)>,
+) {
+    for (entity, mut bubble, mut transform) in &mut bubbles {
+        bubble.timer.tick(time.delta());
+        let elapsed = bubble.timer.elapsed_secs();
+        transform.scale = Vec3::splat((elapsed / BUBBLE_POP_DURATION).min(1.0));
+        if bubble.timer.finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
@@ -0,0 +1,104 @@
+//! A small feed of recent [`GameEvent`]s in the corner of the court, each
+//! line fading out after a few seconds.
+
+use bevy::{prelude::*, ui::Val::*};
+
+use super::{events::GameEvent, hud::InCourtFrame};
+use crate::{AppSystems, screens::Screen, theme::palette::LABEL_TEXT};
+
+const FEED_FONT_SIZE: f32 = 18.0;
+const FEED_MARGIN: f32 = 16.0; // px from the bottom-left corner
+const FEED_LINE_GAP: f32 = 4.0;
+/// Most lines shown at once; older ones drop off
+const MAX_FEED_LINES: usize = 5;
+const FEED_LINE_LIFETIME: f32 = 4.0; // seconds
+/// How long a line takes to fade at the end of its lifetime
+const FEED_FADE_DURATION: f32 = 1.0; // seconds
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<EventFeed>();
+    app.register_type::<FeedLine>();
+
+    app.add_systems(OnEnter(Screen::Gameplay), spawn_event_feed);
+    app.add_systems(
+        Update,
+        (push_feed_lines, fade_feed_lines)
+            .chain()
+            .in_set(AppSystems::Update)
+            .run_if(in_state(Screen::Gameplay)),
+    );
+}
+
+/// The column holding the feed lines, newest at the bottom
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct EventFeed;
+
+/// One line of the feed, and how long it has left
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct FeedLine {
+    lifetime: Timer,
+}
+
+fn spawn_event_feed(mut commands: Commands) {
+    commands.spawn((
+        Name::new("Event Feed"),
+        EventFeed,
+        Node {
+            position_type: PositionType::Absolute,
+            left: Px(FEED_MARGIN),
+            bottom: Px(FEED_MARGIN),
+            flex_direction: FlexDirection::Column,
+            row_gap: Px(FEED_LINE_GAP),
+            ..default()
+        },
+        Pickable::IGNORE,
+        InCourtFrame,
+        StateScoped(Screen::Gameplay),
+    ));
+}
+
+fn push_feed_lines(
+    mut commands: Commands,
+    mut events: EventReader<GameEvent>,
+    feed: Single<(Entity, Option<&Children>), With<EventFeed>>,
+) {
+    let (feed, lines) = *feed;
+    let lines = lines.map_or(&[][..], |lines| &lines[..]);
+    let mut count = lines.len();
+    for line in events.read().filter_map(GameEvent::describe) {
+        commands.entity(feed).with_child((
+            Name::new("Feed Line"),
+            FeedLine {
+                lifetime: Timer::from_seconds(FEED_LINE_LIFETIME, TimerMode::Once),
+            },
+            Text::new(line),
+            TextFont::from_font_size(FEED_FONT_SIZE),
+            TextColor(LABEL_TEXT),
+        ));
+        count += 1;
+    }
+
+    // Drop the oldest lines past the limit
+    let excess = count.saturating_sub(MAX_FEED_LINES);
+    for &line in lines.iter().take(excess) {
+        commands.entity(line).despawn();
+    }
+}
+
+fn fade_feed_lines(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut lines: Query<(Entity, &mut FeedLine, &mut TextColor)>,
+) {
+    for (entity, mut line, mut color) in &mut lines {
+        line.lifetime.tick(time.delta());
+        if line.lifetime.finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        let remaining = line.lifetime.remaining_secs();
+        color.0 = LABEL_TEXT.with_alpha((remaining / FEED_FADE_DURATION).min(1.0));
+    }
+}
@@ -0,0 +1,156 @@
+//! The gameplay event bus: a running account of everything that happens in
+//! a match.
+//!
+//! Core systems write a [`GameEvent`] for each serve, paddle hit, wall bounce,
+//! goal and phase change. Features that only need to react (effects, the
+//! event feed, stats, sound) should read these instead of adding their own
+//! collision observers. Observers are still the right tool for anything that
+//! has to act inside the physics step, like changing a ball's velocity.
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+
+use super::{
+    GamePhase,
+    ball::Ball,
+    court::Boundary,
+    player::{PaddleHit, PlayerSide},
+    rules::ServeClockPenalty,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<GameEvent>();
+    app.add_event::<GameEvent>();
+    app.add_observer(emit_paddle_hits);
+    app.add_observer(emit_wall_bounces);
+    app.add_systems(Update, (emit_phase_changes, log_game_events).chain());
+}
+
+/// Something notable that happened in a match
+#[derive(Event, Debug, Clone, Copy, PartialEq, Reflect)]
+pub enum GameEvent {
+    /// `side` served `ball`
+    Serve { side: PlayerSide, ball: Entity },
+    /// `side` faked the serve
+    ServeFeint { side: PlayerSide },
+    /// `ball` came off `side`'s paddle at `position`
+    PaddleHit {
+        side: PlayerSide,
+        ball: Entity,
+        position: Vec2,
+    },
+    /// `ball` bounced off a wall at `position`
+    WallBounce { ball: Entity, position: Vec2 },
+    /// `side` put points on the board
+    Goal {
+        side: PlayerSide,
+        points: u32,
+        own_goal: bool,
+    },
+    /// The receiver won the rally under side-out scoring and takes the serve
+    SideOut { side: PlayerSide },
+    /// An own goal that the rules don't count, so the point is replayed
+    PointReplayed,
+    /// A serve clipped a wall and `server` serves again
+    LetServe { server: PlayerSide },
+    /// `server` ran out the serve clock
+    ServeClockExpired {
+        server: PlayerSide,
+        penalty: ServeClockPenalty,
+    },
+    /// `side` broke a brick for bonus points
+    BrickBroken { side: PlayerSide, points: u32 },
+    /// A damaged wall segment broke
+    WallBroken,
+    /// The match went into sudden death
+    SuddenDeath,
+    /// The last point was taken back
+    PointUndone,
+    /// The match moved into a new phase
+    PhaseChanged { phase: GamePhase },
+}
+
+impl GameEvent {
+    /// A short line describing the event, e.g. "Left scores +1", or `None`
+    /// for routine events too frequent to call out
+    pub fn describe(&self) -> Option<String> {
+        let line = match *self {
+            GameEvent::Serve { .. }
+            | GameEvent::PaddleHit { .. }
+            | GameEvent::WallBounce { .. }
+            | GameEvent::PhaseChanged { .. } => return None,
+            GameEvent::Goal {
+                side,
+                points,
+                own_goal: true,
+            } => format!("{side:?} scores +{points} (own goal)"),
+            GameEvent::Goal { side, points, .. } => format!("{side:?} scores +{points}"),
+            GameEvent::SideOut { side } => format!("Side out - {side:?} serves"),
+            GameEvent::PointReplayed => "Own goal - point replayed".into(),
+            GameEvent::ServeFeint { side } => format!("{side:?} fakes the serve"),
+            GameEvent::LetServe { server } => format!("Let - {server:?} serves again"),
+            GameEvent::ServeClockExpired {
+                server,
+                penalty: ServeClockPenalty::LoseServe,
+            } => format!("Serve clock - {server:?} loses the serve"),
+            GameEvent::ServeClockExpired {
+                server,
+                penalty: ServeClockPenalty::LosePoint,
+            } => format!("Serve clock - {server:?} loses the point"),
+            GameEvent::BrickBroken { side, points } => {
+                format!("{side:?} breaks a brick +{points}")
+            }
+            GameEvent::WallBroken => "A wall breaks open".into(),
+            GameEvent::SuddenDeath => "Sudden death!".into(),
+            GameEvent::PointUndone => "Point undone".into(),
+        };
+        Some(line)
+    }
+}
+
+fn emit_paddle_hits(trigger: Trigger<PaddleHit>, mut game_events: EventWriter<GameEvent>) {
+    let hit = trigger.event();
+    game_events.write(GameEvent::PaddleHit {
+        side: hit.side,
+        ball: hit.ball,
+        position: hit.contact_point,
+    });
+}
+
+fn emit_wall_bounces(
+    trigger: Trigger<OnCollisionStart>,
+    mut game_events: EventWriter<GameEvent>,
+    balls: Query<&Transform, With<Ball>>,
+    boundaries: Query<(), With<Boundary>>,
+) {
+    let ball = trigger.target();
+    let Ok(transform) = balls.get(ball) else {
+        return;
+    };
+    if boundaries.contains(trigger.event().collider) {
+        game_events.write(GameEvent::WallBounce {
+            ball,
+            position: transform.translation.truncate(),
+        });
+    }
+}
+
+fn emit_phase_changes(
+    mut transitions: EventReader<StateTransitionEvent<GamePhase>>,
+    mut game_events: EventWriter<GameEvent>,
+) {
+    for transition in transitions.read() {
+        if transition.exited == transition.entered {
+            continue;
+        }
+        if let Some(phase) = transition.entered {
+            game_events.write(GameEvent::PhaseChanged { phase });
+        }
+    }
+}
+
+fn log_game_events(mut events: EventReader<GameEvent>) {
+    for event in events.read() {
+        debug!("Game event: {event:?}");
+    }
+}
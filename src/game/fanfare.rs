@@ -0,0 +1,76 @@
+//! A musical stinger at the end of every match: a fanfare for the winner, a
+//! bigger one for a mercy win, or a defeat sting when the one player with
+//! something at stake loses.
+//!
+//! Local matches have a winner on both sides of the couch, so the defeat
+//! sting only plays in the daily challenge, where the challenger is always
+//! on the left.
+
+use bevy::prelude::*;
+
+#[cfg(not(target_family = "wasm"))]
+use super::player::MirroredCourt;
+use super::{GamePhase, player::PlayerSide, rules::MatchRules, scoring::Score};
+#[cfg(not(target_family = "wasm"))]
+use crate::daily::DailyChallenge;
+use crate::{asset_tracking::LoadResource, audio::MusicController};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<FanfareAssets>();
+    app.load_resource::<FanfareAssets>();
+
+    app.add_systems(
+        OnEnter(GamePhase::GameOver),
+        play_fanfare.run_if(resource_exists::<FanfareAssets>),
+    );
+}
+
+#[derive(Resource, Asset, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct FanfareAssets {
+    #[dependency]
+    victory: Handle<AudioSource>,
+    #[dependency]
+    mercy_victory: Handle<AudioSource>,
+    #[dependency]
+    defeat: Handle<AudioSource>,
+}
+
+impl FromWorld for FanfareAssets {
+    fn from_world(world: &mut World) -> Self {
+        let assets = world.resource::<AssetServer>();
+        Self {
+            victory: assets.load("audio/stingers/victory.wav"),
+            mercy_victory: assets.load("audio/stingers/mercy_victory.wav"),
+            defeat: assets.load("audio/stingers/defeat.wav"),
+        }
+    }
+}
+
+/// Fades the match music out, then plays the stinger for how it ended
+fn play_fanfare(
+    score: Res<Score>,
+    rules: Res<MatchRules>,
+    assets: Res<FanfareAssets>,
+    #[cfg(not(target_family = "wasm"))] mirrored: Res<MirroredCourt>,
+    #[cfg(not(target_family = "wasm"))] daily: Option<Res<DailyChallenge>>,
+    mut music: ResMut<MusicController>,
+) {
+    let Some(winner) = score.winner(&rules) else {
+        return;
+    };
+    // The side playing for a result of their own, if any
+    #[cfg(not(target_family = "wasm"))]
+    let challenger: Option<PlayerSide> = daily.map(|_| mirrored.home_side());
+    #[cfg(target_family = "wasm")]
+    let challenger: Option<PlayerSide> = None;
+
+    let stinger = if challenger.is_some_and(|side| side != winner) {
+        &assets.defeat
+    } else if score.is_mercy(&rules) {
+        &assets.mercy_victory
+    } else {
+        &assets.victory
+    };
+    music.fade_out_then(stinger.clone());
+}
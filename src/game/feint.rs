@@ -0,0 +1,76 @@
+//! The fake-out serve: with [`MatchRules::serve_feints`] on, the server can
+//! go through the serve motion once per point without letting the ball go.
+//!
+//! The feint sounds and looks like a serve, so CPU receivers bite on it and
+//! have to recover. Each one is reported as a [`GameEvent::ServeFeint`] and
+//! counted in the match stats.
+
+use bevy::prelude::*;
+
+use super::{
+    GamePhase,
+    ball::{Ball, ServeDirection},
+    events::GameEvent,
+    input_buffer::{BufferedAction, InputBuffer},
+    rules::MatchRules,
+    stats::MatchStats,
+};
+use crate::{screens::Screen, theme::tween::TweenScale};
+
+// The ball pops as if it were struck
+const FEINT_PUNCH_SCALE: f32 = 1.4;
+const FEINT_PUNCH_DURATION: f32 = 0.3; // seconds
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<FeintUsed>();
+    app.init_resource::<FeintUsed>();
+
+    app.add_systems(OnEnter(GamePhase::WaitingToServe), reset_feint);
+    // Taken in fixed steps like the serve itself
+    app.add_systems(
+        FixedUpdate,
+        fake_serve
+            .before(super::ball::handle_serve_input)
+            .run_if(in_state(GamePhase::WaitingToServe).and(in_state(Screen::Gameplay))),
+    );
+}
+
+/// Whether this point's feint has been spent
+#[derive(Resource, Debug, Default, Reflect)]
+#[reflect(Resource)]
+struct FeintUsed(bool);
+
+fn reset_feint(mut used: ResMut<FeintUsed>) {
+    used.0 = false;
+}
+
+fn fake_serve(
+    mut commands: Commands,
+    time: Res<Time<Virtual>>,
+    rules: Res<MatchRules>,
+    serve_direction: Res<ServeDirection>,
+    mut buffer: ResMut<InputBuffer>,
+    mut used: ResMut<FeintUsed>,
+    mut stats: ResMut<MatchStats>,
+    balls: Query<Entity, With<Ball>>,
+    mut game_events: EventWriter<GameEvent>,
+) {
+    // Consumed either way, so a feint pressed out of turn doesn't linger
+    if !buffer.consume(BufferedAction::Feint, time.elapsed_secs()) {
+        return;
+    }
+    if !rules.serve_feints || used.0 {
+        return;
+    }
+    used.0 = true;
+
+    let side = serve_direction.side;
+    stats.get_mut(side).feints += 1;
+    for ball in &balls {
+        commands
+            .entity(ball)
+            .insert(TweenScale::punch(FEINT_PUNCH_SCALE, FEINT_PUNCH_DURATION));
+    }
+    game_events.write(GameEvent::ServeFeint { side });
+    info!("{side:?} player faked the serve");
+}
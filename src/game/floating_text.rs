@@ -0,0 +1,129 @@
+//! Floating text popups ("+1", "ACE!", "SAVE!") that rise and fade where
+//! something happened on the court.
+//!
+//! Anything can spawn one with [`floating_text`]; the popups here are driven
+//! by scoring and save events.
+
+use bevy::prelude::*;
+
+use super::{
+    accessibility::ReducedMotion,
+    ball::{Ball, Rally, ServedBy},
+    profile::PlayerProfiles,
+    scoring::PointScored,
+    stats::Saved,
+};
+use crate::{AppSystems, PausableSystems, screens::Screen};
+
+const POPUP_Z: f32 = 10.0; // Above the night overlay
+const POPUP_FONT_SIZE: f32 = 28.0;
+const POPUP_LIFETIME: f32 = 0.9; // seconds
+const POPUP_RISE_SPEED: f32 = 60.0; // px/s
+/// Space between stacked popups for the same moment
+const POPUP_STACK_OFFSET: f32 = 30.0;
+const ACE_COLOR: Color = Color::srgb(1.0, 0.85, 0.3);
+const SAVE_COLOR: Color = Color::srgb(0.5, 0.9, 1.0);
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<FloatingText>();
+
+    app.add_observer(popup_on_point);
+    app.add_observer(popup_on_save);
+    app.add_systems(
+        Update,
+        update_floating_text
+            .in_set(AppSystems::Update)
+            .in_set(PausableSystems)
+            .run_if(in_state(Screen::Gameplay)),
+    );
+}
+
+/// Text that rises and fades out, then despawns
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct FloatingText {
+    lifetime: Timer,
+    color: Color,
+}
+
+/// A popup at `position` on the court
+pub fn floating_text(text: impl Into<String>, position: Vec2, color: Color) -> impl Bundle {
+    (
+        Name::new("Floating Text"),
+        FloatingText {
+            lifetime: Timer::from_seconds(POPUP_LIFETIME, TimerMode::Once),
+            color,
+        },
+        Text2d::new(text),
+        TextFont {
+            font_size: POPUP_FONT_SIZE,
+            ..default()
+        },
+        TextColor(color),
+        Transform::from_translation(position.extend(POPUP_Z)),
+        StateScoped(Screen::Gameplay),
+    )
+}
+
+fn update_floating_text(
+    mut commands: Commands,
+    time: Res<Time>,
+    reduced_motion: Res<ReducedMotion>,
+    mut popups: Query<(Entity, &mut FloatingText, &mut Transform, &mut TextColor)>,
+) {
+    for (entity, mut popup, mut transform, mut text_color) in &mut popups {
+        popup.lifetime.tick(time.delta());
+        if popup.lifetime.finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        if !reduced_motion.0 {
+            transform.translation.y += POPUP_RISE_SPEED * time.delta_secs();
+        }
+        text_color.0 = popup.color.with_alpha(popup.lifetime.fraction_remaining());
+    }
+}
+
+/// "+1" where the ball went in, and "ACE!" if the server scored untouched
+fn popup_on_point(
+    trigger: Trigger<PointScored>,
+    mut commands: Commands,
+    rally: Res<Rally>,
+    profiles: Res<PlayerProfiles>,
+    balls: Query<(&GlobalTransform, Option<&ServedBy>), With<Ball>>,
+) {
+    let event = trigger.event();
+    let Ok((transform, served_by)) = balls.get(event.ball) else {
+        return;
+    };
+    let position = transform.translation().truncate();
+
+    commands.spawn(floating_text(
+        format!("+{}", event.points),
+        position,
+        profiles.color(event.side),
+    ));
+    if rally.hits == 0 && served_by.is_some_and(|served_by| served_by.0 == event.side) {
+        commands.spawn(floating_text(
+            "ACE!",
+            position + Vec2::Y * POPUP_STACK_OFFSET,
+            ACE_COLOR,
+        ));
+    }
+}
+
+fn popup_on_save(
+    trigger: Trigger<Saved>,
+    mut commands: Commands,
+    balls: Query<&GlobalTransform, With<Ball>>,
+) {
+    let Ok(transform) = balls.get(trigger.target()) else {
+        return;
+    };
+    commands.spawn(floating_text(
+        "SAVE!",
+        transform.translation().truncate(),
+        SAVE_COLOR,
+    ));
+}
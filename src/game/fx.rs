@@ -0,0 +1,352 @@
+//! Particle effects, managed in one place.
+//!
+//! Gameplay code asks for effects by sending [`EmitParticles`]. The manager
+//! scales every request by the [`FxQuality`] setting and keeps the number of
+//! live particles within that tier's budget, so busy moments stay smooth on
+//! WASM and mobile. Particles are small meshes that drift, shrink and expire.
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use rand::prelude::*;
+
+use super::{
+    ability::Curve, accessibility::ReducedMotion, ball::Ball, events::GameEvent, goal::Goal,
+    invisible_ball::Unseen, player::PlayerSide, profile::PlayerProfiles,
+};
+use crate::{AppSystems, PausableSystems, screens::Screen};
+
+// Live particle budgets per quality tier
+const LOW_PARTICLE_BUDGET: usize = 120;
+const HIGH_PARTICLE_BUDGET: usize = 600;
+/// Fraction of each request that is spawned on low quality
+const LOW_QUALITY_SCALE: f32 = 0.35;
+
+const PARTICLE_Z: f32 = -0.5; // Behind the ball and paddles, above the court
+const PARTICLE_SIZE: f32 = 4.0;
+const PARTICLE_DRAG: f32 = 3.0; // Fraction of velocity lost per second
+
+// Effects
+const GOAL_BURST_PARTICLES: usize = 60;
+const HIT_SPARK_PARTICLES: usize = 12;
+const MIN_TRAIL_SPEED: f32 = 50.0; // Resting balls leave no trail
+const CURL_RADIUS: f32 = 6.0; // How far a curving ball's trail swings out
+const CURL_SPEED: f32 = 25.0; // Radians per second the trail winds around
+const GOAL_BURST_COLOR: Color = Color::srgb(1.0, 0.5, 0.3);
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<FxQuality>();
+    app.register_type::<Particle>();
+    app.init_resource::<FxQuality>();
+    app.init_resource::<FxAssets>();
+    app.add_event::<EmitParticles>();
+
+    app.add_observer(burst_on_goal);
+    app.add_systems(
+        Update,
+        tint_goal_bursts.run_if(resource_changed::<PlayerProfiles>),
+    );
+    app.add_systems(
+        Update,
+        (
+            emit_ball_trail,
+            spark_on_paddle_hits,
+            spawn_particles,
+            update_particles,
+        )
+            .chain()
+            .in_set(AppSystems::Update)
+            .in_set(PausableSystems)
+            .run_if(in_state(Screen::Gameplay)),
+    );
+}
+
+/// How many particles effects use
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Resource)]
+pub enum FxQuality {
+    Off,
+    Low,
+    #[default]
+    High,
+}
+
+impl FxQuality {
+    pub fn name(self) -> &'static str {
+        match self {
+            FxQuality::Off => "Off",
+            FxQuality::Low => "Low",
+            FxQuality::High => "High",
+        }
+    }
+
+    /// The next tier, for cycling through them in the settings menu
+    pub fn next(self) -> Self {
+        match self {
+            FxQuality::Off => FxQuality::Low,
+            FxQuality::Low => FxQuality::High,
+            FxQuality::High => FxQuality::Off,
+        }
+    }
+
+    /// The previous tier, for cycling back through them in the settings menu
+    pub fn previous(self) -> Self {
+        match self {
+            FxQuality::Off => FxQuality::High,
+            FxQuality::Low => FxQuality::Off,
+            FxQuality::High => FxQuality::Low,
+        }
+    }
+
+    /// Most particles alive at once
+    fn budget(self) -> usize {
+        match self {
+            FxQuality::Off => 0,
+            FxQuality::Low => LOW_PARTICLE_BUDGET,
+            FxQuality::High => HIGH_PARTICLE_BUDGET,
+        }
+    }
+
+    /// Particles actually spawned for a request of `count`
+    fn scale(self, count: usize) -> usize {
+        match self {
+            FxQuality::Off => 0,
+            FxQuality::Low => (count as f32 * LOW_QUALITY_SCALE).ceil() as usize,
+            FxQuality::High => count,
+        }
+    }
+}
+
+/// The look and motion of a particle effect
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParticleKind {
+    /// Faint dots left behind a moving ball
+    Trail,
+    /// A corkscrew of dots behind a curving ball
+    Curl,
+    /// Quick sparks where the ball hits a paddle
+    Spark,
+    /// A big burst where a goal goes in, in the scorer's color when known
+    GoalBurst(Option<PlayerSide>),
+    /// Embers trailing a fire shot
+    Flame,
+}
+
+impl ParticleKind {
+    /// Sudden effects that reduced motion leaves out
+    fn is_flashy(self) -> bool {
+        matches!(self, ParticleKind::Spark | ParticleKind::GoalBurst(_))
+    }
+}
+
+impl ParticleKind {
+    /// Speed range (pixels per second) and lifetime (seconds)
+    fn motion(self) -> (f32, f32, f32) {
+        match self {
+            ParticleKind::Trail => (0.0, 10.0, 0.25),
+            ParticleKind::Curl => (0.0, 5.0, 0.4),
+            ParticleKind::Spark => (150.0, 300.0, 0.3),
+            ParticleKind::GoalBurst(_) => (100.0, 450.0, 0.9),
+            ParticleKind::Flame => (20.0, 60.0, 0.4),
+        }
+    }
+}
+
+/// Request for `count` particles of `kind` at `position`, before quality
+/// scaling and budgeting
+#[derive(Event, Debug, Clone, Copy)]
+pub struct EmitParticles {
+    pub kind: ParticleKind,
+    pub position: Vec2,
+    pub count: usize,
+}
+
+/// Shared mesh and per-kind materials for particles
+#[derive(Resource)]
+struct FxAssets {
+    mesh: Handle<Mesh>,
+    trail: Handle<ColorMaterial>,
+    curl: Handle<ColorMaterial>,
+    spark: Handle<ColorMaterial>,
+    goal_burst: Handle<ColorMaterial>,
+    flame: Handle<ColorMaterial>,
+    /// Goal bursts for each side, tinted with the player's color
+    side_bursts: [Handle<ColorMaterial>; 2],
+}
+
+impl FxAssets {
+    fn material(&self, kind: ParticleKind) -> Handle<ColorMaterial> {
+        match kind {
+            ParticleKind::Trail => self.trail.clone(),
+            ParticleKind::Curl => self.curl.clone(),
+            ParticleKind::Spark => self.spark.clone(),
+            ParticleKind::GoalBurst(None) => self.goal_burst.clone(),
+            ParticleKind::GoalBurst(Some(side)) => self.side_bursts[side as usize].clone(),
+            ParticleKind::Flame => self.flame.clone(),
+        }
+    }
+}
+
+impl FromWorld for FxAssets {
+    fn from_world(world: &mut World) -> Self {
+        let mesh = world
+            .resource_mut::<Assets<Mesh>>()
+            .add(Rectangle::from_length(PARTICLE_SIZE));
+        let mut materials = world.resource_mut::<Assets<ColorMaterial>>();
+        Self {
+            mesh,
+            trail: materials.add(Color::srgba(1.0, 1.0, 1.0, 0.3)),
+            curl: materials.add(Color::srgba(0.5, 0.85, 1.0, 0.6)),
+            spark: materials.add(Color::srgb(1.0, 0.85, 0.4)),
+            goal_burst: materials.add(GOAL_BURST_COLOR),
+            flame: materials.add(Color::srgb(1.0, 0.4, 0.1)),
+            side_bursts: [
+                materials.add(GOAL_BURST_COLOR),
+                materials.add(GOAL_BURST_COLOR),
+            ],
+        }
+    }
+}
+
+/// A live particle
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component)]
+struct Particle {
+    velocity: Vec2,
+    lifetime: Timer,
+}
+
+/// Leaves a trail behind each moving ball in sight, winding around it while
+/// it curves
+fn emit_ball_trail(
+    time: Res<Time>,
+    mut emit: EventWriter<EmitParticles>,
+    balls: Query<(&Transform, &LinearVelocity, Option<&Curve>), (With<Ball>, Without<Unseen>)>,
+) {
+    for (transform, velocity, curve) in &balls {
+        if velocity.length() < MIN_TRAIL_SPEED {
+            continue;
+        }
+        let position = transform.translation.truncate();
+        let (kind, position) = match curve {
+            Some(curve) => {
+                // Winds the same way the ball is turning
+                let angle = time.elapsed_secs() * CURL_SPEED * curve.rate.signum();
+                let sideways = velocity.perp().normalize_or_zero();
+                (
+                    ParticleKind::Curl,
+                    position + sideways * angle.sin() * CURL_RADIUS,
+                )
+            }
+            None => (ParticleKind::Trail, position),
+        };
+        emit.write(EmitParticles {
+            kind,
+            position,
+            count: 1,
+        });
+    }
+}
+
+fn spark_on_paddle_hits(
+    mut game_events: EventReader<GameEvent>,
+    mut emit: EventWriter<EmitParticles>,
+) {
+    for event in game_events.read() {
+        if let GameEvent::PaddleHit { position, .. } = *event {
+            emit.write(EmitParticles {
+                kind: ParticleKind::Spark,
+                position,
+                count: HIT_SPARK_PARTICLES,
+            });
+        }
+    }
+}
+
+fn burst_on_goal(
+    trigger: Trigger<OnCollisionStart>,
+    mut emit: EventWriter<EmitParticles>,
+    goals: Query<&Goal>,
+    balls: Query<&Transform, With<Ball>>,
+) {
+    let Ok(goal) = goals.get(trigger.target()) else {
+        return;
+    };
+    // Who an out-of-bounds point goes to isn't known here
+    let scorer = goal.scorer();
+    if let Ok(ball) = balls.get(trigger.event().collider) {
+        emit.write(EmitParticles {
+            kind: ParticleKind::GoalBurst(scorer),
+            position: ball.translation.truncate(),
+            count: GOAL_BURST_PARTICLES,
+        });
+    }
+}
+
+fn tint_goal_bursts(
+    profiles: Res<PlayerProfiles>,
+    assets: Res<FxAssets>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    for side in [PlayerSide::Left, PlayerSide::Right] {
+        if let Some(material) = materials.get_mut(&assets.side_bursts[side as usize]) {
+            material.color = profiles.color(side);
+        }
+    }
+}
+
+/// Spawns requested particles, scaled by quality and within the live budget
+fn spawn_particles(
+    mut commands: Commands,
+    quality: Res<FxQuality>,
+    reduced_motion: Res<ReducedMotion>,
+    assets: Res<FxAssets>,
+    mut requests: EventReader<EmitParticles>,
+    particles: Query<(), With<Particle>>,
+) {
+    let mut room = quality.budget().saturating_sub(particles.iter().count());
+    let rng = &mut rand::rng();
+
+    for request in requests.read() {
+        if reduced_motion.0 && request.kind.is_flashy() {
+            continue;
+        }
+        let count = quality.scale(request.count).min(room);
+        room -= count;
+
+        let (min_speed, max_speed, lifetime) = request.kind.motion();
+        for _ in 0..count {
+            let direction = Vec2::from_angle(rng.random_range(0.0..std::f32::consts::TAU));
+            commands.spawn((
+                Name::new("Particle"),
+                Particle {
+                    velocity: direction * rng.random_range(min_speed..=max_speed),
+                    lifetime: Timer::from_seconds(lifetime, TimerMode::Once),
+                },
+                Mesh2d(assets.mesh.clone()),
+                MeshMaterial2d(assets.material(request.kind)),
+                Transform::from_translation(request.position.extend(PARTICLE_Z)),
+                StateScoped(Screen::Gameplay),
+            ));
+        }
+    }
+}
+
+fn update_particles(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut particles: Query<(Entity, &mut Particle, &mut Transform)>,
+) {
+    let dt = time.delta_secs();
+    let drag = (1.0 - PARTICLE_DRAG * dt).max(0.0);
+    for (entity, mut particle, mut transform) in &mut particles {
+        particle.lifetime.tick(time.delta());
+        if particle.lifetime.finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        transform.translation += (particle.velocity * dt).extend(0.0);
+        particle.velocity *= drag;
+        transform.scale = Vec3::splat(1.0 - particle.lifetime.fraction());
+    }
+}
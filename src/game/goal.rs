@@ -0,0 +1,450 @@
+//! Goals: the sensors at each end of the court (and beyond an open court's
+//! side lines) and deciding who scores when a ball gets into one.
+//!
+//! [`call_goal`] holds the scoring decision on its own, away from the
+//! observer, so new rules can extend it without touching physics.
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+
+use super::{
+    ball::{Ball, Deflected, LastTouchedBy, ServeDirection, ServedBy},
+    court::{BOUNDARY_THICKNESS, CourtSize, LINE_COLOR},
+    court_theme::CourtLine,
+    physics::goal_layers,
+    player::PlayerSide,
+    rules::{MatchRules, ScoringSystem},
+    scoring::GoalScored,
+    speedometer::kmh,
+    stats::{GoalShot, MatchStats},
+};
+use crate::screens::Screen;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<Goal>();
+    app.register_type::<GoalMouth>();
+    app.add_systems(
+        FixedUpdate,
+        move_goal_mouths.run_if(in_state(Screen::Gameplay)),
+    );
+}
+
+// Goal area dimensions (sensor colliders), as tall as the court
+const GOAL_WIDTH: f32 = 50.0;
+
+// Moving goal mouths cover part of the end line and sweep up and down it
+const GOAL_MOUTH_HEIGHT: f32 = 160.0;
+const GOAL_MOUTH_SPEED: f32 = 0.8; // radians per second
+const GOAL_POST_THICKNESS: f32 = 4.0;
+const GOAL_MOUTH_COLOR: Color = Color::srgba(1.0, 1.0, 1.0, 0.12);
+
+// Out-of-bounds sensors sit just outside the open court's side lines
+pub const OUT_OF_BOUNDS_HEIGHT: f32 = 50.0;
+
+/// Sensor that ends a point when the ball enters it
+#[derive(Component, Debug, Clone, Copy, Reflect)]
+#[reflect(Component)]
+pub enum Goal {
+    Left,
+    Right,
+    /// Beyond the top or bottom line of an open court
+    OutOfBounds,
+}
+
+impl From<EndGoal> for Goal {
+    fn from(end: EndGoal) -> Self {
+        match end {
+            EndGoal::Left => Goal::Left,
+            EndGoal::Right => Goal::Right,
+        }
+    }
+}
+
+impl Goal {
+    /// The player defending this goal, if it has one
+    pub fn defender(self) -> Option<PlayerSide> {
+        match self {
+            Goal::Left => Some(PlayerSide::Left),
+            Goal::Right => Some(PlayerSide::Right),
+            Goal::OutOfBounds => None,
+        }
+    }
+
+    /// The player a ball in this goal scores for, if that doesn't depend on
+    /// who touched it last
+    pub fn scorer(self) -> Option<PlayerSide> {
+        self.defender().map(PlayerSide::opponent)
+    }
+}
+
+/// One of the two goals at the ends of the court
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EndGoal {
+    Left,
+    Right,
+}
+
+impl EndGoal {
+    /// Centre of the goal sensor, just inside the court edge so it overlaps
+    /// the play area
+    fn x_position(self, size: CourtSize) -> f32 {
+        let x = size.half_width() - GOAL_WIDTH / 2.0;
+        match self {
+            EndGoal::Left => -x,
+            EndGoal::Right => x,
+        }
+    }
+}
+
+/// A goal that only covers part of the end line and sweeps along it
+#[derive(Component, Debug, Clone, Copy, Reflect)]
+#[reflect(Component)]
+pub struct GoalMouth {
+    /// Offset into the sweep, so the two goals move out of step
+    phase: f32,
+}
+
+/// Spawns a goal sensor area
+pub(super) fn spawn_goal(commands: &mut Commands, end: EndGoal, size: CourtSize) -> Entity {
+    let goal = Goal::from(end);
+
+    commands
+        .spawn((
+            Name::new(format!("{goal:?} Goal")),
+            goal,
+            // Sensor collider - doesn't physically block but detects overlaps
+            Sensor,
+            Collider::rectangle(GOAL_WIDTH, size.height),
+            goal_layers(),
+            Transform::from_xyz(end.x_position(size), 0.0, 0.0),
+            // Enable collision events for observer-based detection
+            CollisionEventsEnabled,
+        ))
+        .observe(detect_goal)
+        .id()
+}
+
+/// Spawns a goal sensor covering only part of the end line, with a visible
+/// frame, that [`move_goal_mouths`] sweeps up and down
+pub(super) fn spawn_goal_mouth(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<ColorMaterial>,
+    end: EndGoal,
+    size: CourtSize,
+) -> Entity {
+    let goal = Goal::from(end);
+    let phase = match end {
+        EndGoal::Left => 0.0,
+        EndGoal::Right => std::f32::consts::PI,
+    };
+    let post_mesh = meshes.add(Rectangle::new(GOAL_WIDTH, GOAL_POST_THICKNESS));
+    let post_material = materials.add(LINE_COLOR);
+    let post_y = GOAL_MOUTH_HEIGHT / 2.0;
+
+    commands
+        .spawn((
+            Name::new(format!("{goal:?} Goal")),
+            goal,
+            GoalMouth { phase },
+            Sensor,
+            RigidBody::Kinematic,
+            Collider::rectangle(GOAL_WIDTH, GOAL_MOUTH_HEIGHT),
+            goal_layers(),
+            Mesh2d(meshes.add(Rectangle::new(GOAL_WIDTH, GOAL_MOUTH_HEIGHT))),
+            MeshMaterial2d(materials.add(GOAL_MOUTH_COLOR)),
+            Transform::from_xyz(end.x_position(size), 0.0, 0.0),
+            CollisionEventsEnabled,
+            children![
+                (
+                    Name::new("Top Post"),
+                    Mesh2d(post_mesh.clone()),
+                    MeshMaterial2d(post_material.clone()),
+                    CourtLine::Solid,
+                    Transform::from_xyz(0.0, post_y, 0.0),
+                ),
+                (
+                    Name::new("Bottom Post"),
+                    Mesh2d(post_mesh),
+                    MeshMaterial2d(post_material),
+                    CourtLine::Solid,
+                    Transform::from_xyz(0.0, -post_y, 0.0),
+                ),
+            ],
+        ))
+        .observe(detect_goal)
+        .id()
+}
+
+/// Sweeps moving goal mouths up and down the end lines, staying inside the court
+fn move_goal_mouths(
+    time: Res<Time>,
+    size: Res<CourtSize>,
+    mut mouths: Query<(&GoalMouth, &Position, &mut LinearVelocity)>,
+) {
+    let dt = time.delta_secs();
+    if dt <= 0.0 {
+        return;
+    }
+    let reach = (size.height - GOAL_MOUTH_HEIGHT) / 2.0 - BOUNDARY_THICKNESS;
+
+    // Drive the kinematic sensor with velocity so the next step lands on target
+    for (mouth, position, mut velocity) in &mut mouths {
+        let next_time = time.elapsed_secs() + dt;
+        let target_y = (next_time * GOAL_MOUTH_SPEED + mouth.phase).sin() * reach;
+        velocity.0 = Vec2::new(0.0, (target_y - position.y) / dt);
+    }
+}
+
+/// Spawns an out-of-bounds sensor along an open court's top or bottom line
+pub(super) fn spawn_out_of_bounds(
+    commands: &mut Commands,
+    y_position: f32,
+    court_width: f32,
+) -> Entity {
+    commands
+        .spawn((
+            Name::new("Out Of Bounds"),
+            Goal::OutOfBounds,
+            Sensor,
+            Collider::rectangle(court_width, OUT_OF_BOUNDS_HEIGHT),
+            goal_layers(),
+            Transform::from_xyz(0.0, y_position, 0.0),
+            CollisionEventsEnabled,
+        ))
+        .observe(detect_goal)
+        .id()
+}
+
+/// The outcome of a ball entering a goal
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GoalCall {
+    /// Who the point goes to
+    pub side: PlayerSide,
+    /// The ball was knocked out of bounds
+    pub fault: bool,
+    /// The ball went in straight off the defender's own paddle
+    pub own_goal: bool,
+}
+
+impl GoalCall {
+    /// The point is replayed instead of scored, since the rules don't count
+    /// own goals
+    pub fn replayed(&self, rules: &MatchRules) -> bool {
+        self.own_goal && !rules.own_goals
+    }
+
+    /// Whether the call puts points on the board. With side-out scoring only
+    /// the `server` can score; a receiver winning the rally takes the serve.
+    pub fn awards_point(&self, rules: &MatchRules, server: PlayerSide) -> bool {
+        !self.replayed(rules)
+            && match rules.scoring {
+                ScoringSystem::Rally => true,
+                ScoringSystem::SideOut => self.side == server,
+            }
+    }
+}
+
+/// Decides who a ball entering `goal` scores for.
+///
+/// Out of bounds is a fault by the last player to touch the ball, or by the
+/// server if nobody has returned it yet (`server` is the fallback when the
+/// ball has no recorded server). A ball that was `deflected` off a wall or
+/// obstacle after the last touch is never an own goal.
+pub fn call_goal(
+    goal: Goal,
+    served_by: Option<PlayerSide>,
+    last_touch: Option<PlayerSide>,
+    deflected: bool,
+    server: PlayerSide,
+) -> GoalCall {
+    let (side, fault) = match goal.scorer() {
+        Some(scorer) => (scorer, false),
+        None => {
+            let at_fault = last_touch.or(served_by).unwrap_or(server);
+            (at_fault.opponent(), true)
+        }
+    };
+
+    // Into your own goal straight off your own paddle
+    let own_goal = !fault && !deflected && last_touch.is_some_and(|last_touch| last_touch != side);
+
+    GoalCall {
+        side,
+        fault,
+        own_goal,
+    }
+}
+
+/// Ends the point when a ball enters a goal or goes out of bounds, charting
+/// where it went in
+fn detect_goal(
+    trigger: Trigger<OnCollisionStart>,
+    mut commands: Commands,
+    goals: Query<&Goal>,
+    ball_query: Query<
+        (
+            &Position,
+            &LinearVelocity,
+            Option<&ServedBy>,
+            Option<&LastTouchedBy>,
+            Has<Deflected>,
+        ),
+        With<Ball>,
+    >,
+    serve_direction: Res<ServeDirection>,
+    court: Res<CourtSize>,
+    mut stats: ResMut<MatchStats>,
+) {
+    let ball = trigger.event().collider;
+
+    // Check if the colliding entity is a ball
+    let Ok((position, velocity, served_by, last_touch, deflected)) = ball_query.get(ball) else {
+        return;
+    };
+    let Ok(&goal) = goals.get(trigger.target()) else {
+        return;
+    };
+
+    if let Some(defender) = goal.defender() {
+        stats.goal_shots.push(GoalShot {
+            defender,
+            y: position.y,
+            speed: kmh(velocity.length(), *court),
+        });
+    }
+
+    let call = call_goal(
+        goal,
+        served_by.map(|served_by| served_by.0),
+        last_touch.map(|last_touch| last_touch.0),
+        deflected,
+        serve_direction.side,
+    );
+
+    info!(
+        "{}! {:?} player scores",
+        if call.own_goal {
+            "Own goal"
+        } else if call.fault {
+            "Fault"
+        } else {
+            "Goal"
+        },
+        call.side
+    );
+    commands.trigger(GoalScored {
+        side: call.side,
+        fault: call.fault,
+        own_goal: call.own_goal,
+        ball,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn goal_scores_for_the_attacker() {
+        let call = call_goal(
+            Goal::Right,
+            Some(PlayerSide::Left),
+            Some(PlayerSide::Left),
+            false,
+            PlayerSide::Left,
+        );
+        assert_eq!(
+            call,
+            GoalCall {
+                side: PlayerSide::Left,
+                fault: false,
+                own_goal: false,
+            }
+        );
+    }
+
+    #[test]
+    fn out_of_bounds_is_a_fault_by_the_last_touch() {
+        let call = call_goal(
+            Goal::OutOfBounds,
+            Some(PlayerSide::Left),
+            Some(PlayerSide::Right),
+            false,
+            PlayerSide::Left,
+        );
+        assert_eq!(call.side, PlayerSide::Left);
+        assert!(call.fault);
+        assert!(!call.own_goal);
+    }
+
+    #[test]
+    fn unreturned_serve_out_of_bounds_is_a_fault_by_the_server() {
+        let call = call_goal(Goal::OutOfBounds, None, None, false, PlayerSide::Right);
+        assert_eq!(call.side, PlayerSide::Left);
+        assert!(call.fault);
+    }
+
+    #[test]
+    fn ball_off_the_defenders_paddle_is_an_own_goal() {
+        let call = call_goal(
+            Goal::Left,
+            Some(PlayerSide::Right),
+            Some(PlayerSide::Left),
+            false,
+            PlayerSide::Right,
+        );
+        assert_eq!(call.side, PlayerSide::Right);
+        assert!(!call.fault);
+        assert!(call.own_goal);
+    }
+
+    #[test]
+    fn ball_deflected_after_the_defenders_paddle_is_not_an_own_goal() {
+        let call = call_goal(
+            Goal::Left,
+            Some(PlayerSide::Right),
+            Some(PlayerSide::Left),
+            true,
+            PlayerSide::Right,
+        );
+        assert_eq!(call.side, PlayerSide::Right);
+        assert!(!call.own_goal);
+    }
+
+    #[test]
+    fn own_goals_are_replayed_when_the_rule_is_off() {
+        let call = call_goal(
+            Goal::Left,
+            Some(PlayerSide::Right),
+            Some(PlayerSide::Left),
+            false,
+            PlayerSide::Right,
+        );
+        let mut rules = MatchRules::default();
+        assert!(!call.replayed(&rules));
+        assert!(call.awards_point(&rules, PlayerSide::Right));
+
+        rules.own_goals = false;
+        assert!(call.replayed(&rules));
+        assert!(!call.awards_point(&rules, PlayerSide::Right));
+    }
+
+    #[test]
+    fn side_out_scoring_only_awards_the_server() {
+        let call = call_goal(
+            Goal::Right,
+            Some(PlayerSide::Right),
+            Some(PlayerSide::Left),
+            false,
+            PlayerSide::Right,
+        );
+        let rules = MatchRules {
+            scoring: ScoringSystem::SideOut,
+            ..default()
+        };
+        assert!(!call.awards_point(&rules, PlayerSide::Right));
+        assert!(call.awards_point(&rules, PlayerSide::Left));
+        assert!(call.awards_point(&MatchRules::default(), PlayerSide::Right));
+    }
+}
@@ -0,0 +1,155 @@
+//! A heatmap of where the ball spent the match, for the post-match summary
+//! and telemetry.
+//!
+//! Every physics step while a point is being played, each ball in the court
+//! adds one to the cell of a coarse grid it's in. The summary draws the grid
+//! with each goal line in its defender's color, so a hot end shows where
+//! that player was under pressure.
+
+use avian2d::prelude::*;
+use bevy::{ecs::spawn::SpawnWith, prelude::*, ui::Val::*};
+use serde::Serialize;
+
+use super::{GamePhase, ball::Ball, court::CourtSize, player::PlayerSide, profile::PlayerProfiles};
+use crate::screens::Screen;
+
+/// Cells across and down the court
+const COLUMNS: usize = 16;
+const ROWS: usize = 12;
+
+// Layout, keeping the court's proportions
+const HEATMAP_WIDTH: f32 = 160.0;
+const HEATMAP_BACKGROUND: Color = Color::srgba(1.0, 1.0, 1.0, 0.05);
+const COLD_COLOR: Color = Color::srgb(0.2, 0.3, 0.9);
+const HOT_COLOR: Color = Color::srgb(1.0, 0.35, 0.1);
+const GOAL_LINE_WIDTH: f32 = 3.0;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<BallHeatmap>();
+    app.init_resource::<BallHeatmap>();
+    app.add_systems(OnEnter(Screen::Gameplay), reset_heatmap);
+    app.add_systems(
+        FixedUpdate,
+        sample_ball_positions.run_if(in_state(GamePhase::Playing)),
+    );
+}
+
+/// Physics steps the ball spent in each cell of the court this match, a row
+/// at a time from the top left
+#[derive(Resource, Debug, Clone, Reflect, Serialize)]
+#[reflect(Resource)]
+pub struct BallHeatmap {
+    pub columns: usize,
+    pub rows: usize,
+    pub cells: Vec<u32>,
+}
+
+impl Default for BallHeatmap {
+    fn default() -> Self {
+        Self {
+            columns: COLUMNS,
+            rows: ROWS,
+            cells: vec![0; COLUMNS * ROWS],
+        }
+    }
+}
+
+impl BallHeatmap {
+    /// Counts a sample at `position`, unless it's outside the court
+    fn sample(&mut self, position: Vec2, court: CourtSize) {
+        let x = (position.x / court.width + 0.5) * self.columns as f32;
+        let y = (0.5 - position.y / court.height) * self.rows as f32;
+        if !(0.0..self.columns as f32).contains(&x) || !(0.0..self.rows as f32).contains(&y) {
+            return;
+        }
+        self.cells[y as usize * self.columns + x as usize] += 1;
+    }
+
+    fn hottest(&self) -> u32 {
+        self.cells.iter().copied().max().unwrap_or(0)
+    }
+}
+
+fn reset_heatmap(mut heatmap: ResMut<BallHeatmap>) {
+    *heatmap = BallHeatmap::default();
+}
+
+fn sample_ball_positions(
+    mut heatmap: ResMut<BallHeatmap>,
+    court: Res<CourtSize>,
+    balls: Query<&Position, With<Ball>>,
+) {
+    for position in &balls {
+        heatmap.sample(position.0, *court);
+    }
+}
+
+/// The heatmap as a grid of cells shading from cold to hot, between goal
+/// lines in each defender's color
+pub fn heatmap(heatmap: &BallHeatmap, court: CourtSize, profiles: &PlayerProfiles) -> impl Bundle {
+    let hottest = heatmap.hottest().max(1) as f32;
+    let (columns, rows) = (heatmap.columns, heatmap.rows);
+    let cells: Vec<_> = heatmap
+        .cells
+        .iter()
+        .enumerate()
+        .filter(|&(_, &count)| count > 0)
+        .map(|(index, &count)| {
+            let heat = count as f32 / hottest;
+            let color = COLD_COLOR
+                .mix(&HOT_COLOR, heat)
+                .with_alpha(0.2 + 0.8 * heat);
+            (index % columns, index / columns, color)
+        })
+        .collect();
+    let goal_lines = [
+        (PlayerSide::Left, profiles.color(PlayerSide::Left)),
+        (PlayerSide::Right, profiles.color(PlayerSide::Right)),
+    ];
+
+    (
+        Name::new("Ball Heatmap"),
+        Node {
+            width: Px(HEATMAP_WIDTH),
+            height: Px(HEATMAP_WIDTH * court.height / court.width),
+            ..default()
+        },
+        BackgroundColor(HEATMAP_BACKGROUND),
+        Pickable::IGNORE,
+        Children::spawn(SpawnWith(move |parent: &mut ChildSpawner| {
+            let (cell_width, cell_height) = (100.0 / columns as f32, 100.0 / rows as f32);
+            for (column, row, color) in cells {
+                parent.spawn((
+                    Name::new("Heatmap Cell"),
+                    Node {
+                        position_type: PositionType::Absolute,
+                        left: Percent(column as f32 * cell_width),
+                        top: Percent(row as f32 * cell_height),
+                        width: Percent(cell_width),
+                        height: Percent(cell_height),
+                        ..default()
+                    },
+                    BackgroundColor(color),
+                ));
+            }
+            for (side, color) in goal_lines {
+                let (left, right) = match side {
+                    PlayerSide::Left => (Px(0.0), Auto),
+                    PlayerSide::Right => (Auto, Px(0.0)),
+                };
+                parent.spawn((
+                    Name::new("Goal Line"),
+                    Node {
+                        position_type: PositionType::Absolute,
+                        left,
+                        right,
+                        width: Px(GOAL_LINE_WIDTH),
+                        height: Percent(100.0),
+                        ..default()
+                    },
+                    BackgroundColor(color),
+                ));
+            }
+        })),
+    )
+}
@@ -0,0 +1,93 @@
+//! A UI container that tracks the court's rect on screen, so the HUD stays
+//! anchored to the court however the window is shaped or the camera zooms.
+//!
+//! Tag a UI node with [`InCourtFrame`] and it's moved into the frame before
+//! layout, where percentages are relative to the court rather than the window.
+
+use bevy::{
+    prelude::*,
+    ui::{UiSystem, Val::*},
+};
+
+use super::{camera::SplitCamera, court::CourtSize};
+use crate::screens::Screen;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<CourtFrame>();
+    app.register_type::<InCourtFrame>();
+
+    app.add_systems(OnEnter(Screen::Gameplay), spawn_court_frame);
+    app.add_systems(
+        PostUpdate,
+        (adopt_court_hud, fit_court_frame)
+            .before(UiSystem::Layout)
+            .run_if(in_state(Screen::Gameplay)),
+    );
+}
+
+/// A UI node covering exactly the court's on-screen rect
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct CourtFrame;
+
+/// UI to lay out relative to the court instead of the window
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct InCourtFrame;
+
+fn spawn_court_frame(mut commands: Commands) {
+    commands.spawn((
+        Name::new("Court Frame"),
+        CourtFrame,
+        Node {
+            position_type: PositionType::Absolute,
+            width: Percent(100.0),
+            height: Percent(100.0),
+            ..default()
+        },
+        Pickable::IGNORE,
+        StateScoped(Screen::Gameplay),
+    ));
+}
+
+fn adopt_court_hud(
+    mut commands: Commands,
+    frame: Single<Entity, With<CourtFrame>>,
+    orphans: Query<Entity, (With<InCourtFrame>, Without<ChildOf>)>,
+) {
+    for entity in &orphans {
+        commands.entity(*frame).add_child(entity);
+    }
+}
+
+/// Projects the court's corners through the main camera and fits the frame
+/// to them
+fn fit_court_frame(
+    mut frame: Single<&mut Node, With<CourtFrame>>,
+    camera: Single<(&Camera, &GlobalTransform), (With<Camera2d>, Without<SplitCamera>)>,
+    court: Res<CourtSize>,
+) {
+    let (camera, camera_transform) = *camera;
+    let half_court = court.extent() / 2.0;
+    let (Ok(top_left), Ok(bottom_right)) = (
+        camera.world_to_viewport(
+            camera_transform,
+            Vec3::new(-half_court.x, half_court.y, 0.0),
+        ),
+        camera.world_to_viewport(
+            camera_transform,
+            Vec3::new(half_court.x, -half_court.y, 0.0),
+        ),
+    ) else {
+        return;
+    };
+
+    let size = bottom_right - top_left;
+    let (left, top, width, height) = (Px(top_left.x), Px(top_left.y), Px(size.x), Px(size.y));
+    if frame.left != left || frame.top != top || frame.width != width || frame.height != height {
+        frame.left = left;
+        frame.top = top;
+        frame.width = width;
+        frame.height = height;
+    }
+}
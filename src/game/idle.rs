@@ -0,0 +1,154 @@
+//! Pauses the match when the player at the keyboard seems to have walked
+//! away, so they don't come back to a pile of free goals: after a while
+//! without input, or as soon as the window loses focus.
+//!
+//! Only the home paddle is watched for input: CPU paddles never get bored,
+//! and the other side may not have anyone on it at all.
+
+use bevy::{
+    prelude::*,
+    window::{WindowFocused, WindowOccluded},
+};
+
+use super::{
+    GamePhase,
+    cpu::CpuPlayer,
+    player::{MirroredCourt, PaddleIntent, Player},
+};
+#[cfg(not(target_family = "wasm"))]
+use crate::replay::ReplayPlayback;
+use crate::{AppSystems, PausableSystems, Pause, menus::Menu, screens::Screen};
+
+/// Choices for the idle timeout, in seconds
+const IDLE_TIMEOUTS: [u32; 4] = [0, 15, 30, 60];
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<IdleTimeout>();
+    app.init_resource::<IdleTimeout>();
+    app.register_type::<IdleTime>();
+    app.init_resource::<IdleTime>();
+    app.register_type::<PauseOnFocusLoss>();
+    app.init_resource::<PauseOnFocusLoss>();
+
+    app.add_systems(OnEnter(GamePhase::Playing), reset_idle_time);
+    let watch = watch_for_idle
+        .in_set(AppSystems::Update)
+        .in_set(PausableSystems)
+        .run_if(in_state(GamePhase::Playing));
+    // Nobody's at the keyboard while a replay plays
+    #[cfg(not(target_family = "wasm"))]
+    let watch = watch.run_if(not(resource_exists::<ReplayPlayback>));
+    app.add_systems(Update, watch);
+
+    app.add_systems(
+        Update,
+        pause_on_focus_loss
+            .run_if(in_state(Screen::Gameplay).and(in_state(Menu::None)))
+            .in_set(AppSystems::Update),
+    );
+}
+
+/// Whether the match pauses when the window loses focus or is minimized.
+/// Turn off where pausing isn't allowed, as in netplay.
+#[derive(Resource, Debug, Clone, Copy, Reflect)]
+#[reflect(Resource)]
+pub struct PauseOnFocusLoss(pub bool);
+
+impl Default for PauseOnFocusLoss {
+    fn default() -> Self {
+        Self(true)
+    }
+}
+
+/// Seconds without input before the match pauses itself (0 never does)
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Resource)]
+pub struct IdleTimeout(pub u32);
+
+impl Default for IdleTimeout {
+    fn default() -> Self {
+        Self(30)
+    }
+}
+
+impl IdleTimeout {
+    pub fn next(self) -> Self {
+        let index = IDLE_TIMEOUTS
+            .iter()
+            .position(|seconds| *seconds == self.0)
+            .map_or(0, |index| (index + 1) % IDLE_TIMEOUTS.len());
+        Self(IDLE_TIMEOUTS[index])
+    }
+
+    pub fn previous(self) -> Self {
+        let index = IDLE_TIMEOUTS
+            .iter()
+            .position(|seconds| *seconds == self.0)
+            .map_or(0, |index| {
+                (index + IDLE_TIMEOUTS.len() - 1) % IDLE_TIMEOUTS.len()
+            });
+        Self(IDLE_TIMEOUTS[index])
+    }
+
+    /// The timeout for the settings menu, e.g. "30s"
+    pub fn label(self) -> String {
+        if self.0 == 0 {
+            "Off".into()
+        } else {
+            format!("{}s", self.0)
+        }
+    }
+}
+
+/// How long the home paddle has gone without input this rally
+#[derive(Resource, Debug, Default, Reflect)]
+#[reflect(Resource)]
+struct IdleTime(f32);
+
+fn reset_idle_time(mut idle: ResMut<IdleTime>) {
+    idle.0 = 0.0;
+}
+
+fn watch_for_idle(
+    time: Res<Time>,
+    timeout: Res<IdleTimeout>,
+    mirrored: Res<MirroredCourt>,
+    mut idle: ResMut<IdleTime>,
+    paddles: Query<(&Player, &PaddleIntent), Without<CpuPlayer>>,
+    mut next_pause: ResMut<NextState<Pause>>,
+    mut next_menu: ResMut<NextState<Menu>>,
+) {
+    let Some((_, intent)) = paddles
+        .iter()
+        .find(|(player, _)| player.takes_keyboard_input(&mirrored))
+    else {
+        return;
+    };
+    if timeout.0 == 0 || intent.0 != 0.0 {
+        idle.0 = 0.0;
+        return;
+    }
+
+    idle.0 += time.delta_secs();
+    if idle.0 >= timeout.0 as f32 {
+        idle.0 = 0.0;
+        next_pause.set(Pause(true));
+        next_menu.set(Menu::StillThere);
+    }
+}
+
+/// Opens the pause menu, so play only resumes once the player chooses to
+fn pause_on_focus_loss(
+    setting: Res<PauseOnFocusLoss>,
+    mut focus: EventReader<WindowFocused>,
+    mut occlusion: EventReader<WindowOccluded>,
+    mut next_pause: ResMut<NextState<Pause>>,
+    mut next_menu: ResMut<NextState<Menu>>,
+) {
+    let lost_focus = focus.read().any(|event| !event.focused);
+    let hidden = occlusion.read().any(|event| event.occluded);
+    if setting.0 && (lost_focus || hidden) {
+        next_pause.set(Pause(true));
+        next_menu.set(Menu::Pause);
+    }
+}
@@ -0,0 +1,125 @@
+//! Short input buffer for timing-sensitive actions.
+//!
+//! A press is remembered for [`BUFFER_WINDOW`] seconds, so a serve pressed just
+//! before the ball is ready, or a smash, lob or drop shot pressed just before
+//! contact, still counts.
+
+use bevy::prelude::*;
+
+use super::player::AbilityPressed;
+use crate::{AppSystems, PausableSystems, screens::Screen};
+
+/// How long a press stays buffered
+const BUFFER_WINDOW: f32 = 0.15; // seconds
+
+// Bindings
+const SERVE_KEY: KeyCode = KeyCode::Space;
+const SMASH_KEY: KeyCode = KeyCode::ShiftLeft;
+const LOB_KEY: KeyCode = KeyCode::KeyQ;
+const DROP_SHOT_KEY: KeyCode = KeyCode::KeyR;
+const FEINT_KEY: KeyCode = KeyCode::KeyF;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<InputBuffer>();
+    app.init_resource::<InputBuffer>();
+    app.add_event::<BufferedPress>();
+    app.add_systems(OnEnter(Screen::Gameplay), clear_input_buffer);
+    app.add_systems(
+        Update,
+        record_buffered_input
+            .in_set(AppSystems::RecordInput)
+            .in_set(PausableSystems)
+            .run_if(in_state(Screen::Gameplay)),
+    );
+}
+
+/// Actions that can be pressed slightly early
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum BufferedAction {
+    Serve,
+    /// Going through the serve motion without releasing the ball
+    Feint,
+    Smash,
+    /// A slow return sent up at a steep angle
+    Lob,
+    /// A return that dies just over the net
+    DropShot,
+    /// The character's special ability
+    Ability,
+}
+
+/// Sent whenever a buffered action is pressed, e.g. for recording replays
+#[derive(Event, Debug, Clone, Copy)]
+pub struct BufferedPress(pub BufferedAction);
+
+/// When each buffered action was last pressed, in virtual time
+#[derive(Resource, Debug, Default, Reflect)]
+#[reflect(Resource)]
+pub struct InputBuffer {
+    serve: Option<f32>,
+    feint: Option<f32>,
+    smash: Option<f32>,
+    lob: Option<f32>,
+    drop_shot: Option<f32>,
+    ability: Option<f32>,
+}
+
+impl InputBuffer {
+    fn slot(&mut self, action: BufferedAction) -> &mut Option<f32> {
+        match action {
+            BufferedAction::Serve => &mut self.serve,
+            BufferedAction::Feint => &mut self.feint,
+            BufferedAction::Smash => &mut self.smash,
+            BufferedAction::Lob => &mut self.lob,
+            BufferedAction::DropShot => &mut self.drop_shot,
+            BufferedAction::Ability => &mut self.ability,
+        }
+    }
+
+    /// Remembers a press at `now`
+    pub fn press(&mut self, action: BufferedAction, now: f32) {
+        *self.slot(action) = Some(now);
+    }
+
+    /// Uses up a press made within the buffer window, returning whether there was one
+    pub fn consume(&mut self, action: BufferedAction, now: f32) -> bool {
+        match self.slot(action).take() {
+            Some(pressed_at) => now - pressed_at <= BUFFER_WINDOW,
+            None => false,
+        }
+    }
+}
+
+fn record_buffered_input(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    time: Res<Time<Virtual>>,
+    mut buffer: ResMut<InputBuffer>,
+    mut abilities: EventReader<AbilityPressed>,
+    mut presses: EventWriter<BufferedPress>,
+) {
+    let now = time.elapsed_secs();
+    for (key, action) in [
+        (SERVE_KEY, BufferedAction::Serve),
+        (FEINT_KEY, BufferedAction::Feint),
+        (SMASH_KEY, BufferedAction::Smash),
+        (LOB_KEY, BufferedAction::Lob),
+        (DROP_SHOT_KEY, BufferedAction::DropShot),
+    ] {
+        if keyboard.just_pressed(key) {
+            buffer.press(action, now);
+            presses.write(BufferedPress(action));
+        }
+    }
+
+    // The ability is an input action, so it can be rebound and used from a
+    // gamepad
+    if abilities.read().count() > 0 {
+        buffer.press(BufferedAction::Ability, now);
+        presses.write(BufferedPress(BufferedAction::Ability));
+    }
+}
+
+/// Presses from menus or a previous match shouldn't carry over
+fn clear_input_buffer(mut buffer: ResMut<InputBuffer>) {
+    *buffer = InputBuffer::default();
+}
@@ -0,0 +1,126 @@
+//! The invisible ball, for [`Mutator::InvisibleBall`].
+//!
+//! While a point is on, the ball fades out as it crosses the middle of the
+//! court and only comes back into view near either end, plus a brief flash
+//! whenever it hits a wall or paddle. Players who turn on [`AlwaysShowBall`]
+//! see it throughout.
+
+use bevy::prelude::*;
+
+use super::{
+    GamePhase,
+    accessibility::AlwaysShowBall,
+    ball::Ball,
+    court::CourtSize,
+    events::GameEvent,
+    mutators::{Mutator, Mutators},
+};
+use crate::{AppSystems, PausableSystems, screens::Screen};
+
+/// Distance from the center line at which the ball is fully visible, as a
+/// share of the court width
+const VISIBLE_FROM: f32 = 0.3;
+/// Distance (px) over which it fades out toward the middle
+const FADE_WIDTH: f32 = 80.0;
+/// Seconds a contact flash takes to fade
+const FLASH_DURATION: f32 = 0.3;
+/// Below this alpha a ball counts as out of sight
+const UNSEEN_ALPHA: f32 = 0.5;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<Unseen>();
+    app.register_type::<ContactFlash>();
+
+    app.add_systems(
+        Update,
+        (flash_on_contact, fade_balls)
+            .chain()
+            .in_set(AppSystems::Update)
+            .in_set(PausableSystems)
+            .run_if(
+                in_state(Screen::Gameplay)
+                    .and(|mutators: Res<Mutators>| mutators.is_active(Mutator::InvisibleBall)),
+            ),
+    );
+}
+
+/// A ball currently out of sight. Effects that would give its position away,
+/// like its trail, should skip it.
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct Unseen;
+
+/// A ball showing itself after a contact, for as long as the timer runs
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+struct ContactFlash(Timer);
+
+fn flash_on_contact(mut commands: Commands, mut game_events: EventReader<GameEvent>) {
+    for event in game_events.read() {
+        let ball = match *event {
+            GameEvent::PaddleHit { ball, .. } | GameEvent::WallBounce { ball, .. } => ball,
+            _ => continue,
+        };
+        if let Ok(mut ball) = commands.get_entity(ball) {
+            ball.insert(ContactFlash(Timer::from_seconds(
+                FLASH_DURATION,
+                TimerMode::Once,
+            )));
+        }
+    }
+}
+
+/// Sets each ball's alpha from how far it is from the middle and any flash
+fn fade_balls(
+    mut commands: Commands,
+    time: Res<Time>,
+    always_show: Res<AlwaysShowBall>,
+    court: Res<CourtSize>,
+    game_phase: Option<Res<State<GamePhase>>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut balls: Query<
+        (
+            Entity,
+            &Transform,
+            &MeshMaterial2d<ColorMaterial>,
+            Option<&mut ContactFlash>,
+            Has<Unseen>,
+        ),
+        With<Ball>,
+    >,
+) {
+    // Always in view while waiting to serve
+    let hiding =
+        !always_show.0 && game_phase.is_some_and(|phase| *phase.get() == GamePhase::Playing);
+    let visible_from = court.width * VISIBLE_FROM;
+
+    for (entity, transform, material, flash, unseen) in &mut balls {
+        let flash_alpha = flash.map_or(0.0, |mut flash| {
+            flash.0.tick(time.delta());
+            if flash.0.finished() {
+                commands.entity(entity).remove::<ContactFlash>();
+            }
+            1.0 - flash.0.fraction()
+        });
+        let alpha = if hiding {
+            let from_middle = transform.translation.x.abs();
+            let position_alpha = ((from_middle - visible_from) / FADE_WIDTH + 1.0).clamp(0.0, 1.0);
+            position_alpha.max(flash_alpha)
+        } else {
+            1.0
+        };
+
+        if let Some(material) = materials.get_mut(&material.0) {
+            material.color.set_alpha(alpha);
+        }
+        match (alpha < UNSEEN_ALPHA, unseen) {
+            (true, false) => {
+                commands.entity(entity).insert(Unseen);
+            }
+            (false, true) => {
+                commands.entity(entity).remove::<Unseen>();
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,160 @@
+//! Let serves: a serve that clips the top or bottom wall before reaching the
+//! receiver is called a let and served again.
+//!
+//! The first flight only counts once the ball crosses into the receiver's
+//! half (past [`LET_LINE_X`], the centre line) or a paddle touches it.
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+
+use super::{
+    GamePhase,
+    ball::{Ball, Rally, ServeDirection, ServeRng, serve_ball},
+    config::GameConfig,
+    court::Boundary,
+    events::GameEvent,
+    player::PlayerSide,
+    rules::MatchRules,
+};
+use crate::{AppSystems, PausableSystems, screens::Screen, theme::tween::Toast};
+
+/// Distance from center the serve must travel before a wall touch is fair
+/// play: the centre line, so it only has to reach the receiver's half
+const LET_LINE_X: f32 = 0.0;
+
+// Banner
+const BANNER_TEXT: &str = "LET";
+const BANNER_DURATION: f32 = 1.0; // seconds
+const BANNER_FONT_SIZE: f32 = 40.0;
+const BANNER_Y_OFFSET: f32 = 160.0; // Distance from top
+const BANNER_COLOR: Color = Color::srgb(0.4, 0.8, 1.0);
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<ServeFlight>();
+    app.add_observer(call_let);
+    app.add_systems(
+        OnEnter(GamePhase::Playing),
+        start_serve_flight.run_if(|rules: Res<MatchRules>| rules.let_serves),
+    );
+    app.add_systems(
+        Update,
+        end_serve_flight
+            .in_set(AppSystems::Update)
+            .run_if(in_state(GamePhase::Playing))
+            .run_if(in_state(Screen::Gameplay))
+            .in_set(PausableSystems),
+    );
+}
+
+/// Marks a ball that is still on the first flight of a serve
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct ServeFlight;
+
+fn start_serve_flight(mut commands: Commands, balls: Query<Entity, With<Ball>>) {
+    for ball in &balls {
+        commands.entity(ball).insert(ServeFlight);
+    }
+}
+
+/// The serve is fair once it reaches the let line or a paddle returns it
+fn end_serve_flight(
+    mut commands: Commands,
+    rally: Res<Rally>,
+    serve_direction: Res<ServeDirection>,
+    balls: Query<(Entity, &Transform), (With<Ball>, With<ServeFlight>)>,
+) {
+    for (ball, transform) in &balls {
+        if past_let_line(serve_direction.side, transform.translation.x) || rally.hits > 0 {
+            commands.entity(ball).remove::<ServeFlight>();
+        }
+    }
+}
+
+/// Whether a ball served by `server` has reached the let line at `x`
+fn past_let_line(server: PlayerSide, x: f32) -> bool {
+    match server {
+        PlayerSide::Left => x > LET_LINE_X,
+        PlayerSide::Right => x < -LET_LINE_X,
+    }
+}
+
+/// Re-serves a ball that hit a wall on its first flight
+fn call_let(
+    trigger: Trigger<OnCollisionStart>,
+    mut commands: Commands,
+    serve_direction: Res<ServeDirection>,
+    config: Res<GameConfig>,
+    mut rng: ResMut<ServeRng>,
+    mut game_events: EventWriter<GameEvent>,
+    boundaries: Query<(), With<Boundary>>,
+    mut balls: Query<(&mut Transform, &mut LinearVelocity), (With<Ball>, With<ServeFlight>)>,
+) {
+    let ball = trigger.target();
+    if !boundaries.contains(trigger.event().collider) {
+        return;
+    }
+    let Ok((mut transform, mut velocity)) = balls.get_mut(ball) else {
+        return;
+    };
+
+    info!("Let! {:?} player serves again", serve_direction.side);
+    game_events.write(GameEvent::LetServe {
+        server: serve_direction.side,
+    });
+    transform.translation.x = 0.0;
+    transform.translation.y = 0.0;
+    velocity.0 = Vec2::ZERO;
+    serve_ball(
+        &mut commands,
+        ball,
+        &serve_direction,
+        rng.draw_serve(&config.ball),
+    );
+
+    commands.spawn((
+        Name::new("Let Banner"),
+        Toast::new(BANNER_DURATION),
+        Text::new(BANNER_TEXT),
+        TextFont {
+            font_size: BANNER_FONT_SIZE,
+            ..default()
+        },
+        TextColor(BANNER_COLOR),
+        TextLayout::new_with_justify(JustifyText::Center),
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            top: Val::Px(BANNER_Y_OFFSET),
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        StateScoped(Screen::Gameplay),
+    ));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{
+        ball::ball_wall_y,
+        config::BallConfig,
+        court::{CourtInset, CourtSize},
+    };
+
+    #[test]
+    fn steepest_serve_reaches_the_let_line_before_a_wall() {
+        let (_, steepest) = BallConfig::default().serve_angles();
+        let wall_y = ball_wall_y(
+            &MatchRules::default(),
+            &CourtInset::default(),
+            CourtSize::default(),
+        )
+        .unwrap();
+        // How far across the court the serve gets before it meets a wall
+        let wall_x = wall_y / steepest.to_radians().tan();
+
+        assert!(past_let_line(PlayerSide::Left, wall_x));
+        assert!(past_let_line(PlayerSide::Right, -wall_x));
+    }
+}
@@ -7,8 +7,12 @@ use crate::{
     //audio::music,
     game::{
         ball::spawn_ball,
-        court::spawn_court,
-        player::{self, PlayerAssets, PlayerSide, player},
+        config::GameConfig,
+        court::{CourtSize, spawn_court},
+        mutators::{MutatorTweaks, Mutators},
+        player::{PlayerAssets, PlayerSide, player},
+        profile::PlayerProfiles,
+        rules::MatchRules,
     },
     screens::Screen,
 };
@@ -22,7 +26,7 @@ pub(super) fn plugin(app: &mut App) {
 #[reflect(Resource)]
 pub struct LevelAssets {
     #[dependency]
-    music: Handle<AudioSource>,
+    pub music: Handle<AudioSource>,
 }
 
 impl FromWorld for LevelAssets {
@@ -39,6 +43,12 @@ pub fn spawn_level(
     mut commands: Commands,
     _level_assets: Res<LevelAssets>,
     player_assets: Res<PlayerAssets>,
+    rules: Res<MatchRules>,
+    mutators: Res<Mutators>,
+    tweaks: Res<MutatorTweaks>,
+    profiles: Res<PlayerProfiles>,
+    config: Res<GameConfig>,
+    court: Res<CourtSize>,
     mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<ColorMaterial>>,
@@ -54,16 +64,34 @@ pub fn spawn_level(
         .id();
 
     // Spawn court as a child
-    let court_entity = spawn_court(&mut commands, &mut meshes, &mut materials);
+    let court_entity = spawn_court(
+        &mut commands,
+        &mut meshes,
+        &mut materials,
+        &rules,
+        &config,
+        *court,
+    );
+
+    // Paddle sizes with any mutators on top of the handicaps
+    let handicap = |side| {
+        let mut handicap = *rules.handicap(side);
+        handicap.size_multiplier *= mutators.paddle_scale() * tweaks.paddle_scale;
+        handicap
+    };
 
     // Spawn players, ball, and music
-    let children = vec![
+    let mut children = vec![
         court_entity,
         // Left paddle (player-controlled)
         commands
             .spawn(player(
                 PlayerSide::Left,
-                Vec3::new(-player::PADDLE_X_OFFSET, 0.0, 0.0),
+                Vec3::new(PlayerSide::Left.paddle_x(*court), 0.0, 0.0),
+                &handicap(PlayerSide::Left),
+                profiles.get(PlayerSide::Left).character,
+                profiles.color(PlayerSide::Left),
+                &config,
                 &player_assets,
                 &mut texture_atlas_layouts,
             ))
@@ -72,13 +100,15 @@ pub fn spawn_level(
         commands
             .spawn(player(
                 PlayerSide::Right,
-                Vec3::new(player::PADDLE_X_OFFSET, 0.0, 0.0),
+                Vec3::new(PlayerSide::Right.paddle_x(*court), 0.0, 0.0),
+                &handicap(PlayerSide::Right),
+                profiles.get(PlayerSide::Right).character,
+                profiles.color(PlayerSide::Right),
+                &config,
                 &player_assets,
                 &mut texture_atlas_layouts,
             ))
             .id(),
-        // Ball
-        spawn_ball(&mut commands, &mut meshes, &mut materials),
         // TODO: Find some good music
         //commands
         //    .spawn((
@@ -88,6 +118,11 @@ pub fn spawn_level(
         //    .id(),
     ];
 
+    // Ball (two in chaos mode)
+    for _ in 0..rules.ball_count() {
+        children.push(spawn_ball(&mut commands, &mut meshes, &mut materials));
+    }
+
     // Add all children to the level
     commands.entity(level_entity).add_children(&children);
 }
@@ -1,13 +1,67 @@
 use bevy::prelude::*;
 
+pub mod ability;
+pub mod accessibility;
 mod animation;
+pub mod arena;
 pub mod ball;
-mod court;
+pub mod ball_kind;
+mod bricks;
+pub mod camera;
+mod chaos;
+pub mod coach;
+pub mod config;
+pub mod court;
+pub mod court_sounds;
+pub mod court_theme;
+pub mod cpu;
 mod debug;
+mod decoy;
+pub mod emote;
+mod event_feed;
+pub mod events;
+mod fanfare;
+mod feint;
+pub mod floating_text;
+pub mod fx;
+pub mod goal;
+pub mod heatmap;
+mod hud;
+pub mod idle;
+pub mod input_buffer;
+mod invisible_ball;
+mod let_serve;
 pub mod level;
+pub mod momentum;
+mod morph;
+pub mod mutators;
+mod night;
+mod overtime;
+mod paddle_wear;
 mod physics;
 pub mod player;
-mod scoring;
+pub mod power_serve;
+pub mod profile;
+pub mod roster;
+pub mod rules;
+pub mod score_history;
+pub mod scoring;
+#[cfg(all(feature = "scripting", not(target_family = "wasm")))]
+pub mod scripting;
+mod serve_clock;
+pub mod shots;
+pub mod snapshot;
+pub mod speedometer;
+pub mod stats;
+#[cfg(feature = "dev")]
+pub mod stress;
+mod summary;
+pub mod switch_sides;
+pub mod tilt;
+pub mod touch;
+pub mod undo;
+mod wall_damage;
+mod watchdog;
 
 use crate::screens::Screen;
 
@@ -15,20 +69,83 @@ pub(super) fn plugin(app: &mut App) {
     // Add GamePhase as a sub-state of Screen::Gameplay
     app.add_sub_state::<GamePhase>();
 
+    // Split up to stay within the plugin tuple limit
     app.add_plugins((
+        ability::plugin,
+        accessibility::plugin,
         animation::plugin,
+        arena::plugin,
         ball::plugin,
+        ball_kind::plugin,
+        bricks::plugin,
+        camera::plugin,
+        chaos::plugin,
+        coach::plugin,
         court::plugin,
+        court_sounds::plugin,
+        court_theme::plugin,
         debug::plugin,
+        emote::plugin,
+    ));
+    app.add_plugins((
+        event_feed::plugin,
+        events::plugin,
+        fanfare::plugin,
+        floating_text::plugin,
+        fx::plugin,
+        goal::plugin,
+        hud::plugin,
+        input_buffer::plugin,
+        let_serve::plugin,
         level::plugin,
+        momentum::plugin,
+        morph::plugin,
+        mutators::plugin,
+        night::plugin,
+        overtime::plugin,
+    ));
+    app.add_plugins((
         player::plugin,
+        profile::plugin,
+        roster::plugin,
+        rules::plugin,
+        score_history::plugin,
         scoring::plugin,
+        snapshot::plugin,
+        speedometer::plugin,
+        stats::plugin,
+        summary::plugin,
+        tilt::plugin,
+        touch::plugin,
+        undo::plugin,
+        wall_damage::plugin,
+        watchdog::plugin,
+    ));
+    app.add_plugins((
+        config::plugin,
+        cpu::plugin,
+        decoy::plugin,
+        idle::plugin,
+        feint::plugin,
+        heatmap::plugin,
+        invisible_ball::plugin,
+        paddle_wear::plugin,
+        physics::plugin,
+        power_serve::plugin,
+        serve_clock::plugin,
+        shots::plugin,
+        switch_sides::plugin,
     ));
+    #[cfg(feature = "dev")]
+    app.add_plugins(stress::plugin);
+    #[cfg(all(feature = "scripting", not(target_family = "wasm")))]
+    app.add_plugins(scripting::plugin);
 }
 
 /// Sub-states for different phases of gameplay
-#[derive(SubStates, Debug, Clone, Copy, Eq, PartialEq, Hash, Default)]
+#[derive(SubStates, Debug, Clone, Copy, Eq, PartialEq, Hash, Default, Reflect)]
 #[source(Screen = Screen::Gameplay)]
+#[states(scoped_entities)]
 pub enum GamePhase {
     #[default]
     WaitingToServe, // Waiting for player to press space
@@ -0,0 +1,225 @@
+//! Momentum and the "on fire" state.
+//!
+//! Returns and points charge each player's meter; conceding empties it. With a
+//! full meter the player is on fire, and their next return is a fire shot: a
+//! faster ball with a flaming trail that knocks the opposing paddle back.
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+
+use super::{
+    ball::Ball,
+    fx::{EmitParticles, ParticleKind},
+    hud::InCourtFrame,
+    player::{Knockback, PaddleHit, PlayerSide},
+    profile::PlayerProfiles,
+    scoring::GoalScored,
+};
+use crate::{AppSystems, PausableSystems, screens::Screen};
+
+// Charging
+const RETURN_CHARGE: f32 = 0.1;
+const POINT_CHARGE: f32 = 0.25;
+
+// Fire shot
+const FIRE_SHOT_SPEED_MULTIPLIER: f32 = 1.6;
+const FIRE_SHOT_KNOCKBACK: f32 = 250.0; // pixels per second
+const FLAME_PARTICLES: usize = 3; // per frame
+
+// Meters sit under the scores
+const METER_WIDTH: f32 = 80.0;
+const METER_HEIGHT: f32 = 8.0;
+/// Distance from the top of the court, as a percentage of its height
+const METER_TOP: f32 = 14.0;
+const METER_BACKGROUND: Color = Color::srgba(1.0, 1.0, 1.0, 0.15);
+const ON_FIRE_COLOR: Color = Color::srgb(1.0, 0.45, 0.1);
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<Momentum>()
+        .register_type::<FireShot>()
+        .register_type::<MomentumMeter>()
+        .init_resource::<Momentum>()
+        .add_systems(OnEnter(Screen::Gameplay), (reset_momentum, spawn_meters))
+        .add_observer(charge_on_goal)
+        .add_observer(charge_on_return)
+        .add_observer(knock_back_receiver)
+        .add_systems(
+            Update,
+            (
+                update_meters.run_if(resource_changed::<Momentum>),
+                emit_flames
+                    .in_set(AppSystems::Update)
+                    .in_set(PausableSystems),
+            )
+                .run_if(in_state(Screen::Gameplay)),
+        );
+}
+
+/// Each player's meter, from empty (0.0) to on fire (1.0)
+#[derive(Resource, Debug, Clone, Default, Reflect)]
+#[reflect(Resource)]
+pub struct Momentum {
+    pub left: f32,
+    pub right: f32,
+}
+
+impl Momentum {
+    pub fn get(&self, side: PlayerSide) -> f32 {
+        match side {
+            PlayerSide::Left => self.left,
+            PlayerSide::Right => self.right,
+        }
+    }
+
+    fn get_mut(&mut self, side: PlayerSide) -> &mut f32 {
+        match side {
+            PlayerSide::Left => &mut self.left,
+            PlayerSide::Right => &mut self.right,
+        }
+    }
+
+    fn charge(&mut self, side: PlayerSide, amount: f32) {
+        let meter = self.get_mut(side);
+        *meter = (*meter + amount).min(1.0);
+    }
+
+    /// Uses up `amount` of `side`'s meter, if there's that much in it
+    pub fn spend(&mut self, side: PlayerSide, amount: f32) -> bool {
+        let meter = self.get_mut(side);
+        if *meter < amount {
+            return false;
+        }
+        *meter -= amount;
+        true
+    }
+
+    /// Whether `side`'s next return is a fire shot
+    pub fn is_on_fire(&self, side: PlayerSide) -> bool {
+        self.get(side) >= 1.0
+    }
+}
+
+/// A ball hit as a fire shot by the given player
+#[derive(Component, Debug, Clone, Copy, Reflect)]
+#[reflect(Component)]
+pub struct FireShot(pub PlayerSide);
+
+/// The fill of a player's momentum meter
+#[derive(Component, Debug, Clone, Copy, Reflect)]
+#[reflect(Component)]
+struct MomentumMeter(PlayerSide);
+
+fn reset_momentum(mut momentum: ResMut<Momentum>) {
+    *momentum = Momentum::default();
+}
+
+fn spawn_meters(mut commands: Commands) {
+    // Centred under each score, over the middle of each half of the court
+    for (side, center) in [(PlayerSide::Left, 25.0), (PlayerSide::Right, 75.0)] {
+        commands.spawn((
+            Name::new(format!("{side:?} Momentum Meter")),
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Percent(center),
+                top: Val::Percent(METER_TOP),
+                width: Val::Px(METER_WIDTH),
+                height: Val::Px(METER_HEIGHT),
+                margin: UiRect::left(Val::Px(-METER_WIDTH / 2.0)),
+                ..default()
+            },
+            BackgroundColor(METER_BACKGROUND),
+            InCourtFrame,
+            StateScoped(Screen::Gameplay),
+            children![(
+                Name::new("Fill"),
+                MomentumMeter(side),
+                Node {
+                    width: Val::Percent(0.0),
+                    height: Val::Percent(100.0),
+                    ..default()
+                },
+                BackgroundColor(Color::WHITE),
+            )],
+        ));
+    }
+}
+
+fn update_meters(
+    momentum: Res<Momentum>,
+    profiles: Res<PlayerProfiles>,
+    mut meters: Query<(&MomentumMeter, &mut Node, &mut BackgroundColor)>,
+) {
+    for (meter, mut node, mut color) in &mut meters {
+        node.width = Val::Percent(momentum.get(meter.0) * 100.0);
+        color.0 = if momentum.is_on_fire(meter.0) {
+            ON_FIRE_COLOR
+        } else {
+            profiles.color(meter.0)
+        };
+    }
+}
+
+/// Points charge the scorer; conceding breaks the streak
+fn charge_on_goal(trigger: Trigger<GoalScored>, mut momentum: ResMut<Momentum>) {
+    let side = trigger.event().side;
+    momentum.charge(side, POINT_CHARGE);
+    *momentum.get_mut(side.opponent()) = 0.0;
+}
+
+/// Returns charge the hitter, or fire the shot when the meter is full
+fn charge_on_return(
+    trigger: Trigger<PaddleHit>,
+    mut commands: Commands,
+    mut momentum: ResMut<Momentum>,
+    mut balls: Query<&mut LinearVelocity, With<Ball>>,
+) {
+    let &PaddleHit { side, ball, .. } = trigger.event();
+    let Ok(mut velocity) = balls.get_mut(ball) else {
+        return;
+    };
+
+    if !momentum.is_on_fire(side) {
+        momentum.charge(side, RETURN_CHARGE);
+        return;
+    }
+
+    velocity.0 *= FIRE_SHOT_SPEED_MULTIPLIER;
+    *momentum.get_mut(side) = 0.0;
+    commands.entity(ball).insert(FireShot(side));
+    info!("{side:?} player is on fire!");
+}
+
+/// A fire shot shoves back the paddle that receives it
+fn knock_back_receiver(
+    trigger: Trigger<PaddleHit>,
+    mut commands: Commands,
+    balls: Query<&FireShot, With<Ball>>,
+    mut paddles: Query<&mut Knockback>,
+) {
+    let hit = trigger.event();
+    let Ok(&FireShot(shooter)) = balls.get(hit.ball) else {
+        return;
+    };
+    if hit.side == shooter {
+        return;
+    }
+    let Ok(mut knockback) = paddles.get_mut(hit.paddle) else {
+        return;
+    };
+
+    knockback.push(FIRE_SHOT_KNOCKBACK);
+    commands.entity(ball).remove::<FireShot>();
+}
+
+fn emit_flames(
+    mut emit: EventWriter<EmitParticles>,
+    balls: Query<&Transform, (With<Ball>, With<FireShot>)>,
+) {
+    for transform in &balls {
+        emit.write(EmitParticles {
+            kind: ParticleKind::Flame,
+            position: transform.translation.truncate(),
+            count: FLAME_PARTICLES,
+        });
+    }
+}
@@ -0,0 +1,165 @@
+//! Paddles morphing into characters.
+//!
+//! Every paddle starts out as a plain Pong rectangle. Once a player's
+//! momentum meter fills up they're powered up, and their paddle squashes
+//! down and springs back as an animated character, with a collider to match.
+//! When the power is spent it morphs back the same way.
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+
+use super::{
+    animation::{PlayerAnimation, facing_court_flipped},
+    config::GameConfig,
+    momentum::Momentum,
+    player::{PaddleSize, Player},
+};
+use crate::{AppSystems, PausableSystems, screens::Screen};
+
+/// How long a full morph takes, squash and spring back together
+const MORPH_DURATION: f32 = 0.4; // seconds
+/// Width of the character's collider; the sprite itself is square
+const CHARACTER_WIDTH: f32 = 24.0;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<PaddleForm>();
+    app.register_type::<CharacterSheet>();
+    app.register_type::<Morph>();
+
+    app.add_systems(
+        Update,
+        (start_morphs, animate_morphs)
+            .chain()
+            .in_set(AppSystems::Update)
+            .in_set(PausableSystems)
+            .run_if(in_state(Screen::Gameplay)),
+    );
+}
+
+/// What a paddle currently looks like
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Component)]
+pub enum PaddleForm {
+    #[default]
+    Paddle,
+    Character,
+}
+
+/// The sprite sheet a paddle morphs into
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component)]
+pub struct CharacterSheet {
+    image: Handle<Image>,
+    layout: Handle<TextureAtlasLayout>,
+}
+
+impl CharacterSheet {
+    pub fn new(image: Handle<Image>, layout: Handle<TextureAtlasLayout>) -> Self {
+        Self { image, layout }
+    }
+}
+
+/// A morph in progress; the paddle swaps form halfway through
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component)]
+struct Morph {
+    to: PaddleForm,
+    timer: Timer,
+}
+
+/// The collider for a paddle of `size` in `form`
+pub fn paddle_collider(form: PaddleForm, size: Vec2) -> Collider {
+    match form {
+        PaddleForm::Character => {
+            Collider::capsule(CHARACTER_WIDTH / 2.0, (size.y - CHARACTER_WIDTH).max(0.0))
+        }
+        PaddleForm::Paddle => Collider::rectangle(size.x, size.y),
+    }
+}
+
+/// How big a paddle of `size` in `form` is drawn. Characters are drawn
+/// square, paddles at their collider size.
+pub fn paddle_sprite_size(form: PaddleForm, size: Vec2) -> Vec2 {
+    match form {
+        PaddleForm::Character => Vec2::splat(size.y),
+        PaddleForm::Paddle => size,
+    }
+}
+
+/// Powered-up players become characters, and turn back once the power is spent
+fn start_morphs(
+    mut commands: Commands,
+    momentum: Res<Momentum>,
+    paddles: Query<(Entity, &Player, &PaddleForm), Without<Morph>>,
+) {
+    for (entity, player, form) in &paddles {
+        let wanted = if momentum.is_on_fire(player.side) {
+            PaddleForm::Character
+        } else {
+            PaddleForm::Paddle
+        };
+        if *form != wanted {
+            commands.entity(entity).insert(Morph {
+                to: wanted,
+                timer: Timer::from_seconds(MORPH_DURATION, TimerMode::Once),
+            });
+        }
+    }
+}
+
+/// Squashes the sprite to nothing, swaps form, then springs it back out
+fn animate_morphs(
+    mut commands: Commands,
+    time: Res<Time>,
+    config: Res<GameConfig>,
+    mut paddles: Query<(
+        Entity,
+        &Player,
+        &mut Morph,
+        &mut PaddleForm,
+        &mut PaddleSize,
+        &mut Sprite,
+        &CharacterSheet,
+    )>,
+) {
+    for (entity, player, mut morph, mut form, mut size, mut sprite, character) in &mut paddles {
+        morph.timer.tick(time.delta());
+        let progress = morph.timer.fraction();
+
+        if progress >= 0.5 && *form != morph.to {
+            *form = morph.to;
+            match morph.to {
+                PaddleForm::Character => {
+                    size.0.x = CHARACTER_WIDTH;
+                    sprite.image = character.image.clone();
+                    sprite.texture_atlas = Some(TextureAtlas {
+                        layout: character.layout.clone(),
+                        index: 0,
+                    });
+                    sprite.flip_x = facing_court_flipped(player.side);
+                    commands
+                        .entity(entity)
+                        .insert((PlayerAnimation::new(), paddle_collider(*form, size.0)));
+                }
+                PaddleForm::Paddle => {
+                    size.0.x = config.paddle.width;
+                    sprite.image = Handle::default();
+                    sprite.texture_atlas = None;
+                    sprite.flip_x = false;
+                    commands
+                        .entity(entity)
+                        .remove::<PlayerAnimation>()
+                        .insert(paddle_collider(*form, size.0));
+                }
+            }
+        }
+
+        let full_size = paddle_sprite_size(*form, size.0);
+        let squash = (progress * 2.0 - 1.0).abs();
+        sprite.custom_size = Some(Vec2::new(full_size.x * squash, full_size.y));
+
+        if morph.timer.finished() {
+            commands.entity(entity).remove::<Morph>();
+        }
+    }
+}
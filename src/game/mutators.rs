@@ -0,0 +1,213 @@
+//! Mutators: optional modifiers toggled before a match.
+//!
+//! The active set lives in the [`Mutators`] resource. Each mutator is applied
+//! by the system it affects (ball size and pace in `ball_kind` and `ball`,
+//! paddle size in `level`, fog and night in `night`, bank shots in `scoring`,
+//! paddle wear in `paddle_wear`, decoys in `decoy`, the invisible ball in
+//! `invisible_ball`),
+//! so a new variant is a new enum entry plus a check where it matters.
+//!
+//! Scripted mutators (with the `scripting` feature) can't add checks of
+//! their own, so they adjust [`MutatorTweaks`] instead, which the same
+//! systems apply on top.
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    ball::{Ball, LastTouchedBy},
+    court::Boundary,
+    player::Player,
+};
+
+const BIG_BALL_SCALE: f32 = 2.0;
+const FAST_BALL_SPEED: f32 = 1.5;
+const TINY_PADDLE_SCALE: f32 = 0.6;
+/// Points for a goal banked in off a wall with [`Mutator::BankShots`]
+pub const BANK_SHOT_POINTS: u32 = 2;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<Mutator>()
+        .register_type::<Mutators>()
+        .register_type::<Banked>()
+        .register_type::<MutatorTweaks>()
+        .init_resource::<Mutators>()
+        .init_resource::<MutatorTweaks>();
+    app.add_observer(track_banks);
+}
+
+/// A single match modifier
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect, Serialize, Deserialize)]
+pub enum Mutator {
+    /// Balls are twice the size
+    BigBall,
+    /// Balls are served half again as fast
+    FastBall,
+    /// Paddles are a little over half height
+    TinyPaddles,
+    /// The court is hazy away from the balls and paddles
+    Fog,
+    /// The court is dark apart from pools of light around the balls and paddles
+    Night,
+    /// Goals banked in off a wall count double
+    BankShots,
+    /// Every hit wears a paddle down until the next goal
+    PaddleWear,
+    /// Every few wall bounces split off a decoy ball that can't score
+    Decoys,
+    /// The ball vanishes mid-court, showing itself only on contact
+    InvisibleBall,
+}
+
+impl Mutator {
+    pub const ALL: [Mutator; 9] = [
+        Mutator::BigBall,
+        Mutator::FastBall,
+        Mutator::TinyPaddles,
+        Mutator::Fog,
+        Mutator::Night,
+        Mutator::BankShots,
+        Mutator::PaddleWear,
+        Mutator::Decoys,
+        Mutator::InvisibleBall,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Mutator::BigBall => "Big Ball",
+            Mutator::FastBall => "Fast Ball",
+            Mutator::TinyPaddles => "Tiny Paddles",
+            Mutator::Fog => "Fog",
+            Mutator::Night => "Night",
+            Mutator::BankShots => "Double Bank Shots",
+            Mutator::PaddleWear => "Paddle Wear",
+            Mutator::Decoys => "Decoy Balls",
+            Mutator::InvisibleBall => "Invisible Ball",
+        }
+    }
+
+    /// What the mutator does, for the help overlay
+    pub fn description(self) -> &'static str {
+        match self {
+            Mutator::BigBall => "Balls are twice the size",
+            Mutator::FastBall => "Balls are served half again as fast",
+            Mutator::TinyPaddles => "Paddles are a little over half height",
+            Mutator::Fog => "The court is hazy away from the balls and paddles",
+            Mutator::Night => "The court is dark apart from the balls and paddles",
+            Mutator::BankShots => "Goals banked in off a wall count double",
+            Mutator::PaddleWear => "Every hit shrinks your paddle until the next goal",
+            Mutator::Decoys => "Wall bounces split off fading decoys that can't score",
+            Mutator::InvisibleBall => "The ball vanishes mid-court and flashes on contact",
+        }
+    }
+}
+
+/// Mutators active for the current match
+#[derive(Resource, Debug, Clone, Default, PartialEq, Reflect, Serialize, Deserialize)]
+#[reflect(Resource)]
+pub struct Mutators(Vec<Mutator>);
+
+impl Mutators {
+    pub fn new(mutators: impl IntoIterator<Item = Mutator>) -> Self {
+        let mut active = Self::default();
+        for mutator in mutators {
+            if !active.is_active(mutator) {
+                active.0.push(mutator);
+            }
+        }
+        active
+    }
+
+    pub fn is_active(&self, mutator: Mutator) -> bool {
+        self.0.contains(&mutator)
+    }
+
+    pub fn toggle(&mut self, mutator: Mutator) {
+        if self.is_active(mutator) {
+            self.0.retain(|&active| active != mutator);
+        } else {
+            self.0.push(mutator);
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Mutator> + '_ {
+        self.0.iter().copied()
+    }
+
+    /// Scales every ball's radius
+    pub fn ball_scale(&self) -> f32 {
+        if self.is_active(Mutator::BigBall) {
+            BIG_BALL_SCALE
+        } else {
+            1.0
+        }
+    }
+
+    /// Scales the serve speed
+    pub fn ball_speed_multiplier(&self) -> f32 {
+        if self.is_active(Mutator::FastBall) {
+            FAST_BALL_SPEED
+        } else {
+            1.0
+        }
+    }
+
+    /// Scales both paddles' height
+    pub fn paddle_scale(&self) -> f32 {
+        if self.is_active(Mutator::TinyPaddles) {
+            TINY_PADDLE_SCALE
+        } else {
+            1.0
+        }
+    }
+}
+
+/// Adjustments made by scripted mutators, reset at the start of each match
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Resource)]
+pub struct MutatorTweaks {
+    /// Scales the serve speed, and the speed of balls in play when it changes
+    pub ball_speed: f32,
+    /// Scales both paddles' height as they spawn
+    pub paddle_scale: f32,
+    /// Extra points on every goal
+    pub score_bonus: u32,
+}
+
+impl Default for MutatorTweaks {
+    fn default() -> Self {
+        Self {
+            ball_speed: 1.0,
+            paddle_scale: 1.0,
+            score_bonus: 0,
+        }
+    }
+}
+
+/// Marks a ball that has come off a wall since a paddle last touched it
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct Banked;
+
+/// Tracks whether a returned ball has been banked off a wall. Serves don't
+/// count: [`LastTouchedBy`] is cleared on the serve, and the next paddle touch
+/// clears the bank.
+fn track_banks(
+    trigger: Trigger<OnCollisionStart>,
+    mut commands: Commands,
+    balls: Query<Has<LastTouchedBy>, With<Ball>>,
+    boundaries: Query<(), With<Boundary>>,
+    paddles: Query<(), With<Player>>,
+) {
+    let ball = trigger.target();
+    let Ok(returned) = balls.get(ball) else {
+        return;
+    };
+    let collider = trigger.event().collider;
+    if paddles.contains(collider) {
+        commands.entity(ball).remove::<Banked>();
+    } else if returned && boundaries.contains(collider) {
+        commands.entity(ball).insert(Banked);
+    }
+}
@@ -0,0 +1,179 @@
+//! Night and fog mutators: the court is dark (or hazy) apart from pools of
+//! light around the balls and paddles.
+//!
+//! The darkness is a single overlay drawn by `shaders/night.wgsl`, fed the
+//! light positions every frame. When a ball crosses the center line the
+//! court flashes bright for a moment so nobody loses track of it.
+
+use bevy::{
+    platform::collections::HashMap,
+    prelude::*,
+    render::render_resource::{AsBindGroup, ShaderRef, ShaderType},
+    sprite::{AlphaMode2d, Material2d, Material2dPlugin},
+};
+
+use super::{
+    ball::Ball,
+    court::CourtSize,
+    mutators::{Mutator, Mutators},
+    player::Player,
+};
+use crate::screens::Screen;
+
+const SHADER_PATH: &str = "shaders/night.wgsl";
+
+/// Must match the array length in the shader
+const MAX_LIGHTS: usize = 6;
+
+const NIGHT_DARKNESS: f32 = 0.95;
+const FOG_DARKNESS: f32 = 0.75;
+const BALL_LIGHT_RADIUS: f32 = 90.0;
+const PADDLE_LIGHT_RADIUS: f32 = 70.0;
+/// Fog thins out further from the balls and paddles than night does
+const FOG_RADIUS_SCALE: f32 = 1.6;
+const NIGHT_Z: f32 = 5.0; // Above everything in the court
+/// The overlay reaches well past the court so zoomed-out cameras stay dark
+const OVERLAY_SCALE: f32 = 3.0;
+
+// Safety flash when a ball crosses the center line
+const FLASH_DURATION: f32 = 0.25; // seconds
+const FLASH_DARKNESS: f32 = 0.4;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_plugins(Material2dPlugin::<NightMaterial>::default());
+    app.add_systems(
+        OnEnter(Screen::Gameplay),
+        spawn_night_overlay.run_if(|mutators: Res<Mutators>| {
+            mutators.is_active(Mutator::Night) || mutators.is_active(Mutator::Fog)
+        }),
+    );
+    app.add_systems(Update, light_the_court.run_if(in_state(Screen::Gameplay)));
+}
+
+#[derive(ShaderType, Debug, Clone, Default)]
+struct NightSettings {
+    /// (x, y, radius, unused) per light
+    lights: [Vec4; MAX_LIGHTS],
+    light_count: u32,
+    darkness: f32,
+}
+
+#[derive(Asset, TypePath, AsBindGroup, Debug, Clone)]
+struct NightMaterial {
+    #[uniform(0)]
+    settings: NightSettings,
+}
+
+impl Material2d for NightMaterial {
+    fn fragment_shader() -> ShaderRef {
+        SHADER_PATH.into()
+    }
+
+    fn alpha_mode(&self) -> AlphaMode2d {
+        AlphaMode2d::Blend
+    }
+}
+
+/// The darkness overlay
+#[derive(Component)]
+struct NightOverlay {
+    /// Darkness away from the lights, outside of a flash
+    darkness: f32,
+    /// Scales the light radii
+    radius_scale: f32,
+    /// Seconds left of the current safety flash
+    flash: f32,
+}
+
+fn spawn_night_overlay(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<NightMaterial>>,
+    mutators: Res<Mutators>,
+    court: Res<CourtSize>,
+) {
+    // Night wins if both are on
+    let (darkness, radius_scale) = if mutators.is_active(Mutator::Night) {
+        (NIGHT_DARKNESS, 1.0)
+    } else {
+        (FOG_DARKNESS, FOG_RADIUS_SCALE)
+    };
+
+    commands.spawn((
+        Name::new("Night Overlay"),
+        NightOverlay {
+            darkness,
+            radius_scale,
+            flash: 0.0,
+        },
+        Mesh2d(meshes.add(Rectangle::from_size(court.extent() * OVERLAY_SCALE))),
+        MeshMaterial2d(materials.add(NightMaterial {
+            settings: NightSettings {
+                darkness,
+                ..default()
+            },
+        })),
+        Transform::from_xyz(0.0, 0.0, NIGHT_Z),
+        StateScoped(Screen::Gameplay),
+    ));
+}
+
+/// Moves the lights onto the balls and paddles, and flashes the court when
+/// a ball changes halves
+fn light_the_court(
+    time: Res<Time>,
+    mut ball_halves: Local<HashMap<Entity, bool>>,
+    mut overlays: Query<(&mut NightOverlay, &MeshMaterial2d<NightMaterial>)>,
+    mut materials: ResMut<Assets<NightMaterial>>,
+    balls: Query<(Entity, &GlobalTransform), With<Ball>>,
+    paddles: Query<&GlobalTransform, With<Player>>,
+) {
+    let Ok((mut overlay, material)) = overlays.single_mut() else {
+        ball_halves.clear();
+        return;
+    };
+
+    // Flash whenever a ball crosses into the other half
+    let mut crossed = false;
+    ball_halves.retain(|ball, _| balls.contains(*ball));
+    for (ball, transform) in &balls {
+        let right_half = transform.translation().x > 0.0;
+        if let Some(was_right) = ball_halves.insert(ball, right_half) {
+            crossed |= was_right != right_half;
+        }
+    }
+    overlay.flash = if crossed {
+        FLASH_DURATION
+    } else {
+        (overlay.flash - time.delta_secs()).max(0.0)
+    };
+
+    let Some(material) = materials.get_mut(&material.0) else {
+        return;
+    };
+    let settings = &mut material.settings;
+
+    let lights = balls
+        .iter()
+        .map(|(_, transform)| (transform, BALL_LIGHT_RADIUS))
+        .chain(
+            paddles
+                .iter()
+                .map(|transform| (transform, PADDLE_LIGHT_RADIUS)),
+        )
+        .take(MAX_LIGHTS);
+    let mut count = 0;
+    for (slot, (transform, radius)) in settings.lights.iter_mut().zip(lights) {
+        *slot = transform
+            .translation()
+            .truncate()
+            .extend(radius * overlay.radius_scale)
+            .extend(0.0);
+        count += 1;
+    }
+    settings.light_count = count;
+
+    // Fade from the flash back to full darkness
+    let flash = overlay.flash / FLASH_DURATION;
+    settings.darkness = overlay.darkness + (FLASH_DARKNESS - overlay.darkness) * flash;
+}
@@ -0,0 +1,165 @@
+//! Sudden-death overtime: at `target - 1` all, the next goal wins.
+//!
+//! The win itself falls out of the normal scoring rules; this module adds the
+//! tension cues (a pulsing banner as it starts and sped-up music while it
+//! lasts), and, if the rules ask for it, closes the court in to force a
+//! quick finish.
+
+use bevy::prelude::*;
+
+use super::{
+    GamePhase, accessibility::motion_allowed, court::CourtInset, events::GameEvent,
+    level::LevelAssets, rules::MatchRules, scoring::Score,
+};
+use crate::{AppSystems, PausableSystems, audio::Music, screens::Screen, theme::tween::Toast};
+
+// Banner
+const BANNER_TEXT: &str = "SUDDEN DEATH - NEXT GOAL WINS";
+const BANNER_DURATION: f32 = 3.0; // seconds
+const BANNER_FONT_SIZE: f32 = 32.0;
+const BANNER_Y_OFFSET: f32 = 110.0; // Distance from top
+const BANNER_COLOR: Color = Color::srgb(1.0, 0.25, 0.2);
+const BANNER_PULSE_SPEED: f32 = 4.0; // radians per second
+
+// Tension music plays the level track a bit faster
+const TENSION_MUSIC_SPEED: f32 = 1.25;
+
+// Shrinking court: each wall closes in a step every interval, up to a limit
+const SHRINK_INTERVAL: f32 = 1.0; // seconds
+const SHRINK_STEP: f32 = 6.0; // pixels per wall
+const MAX_SHRINK: f32 = 180.0; // pixels per wall
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_sub_state::<Overtime>();
+
+    app.add_systems(
+        Update,
+        enter_sudden_death.run_if(in_state(Overtime::Regulation).and(resource_changed::<Score>)),
+    );
+    app.add_systems(
+        OnEnter(Overtime::SuddenDeath),
+        (spawn_sudden_death_banner, start_tension_music),
+    );
+    app.add_systems(OnEnter(GamePhase::GameOver), end_overtime);
+    app.add_systems(OnExit(Overtime::SuddenDeath), reopen_court);
+    app.add_systems(
+        Update,
+        shrink_court
+            .in_set(AppSystems::Update)
+            .in_set(PausableSystems)
+            .run_if(
+                in_state(Overtime::SuddenDeath)
+                    .and(in_state(GamePhase::Playing))
+                    .and(|rules: Res<MatchRules>| rules.shrinking_court),
+            ),
+    );
+    app.add_systems(
+        Update,
+        pulse_banner
+            .in_set(AppSystems::Update)
+            .in_set(PausableSystems)
+            .run_if(in_state(Overtime::SuddenDeath).and(motion_allowed)),
+    );
+}
+
+/// Whether the match is in regulation or sudden death
+#[derive(SubStates, Debug, Clone, Copy, Eq, PartialEq, Hash, Default)]
+#[source(Screen = Screen::Gameplay)]
+#[states(scoped_entities)]
+pub enum Overtime {
+    #[default]
+    Regulation,
+    SuddenDeath,
+}
+
+/// Time until the court next closes in
+#[derive(Resource)]
+struct ShrinkTimer(Timer);
+
+impl Default for ShrinkTimer {
+    fn default() -> Self {
+        Self(Timer::from_seconds(SHRINK_INTERVAL, TimerMode::Repeating))
+    }
+}
+
+/// Marker for the sudden-death banner
+#[derive(Component)]
+struct SuddenDeathBanner;
+
+fn enter_sudden_death(
+    score: Res<Score>,
+    rules: Res<MatchRules>,
+    mut next_overtime: ResMut<NextState<Overtime>>,
+    mut game_events: EventWriter<GameEvent>,
+) {
+    if score.is_sudden_death(&rules) {
+        info!("Sudden death at {} - {}", score.left, score.right);
+        next_overtime.set(Overtime::SuddenDeath);
+        game_events.write(GameEvent::SuddenDeath);
+    }
+}
+
+fn end_overtime(mut next_overtime: ResMut<NextState<Overtime>>) {
+    next_overtime.set(Overtime::Regulation);
+}
+
+fn spawn_sudden_death_banner(mut commands: Commands) {
+    commands.spawn((
+        Name::new("Sudden Death Banner"),
+        SuddenDeathBanner,
+        Text::new(BANNER_TEXT),
+        TextFont {
+            font_size: BANNER_FONT_SIZE,
+            ..default()
+        },
+        TextColor(BANNER_COLOR),
+        TextLayout::new_with_justify(JustifyText::Center),
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            top: Val::Px(BANNER_Y_OFFSET),
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        Toast::new(BANNER_DURATION),
+        StateScoped(Overtime::SuddenDeath),
+    ));
+}
+
+fn shrink_court(time: Res<Time>, mut timer: Local<ShrinkTimer>, mut inset: ResMut<CourtInset>) {
+    timer.0.tick(time.delta());
+    if timer.0.just_finished() && inset.0 < MAX_SHRINK {
+        inset.0 = (inset.0 + SHRINK_STEP).min(MAX_SHRINK);
+    }
+}
+
+fn reopen_court(mut inset: ResMut<CourtInset>) {
+    inset.0 = 0.0;
+}
+
+/// Pulses the banner until its toast starts fading it out
+fn pulse_banner(
+    time: Res<Time>,
+    mut banners: Query<(&mut TextColor, &Toast), With<SuddenDeathBanner>>,
+) {
+    let alpha = 0.6 + 0.4 * (time.elapsed_secs() * BANNER_PULSE_SPEED).sin();
+    for (mut color, toast) in &mut banners {
+        if !toast.is_fading() {
+            color.0 = BANNER_COLOR.with_alpha(alpha);
+        }
+    }
+}
+
+fn start_tension_music(mut commands: Commands, level_assets: Option<Res<LevelAssets>>) {
+    let Some(level_assets) = level_assets else {
+        return;
+    };
+
+    commands.spawn((
+        Name::new("Sudden Death Music"),
+        AudioPlayer(level_assets.music.clone()),
+        PlaybackSettings::LOOP.with_speed(TENSION_MUSIC_SPEED),
+        Music,
+        StateScoped(Overtime::SuddenDeath),
+    ));
+}
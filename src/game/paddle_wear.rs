@@ -0,0 +1,88 @@
+//! Paddle wear, for [`Mutator::PaddleWear`].
+//!
+//! Each hit shaves a little off the paddle that made it, down to a floor,
+//! so long rallies get harder to hold and an early winner pays off. A goal
+//! restores both paddles to the height they started the point at.
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+
+use super::{
+    morph::{PaddleForm, paddle_collider, paddle_sprite_size},
+    mutators::{Mutator, Mutators},
+    player::{PaddleHit, PaddleSize},
+    scoring::GoalScored,
+};
+
+/// Share of its height a paddle keeps after each hit
+const WEAR_PER_HIT: f32 = 0.93;
+/// The least a paddle wears down to, as a share of its full height
+const MIN_WEAR: f32 = 0.5;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<PaddleWear>();
+    app.add_observer(wear_paddle);
+    app.add_observer(restore_paddles);
+}
+
+/// A paddle worn down this point, and the height to restore it to
+#[derive(Component, Debug, Clone, Copy, Reflect)]
+#[reflect(Component)]
+struct PaddleWear {
+    full_height: f32,
+}
+
+fn wear_paddle(
+    trigger: Trigger<PaddleHit>,
+    mut commands: Commands,
+    mutators: Res<Mutators>,
+    mut paddles: Query<(
+        &PaddleForm,
+        &mut PaddleSize,
+        &mut Sprite,
+        Option<&PaddleWear>,
+    )>,
+) {
+    if !mutators.is_active(Mutator::PaddleWear) {
+        return;
+    }
+    let paddle = trigger.event().paddle;
+    let Ok((&form, mut size, mut sprite, wear)) = paddles.get_mut(paddle) else {
+        return;
+    };
+    let full_height = wear.map_or(size.0.y, |wear| wear.full_height);
+    size.0.y = (size.0.y * WEAR_PER_HIT).max(full_height * MIN_WEAR);
+    resize(&mut commands, paddle, form, size.0, &mut sprite);
+    commands.entity(paddle).insert(PaddleWear { full_height });
+}
+
+fn restore_paddles(
+    _: Trigger<GoalScored>,
+    mut commands: Commands,
+    mut paddles: Query<(
+        Entity,
+        &PaddleForm,
+        &PaddleWear,
+        &mut PaddleSize,
+        &mut Sprite,
+    )>,
+) {
+    for (entity, &form, wear, mut size, mut sprite) in &mut paddles {
+        size.0.y = wear.full_height;
+        resize(&mut commands, entity, form, size.0, &mut sprite);
+        commands.entity(entity).remove::<PaddleWear>();
+    }
+}
+
+/// Fits the paddle's collider and sprite to its new size. A morph in
+/// progress redraws the sprite itself.
+fn resize(
+    commands: &mut Commands,
+    entity: Entity,
+    form: PaddleForm,
+    size: Vec2,
+    sprite: &mut Sprite,
+) {
+    commands.entity(entity).insert(paddle_collider(form, size));
+    sprite.custom_size = Some(paddle_sprite_size(form, size));
+}
@@ -1,20 +1,45 @@
 //! Physics configuration and collision layers for the game.
 
 use avian2d::prelude::*;
+use bevy::prelude::*;
+
+use super::{ball::Ball, config::GameConfig, court::Boundary, player::Player};
+
+// Restitution and paddle speed are tuned in the `GameConfig`
 
 // Boundary physics properties
 /// Zero friction for boundaries - perfect elastic collision
 pub const BOUNDARY_FRICTION: f32 = 0.0;
-/// Perfect restitution for boundaries - maintains ball velocity
-pub const BOUNDARY_RESTITUTION: f32 = 1.0;
 
 // Paddle physics properties
 /// Zero friction for paddles - perfect elastic collision
 pub const PADDLE_FRICTION: f32 = 0.0;
-/// Perfect restitution for paddles - maintains ball velocity
-pub const PADDLE_RESTITUTION: f32 = 1.0;
-/// Default maximum speed for paddle movement (pixels per second)
-pub const PADDLE_MAX_SPEED: f32 = 400.0;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(Update, apply_restitution);
+}
+
+/// Keeps the restitution of balls, walls and paddles in line with the
+/// [`GameConfig`], including ones spawned after it last changed
+fn apply_restitution(
+    config: Res<GameConfig>,
+    mut bodies: Query<(&mut Restitution, Has<Ball>, Has<Boundary>, Has<Player>)>,
+) {
+    for (mut restitution, ball, boundary, player) in &mut bodies {
+        let wanted = if ball {
+            config.ball.restitution
+        } else if boundary {
+            config.court.wall_restitution
+        } else if player {
+            config.paddle.restitution
+        } else {
+            continue;
+        };
+        if (config.is_changed() || restitution.is_added()) && restitution.coefficient != wanted {
+            restitution.coefficient = wanted;
+        }
+    }
+}
 
 /// Collision layers for different game entities.
 /// Each variant automatically gets assigned to a layer (0, 1, 2, etc.)
@@ -32,15 +57,21 @@ pub const PADDLE_MAX_SPEED: f32 = 400.0;
 ///
 /// ## Collision Matrix
 /// ```text
-///          | Default | Paddle | Ball | Boundary | Goal | PowerUp |
-/// ---------|---------|--------|------|----------|------|---------|
-/// Default  |   ❌    |   ❌   |  ❌  |    ❌    |  ❌  |   ❌    |
-/// Paddle   |   ❌    |   ❌   |  ✅  |    ✅    |  ❌  |   ❌    |
-/// Ball     |   ❌    |   ✅   |  ❌  |    ✅    |  ✅  |   ✅    |
-/// Boundary |   ❌    |   ✅   |  ✅  |    ❌    |  ❌  |   ❌    |
-/// Goal     |   ❌    |   ❌   |  ✅  |    ❌    |  ❌  |   ❌    |
-/// PowerUp  |   ❌    |   ❌   |  ✅  |    ❌    |  ❌  |   ❌    |
+///          | Default | Paddle | Ball | Boundary | Goal | PowerUp | Brick | Decoy |
+/// ---------|---------|--------|------|----------|------|---------|-------|-------|
+/// Default  |   ❌    |   ❌   |  ❌  |    ❌    |  ❌  |   ❌    |  ❌   |  ❌   |
+/// Paddle   |   ❌    |   ❌   |  ✅  |    ❌    |  ❌  |   ❌    |  ❌   |  ❌   |
+/// Ball     |   ❌    |   ✅   |  ❌  |    ✅    |  ✅  |   ✅    |  ✅   |  ❌   |
+/// Boundary |   ❌    |   ❌   |  ✅  |    ❌    |  ❌  |   ❌    |  ❌   |  ✅   |
+/// Goal     |   ❌    |   ❌   |  ✅  |    ❌    |  ❌  |   ❌    |  ❌   |  ❌   |
+/// PowerUp  |   ❌    |   ❌   |  ✅  |    ❌    |  ❌  |   ❌    |  ❌   |  ❌   |
+/// Brick    |   ❌    |   ❌   |  ✅  |    ❌    |  ❌  |   ❌    |  ❌   |  ❌   |
+/// Decoy    |   ❌    |   ❌   |  ❌  |    ✅    |  ❌  |   ❌    |  ❌   |  ❌   |
 /// ```
+///
+/// Paddles don't collide with boundaries: pushing a dynamic paddle into a wall
+/// made it jitter, so paddles are kept on the court by a clamp system instead.
+/// Decoy balls only bounce off the walls, passing through paddles and goals.
 #[derive(PhysicsLayer, Clone, Copy, Debug, Default)]
 #[allow(dead_code)] // PowerUp variant will be used in Phase 2
 pub enum GameLayer {
@@ -51,12 +82,14 @@ pub enum GameLayer {
     Boundary, // Layer 3
     PowerUp,  // Layer 4
     Goal,     // Layer 5
+    Brick,    // Layer 6
+    Decoy,    // Layer 7
 }
 
 /// Creates collision layers for paddles.
-/// Paddles collide with balls and boundaries, but not with other paddles or powerups.
+/// Paddles only collide with balls; they're kept inside the court by a clamp.
 pub fn paddle_layers() -> CollisionLayers {
-    CollisionLayers::new(GameLayer::Paddle, [GameLayer::Ball, GameLayer::Boundary])
+    CollisionLayers::new(GameLayer::Paddle, [GameLayer::Ball])
 }
 
 /// Creates collision layers for the ball.
@@ -69,14 +102,21 @@ pub fn ball_layers() -> CollisionLayers {
             GameLayer::Boundary,
             GameLayer::Goal,
             GameLayer::PowerUp,
+            GameLayer::Brick,
         ],
     )
 }
 
 /// Creates collision layers for boundaries.
-/// Boundaries collide with paddles and balls.
+/// Boundaries only collide with balls and decoys.
 pub fn boundary_layers() -> CollisionLayers {
-    CollisionLayers::new(GameLayer::Boundary, [GameLayer::Paddle, GameLayer::Ball])
+    CollisionLayers::new(GameLayer::Boundary, [GameLayer::Ball, GameLayer::Decoy])
+}
+
+/// Creates collision layers for open-court side lines.
+/// They're only drawn; the ball flies straight through them out of bounds.
+pub fn side_line_layers() -> CollisionLayers {
+    CollisionLayers::new(GameLayer::Boundary, [] as [GameLayer; 0])
 }
 
 /// Creates collision layers for powerups.
@@ -86,12 +126,24 @@ pub fn powerup_layers() -> CollisionLayers {
     CollisionLayers::new(GameLayer::PowerUp, [GameLayer::Ball])
 }
 
+/// Creates collision layers for bricks.
+/// Bricks only collide with balls, which break them.
+pub fn brick_layers() -> CollisionLayers {
+    CollisionLayers::new(GameLayer::Brick, [GameLayer::Ball])
+}
+
 /// Creates collision layers for goal sensors.
 /// Goals only detect balls passing through them.
 pub fn goal_layers() -> CollisionLayers {
     CollisionLayers::new(GameLayer::Goal, [GameLayer::Ball])
 }
 
+/// Creates collision layers for decoy balls.
+/// Decoys only bounce off boundaries, so they can't score or be returned.
+pub fn decoy_layers() -> CollisionLayers {
+    CollisionLayers::new(GameLayer::Decoy, [GameLayer::Boundary])
+}
+
 /// Creates collision layers for default/unassigned entities.
 /// Default entities don't collide with anything.
 ///
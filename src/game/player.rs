@@ -7,23 +7,48 @@ use bevy::{
 };
 
 use bevy_enhanced_input::prelude::*;
+use serde::{Deserialize, Serialize};
 
+#[cfg(not(target_family = "wasm"))]
+use crate::daily::DailyChallenge;
 use crate::{
     asset_tracking::LoadResource,
-    game::court::COURT_HEIGHT,
-    game::physics::{PADDLE_FRICTION, PADDLE_MAX_SPEED, PADDLE_RESTITUTION, paddle_layers},
+    game::ball::{BALL_RADIUS, Ball, ball_wall_y, predict_crossing},
+    game::config::GameConfig,
+    game::court::{BOUNDARY_THICKNESS, CourtInset, CourtSize},
+    game::emote::{EmoteGoodGame, EmoteNice, EmoteOops, EmoteTaunt},
+    game::morph::{CharacterSheet, PaddleForm},
+    game::physics::{PADDLE_FRICTION, paddle_layers},
+    game::roster::Character,
+    game::rules::{Handicap, MatchRules},
+    screens::Screen,
+    tournament::Tournament,
 };
 
-// Paddle dimensions (relative to court size)
-const PADDLE_HEIGHT_RATIO: f32 = 0.125; // 1/8 of court height
-const PADDLE_WIDTH: f32 = 12.0;
+// Paddle positioning (size, speed and knockback are in the `GameConfig`)
+const PADDLE_GOAL_GAP: f32 = 50.0; // Distance in front of the goal line
 
-// Paddle positioning
-pub const PADDLE_X_OFFSET: f32 = 350.0; // Distance from center
+// Aim assist
+const MAX_AIM_ASSIST: f32 = 1.0;
+const AIM_ASSIST_STEP: f32 = 0.25;
+const AIM_ASSIST_PULL: f32 = 0.4; // Share of max speed at full strength
+const AIM_ASSIST_WINDOW: f32 = 1.0; // seconds before the ball arrives
+const AIM_ASSIST_RANGE: f32 = 60.0; // pixels off target for the full pull
 
 pub(super) fn plugin(app: &mut App) {
     app.register_type::<PlayerSide>();
     app.register_type::<Player>();
+    app.register_type::<PaddleSpeed>();
+    app.register_type::<PaddleSize>();
+    app.register_type::<PaddleIntent>();
+    app.register_type::<Knockback>();
+    app.register_type::<MovementFeel>();
+    app.init_resource::<MovementFeel>();
+    app.register_type::<AimAssist>();
+    app.init_resource::<AimAssist>();
+    app.register_type::<MirroredCourt>();
+    app.init_resource::<MirroredCourt>();
+    app.add_observer(announce_paddle_hit);
 
     app.register_type::<PlayerAssets>();
     app.load_resource::<PlayerAssets>();
@@ -31,21 +56,66 @@ pub(super) fn plugin(app: &mut App) {
     // Input handling
     app.add_plugins(EnhancedInputPlugin)
         .add_input_context::<Gameplay>()
-        .add_observer(move_player);
+        .add_event::<AbilityPressed>()
+        .add_observer(move_player)
+        .add_observer(stop_player)
+        .add_observer(press_ability);
+
+    // Steer paddles and keep them inside the court before each physics step
+    // (avian runs in `FixedPostUpdate`)
+    app.add_systems(
+        FixedUpdate,
+        (
+            accelerate_paddles,
+            recover_from_knockback,
+            clamp_paddles_to_court,
+        )
+            .chain()
+            .run_if(in_state(Screen::Gameplay)),
+    );
 }
 
-#[derive(Reflect, Default, Clone, Copy, PartialEq, Eq, Debug)]
+#[derive(Reflect, Default, Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
 pub enum PlayerSide {
     #[default]
     Left,
     Right,
 }
 
+impl PlayerSide {
+    /// The other side of the court
+    pub fn opponent(self) -> Self {
+        match self {
+            PlayerSide::Left => PlayerSide::Right,
+            PlayerSide::Right => PlayerSide::Left,
+        }
+    }
+
+    /// Where this side's paddle sits across a court of `size`, before any
+    /// knockback
+    pub fn paddle_x(self, size: CourtSize) -> f32 {
+        let x = size.half_width() - PADDLE_GOAL_GAP;
+        match self {
+            PlayerSide::Left => -x,
+            PlayerSide::Right => x,
+        }
+    }
+}
+
 /// Movement action for players - outputs Vec2 for full 2D movement
 #[derive(Debug, InputAction)]
 #[action_output(Vec2)]
 pub struct Move;
 
+/// Fires the character's special ability
+#[derive(Debug, InputAction)]
+#[action_output(bool)]
+pub struct UseAbility;
+
+/// Sent when a player presses their ability button, for the input buffer
+#[derive(Event, Debug, Clone, Copy)]
+pub struct AbilityPressed;
+
 /// Context for active gameplay (as opposed to menus)
 #[derive(Component, Default)]
 pub struct Gameplay;
@@ -59,27 +129,30 @@ pub struct PhysicsBundle {
     velocity: LinearVelocity,
     locked_axes: LockedAxes,
     gravity_scale: GravityScale,
-    damping: LinearDamping,
     friction: Friction,
     restitution: Restitution,
     interpolation: TransformInterpolation,
-    mass: ColliderDensity,
 }
 
 /// The player character.
 pub fn player(
     side: PlayerSide,
     position: Vec3,
-    _player_assets: &PlayerAssets,
-    _texture_atlas_layouts: &mut Assets<TextureAtlasLayout>,
+    handicap: &Handicap,
+    character: Character,
+    color: Color,
+    config: &GameConfig,
+    player_assets: &PlayerAssets,
+    texture_atlas_layouts: &mut Assets<TextureAtlasLayout>,
 ) -> impl Bundle {
     // A texture atlas is a way to split a single image into a grid of related images.
     // You can learn more in this example: https://github.com/bevyengine/bevy/blob/latest/examples/2d/texture_atlas.rs
-    //let layout = TextureAtlasLayout::from_grid(UVec2::splat(32), 6, 2, Some(UVec2::splat(1)), None);
-    //let texture_atlas_layout = texture_atlas_layouts.add(layout);
-    //let player_animation = PlayerAnimation::new();
+    let layout = TextureAtlasLayout::from_grid(UVec2::splat(32), 6, 2, Some(UVec2::splat(1)), None);
+    let texture_atlas_layout = texture_atlas_layouts.add(layout);
 
-    let paddle_height = COURT_HEIGHT * PADDLE_HEIGHT_RATIO;
+    let stats = character.stats();
+    let paddle_width = config.paddle.width;
+    let paddle_height = config.paddle.height * handicap.size_multiplier * stats.size_multiplier;
 
     // Create actions for both paddles (observer will filter by side)
     let actions = actions!(Gameplay[
@@ -87,47 +160,69 @@ pub fn player(
             Action::<Move>::new(),
             Bindings::spawn(Cardinal::wasd_keys()),
         ),
+        (
+            Action::<UseAbility>::new(),
+            bindings![KeyCode::KeyE, GamepadButton::RightTrigger2],
+        ),
+        (
+            Action::<EmoteGoodGame>::new(),
+            bindings![KeyCode::Digit1, GamepadButton::DPadUp],
+        ),
+        (
+            Action::<EmoteNice>::new(),
+            bindings![KeyCode::Digit2, GamepadButton::DPadRight],
+        ),
+        (
+            Action::<EmoteOops>::new(),
+            bindings![KeyCode::Digit3, GamepadButton::DPadDown],
+        ),
+        (
+            Action::<EmoteTaunt>::new(),
+            bindings![KeyCode::Digit4, GamepadButton::DPadLeft],
+        ),
     ]);
 
     (
         Name::new("Player"),
         Player { side },
+        character,
+        PaddleSpeed(handicap.speed_multiplier * stats.speed_multiplier),
+        PaddleSize(Vec2::new(paddle_width, paddle_height)),
+        PaddleIntent::default(),
+        Knockback::default(),
         Gameplay, // Add the context component
+        // Starts as a Pong-style paddle and morphs into the character when
+        // powered up
+        PaddleForm::Paddle,
+        CharacterSheet::new(player_assets.ducky.clone(), texture_atlas_layout),
         Sprite {
-            // Starts with Pong-style paddles that morph later
-            //image: player_assets.ducky.clone(),
-            //texture_atlas: Some(TextureAtlas {
-            //    layout: texture_atlas_layout,
-            //    index: player_animation.get_atlas_index(),
-            //}),
-            color: Color::WHITE,
-            custom_size: Some(Vec2::new(PADDLE_WIDTH, paddle_height)),
+            color,
+            custom_size: Some(Vec2::new(paddle_width, paddle_height)),
             ..default()
         },
         Transform::from_translation(position),
         // Physics components bundled together
         PhysicsBundle {
-            rigid_body: RigidBody::Dynamic,
-            collider: Collider::rectangle(PADDLE_WIDTH, paddle_height),
+            // Kinematic: velocity comes straight from input, and the ball
+            // can't shove the paddle around
+            rigid_body: RigidBody::Kinematic,
+            collider: Collider::rectangle(paddle_width, paddle_height),
             layers: paddle_layers(),
             velocity: LinearVelocity::default(),
             // Lock rotation and horizontal movement
             locked_axes: LockedAxes::new().lock_rotation().lock_translation_x(),
             // Prevent gravity from affecting the paddle
             gravity_scale: GravityScale(0.0),
-            // Extremely high linear damping to stop instantly when no input
-            damping: LinearDamping(50.0),
             // Physics material properties for paddles
             friction: Friction::new(PADDLE_FRICTION),
-            restitution: Restitution::new(PADDLE_RESTITUTION),
+            restitution: Restitution::new(config.paddle.restitution),
             // Enable transform interpolation for smooth visual movement
             interpolation: TransformInterpolation,
-            // Low density to reduce momentum
-            mass: ColliderDensity(1.0),
         },
+        // Report ball contacts as `PaddleHit`s
+        CollisionEventsEnabled,
         // Input actions
         actions,
-        //player_animation,
     )
 }
 
@@ -137,6 +232,199 @@ pub struct Player {
     pub side: PlayerSide,
 }
 
+impl Player {
+    /// Whether the `Move` action drives this paddle. Only one paddle is on
+    /// the keyboard for now: the left one, or the right on a mirrored court.
+    pub fn takes_keyboard_input(&self, mirrored: &MirroredCourt) -> bool {
+        self.side == mirrored.home_side()
+    }
+}
+
+/// Puts the player on the keyboard (and touch and tilt) on the right of the
+/// court instead of the left, for left-handed players and cabinets wired
+/// that way. Sides stay where they are on screen, so serves, goals and the
+/// HUD follow the paddle without any changes of their own.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect, Serialize, Deserialize)]
+#[reflect(Resource)]
+pub struct MirroredCourt(pub bool);
+
+impl MirroredCourt {
+    /// The side the local player controls
+    pub fn home_side(self) -> PlayerSide {
+        if self.0 {
+            PlayerSide::Right
+        } else {
+            PlayerSide::Left
+        }
+    }
+}
+
+/// Triggered on a paddle when a ball starts touching it. Anything that
+/// reacts to returns (rally counts, spin, momentum, stats, sound) should
+/// observe this rather than matching up collisions itself.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct PaddleHit {
+    pub paddle: Entity,
+    pub side: PlayerSide,
+    pub ball: Entity,
+    /// Where the ball met the paddle's face, in world space
+    pub contact_point: Vec2,
+    /// The ball's speed as it comes off the paddle, in pixels per second
+    pub ball_speed: f32,
+}
+
+fn announce_paddle_hit(
+    trigger: Trigger<OnCollisionStart>,
+    mut commands: Commands,
+    paddles: Query<(&Player, &Transform, &PaddleSize)>,
+    balls: Query<(&Transform, &LinearVelocity), With<Ball>>,
+) {
+    let paddle = trigger.target();
+    let Ok((player, paddle_transform, size)) = paddles.get(paddle) else {
+        return;
+    };
+    let ball = trigger.event().collider;
+    let Ok((ball_transform, velocity)) = balls.get(ball) else {
+        return;
+    };
+
+    // The nearest point on the face turned toward the ball
+    let center = paddle_transform.translation.truncate();
+    let half_size = size.0 / 2.0;
+    let ball_position = ball_transform.translation.truncate();
+    let contact_point = Vec2::new(
+        center.x + (ball_position.x - center.x).signum() * half_size.x,
+        ball_position
+            .y
+            .clamp(center.y - half_size.y, center.y + half_size.y),
+    );
+
+    commands.trigger_targets(
+        PaddleHit {
+            paddle,
+            side: player.side,
+            ball,
+            contact_point,
+            ball_speed: velocity.length(),
+        },
+        paddle,
+    );
+}
+
+/// Multiplier on the configured top paddle speed, from handicaps and the
+/// character
+#[derive(Component, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct PaddleSpeed(pub f32);
+
+/// Where a paddle wants to go, from -1.0 (full speed down) to 1.0 (full speed up).
+///
+/// Anything that drives a paddle (keyboard input, an AI) writes this; the
+/// paddle then speeds up and slows down according to [`MovementFeel`].
+#[derive(Component, Debug, Clone, Copy, PartialEq, Default, Reflect)]
+#[reflect(Component)]
+pub struct PaddleIntent(pub f32);
+
+/// How far a paddle has been shoved back toward its own goal, and how fast
+/// it's moving. Springs back to the paddle line on its own.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Default, Reflect)]
+#[reflect(Component)]
+pub struct Knockback {
+    /// Pixels behind the paddle line
+    pub offset: f32,
+    /// Pixels per second, positive away from the center
+    pub velocity: f32,
+}
+
+impl Knockback {
+    /// Shoves the paddle back at `speed` pixels per second
+    pub fn push(&mut self, speed: f32) {
+        self.velocity += speed;
+    }
+}
+
+/// How strongly the keyboard paddle is drawn toward where the ball will
+/// arrive, from 0 (off) to 1. Casual play only: tournaments and daily
+/// challenges ignore it.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Default, Reflect)]
+#[reflect(Resource)]
+pub struct AimAssist(pub f32);
+
+impl AimAssist {
+    pub fn stronger(&mut self) {
+        self.0 = (self.0 + AIM_ASSIST_STEP).min(MAX_AIM_ASSIST);
+    }
+
+    pub fn weaker(&mut self) {
+        self.0 = (self.0 - AIM_ASSIST_STEP).max(0.0);
+    }
+
+    /// The strength for the settings menu, e.g. "50%"
+    pub fn label(self) -> String {
+        if self.0 <= 0.0 {
+            "Off".into()
+        } else {
+            format!("{:.0}%", self.0 * 100.0)
+        }
+    }
+}
+
+/// How quickly paddles respond to input
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect, Serialize, Deserialize)]
+#[reflect(Resource)]
+pub enum MovementFeel {
+    /// Full speed immediately, stops dead
+    #[default]
+    Instant,
+    /// Ramps up over a few frames, stops quickly
+    EaseIn,
+    /// Slow to get going and slow to stop
+    Heavy,
+}
+
+impl MovementFeel {
+    /// Acceleration and deceleration, in multiples of max speed per second.
+    /// `None` means the paddle snaps straight to its target speed.
+    fn rates(self) -> Option<(f32, f32)> {
+        match self {
+            MovementFeel::Instant => None,
+            MovementFeel::EaseIn => Some((6.0, 12.0)),
+            MovementFeel::Heavy => Some((2.5, 3.0)),
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            MovementFeel::Instant => "Instant",
+            MovementFeel::EaseIn => "Ease-in",
+            MovementFeel::Heavy => "Heavy",
+        }
+    }
+
+    /// The next preset, for cycling through them in the settings menu
+    pub fn next(self) -> Self {
+        match self {
+            MovementFeel::Instant => MovementFeel::EaseIn,
+            MovementFeel::EaseIn => MovementFeel::Heavy,
+            MovementFeel::Heavy => MovementFeel::Instant,
+        }
+    }
+
+    /// The previous preset, for cycling back through them in the settings menu
+    pub fn previous(self) -> Self {
+        match self {
+            MovementFeel::Instant => MovementFeel::Heavy,
+            MovementFeel::EaseIn => MovementFeel::Instant,
+            MovementFeel::Heavy => MovementFeel::EaseIn,
+        }
+    }
+}
+
+/// Paddle dimensions in pixels, after handicaps
+#[derive(Component, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct PaddleSize(pub Vec2);
+
 #[derive(Resource, Asset, Clone, Reflect)]
 #[reflect(Resource)]
 pub struct PlayerAssets {
@@ -168,12 +456,178 @@ impl FromWorld for PlayerAssets {
 }
 
 /// Apply movement when Move action is fired
-fn move_player(trigger: Trigger<Fired<Move>>, mut paddles: Query<(&Player, &mut LinearVelocity)>) {
-    if let Ok((player, mut velocity)) = paddles.get_mut(trigger.target()) {
-        // Only move left paddle for now
-        if player.side == PlayerSide::Left {
+fn move_player(
+    trigger: Trigger<Fired<Move>>,
+    mirrored: Res<MirroredCourt>,
+    mut paddles: Query<(&Player, &mut PaddleIntent)>,
+) {
+    if let Ok((player, mut intent)) = paddles.get_mut(trigger.target()) {
+        if player.takes_keyboard_input(&mirrored) {
             // Only use the y component of the movement vector
-            velocity.y = trigger.value.y * PADDLE_MAX_SPEED;
+            intent.0 = trigger.value.y.clamp(-1.0, 1.0);
+        }
+    }
+}
+
+/// Passes ability presses on to the input buffer, which ignores them while a
+/// replay is playing
+fn press_ability(
+    trigger: Trigger<Started<UseAbility>>,
+    mirrored: Res<MirroredCourt>,
+    paddles: Query<&Player>,
+    mut pressed: EventWriter<AbilityPressed>,
+) {
+    if let Ok(player) = paddles.get(trigger.target()) {
+        if player.takes_keyboard_input(&mirrored) {
+            pressed.write(AbilityPressed);
+        }
+    }
+}
+
+/// Stop the paddle once the Move action is released
+fn stop_player(
+    trigger: Trigger<Completed<Move>>,
+    mirrored: Res<MirroredCourt>,
+    mut paddles: Query<(&Player, &mut PaddleIntent)>,
+) {
+    if let Ok((player, mut intent)) = paddles.get_mut(trigger.target()) {
+        if player.takes_keyboard_input(&mirrored) {
+            intent.0 = 0.0;
+        }
+    }
+}
+
+/// Moves each paddle's velocity toward its intent at the current feel's
+/// rates, nudged by any aim assist
+fn accelerate_paddles(
+    time: Res<Time>,
+    config: Res<GameConfig>,
+    feel: Res<MovementFeel>,
+    aim_assist: Res<AimAssist>,
+    mirrored: Res<MirroredCourt>,
+    rules: Res<MatchRules>,
+    inset: Res<CourtInset>,
+    court: Res<CourtSize>,
+    tournament: Option<Res<Tournament>>,
+    #[cfg(not(target_family = "wasm"))] daily: Option<Res<DailyChallenge>>,
+    balls: Query<(&Position, &LinearVelocity), (With<Ball>, Without<Player>)>,
+    mut paddles: Query<(
+        &Player,
+        &Position,
+        &PaddleSize,
+        &PaddleIntent,
+        &PaddleSpeed,
+        &mut LinearVelocity,
+    )>,
+) {
+    #[cfg(not(target_family = "wasm"))]
+    let ranked = tournament.is_some() || daily.is_some();
+    #[cfg(target_family = "wasm")]
+    let ranked = tournament.is_some();
+    let assist = if ranked { 0.0 } else { aim_assist.0 };
+    let wall_y = ball_wall_y(&rules, &inset, *court);
+
+    for (player, position, size, intent, speed, mut velocity) in &mut paddles {
+        let mut intent = intent.0;
+        if assist > 0.0 && player.takes_keyboard_input(&mirrored) {
+            let face_x = match player.side {
+                PlayerSide::Left => position.x + size.0.x / 2.0 + BALL_RADIUS,
+                PlayerSide::Right => position.x - size.0.x / 2.0 - BALL_RADIUS,
+            };
+            // Toward the ball arriving soonest
+            let intercept = balls
+                .iter()
+                .filter_map(|(ball, ball_velocity)| {
+                    predict_crossing(ball.0, ball_velocity.0, face_x, wall_y)
+                })
+                .filter(|(_, time)| *time <= AIM_ASSIST_WINDOW)
+                .min_by(|(_, a), (_, b)| a.total_cmp(b));
+            if let Some((intercept, _)) = intercept {
+                let pull = ((intercept.y - position.y) / AIM_ASSIST_RANGE).clamp(-1.0, 1.0);
+                intent = (intent + pull * assist * AIM_ASSIST_PULL).clamp(-1.0, 1.0);
+            }
+        }
+
+        let max_speed = config.paddle.max_speed * speed.0;
+        let target = intent * max_speed;
+        let Some((acceleration, deceleration)) = feel.rates() else {
+            velocity.y = target;
+            continue;
+        };
+
+        // Speeding up in the same direction accelerates; anything else
+        // (releasing, reversing) decelerates
+        let speeding_up = target.abs() > velocity.y.abs() && target * velocity.y >= 0.0;
+        let rate = if speeding_up {
+            acceleration
+        } else {
+            deceleration * config.paddle.damping
+        };
+
+        let max_change = rate * max_speed * time.delta_secs();
+        velocity.y += (target - velocity.y).clamp(-max_change, max_change);
+    }
+}
+
+/// Springs knocked-back paddles back to their line, never letting them get
+/// pushed further behind it than the config allows
+fn recover_from_knockback(
+    time: Res<Time>,
+    config: Res<GameConfig>,
+    size: Res<CourtSize>,
+    mut paddles: Query<(&Player, &mut Knockback, &mut Position)>,
+) {
+    let dt = time.delta_secs();
+    for (player, mut knockback, mut position) in &mut paddles {
+        if knockback.offset == 0.0 && knockback.velocity == 0.0 {
+            continue;
+        }
+
+        let spring = -knockback.offset * config.paddle.knockback_stiffness;
+        let damping = -knockback.velocity * config.paddle.knockback_damping;
+        knockback.velocity += (spring + damping) * dt;
+        knockback.offset += knockback.velocity * dt;
+
+        if knockback.offset > config.paddle.max_knockback {
+            knockback.offset = config.paddle.max_knockback;
+            knockback.velocity = knockback.velocity.min(0.0);
+        }
+        // Settle exactly on the line rather than creeping forever
+        if knockback.offset <= 0.0 || knockback.offset.abs() + knockback.velocity.abs() < 0.01 {
+            *knockback = Knockback::default();
+        }
+
+        let line_x = player.side.paddle_x(*size);
+        position.x = match player.side {
+            PlayerSide::Left => line_x - knockback.offset,
+            PlayerSide::Right => line_x + knockback.offset,
+        };
+    }
+}
+
+/// Keeps paddles between the top and bottom boundaries.
+///
+/// Rather than letting paddles push against the walls, the velocity is trimmed
+/// so the next physics step ends exactly at the edge, so the paddle eases into
+/// place instead of bouncing.
+fn clamp_paddles_to_court(
+    time: Res<Time>,
+    inset: Res<CourtInset>,
+    court: Res<CourtSize>,
+    mut paddles: Query<(&PaddleSize, &mut Position, &mut LinearVelocity), With<Player>>,
+) {
+    let dt = time.delta_secs();
+    for (size, mut position, mut velocity) in &mut paddles {
+        let limit = (court.half_height() - BOUNDARY_THICKNESS - inset.0 - size.0.y / 2.0).max(0.0);
+
+        // Snap back anything that already ended up outside
+        position.y = position.y.clamp(-limit, limit);
+
+        if dt > 0.0 {
+            let next_y = position.y + velocity.y * dt;
+            if next_y.abs() > limit {
+                velocity.y = (next_y.clamp(-limit, limit) - position.y) / dt;
+            }
         }
     }
 }
@@ -0,0 +1,225 @@
+//! The power serve: an opt-in, timing-based serve for the keyboard player.
+//!
+//! With [`PowerServe`] on, the first serve press starts a meter swinging
+//! between empty and full, and the second locks it in. The fuller the meter,
+//! the faster and flatter the serve; a weak lock floats out slow and steep.
+//! The serve goes up unless the paddle is moving down. CPU serves stay
+//! random.
+
+use bevy::{prelude::*, ui::Val::*};
+use serde::{Deserialize, Serialize};
+
+use super::{
+    GamePhase,
+    ball::{ChosenServe, ServeDirection, ServeShot},
+    config::GameConfig,
+    cpu::CpuPlayer,
+    hud::InCourtFrame,
+    input_buffer::{BufferedAction, InputBuffer},
+    player::{MirroredCourt, PaddleIntent, Player, PlayerSide},
+    profile::PlayerProfiles,
+};
+use crate::{
+    AppSystems, PausableSystems,
+    screens::{Screen, transition::Transition},
+};
+
+/// Seconds for the meter to fill and empty again
+const METER_PERIOD: f32 = 1.2;
+/// Serve speed multiplier with an empty and a full meter
+const SERVE_SPEED: (f32, f32) = (0.8, 1.3);
+
+const METER_TOP: f32 = 68.0; // percent of the court height
+const METER_WIDTH: f32 = 220.0;
+const METER_HEIGHT: f32 = 14.0;
+const METER_TRACK_COLOR: Color = Color::srgba(1.0, 1.0, 1.0, 0.15);
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<PowerServe>();
+    app.init_resource::<PowerServe>();
+    app.register_type::<PowerMeter>();
+    app.init_resource::<PowerMeter>();
+    app.register_type::<PowerMeterBar>();
+    app.register_type::<PowerMeterFill>();
+
+    app.add_systems(
+        OnEnter(GamePhase::WaitingToServe),
+        (reset_power_meter, spawn_power_meter).run_if(in_state(Screen::Gameplay)),
+    );
+    // Counted in fixed steps, so replays lock the meter where it was locked
+    app.add_systems(
+        FixedUpdate,
+        (
+            tick_power_meter,
+            use_power_meter
+                .before(super::ball::handle_serve_input)
+                .run_if(
+                    in_state(Screen::Gameplay)
+                        .and(in_state(Transition::Idle))
+                        .and(power_serve_armed),
+                ),
+        )
+            .chain()
+            .run_if(in_state(GamePhase::WaitingToServe)),
+    );
+    app.add_systems(
+        Update,
+        update_power_meter
+            .in_set(AppSystems::Update)
+            .in_set(PausableSystems)
+            .run_if(in_state(GamePhase::WaitingToServe).and(in_state(Screen::Gameplay))),
+    );
+}
+
+/// Whether the keyboard player serves with the power meter
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect, Serialize, Deserialize)]
+#[reflect(Resource)]
+pub struct PowerServe(pub bool);
+
+/// Who started the meter swinging and how many seconds ago, or `None`
+/// before the first press
+#[derive(Resource, Debug, Default, Reflect)]
+#[reflect(Resource)]
+struct PowerMeter(Option<(PlayerSide, f32)>);
+
+impl PowerMeter {
+    /// Whether it's swinging for `side`'s serve. The serve can change hands
+    /// while waiting, e.g. on the serve clock.
+    fn swinging_for(&self, side: PlayerSide) -> bool {
+        self.0.is_some_and(|(started_by, _)| started_by == side)
+    }
+
+    /// How full the meter is, from 0.0 to 1.0 and back each period
+    fn level(&self) -> f32 {
+        let elapsed = self.0.map_or(0.0, |(_, elapsed)| elapsed);
+        let phase = (elapsed / METER_PERIOD).fract();
+        1.0 - (2.0 * phase - 1.0).abs()
+    }
+}
+
+/// The meter under the serve prompt
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct PowerMeterBar;
+
+/// The filled part of the meter
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct PowerMeterFill;
+
+/// The serve is the keyboard player's and they've opted in
+fn power_serve_armed(
+    power_serve: Res<PowerServe>,
+    serve_direction: Res<ServeDirection>,
+    mirrored: Res<MirroredCourt>,
+    cpus: Query<&Player, With<CpuPlayer>>,
+) -> bool {
+    let server = serve_direction.side;
+    power_serve.0
+        && server == mirrored.home_side()
+        && !cpus.iter().any(|player| player.side == server)
+}
+
+fn reset_power_meter(mut meter: ResMut<PowerMeter>, mut chosen: ResMut<ChosenServe>) {
+    meter.0 = None;
+    chosen.0 = None;
+}
+
+fn tick_power_meter(time: Res<Time>, mut meter: ResMut<PowerMeter>) {
+    if let Some((_, elapsed)) = &mut meter.0 {
+        *elapsed += time.delta_secs();
+    }
+}
+
+/// Starts the meter on the first serve press and serves on the second, at
+/// the strength it was locked at. Takes the press before the plain serve
+/// can.
+fn use_power_meter(
+    time: Res<Time<Virtual>>,
+    serve_direction: Res<ServeDirection>,
+    config: Res<GameConfig>,
+    mut buffer: ResMut<InputBuffer>,
+    mut meter: ResMut<PowerMeter>,
+    mut chosen: ResMut<ChosenServe>,
+    mut game_phase: ResMut<NextState<GamePhase>>,
+    paddles: Query<(&Player, &PaddleIntent)>,
+) {
+    if !buffer.consume(BufferedAction::Serve, time.elapsed_secs()) {
+        return;
+    }
+    let server = serve_direction.side;
+    if !meter.swinging_for(server) {
+        meter.0 = Some((server, 0.0));
+        return;
+    }
+
+    let level = meter.level();
+    let down = paddles
+        .iter()
+        .any(|(player, intent)| player.side == server && intent.0 < 0.0);
+    let (low, high) = config.ball.serve_angles();
+    let angle = high + (low - high) * level;
+    let (low, high) = SERVE_SPEED;
+    chosen.0 = Some(ServeShot {
+        angle: if down { -angle } else { angle },
+        speed: low + (high - low) * level,
+    });
+    info!("Power serve locked at {:.0}%", level * 100.0);
+    game_phase.set(GamePhase::Playing);
+}
+
+fn spawn_power_meter(
+    mut commands: Commands,
+    profiles: Res<PlayerProfiles>,
+    serve_direction: Res<ServeDirection>,
+) {
+    commands.spawn((
+        Name::new("Power Meter"),
+        PowerMeterBar,
+        Node {
+            position_type: PositionType::Absolute,
+            width: Percent(100.0),
+            top: Percent(METER_TOP),
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        Visibility::Hidden,
+        Pickable::IGNORE,
+        InCourtFrame,
+        StateScoped(GamePhase::WaitingToServe),
+        children![(
+            Name::new("Power Meter Track"),
+            Node {
+                width: Px(METER_WIDTH),
+                height: Px(METER_HEIGHT),
+                ..default()
+            },
+            BackgroundColor(METER_TRACK_COLOR),
+            children![(
+                Name::new("Power Meter Fill"),
+                PowerMeterFill,
+                Node {
+                    width: Percent(0.0),
+                    height: Percent(100.0),
+                    ..default()
+                },
+                BackgroundColor(profiles.color(serve_direction.side)),
+            )],
+        )],
+    ));
+}
+
+/// Shows the meter once it's swinging
+fn update_power_meter(
+    meter: Res<PowerMeter>,
+    serve_direction: Res<ServeDirection>,
+    mut bar: Single<&mut Visibility, With<PowerMeterBar>>,
+    mut fill: Single<&mut Node, With<PowerMeterFill>>,
+) {
+    **bar = if meter.swinging_for(serve_direction.side) {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    };
+    fill.width = Percent(meter.level() * 100.0);
+}
@@ -0,0 +1,132 @@
+//! Per-player profiles: a color, which tints the player's paddle, score, serve
+//! indicator and goal effects so the two sides are easy to tell apart, and
+//! the character they play as.
+
+use bevy::prelude::*;
+
+use super::{
+    player::{Player, PlayerSide},
+    roster::Character,
+    scoring::ScoreDisplay,
+};
+use crate::screens::Screen;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<PlayerProfiles>();
+    app.init_resource::<PlayerProfiles>();
+
+    // Pick up color changes made from the pause menu
+    app.add_systems(
+        Update,
+        tint_players.run_if(in_state(Screen::Gameplay).and(resource_changed::<PlayerProfiles>)),
+    );
+}
+
+/// Colors a player can pick
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+pub enum PlayerColor {
+    #[default]
+    White,
+    Red,
+    Blue,
+    Green,
+    Yellow,
+    Purple,
+}
+
+impl PlayerColor {
+    pub fn color(self) -> Color {
+        match self {
+            PlayerColor::White => Color::WHITE,
+            PlayerColor::Red => Color::srgb(1.0, 0.4, 0.4),
+            PlayerColor::Blue => Color::srgb(0.4, 0.65, 1.0),
+            PlayerColor::Green => Color::srgb(0.45, 0.9, 0.5),
+            PlayerColor::Yellow => Color::srgb(1.0, 0.9, 0.4),
+            PlayerColor::Purple => Color::srgb(0.75, 0.5, 1.0),
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            PlayerColor::White => "White",
+            PlayerColor::Red => "Red",
+            PlayerColor::Blue => "Blue",
+            PlayerColor::Green => "Green",
+            PlayerColor::Yellow => "Yellow",
+            PlayerColor::Purple => "Purple",
+        }
+    }
+
+    /// The next color, for cycling through them in the settings menu
+    pub fn next(self) -> Self {
+        match self {
+            PlayerColor::White => PlayerColor::Red,
+            PlayerColor::Red => PlayerColor::Blue,
+            PlayerColor::Blue => PlayerColor::Green,
+            PlayerColor::Green => PlayerColor::Yellow,
+            PlayerColor::Yellow => PlayerColor::Purple,
+            PlayerColor::Purple => PlayerColor::White,
+        }
+    }
+
+    /// The previous color, for cycling back through them in the settings menu
+    pub fn previous(self) -> Self {
+        match self {
+            PlayerColor::White => PlayerColor::Purple,
+            PlayerColor::Red => PlayerColor::White,
+            PlayerColor::Blue => PlayerColor::Red,
+            PlayerColor::Green => PlayerColor::Blue,
+            PlayerColor::Yellow => PlayerColor::Green,
+            PlayerColor::Purple => PlayerColor::Yellow,
+        }
+    }
+}
+
+/// One player's preferences
+#[derive(Debug, Clone, Default, Reflect)]
+pub struct PlayerProfile {
+    pub color: PlayerColor,
+    pub character: Character,
+}
+
+/// The profiles of the players on each side
+#[derive(Resource, Debug, Clone, Default, Reflect)]
+#[reflect(Resource)]
+pub struct PlayerProfiles {
+    pub left: PlayerProfile,
+    pub right: PlayerProfile,
+}
+
+impl PlayerProfiles {
+    pub fn get(&self, side: PlayerSide) -> &PlayerProfile {
+        match side {
+            PlayerSide::Left => &self.left,
+            PlayerSide::Right => &self.right,
+        }
+    }
+
+    pub fn get_mut(&mut self, side: PlayerSide) -> &mut PlayerProfile {
+        match side {
+            PlayerSide::Left => &mut self.left,
+            PlayerSide::Right => &mut self.right,
+        }
+    }
+
+    /// The tint for everything on `side`
+    pub fn color(&self, side: PlayerSide) -> Color {
+        self.get(side).color.color()
+    }
+}
+
+fn tint_players(
+    profiles: Res<PlayerProfiles>,
+    mut paddles: Query<(&Player, &mut Sprite)>,
+    mut scores: Query<(&ScoreDisplay, &mut TextColor)>,
+) {
+    for (player, mut sprite) in &mut paddles {
+        sprite.color = profiles.color(player.side);
+    }
+    for (display, mut color) in &mut scores {
+        color.0 = profiles.color(display.side());
+    }
+}
@@ -0,0 +1,80 @@
+//! The character roster. Each player picks a character before the match,
+//! which changes their paddle's size and speed and may bring a special
+//! ability.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::ability::Ability;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<Character>();
+}
+
+/// A playable character. Also put on each paddle, for its ability.
+#[derive(
+    Component, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect, Serialize, Deserialize,
+)]
+#[reflect(Component)]
+pub enum Character {
+    #[default]
+    Classic,
+    Sprinter,
+    Wall,
+    Blinker,
+    Bender,
+}
+
+/// How a character plays
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CharacterStats {
+    pub size_multiplier: f32,
+    pub speed_multiplier: f32,
+    pub ability: Option<Ability>,
+}
+
+impl Character {
+    pub const ALL: [Character; 5] = [
+        Character::Classic,
+        Character::Sprinter,
+        Character::Wall,
+        Character::Blinker,
+        Character::Bender,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Character::Classic => "Classic",
+            Character::Sprinter => "Sprinter",
+            Character::Wall => "The Wall",
+            Character::Blinker => "Blinker",
+            Character::Bender => "Bender",
+        }
+    }
+
+    pub fn stats(self) -> CharacterStats {
+        let (size_multiplier, speed_multiplier, ability) = match self {
+            Character::Classic => (1.0, 1.0, None),
+            Character::Sprinter => (0.85, 1.3, None),
+            Character::Wall => (1.35, 0.75, None),
+            Character::Blinker => (0.95, 1.0, Some(Ability::Teleport)),
+            Character::Bender => (1.0, 0.95, Some(Ability::CurveShot)),
+        };
+        CharacterStats {
+            size_multiplier,
+            speed_multiplier,
+            ability,
+        }
+    }
+
+    /// The next character, for cycling through them on the select screen
+    pub fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|&c| c == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    pub fn previous(self) -> Self {
+        let index = Self::ALL.iter().position(|&c| c == self).unwrap_or(0);
+        Self::ALL[(index + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
+}
@@ -0,0 +1,233 @@
+//! Match rules chosen before a match starts.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::{ball_kind::BallKind, player::PlayerSide};
+
+// Scoring defaults
+const DEFAULT_TARGET_SCORE: u32 = 11; // First to 11 wins
+const DEFAULT_MERCY_SCORE: u32 = 7; // Mercy rule at 7-0
+pub const MIN_TARGET_SCORE: u32 = 3;
+pub const MAX_TARGET_SCORE: u32 = 21;
+pub const MAX_UNDO_LIMIT: u32 = 5;
+/// Longest serve clock, in seconds
+pub const MAX_SERVE_CLOCK: u32 = 30;
+pub const SERVE_CLOCK_STEP: u32 = 5;
+
+// Handicap limits
+pub const MAX_SCORE_OFFSET: u32 = 5;
+pub const MIN_SPEED_MULTIPLIER: f32 = 0.5;
+pub const MAX_SPEED_MULTIPLIER: f32 = 1.5;
+pub const MIN_SIZE_MULTIPLIER: f32 = 0.5;
+pub const MAX_SIZE_MULTIPLIER: f32 = 2.0;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<MatchRules>()
+        .register_type::<Handicap>()
+        .register_type::<ScoringSystem>()
+        .register_type::<ServeRotation>()
+        .register_type::<CourtLayout>()
+        .register_type::<ServeClockPenalty>()
+        .init_resource::<MatchRules>();
+}
+
+/// Rules for the current match
+#[derive(Resource, Debug, Clone, Reflect, Serialize, Deserialize)]
+#[reflect(Resource)]
+pub struct MatchRules {
+    /// Points needed to win
+    pub target_score: u32,
+    /// Points that win outright while the opponent has none
+    pub mercy_score: u32,
+    /// At `target - 1` all, the next goal wins and the match goes to overtime
+    pub sudden_death: bool,
+    /// Reaching the target isn't enough without a two-point lead, so a late
+    /// tie goes to deuce instead of sudden death
+    pub win_by_two: bool,
+    /// Whether every rally scores or only the serving side can score
+    pub scoring: ScoringSystem,
+    /// Who serves after each point (side-out scoring always uses winner serves)
+    pub serve_rotation: ServeRotation,
+    /// A serve that clips a wall on its first flight is a let and is re-served
+    pub let_serves: bool,
+    /// Whether the top and bottom edges are walls or out-of-bounds lines
+    pub court: CourtLayout,
+    /// Points that can be undone per casual match (0 disables undo)
+    pub undo_limit: u32,
+    /// The ball in play
+    pub ball: BallKind,
+    /// In sudden death the top and bottom walls slowly close in
+    pub shrinking_court: bool,
+    /// Goals only cover part of each end line and sweep up and down it
+    pub moving_goals: bool,
+    /// Brick-breaker hybrid: breakable bricks mid-court are worth bonus points
+    pub bricks: bool,
+    /// Walls break after repeated hits, letting the ball out (walled courts only)
+    pub wall_damage: bool,
+    /// Own goals (into your own goal off your own paddle) score for the
+    /// opponent. When off, the point is replayed.
+    pub own_goals: bool,
+    /// Two-ball chaos: both balls stay in play all match and a goal only
+    /// re-serves the ball that scored
+    pub two_balls: bool,
+    /// The players change ends once either is halfway to the target
+    pub switch_sides: bool,
+    /// Seconds the server has to serve (0 for no limit)
+    pub serve_clock: u32,
+    /// What running out the serve clock costs
+    pub serve_clock_penalty: ServeClockPenalty,
+    /// The server may fake the serve once per point
+    pub serve_feints: bool,
+    pub left_handicap: Handicap,
+    pub right_handicap: Handicap,
+}
+
+impl Default for MatchRules {
+    fn default() -> Self {
+        Self {
+            target_score: DEFAULT_TARGET_SCORE,
+            mercy_score: DEFAULT_MERCY_SCORE,
+            sudden_death: true,
+            win_by_two: false,
+            scoring: ScoringSystem::default(),
+            serve_rotation: ServeRotation::default(),
+            let_serves: false,
+            court: CourtLayout::default(),
+            undo_limit: 0,
+            ball: BallKind::default(),
+            shrinking_court: false,
+            moving_goals: false,
+            bricks: false,
+            wall_damage: false,
+            own_goals: true,
+            two_balls: false,
+            switch_sides: false,
+            serve_clock: 0,
+            serve_clock_penalty: ServeClockPenalty::default(),
+            serve_feints: false,
+            left_handicap: Handicap::default(),
+            right_handicap: Handicap::default(),
+        }
+    }
+}
+
+impl MatchRules {
+    pub fn handicap(&self, side: PlayerSide) -> &Handicap {
+        match side {
+            PlayerSide::Left => &self.left_handicap,
+            PlayerSide::Right => &self.right_handicap,
+        }
+    }
+
+    pub fn handicap_mut(&mut self, side: PlayerSide) -> &mut Handicap {
+        match side {
+            PlayerSide::Left => &mut self.left_handicap,
+            PlayerSide::Right => &mut self.right_handicap,
+        }
+    }
+
+    /// Balls in play at once
+    pub fn ball_count(&self) -> usize {
+        if self.two_balls { 2 } else { 1 }
+    }
+}
+
+/// How rallies turn into points
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Reflect, Serialize, Deserialize)]
+pub enum ScoringSystem {
+    /// Whoever wins the rally scores
+    #[default]
+    Rally,
+    /// Only the serving side scores; a receiver who wins the rally takes the
+    /// serve instead (table-tennis style side-out)
+    SideOut,
+}
+
+/// Who serves the next point
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Reflect, Serialize, Deserialize)]
+pub enum ServeRotation {
+    /// The player who conceded serves
+    #[default]
+    LoserServes,
+    /// The player who won the point serves
+    WinnerServes,
+    /// Serve alternates every [`SERVES_PER_TURN`] points, and every point at deuce
+    Alternate,
+}
+
+impl ServeRotation {
+    /// The next rotation, for cycling through them in the rules menu
+    pub fn next(self) -> Self {
+        match self {
+            ServeRotation::LoserServes => ServeRotation::WinnerServes,
+            ServeRotation::WinnerServes => ServeRotation::Alternate,
+            ServeRotation::Alternate => ServeRotation::LoserServes,
+        }
+    }
+
+    /// The previous rotation, for cycling backwards
+    pub fn previous(self) -> Self {
+        match self {
+            ServeRotation::LoserServes => ServeRotation::Alternate,
+            ServeRotation::WinnerServes => ServeRotation::LoserServes,
+            ServeRotation::Alternate => ServeRotation::WinnerServes,
+        }
+    }
+}
+
+/// Consecutive serves per player with [`ServeRotation::Alternate`]
+pub const SERVES_PER_TURN: u32 = 2;
+
+impl MatchRules {
+    /// The rotation actually in effect, since side-out scoring forces the
+    /// rally winner to serve
+    pub fn effective_serve_rotation(&self) -> ServeRotation {
+        match self.scoring {
+            ScoringSystem::Rally => self.serve_rotation,
+            ScoringSystem::SideOut => ServeRotation::WinnerServes,
+        }
+    }
+}
+
+/// What a server who runs out the serve clock loses
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Reflect, Serialize, Deserialize)]
+pub enum ServeClockPenalty {
+    /// The serve passes to the opponent
+    #[default]
+    LoseServe,
+    /// The opponent wins the point, as if the serve had gone out
+    LosePoint,
+}
+
+/// Court variants
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Reflect, Serialize, Deserialize)]
+pub enum CourtLayout {
+    /// Classic court: the ball bounces off the top and bottom walls
+    #[default]
+    Walled,
+    /// No walls: a ball leaving the top or bottom is a fault by whoever
+    /// touched it last
+    Open,
+}
+
+/// Per-player adjustments to even out mismatched players
+#[derive(Debug, Clone, Copy, PartialEq, Reflect, Serialize, Deserialize)]
+pub struct Handicap {
+    /// Points the player starts the match with
+    pub score_offset: u32,
+    /// Scales the paddle's maximum speed
+    pub speed_multiplier: f32,
+    /// Scales the paddle's height
+    pub size_multiplier: f32,
+}
+
+impl Default for Handicap {
+    fn default() -> Self {
+        Self {
+            score_offset: 0,
+            speed_multiplier: 1.0,
+            size_multiplier: 1.0,
+        }
+    }
+}
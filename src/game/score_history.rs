@@ -0,0 +1,230 @@
+//! A timestamped log of every point in the match, and the momentum timeline
+//! drawn from it.
+//!
+//! The timeline plots the lead after each point against the match clock,
+//! with a band behind each scoring streak. It's shown on the post-match
+//! summary and in the pause menu.
+
+use bevy::{ecs::spawn::SpawnWith, prelude::*, ui::Val::*};
+use serde::Serialize;
+
+use super::{
+    player::PlayerSide,
+    profile::PlayerProfiles,
+    scoring::{PointScored, Score},
+    undo::PointUndone,
+};
+use crate::{AppSystems, PausableSystems, screens::Screen};
+
+/// Points in a row by one player that count as a streak
+const STREAK_LENGTH: usize = 3;
+
+// Timeline layout
+const TIMELINE_WIDTH: f32 = 360.0;
+const TIMELINE_HEIGHT: f32 = 80.0;
+const TIMELINE_BACKGROUND: Color = Color::srgba(1.0, 1.0, 1.0, 0.05);
+const TIMELINE_AXIS_COLOR: Color = Color::srgba(1.0, 1.0, 1.0, 0.3);
+const POINT_SIZE: f32 = 8.0;
+const STREAK_ALPHA: f32 = 0.2;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<ScoreHistory>();
+    app.register_type::<ScoredPoint>();
+    app.init_resource::<ScoreHistory>();
+    app.add_systems(OnEnter(Screen::Gameplay), reset_history);
+    app.add_observer(record_point);
+    app.add_systems(
+        Update,
+        (
+            tick_match_clock
+                .in_set(AppSystems::TickTimers)
+                .in_set(PausableSystems),
+            forget_undone_points.in_set(AppSystems::Update),
+        )
+            .run_if(in_state(Screen::Gameplay)),
+    );
+}
+
+/// Every point scored this match, oldest first
+#[derive(Resource, Debug, Clone, Default, Reflect, Serialize)]
+#[reflect(Resource)]
+pub struct ScoreHistory {
+    /// Seconds of unpaused play since the match started
+    pub elapsed: f32,
+    pub points: Vec<ScoredPoint>,
+}
+
+/// One point on the board
+#[derive(Debug, Clone, Copy, Reflect, Serialize)]
+pub struct ScoredPoint {
+    /// Match clock when it was scored
+    pub time: f32,
+    pub side: PlayerSide,
+    /// The score just after it
+    pub left: u32,
+    pub right: u32,
+}
+
+impl ScoredPoint {
+    /// How far ahead the left player is
+    fn lead(&self) -> i32 {
+        self.left as i32 - self.right as i32
+    }
+}
+
+impl ScoreHistory {
+    /// Runs of [`STREAK_LENGTH`] or more points by the same player, as
+    /// `(scorer, first index, last index)`
+    pub fn streaks(&self) -> Vec<(PlayerSide, usize, usize)> {
+        let mut streaks = Vec::new();
+        let mut start = 0;
+        for end in 1..=self.points.len() {
+            let run_over =
+                end == self.points.len() || self.points[end].side != self.points[start].side;
+            if run_over {
+                if end - start >= STREAK_LENGTH {
+                    streaks.push((self.points[start].side, start, end - 1));
+                }
+                start = end;
+            }
+        }
+        streaks
+    }
+}
+
+fn reset_history(mut history: ResMut<ScoreHistory>) {
+    *history = ScoreHistory::default();
+}
+
+fn tick_match_clock(time: Res<Time>, mut history: ResMut<ScoreHistory>) {
+    history.elapsed += time.delta_secs();
+}
+
+/// The score is already updated by the time the point is announced
+fn record_point(
+    trigger: Trigger<PointScored>,
+    score: Res<Score>,
+    mut history: ResMut<ScoreHistory>,
+) {
+    let point = ScoredPoint {
+        time: history.elapsed,
+        side: trigger.event().side,
+        left: score.left,
+        right: score.right,
+    };
+    history.points.push(point);
+}
+
+/// Drops points taken back with an undo
+fn forget_undone_points(
+    mut undone: EventReader<PointUndone>,
+    score: Res<Score>,
+    mut history: ResMut<ScoreHistory>,
+) {
+    if undone.read().count() == 0 {
+        return;
+    }
+    history
+        .points
+        .retain(|point| point.left <= score.left && point.right <= score.right);
+}
+
+/// The momentum timeline: one dot per point at the lead it left, left
+/// player's lead upward, over bands marking each streak
+pub fn timeline(history: &ScoreHistory, profiles: &PlayerProfiles) -> impl Bundle {
+    let duration = history
+        .points
+        .last()
+        .map_or(0.0, |point| point.time)
+        .max(history.elapsed)
+        .max(f32::EPSILON);
+    let biggest_lead = history
+        .points
+        .iter()
+        .map(|point| point.lead().unsigned_abs())
+        .max()
+        .unwrap_or(0)
+        .max(1);
+    // Percentages across and down the timeline
+    let x = move |time: f32| time / duration * 100.0;
+    let y = move |lead: i32| 50.0 - lead as f32 / biggest_lead as f32 * 50.0;
+
+    let bands: Vec<_> = history
+        .streaks()
+        .into_iter()
+        .map(|(side, first, last)| {
+            // From the point before the streak started to its last point
+            let from = first
+                .checked_sub(1)
+                .map_or(0.0, |before| history.points[before].time);
+            (
+                x(from),
+                x(history.points[last].time),
+                profiles.color(side).with_alpha(STREAK_ALPHA),
+            )
+        })
+        .collect();
+    let dots: Vec<_> = history
+        .points
+        .iter()
+        .map(|point| (x(point.time), y(point.lead()), profiles.color(point.side)))
+        .collect();
+
+    (
+        Name::new("Momentum Timeline"),
+        Node {
+            width: Px(TIMELINE_WIDTH),
+            height: Px(TIMELINE_HEIGHT),
+            ..default()
+        },
+        BackgroundColor(TIMELINE_BACKGROUND),
+        Pickable::IGNORE,
+        Children::spawn(SpawnWith(move |parent: &mut ChildSpawner| {
+            for (from, to, color) in bands {
+                parent.spawn((
+                    Name::new("Streak Band"),
+                    Node {
+                        position_type: PositionType::Absolute,
+                        left: Percent(from),
+                        width: Percent(to - from),
+                        height: Percent(100.0),
+                        ..default()
+                    },
+                    BackgroundColor(color),
+                ));
+            }
+            parent.spawn((
+                Name::new("Level Line"),
+                Node {
+                    position_type: PositionType::Absolute,
+                    top: Percent(50.0),
+                    width: Percent(100.0),
+                    height: Px(1.0),
+                    ..default()
+                },
+                BackgroundColor(TIMELINE_AXIS_COLOR),
+            ));
+            for (left, top, color) in dots {
+                parent.spawn((
+                    Name::new("Point"),
+                    Node {
+                        position_type: PositionType::Absolute,
+                        left: Percent(left),
+                        top: Percent(top),
+                        width: Px(POINT_SIZE),
+                        height: Px(POINT_SIZE),
+                        margin: UiRect::new(
+                            Px(-POINT_SIZE / 2.0),
+                            Px(0.0),
+                            Px(-POINT_SIZE / 2.0),
+                            Px(0.0),
+                        ),
+                        ..default()
+                    },
+                    BorderRadius::MAX,
+                    BackgroundColor(color),
+                ));
+            }
+        })),
+    )
+}
@@ -4,20 +4,42 @@ use bevy::prelude::*;
 
 use super::{
     GamePhase,
-    ball::{Ball, ServeDirection, spawn_ball},
+    ball::{Ball, ServeDirection, ServedBy, spawn_ball},
+    chaos::reserve_ball,
+    config::GameConfig,
+    events::GameEvent,
+    goal::GoalCall,
+    hud::InCourtFrame,
+    mutators::{BANK_SHOT_POINTS, Banked, Mutator, MutatorTweaks, Mutators},
     player::PlayerSide,
+    profile::PlayerProfiles,
+    rules::MatchRules,
+    stats::MatchStats,
 };
-use crate::screens::Screen;
+use crate::{
+    screens::Screen,
+    theme::tween::{Pulse, Toast, TweenScale},
+};
+
+#[cfg(feature = "dev")]
+use super::debug::console::{ConsoleResult, RegisterConsoleCommand};
 
 // Scoring configuration
-const MAX_SCORE: u32 = 11; // First to 11 wins
-const MERCY_SCORE: u32 = 7; // Mercy rule at 7-0
 const SCORE_UI_FONT_SIZE: f32 = 48.0;
-const SCORE_UI_Y_OFFSET: f32 = 50.0; // Distance from top (moved above court)
-const SCORE_UI_X_OFFSET: f32 = 100.0; // Distance from center
-
-// Goal scored pause duration
-const GOAL_PAUSE_DURATION: f32 = 1.0; // 1 second pause after goal
+/// Distance from the top of the court, as a percentage of its height
+const SCORE_UI_TOP: f32 = 4.0;
+const SCORE_PUNCH_SCALE: f32 = 1.6;
+const SCORE_PUNCH_DURATION: f32 = 0.5; // seconds
+const SCORE_PULSE_COLOR: Color = Color::WHITE;
+const SCORE_PULSE_DURATION: f32 = 0.6; // seconds
+
+// Fault and own goal banners
+const FAULT_FONT_SIZE: f32 = 36.0;
+/// Distance from the top of the court, as a percentage of its height
+const FAULT_TOP: f32 = 25.0;
+const FAULT_COLOR: Color = Color::srgb(1.0, 0.6, 0.2);
+const OWN_GOAL_COLOR: Color = Color::srgb(1.0, 0.35, 0.6);
+const BANK_SHOT_COLOR: Color = Color::srgb(0.4, 0.85, 1.0);
 
 pub(super) fn plugin(app: &mut App) {
     app.register_type::<Score>()
@@ -26,23 +48,54 @@ pub(super) fn plugin(app: &mut App) {
         .init_resource::<Score>()
         .init_resource::<GoalTimer>()
         .add_event::<GoalScored>()
-        .add_systems(OnEnter(Screen::Gameplay), setup_score_ui)
+        .add_event::<PointScored>()
+        .add_systems(
+            OnEnter(Screen::Gameplay),
+            (apply_starting_score, setup_score_ui),
+        )
         .add_systems(
             Update,
             (
                 update_score_display.run_if(in_state(Screen::Gameplay)),
                 handle_goal_pause.run_if(in_state(GamePhase::GoalScored)),
-                handle_game_over_input.run_if(in_state(GamePhase::GameOver)),
             ),
         )
-        .add_systems(OnEnter(GamePhase::GameOver), setup_game_over_screen)
         .add_observer(handle_goal_and_check_win);
+
+    #[cfg(feature = "dev")]
+    app.register_console_command("score", "score set <left> <right>", score_command);
 }
 
 /// Event triggered when a goal is scored
 #[derive(Event, Reflect)]
 pub struct GoalScored {
     pub side: PlayerSide,
+    /// The point came from the opponent hitting the ball out of bounds
+    pub fault: bool,
+    /// The ball went in off the conceding player's own paddle
+    pub own_goal: bool,
+    /// The ball that scored
+    pub ball: Entity,
+}
+
+impl GoalScored {
+    /// The goal call this event announces
+    pub fn call(&self) -> GoalCall {
+        GoalCall {
+            side: self.side,
+            fault: self.fault,
+            own_goal: self.own_goal,
+        }
+    }
+}
+
+/// Event triggered when a goal actually puts points on the board
+#[derive(Event, Reflect)]
+pub struct PointScored {
+    pub side: PlayerSide,
+    pub points: u32,
+    /// The ball that scored
+    pub ball: Entity,
 }
 
 /// Tracks the current game score
@@ -54,29 +107,64 @@ pub struct Score {
 }
 
 impl Score {
-    /// Returns true if either player has won (normal or mercy)
-    pub fn has_winner(&self) -> bool {
-        // Normal win: first to 11
-        if self.left >= MAX_SCORE || self.right >= MAX_SCORE {
-            return true;
+    pub fn get(&self, side: PlayerSide) -> u32 {
+        match side {
+            PlayerSide::Left => self.left,
+            PlayerSide::Right => self.right,
         }
-
-        // Mercy win: 7-0
-        (self.left >= MERCY_SCORE && self.right == 0)
-            || (self.right >= MERCY_SCORE && self.left == 0)
     }
 
     /// Returns the winning side if there is one
-    pub fn winner(&self) -> Option<PlayerSide> {
-        // Check normal win
-        if self.left >= MAX_SCORE || (self.left >= MERCY_SCORE && self.right == 0) {
+    pub fn winner(&self, rules: &MatchRules) -> Option<PlayerSide> {
+        let wins = |ours: u32, theirs: u32| {
+            // Normal win: first to the target, by two if the rules ask for it
+            let normal = ours >= rules.target_score && (!rules.win_by_two || ours >= theirs + 2);
+            // Mercy win: e.g. 7-0
+            let mercy = ours >= rules.mercy_score && theirs == 0;
+            normal || mercy
+        };
+
+        if wins(self.left, self.right) {
             Some(PlayerSide::Left)
-        } else if self.right >= MAX_SCORE || (self.right >= MERCY_SCORE && self.left == 0) {
+        } else if wins(self.right, self.left) {
             Some(PlayerSide::Right)
         } else {
             None
         }
     }
+
+    /// Returns true if the match was won on the mercy rule
+    pub fn is_mercy(&self, rules: &MatchRules) -> bool {
+        (self.left >= rules.mercy_score && self.right == 0)
+            || (self.right >= rules.mercy_score && self.left == 0)
+    }
+
+    /// Returns true if the match has gone to deuce (tied late when winning by two)
+    pub fn is_deuce(&self, rules: &MatchRules) -> bool {
+        let tied_at = rules.target_score.saturating_sub(1);
+        rules.win_by_two && self.left >= tied_at && self.right >= tied_at
+    }
+
+    /// Returns true if one more point for `side` would win the match
+    pub fn is_match_point(&self, side: PlayerSide, rules: &MatchRules) -> bool {
+        let next = match side {
+            PlayerSide::Left => Score {
+                left: self.left + 1,
+                right: self.right,
+            },
+            PlayerSide::Right => Score {
+                left: self.left,
+                right: self.right + 1,
+            },
+        };
+        next.winner(rules) == Some(side)
+    }
+
+    /// Returns true if the next goal decides the match (e.g. 10-10)
+    pub fn is_sudden_death(&self, rules: &MatchRules) -> bool {
+        let tied_at = rules.target_score.saturating_sub(1);
+        rules.sudden_death && !rules.win_by_two && self.left == tied_at && self.right == tied_at
+    }
 }
 
 /// Marker for score display UI elements
@@ -87,6 +175,15 @@ pub enum ScoreDisplay {
     Right,
 }
 
+impl ScoreDisplay {
+    pub fn side(&self) -> PlayerSide {
+        match self {
+            ScoreDisplay::Left => PlayerSide::Left,
+            ScoreDisplay::Right => PlayerSide::Right,
+        }
+    }
+}
+
 /// Timer for goal scored pause
 #[derive(Resource, Default, Reflect)]
 #[reflect(Resource)]
@@ -94,8 +191,14 @@ pub struct GoalTimer {
     pub timer: Timer,
 }
 
+/// Starts each match from the players' handicap score offsets
+fn apply_starting_score(mut score: ResMut<Score>, rules: Res<MatchRules>) {
+    score.left = rules.handicap(PlayerSide::Left).score_offset;
+    score.right = rules.handicap(PlayerSide::Right).score_offset;
+}
+
 /// Sets up the score UI
-fn setup_score_ui(mut commands: Commands) {
+fn setup_score_ui(mut commands: Commands, profiles: Res<PlayerProfiles>) {
     // Left player score
     commands.spawn((
         Name::new("Left Score"),
@@ -105,13 +208,17 @@ fn setup_score_ui(mut commands: Commands) {
             font_size: SCORE_UI_FONT_SIZE,
             ..default()
         },
+        TextColor(profiles.color(PlayerSide::Left)),
+        TextLayout::new_with_justify(JustifyText::Center),
+        // Centred over the left half of the court
         Node {
             position_type: PositionType::Absolute,
-            left: Val::Percent(50.0),
-            top: Val::Px(SCORE_UI_Y_OFFSET),
-            margin: UiRect::left(Val::Px(-SCORE_UI_X_OFFSET)),
+            left: Val::Percent(0.0),
+            width: Val::Percent(50.0),
+            top: Val::Percent(SCORE_UI_TOP),
             ..default()
         },
+        InCourtFrame,
         StateScoped(Screen::Gameplay),
     ));
 
@@ -124,28 +231,44 @@ fn setup_score_ui(mut commands: Commands) {
             font_size: SCORE_UI_FONT_SIZE,
             ..default()
         },
+        TextColor(profiles.color(PlayerSide::Right)),
+        TextLayout::new_with_justify(JustifyText::Center),
+        // Centred over the right half of the court
         Node {
             position_type: PositionType::Absolute,
             left: Val::Percent(50.0),
-            top: Val::Px(SCORE_UI_Y_OFFSET),
-            margin: UiRect::left(Val::Px(SCORE_UI_X_OFFSET)),
+            width: Val::Percent(50.0),
+            top: Val::Percent(SCORE_UI_TOP),
             ..default()
         },
+        InCourtFrame,
         StateScoped(Screen::Gameplay),
     ));
 }
 
-/// Updates the score display UI
-fn update_score_display(score: Res<Score>, mut query: Query<(&mut Text, &ScoreDisplay)>) {
+/// Updates the score display UI, punching and flashing any score that changed
+fn update_score_display(
+    mut commands: Commands,
+    score: Res<Score>,
+    profiles: Res<PlayerProfiles>,
+    mut query: Query<(Entity, &mut Text, &ScoreDisplay)>,
+) {
     if !score.is_changed() {
         return;
     }
 
-    for (mut text, display) in &mut query {
-        match display {
-            ScoreDisplay::Left => text.0 = score.left.to_string(),
-            ScoreDisplay::Right => text.0 = score.right.to_string(),
+    for (entity, mut text, display) in &mut query {
+        let value = score.get(display.side()).to_string();
+        if text.0 == value {
+            continue;
         }
+        text.0 = value;
+
+        let rest = profiles.color(display.side());
+        commands.entity(entity).insert((
+            Pulse::new(SCORE_PULSE_COLOR, rest, SCORE_PULSE_DURATION),
+            TweenScale::punch(SCORE_PUNCH_SCALE, SCORE_PUNCH_DURATION),
+        ));
     }
 }
 
@@ -154,25 +277,92 @@ fn handle_goal_and_check_win(
     trigger: Trigger<GoalScored>,
     mut commands: Commands,
     mut score: ResMut<Score>,
+    mut stats: ResMut<MatchStats>,
+    rules: Res<MatchRules>,
+    config: Res<GameConfig>,
+    mutators: Res<Mutators>,
+    tweaks: Res<MutatorTweaks>,
     balls: Query<Entity, With<Ball>>,
+    served_by: Query<&ServedBy>,
+    banked: Query<(), With<Banked>>,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<ColorMaterial>>,
     mut serve_direction: ResMut<ServeDirection>,
     mut game_phase: ResMut<NextState<GamePhase>>,
+    mut game_events: EventWriter<GameEvent>,
 ) {
     let goal_event = trigger.event();
 
+    // With two balls, a second goal can land in the same frame as the winner
+    if score.winner(&rules).is_some() {
+        return;
+    }
+
+    // Own goals are replayed, with the same server, unless the rules count them
+    let call = goal_event.call();
+    let replayed = call.replayed(&rules);
+
+    // With side-out scoring only the server can score; the receiver winning
+    // the rally just takes the serve
+    let server = served_by
+        .get(goal_event.ball)
+        .map_or(serve_direction.side, |served_by| served_by.0);
+    let point_awarded = call.awards_point(&rules, server);
+
+    // Banked in off a wall after a return
+    let bank_shot = point_awarded
+        && mutators.is_active(Mutator::BankShots)
+        && !goal_event.fault
+        && !goal_event.own_goal
+        && banked.contains(goal_event.ball);
+    let points = if bank_shot { BANK_SHOT_POINTS } else { 1 } + tweaks.score_bonus;
+    if point_awarded {
+        commands.trigger(PointScored {
+            side: goal_event.side,
+            points,
+            ball: goal_event.ball,
+        });
+    }
+
+    if goal_event.own_goal && !replayed {
+        stats.get_mut(goal_event.side.opponent()).own_goals += 1;
+    }
+    if point_awarded && score.is_match_point(goal_event.side.opponent(), &rules) {
+        stats.get_mut(goal_event.side).clutch_points += 1;
+    }
+
+    if point_awarded {
+        game_events.write(GameEvent::Goal {
+            side: goal_event.side,
+            points,
+            own_goal: goal_event.own_goal,
+        });
+    }
+
     // Update score based on which side scored
     match goal_event.side {
+        _ if replayed => {
+            game_events.write(GameEvent::PointReplayed);
+            info!(
+                "Own goal by {:?} player, replaying the point",
+                goal_event.side.opponent()
+            );
+        }
+        _ if !point_awarded => {
+            game_events.write(GameEvent::SideOut {
+                side: goal_event.side,
+            });
+            info!("Side out! {:?} player wins the serve", goal_event.side);
+        }
         PlayerSide::Left => {
-            score.left += 1;
+            score.left += points;
             info!(
                 "Left player scores! Score: {} - {}",
                 score.left, score.right
             );
         }
         PlayerSide::Right => {
-            score.right += 1;
+            score.right += points;
             info!(
                 "Right player scores! Score: {} - {}",
                 score.left, score.right
@@ -180,17 +370,14 @@ fn handle_goal_and_check_win(
         }
     }
 
-    // Despawn all balls
-    for ball_entity in &balls {
-        commands.entity(ball_entity).despawn();
-    }
-
     // Check win condition after updating score
-    if score.has_winner() {
-        let winner = score.winner().unwrap();
-        let win_type = if (score.left >= MERCY_SCORE && score.right == 0)
-            || (score.right >= MERCY_SCORE && score.left == 0)
-        {
+    if let Some(winner) = score.winner(&rules) {
+        // Despawn all balls
+        for ball_entity in &balls {
+            commands.entity(ball_entity).despawn();
+        }
+
+        let win_type = if score.is_mercy(&rules) {
             "Mercy win"
         } else {
             "Game win"
@@ -205,25 +392,88 @@ fn handle_goal_and_check_win(
         game_phase.set(GamePhase::GameOver);
     } else {
         // Game continues - set up next serve
-        // The player who was scored on gets to serve
-        serve_direction.side = match goal_event.side {
-            PlayerSide::Left => PlayerSide::Right, // Left scored, so right serves
-            PlayerSide::Right => PlayerSide::Left, // Right scored, so left serves
-        };
+        if !replayed {
+            let deuce = score.is_deuce(&rules);
+            serve_direction.rotate(rules.effective_serve_rotation(), goal_event.side, deuce);
+        }
+
+        // In two-ball chaos only the scoring ball is replaced and play goes on
+        if rules.two_balls {
+            commands.entity(goal_event.ball).despawn();
+            reserve_ball(
+                &mut commands,
+                &mut meshes,
+                &mut materials,
+                serve_direction.side,
+            );
+            return;
+        }
+
+        for ball_entity in &balls {
+            commands.entity(ball_entity).despawn();
+        }
 
         // Spawn new ball (without serving)
         spawn_ball(&mut commands, &mut meshes, &mut materials);
 
+        let conceded_by = goal_event.side.opponent();
+        if goal_event.own_goal {
+            let text = if replayed {
+                "OWN GOAL - point replayed".to_string()
+            } else {
+                format!("OWN GOAL - {conceded_by:?} player")
+            };
+            spawn_goal_banner(&mut commands, &config, text, OWN_GOAL_COLOR);
+        } else if goal_event.fault {
+            spawn_goal_banner(
+                &mut commands,
+                &config,
+                format!("FAULT - {conceded_by:?} player out of bounds"),
+                FAULT_COLOR,
+            );
+        } else if bank_shot {
+            spawn_goal_banner(
+                &mut commands,
+                &config,
+                format!("BANK SHOT - {BANK_SHOT_POINTS} points"),
+                BANK_SHOT_COLOR,
+            );
+        }
+
         // Transition to goal scored state
         game_phase.set(GamePhase::GoalScored);
 
         // Start the goal timer
         commands.insert_resource(GoalTimer {
-            timer: Timer::from_seconds(GOAL_PAUSE_DURATION, TimerMode::Once),
+            timer: Timer::from_seconds(config.scoring.goal_pause, TimerMode::Once),
         });
     }
 }
 
+/// Calls a fault, own goal or bank shot during the pause after the point
+fn spawn_goal_banner(commands: &mut Commands, config: &GameConfig, text: String, color: Color) {
+    commands.spawn((
+        Name::new("Goal Banner"),
+        Text::new(text),
+        TextFont {
+            font_size: FAULT_FONT_SIZE,
+            ..default()
+        },
+        TextColor(color),
+        TextLayout::new_with_justify(JustifyText::Center),
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            top: Val::Percent(FAULT_TOP),
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        InCourtFrame,
+        Toast::new(config.scoring.goal_pause),
+        StateScoped(GamePhase::GoalScored),
+    ));
+}
+
 /// Handles the pause after a goal is scored
 fn handle_goal_pause(
     time: Res<Time>,
@@ -238,97 +488,24 @@ fn handle_goal_pause(
     }
 }
 
-/// Sets up the game over screen
-fn setup_game_over_screen(mut commands: Commands, score: Res<Score>) {
-    let winner = score.winner().expect("Game over without winner");
-    let win_type = if (score.left >= MERCY_SCORE && score.right == 0)
-        || (score.right >= MERCY_SCORE && score.left == 0)
-    {
-        "MERCY WIN!"
-    } else {
-        "VICTORY!"
+/// Console command for overriding the score
+#[cfg(feature = "dev")]
+fn score_command(In(args): In<Vec<String>>, mut score: ResMut<Score>) -> ConsoleResult {
+    let [action, left, right] = args.as_slice() else {
+        return Err("usage: score set <left> <right>".into());
     };
+    if action != "set" {
+        return Err(format!("unknown action '{action}'"));
+    }
 
-    // Game over overlay
-    commands
-        .spawn((
-            Name::new("Game Over Overlay"),
-            Node {
-                width: Val::Percent(100.0),
-                height: Val::Percent(100.0),
-                justify_content: JustifyContent::Center,
-                align_items: AlignItems::Center,
-                flex_direction: FlexDirection::Column,
-                row_gap: Val::Px(20.0),
-                ..default()
-            },
-            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.8)),
-            StateScoped(GamePhase::GameOver),
-        ))
-        .with_children(|parent| {
-            // Win type text
-            parent.spawn((
-                Text::new(win_type),
-                TextFont {
-                    font_size: 72.0,
-                    ..default()
-                },
-                TextColor(Color::WHITE),
-            ));
-
-            // Winner text
-            parent.spawn((
-                Text::new(format!(
-                    "{} Player Wins!",
-                    match winner {
-                        PlayerSide::Left => "Left",
-                        PlayerSide::Right => "Right",
-                    }
-                )),
-                TextFont {
-                    font_size: 48.0,
-                    ..default()
-                },
-                TextColor(Color::WHITE),
-            ));
-
-            // Final score
-            parent.spawn((
-                Text::new(format!("Final Score: {} - {}", score.left, score.right)),
-                TextFont {
-                    font_size: 36.0,
-                    ..default()
-                },
-                TextColor(Color::WHITE),
-            ));
-
-            // Instructions
-            parent.spawn((
-                Text::new("Press SPACE to play again or ESC for menu"),
-                TextFont {
-                    font_size: 24.0,
-                    ..default()
-                },
-                TextColor(Color::srgb(0.7, 0.7, 0.7)),
-            ));
-        });
-}
+    let left = left
+        .parse()
+        .map_err(|_| format!("invalid score '{left}'"))?;
+    let right = right
+        .parse()
+        .map_err(|_| format!("invalid score '{right}'"))?;
+    score.left = left;
+    score.right = right;
 
-/// Handles input on the game over screen
-fn handle_game_over_input(
-    keyboard: Res<ButtonInput<KeyCode>>,
-    mut next_screen: ResMut<NextState<Screen>>,
-    mut score: ResMut<Score>,
-) {
-    if keyboard.just_pressed(KeyCode::Space) {
-        // Reset score and play again
-        score.left = 0;
-        score.right = 0;
-        next_screen.set(Screen::Gameplay);
-    } else if keyboard.just_pressed(KeyCode::Escape) {
-        // Return to title screen
-        score.left = 0;
-        score.right = 0;
-        next_screen.set(Screen::Title);
-    }
+    Ok(format!("Score set to {left} - {right}"))
 }
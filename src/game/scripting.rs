@@ -0,0 +1,322 @@
+//! Scripted mutators: community game variants written in Rhai, without
+//! touching the game's code.
+//!
+//! Every `*.mutator.rhai` file in `assets/mutators/` shows up on the
+//! mutators menu, off by default. An enabled script can define either of
+//! these functions, which run with `this` bound to the match's
+//! [`MutatorTweaks`] (`ball_speed`, `paddle_scale` and `score_bonus`):
+//!
+//! ```rhai
+//! // Before the paddles spawn
+//! fn on_match_start() {
+//!     this.paddle_scale = 0.8;
+//! }
+//!
+//! // For every game event, e.g. #{ kind: "paddle_hit", tick: 120, side: "Left" }
+//! fn on_event(event) {
+//!     if event.kind == "paddle_hit" {
+//!         this.ball_speed *= 1.05;
+//!     }
+//! }
+//! ```
+//!
+//! Events have the same fields as in exported match telemetry. Tweaks are
+//! clamped to sane ranges, and `paddle_scale` can only change before the
+//! paddles spawn. A script that errors or runs too long is switched off.
+//!
+//! Scripts are native-only, like the replay telemetry their events come from.
+
+use avian2d::prelude::*;
+use bevy::{
+    asset::{AssetLoader, LoadContext, LoadedFolder, io::Reader},
+    prelude::*,
+};
+use rhai::{AST, CallFnOptions, Dynamic, Engine, FLOAT, INT, Map, Scope};
+
+use super::{ball::Ball, events::GameEvent, level::spawn_level, mutators::MutatorTweaks};
+use crate::{
+    AppSystems,
+    replay::{ReplayClock, telemetry::TelemetryEvent},
+    screens::Screen,
+};
+
+const SCRIPT_FOLDER: &str = "mutators";
+/// Operations a script may run per call before it's stopped
+const MAX_OPERATIONS: u64 = 10_000;
+const MAX_CALL_LEVELS: usize = 16;
+
+const BALL_SPEED_RANGE: (f32, f32) = (0.25, 3.0);
+const PADDLE_SCALE_RANGE: (f32, f32) = (0.25, 2.0);
+const MAX_SCORE_BONUS: u32 = 5;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_asset::<MutatorScript>();
+    app.init_asset_loader::<MutatorScriptLoader>();
+    app.init_resource::<ScriptedMutators>();
+
+    app.add_systems(Update, compile_scripts);
+    app.add_systems(OnEnter(Screen::Gameplay), start_match.before(spawn_level));
+    app.add_systems(
+        Update,
+        run_scripts
+            .in_set(AppSystems::Update)
+            .run_if(in_state(Screen::Gameplay)),
+    );
+}
+
+/// The source of a scripted mutator
+#[derive(Asset, TypePath, Debug)]
+pub struct MutatorScript {
+    source: String,
+}
+
+#[derive(Default)]
+struct MutatorScriptLoader;
+
+impl AssetLoader for MutatorScriptLoader {
+    type Asset = MutatorScript;
+    type Settings = ();
+    type Error = Box<dyn std::error::Error + Send + Sync>;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<MutatorScript, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        Ok(MutatorScript {
+            source: String::from_utf8(bytes)?,
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["mutator.rhai"]
+    }
+}
+
+/// Every scripted mutator found, compiled and ready to run
+#[derive(Resource)]
+pub struct ScriptedMutators {
+    engine: Engine,
+    /// Keeps the scripts loaded and watched
+    _folder: Handle<LoadedFolder>,
+    scripts: Vec<ScriptedMutator>,
+}
+
+struct ScriptedMutator {
+    name: String,
+    enabled: bool,
+    id: AssetId<MutatorScript>,
+    ast: AST,
+}
+
+impl FromWorld for ScriptedMutators {
+    fn from_world(world: &mut World) -> Self {
+        let mut engine = Engine::new();
+        engine.set_max_operations(MAX_OPERATIONS);
+        engine.set_max_call_levels(MAX_CALL_LEVELS);
+        Self {
+            engine,
+            _folder: world.resource::<AssetServer>().load_folder(SCRIPT_FOLDER),
+            scripts: Vec::new(),
+        }
+    }
+}
+
+impl ScriptedMutators {
+    /// Names of every script, in the order they were found
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.scripts.iter().map(|script| script.name.as_str())
+    }
+
+    pub fn is_enabled(&self, name: &str) -> bool {
+        self.scripts
+            .iter()
+            .any(|script| script.name == name && script.enabled)
+    }
+
+    pub fn toggle(&mut self, name: &str) {
+        if let Some(script) = self.scripts.iter_mut().find(|script| script.name == name) {
+            script.enabled = !script.enabled;
+        }
+    }
+
+    /// Calls `function` on every enabled script that defines it, letting it
+    /// adjust `tweaks`
+    fn call(
+        &mut self,
+        function: &str,
+        args: impl Fn() -> Vec<Dynamic>,
+        tweaks: &mut MutatorTweaks,
+    ) {
+        for script in &mut self.scripts {
+            let defined = script
+                .ast
+                .iter_functions()
+                .any(|defined| defined.name == function);
+            if !script.enabled || !defined {
+                continue;
+            }
+
+            let mut this = tweaks_map(tweaks);
+            let options = CallFnOptions::new()
+                .eval_ast(false)
+                .bind_this_ptr(&mut this);
+            let result = self.engine.call_fn_with_options::<Dynamic>(
+                options,
+                &mut Scope::new(),
+                &script.ast,
+                function,
+                args(),
+            );
+            if let Err(error) = result {
+                warn!(
+                    "Mutator \"{}\" failed and was switched off: {error}",
+                    script.name
+                );
+                script.enabled = false;
+                continue;
+            }
+            if let Some(map) = this.try_cast::<Map>() {
+                read_tweaks(&map, tweaks);
+            }
+        }
+    }
+}
+
+/// A friendly name from the file name, e.g. `rally_rush.mutator.rhai` is
+/// "Rally Rush"
+fn script_name(path: &str) -> String {
+    let file = path.rsplit('/').next().unwrap_or(path);
+    let stem = file.split('.').next().unwrap_or(file);
+    stem.split(['_', '-'])
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            chars.next().map_or_else(String::new, |first| {
+                first.to_uppercase().chain(chars).collect()
+            })
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn tweaks_map(tweaks: &MutatorTweaks) -> Dynamic {
+    let mut map = Map::new();
+    map.insert(
+        "ball_speed".into(),
+        Dynamic::from_float(tweaks.ball_speed as FLOAT),
+    );
+    map.insert(
+        "paddle_scale".into(),
+        Dynamic::from_float(tweaks.paddle_scale as FLOAT),
+    );
+    map.insert(
+        "score_bonus".into(),
+        Dynamic::from_int(tweaks.score_bonus as INT),
+    );
+    Dynamic::from_map(map)
+}
+
+/// Copies back whatever the script changed, clamped to the allowed ranges
+fn read_tweaks(map: &Map, tweaks: &mut MutatorTweaks) {
+    let number = |key: &str| {
+        let value = map.get(key)?;
+        value
+            .as_float()
+            .ok()
+            .or_else(|| value.as_int().ok().map(|int| int as FLOAT))
+    };
+    if let Some(ball_speed) = number("ball_speed") {
+        tweaks.ball_speed = (ball_speed as f32).clamp(BALL_SPEED_RANGE.0, BALL_SPEED_RANGE.1);
+    }
+    if let Some(paddle_scale) = number("paddle_scale") {
+        tweaks.paddle_scale =
+            (paddle_scale as f32).clamp(PADDLE_SCALE_RANGE.0, PADDLE_SCALE_RANGE.1);
+    }
+    if let Some(score_bonus) = number("score_bonus") {
+        tweaks.score_bonus = (score_bonus.max(0.0) as u32).min(MAX_SCORE_BONUS);
+    }
+}
+
+/// Compiles scripts as they load, and again whenever one is edited
+fn compile_scripts(
+    mut events: EventReader<AssetEvent<MutatorScript>>,
+    sources: Res<Assets<MutatorScript>>,
+    asset_server: Res<AssetServer>,
+    mut mutators: ResMut<ScriptedMutators>,
+) {
+    for event in events.read() {
+        match *event {
+            AssetEvent::LoadedWithDependencies { id } | AssetEvent::Modified { id } => {
+                let Some(source) = sources.get(id) else {
+                    continue;
+                };
+                let name = asset_server.get_path(id).map_or_else(
+                    || "Unnamed".to_string(),
+                    |path| script_name(&path.to_string()),
+                );
+                let ast = match mutators.engine.compile(&source.source) {
+                    Ok(ast) => ast,
+                    Err(error) => {
+                        warn!("Couldn't compile mutator \"{name}\": {error}");
+                        continue;
+                    }
+                };
+                // An edited script keeps its place and whether it's on
+                match mutators.scripts.iter_mut().find(|script| script.id == id) {
+                    Some(script) => script.ast = ast,
+                    None => mutators.scripts.push(ScriptedMutator {
+                        name,
+                        enabled: false,
+                        id,
+                        ast,
+                    }),
+                }
+            }
+            AssetEvent::Removed { id } => mutators.scripts.retain(|script| script.id != id),
+            _ => {}
+        }
+    }
+}
+
+/// Starts every match from untouched tweaks, then lets scripts set it up
+fn start_match(mut mutators: ResMut<ScriptedMutators>, mut tweaks: ResMut<MutatorTweaks>) {
+    *tweaks = MutatorTweaks::default();
+    mutators.call("on_match_start", Vec::new, &mut tweaks);
+}
+
+/// Hands each game event to the scripts, and brings balls already in play up
+/// to any new speed
+fn run_scripts(
+    mut events: EventReader<GameEvent>,
+    clock: Res<ReplayClock>,
+    mut mutators: ResMut<ScriptedMutators>,
+    mut tweaks: ResMut<MutatorTweaks>,
+    mut balls: Query<&mut LinearVelocity, With<Ball>>,
+) {
+    let before = *tweaks;
+    let mut updated = before;
+    for event in events.read() {
+        let event = TelemetryEvent::new(clock.tick, event);
+        let Ok(event) = rhai::serde::to_dynamic(&event) else {
+            continue;
+        };
+        mutators.call("on_event", || vec![event.clone()], &mut updated);
+    }
+    // The paddles are already out
+    updated.paddle_scale = before.paddle_scale;
+    if updated == before {
+        return;
+    }
+
+    if updated.ball_speed != before.ball_speed {
+        let scale = updated.ball_speed / before.ball_speed;
+        for mut velocity in &mut balls {
+            velocity.0 *= scale;
+        }
+    }
+    *tweaks = updated;
+}
@@ -0,0 +1,188 @@
+//! The serve clock: with [`MatchRules::serve_clock`] set, the server has that
+//! long to serve or pays the [`ServeClockPenalty`]. A countdown shows below
+//! the score, and the announcer warns as it runs out.
+
+use bevy::{prelude::*, ui::Val::*};
+
+use super::{
+    GamePhase,
+    ball::{Ball, ServeDirection},
+    events::GameEvent,
+    floating_text::floating_text,
+    hud::InCourtFrame,
+    rules::{MatchRules, ServeClockPenalty},
+    scoring::GoalScored,
+};
+use crate::{
+    AppSystems, PausableSystems, asset_tracking::LoadResource, audio::announcer_line,
+    screens::Screen,
+};
+
+/// Seconds left when the announcer warns the server
+const WARNING_SECONDS: f32 = 3.0;
+
+const CLOCK_TOP: f32 = 14.0;
+const CLOCK_FONT_SIZE: f32 = 28.0;
+const CLOCK_COLOR: Color = Color::srgba(1.0, 1.0, 1.0, 0.8);
+const WARNING_COLOR: Color = Color::srgb(1.0, 0.4, 0.3);
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<ServeClock>();
+    app.init_resource::<ServeClock>();
+    app.register_type::<ServeClockDisplay>();
+    app.register_type::<ServeClockAssets>();
+    app.load_resource::<ServeClockAssets>();
+
+    app.add_systems(OnEnter(Screen::Gameplay), spawn_serve_clock_display);
+    app.add_systems(OnEnter(GamePhase::WaitingToServe), start_serve_clock);
+    app.add_systems(
+        Update,
+        (
+            tick_serve_clock
+                .in_set(AppSystems::TickTimers)
+                .run_if(in_state(GamePhase::WaitingToServe).and(serve_clock_on)),
+            update_serve_clock_display.in_set(AppSystems::Update),
+        )
+            .in_set(PausableSystems)
+            .run_if(in_state(Screen::Gameplay)),
+    );
+}
+
+#[derive(Resource, Asset, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct ServeClockAssets {
+    #[dependency]
+    warning: Handle<AudioSource>,
+}
+
+impl FromWorld for ServeClockAssets {
+    fn from_world(world: &mut World) -> Self {
+        let assets = world.resource::<AssetServer>();
+        Self {
+            warning: assets.load("audio/announcer/serve_clock.wav"),
+        }
+    }
+}
+
+/// Time left for the current serve
+#[derive(Resource, Debug, Default, Reflect)]
+#[reflect(Resource)]
+struct ServeClock {
+    timer: Timer,
+    warned: bool,
+}
+
+/// The countdown under the score
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct ServeClockDisplay;
+
+fn serve_clock_on(rules: Res<MatchRules>) -> bool {
+    rules.serve_clock > 0
+}
+
+fn start_serve_clock(mut clock: ResMut<ServeClock>, rules: Res<MatchRules>) {
+    *clock = ServeClock {
+        timer: Timer::from_seconds(rules.serve_clock as f32, TimerMode::Once),
+        warned: false,
+    };
+}
+
+fn tick_serve_clock(
+    mut commands: Commands,
+    time: Res<Time>,
+    rules: Res<MatchRules>,
+    assets: Option<Res<ServeClockAssets>>,
+    mut clock: ResMut<ServeClock>,
+    mut serve_direction: ResMut<ServeDirection>,
+    balls: Query<Entity, With<Ball>>,
+    mut game_events: EventWriter<GameEvent>,
+) {
+    clock.timer.tick(time.delta());
+    if !clock.warned && clock.timer.remaining_secs() <= WARNING_SECONDS {
+        clock.warned = true;
+        if let Some(assets) = assets {
+            commands.spawn(announcer_line(assets.warning.clone()));
+        }
+    }
+    if !clock.timer.just_finished() {
+        return;
+    }
+
+    let server = serve_direction.side;
+    game_events.write(GameEvent::ServeClockExpired {
+        server,
+        penalty: rules.serve_clock_penalty,
+    });
+    match rules.serve_clock_penalty {
+        ServeClockPenalty::LoseServe => {
+            serve_direction.side = server.opponent();
+            serve_direction.serves_in_turn = 0;
+            clock.timer.reset();
+            clock.warned = false;
+            commands.spawn(floating_text(
+                "TIME - SERVE LOST",
+                Vec2::ZERO,
+                WARNING_COLOR,
+            ));
+        }
+        // Scored like any other goal, as if the serve had gone out
+        ServeClockPenalty::LosePoint => {
+            let Some(ball) = balls.iter().next() else {
+                return;
+            };
+            commands.trigger(GoalScored {
+                side: server.opponent(),
+                fault: false,
+                own_goal: false,
+                ball,
+            });
+        }
+    }
+}
+
+fn spawn_serve_clock_display(mut commands: Commands) {
+    commands.spawn((
+        Name::new("Serve Clock"),
+        ServeClockDisplay,
+        Text::default(),
+        TextFont {
+            font_size: CLOCK_FONT_SIZE,
+            ..default()
+        },
+        TextColor(CLOCK_COLOR),
+        TextLayout::new_with_justify(JustifyText::Center),
+        Node {
+            position_type: PositionType::Absolute,
+            width: Percent(100.0),
+            top: Percent(CLOCK_TOP),
+            ..default()
+        },
+        Visibility::Hidden,
+        Pickable::IGNORE,
+        InCourtFrame,
+        StateScoped(Screen::Gameplay),
+    ));
+}
+
+/// Shows the seconds left while a serve is due, turning red near the end
+fn update_serve_clock_display(
+    clock: Res<ServeClock>,
+    rules: Res<MatchRules>,
+    phase: Res<State<GamePhase>>,
+    mut display: Single<(&mut Text, &mut TextColor, &mut Visibility), With<ServeClockDisplay>>,
+) {
+    let (text, color, visibility) = &mut *display;
+    if rules.serve_clock == 0 || *phase.get() != GamePhase::WaitingToServe {
+        **visibility = Visibility::Hidden;
+        return;
+    }
+    let remaining = clock.timer.remaining_secs();
+    **visibility = Visibility::Inherited;
+    text.0 = format!("{}", remaining.ceil() as u32);
+    color.0 = if remaining <= WARNING_SECONDS {
+        WARNING_COLOR
+    } else {
+        CLOCK_COLOR
+    };
+}
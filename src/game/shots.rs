@@ -0,0 +1,88 @@
+//! Alternate returns: the lob and the drop shot.
+//!
+//! Both are pressed through the input buffer like a smash, just before
+//! contact. A lob goes back slowly at a steep angle, bouncing off the walls
+//! toward a spot the opponent has to run for; a drop shot keeps its line but
+//! loses most of its pace. The ball carries the [`Shot`] until the next
+//! return, so CPUs and effects can tell what's coming.
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+
+use super::{
+    ball::Ball,
+    input_buffer::{BufferedAction, InputBuffer},
+    player::{MirroredCourt, PaddleHit},
+};
+
+// Lob
+const LOB_SPEED_MULTIPLIER: f32 = 0.75;
+const LOB_ANGLE: f32 = 60.0; // degrees from horizontal
+
+// Drop shot
+const DROP_SHOT_SPEED_MULTIPLIER: f32 = 0.45;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<Shot>();
+    app.add_observer(apply_shot);
+}
+
+/// A ball returned as something other than a plain hit
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub enum Shot {
+    Lob,
+    DropShot,
+}
+
+/// Turns a return into a lob or drop shot when the keyboard player pressed
+/// one just before contact; any other return is a plain hit again
+fn apply_shot(
+    trigger: Trigger<PaddleHit>,
+    mut commands: Commands,
+    time: Res<Time<Virtual>>,
+    mut buffer: ResMut<InputBuffer>,
+    mirrored: Res<MirroredCourt>,
+    mut balls: Query<&mut LinearVelocity, With<Ball>>,
+) {
+    let hit = trigger.event();
+    let Ok(mut velocity) = balls.get_mut(hit.ball) else {
+        return;
+    };
+
+    // Only one paddle takes keyboard input for now
+    let now = time.elapsed_secs();
+    let shot = if hit.side != mirrored.home_side() {
+        None
+    } else if buffer.consume(BufferedAction::Lob, now) {
+        Some(Shot::Lob)
+    } else if buffer.consume(BufferedAction::DropShot, now) {
+        Some(Shot::DropShot)
+    } else {
+        None
+    };
+
+    let Some(shot) = shot else {
+        commands.entity(hit.ball).remove::<Shot>();
+        return;
+    };
+    match shot {
+        Shot::Lob => {
+            // Up the way it was already heading, or away from the nearer wall
+            let vertical = if velocity.y != 0.0 {
+                velocity.y.signum()
+            } else {
+                -hit.contact_point.y.signum()
+            };
+            let angle = LOB_ANGLE.to_radians();
+            let direction = Vec2::new(angle.cos() * velocity.x.signum(), angle.sin() * vertical);
+            velocity.0 = direction * velocity.length() * LOB_SPEED_MULTIPLIER;
+            info!("Lob!");
+        }
+        Shot::DropShot => {
+            velocity.0 *= DROP_SHOT_SPEED_MULTIPLIER;
+            info!("Drop shot!");
+        }
+    }
+    commands.entity(hit.ball).insert(shot);
+}
@@ -0,0 +1,202 @@
+//! Capturing and restoring the gameplay state.
+//!
+//! A [`GameSnapshot`] holds everything that decides how a match plays on from a
+//! given moment: the phase, score, serve state and the motion of every ball and
+//! paddle. Restoring one puts the match back exactly there, which is the
+//! building block for rollback, replay seeking and undoing points.
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+
+use super::{
+    GamePhase,
+    ball::{Ball, Rally, ServeDirection, ServeRng, spawn_ball},
+    player::{PaddleIntent, Player, PlayerSide},
+    scoring::{GoalTimer, Score},
+};
+
+#[cfg(feature = "dev")]
+use super::debug::console::{ConsoleResult, RegisterConsoleCommand};
+
+pub(super) fn plugin(app: &mut App) {
+    #[cfg(feature = "dev")]
+    app.register_console_command("snapshot", "snapshot save | load", snapshot_command);
+}
+
+/// The state of a match at one moment
+#[derive(Clone, Debug)]
+pub struct GameSnapshot {
+    phase: GamePhase,
+    score: [u32; 2],
+    goal_timer: Timer,
+    server: PlayerSide,
+    serves_in_turn: u32,
+    rally_hits: u32,
+    last_hit: Option<PlayerSide>,
+    serve_seed: u64,
+    serves: u32,
+    balls: Vec<BodyState>,
+    paddles: Vec<PaddleState>,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct BodyState {
+    position: Vec2,
+    velocity: Vec2,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct PaddleState {
+    side: PlayerSide,
+    body: BodyState,
+    intent: f32,
+}
+
+impl GameSnapshot {
+    /// Captures the current match, or `None` outside of gameplay
+    pub fn capture(world: &mut World) -> Option<Self> {
+        let phase = *world.get_resource::<State<GamePhase>>()?.get();
+        let score = world.resource::<Score>();
+        let score = [score.left, score.right];
+        let goal_timer = world.resource::<GoalTimer>().timer.clone();
+        let serve_direction = world.resource::<ServeDirection>();
+        let (server, serves_in_turn) = (serve_direction.side, serve_direction.serves_in_turn);
+        let rally = world.resource::<Rally>();
+        let (rally_hits, last_hit) = (rally.hits, rally.last_hit);
+        let rng = world.resource::<ServeRng>();
+        let (serve_seed, serves) = (rng.seed(), rng.serves());
+
+        let balls = world
+            .query_filtered::<(&Position, &LinearVelocity), With<Ball>>()
+            .iter(world)
+            .map(|(position, velocity)| BodyState {
+                position: position.0,
+                velocity: velocity.0,
+            })
+            .collect();
+        let paddles = world
+            .query::<(&Player, &Position, &LinearVelocity, &PaddleIntent)>()
+            .iter(world)
+            .map(|(player, position, velocity, intent)| PaddleState {
+                side: player.side,
+                body: BodyState {
+                    position: position.0,
+                    velocity: velocity.0,
+                },
+                intent: intent.0,
+            })
+            .collect();
+
+        Some(Self {
+            phase,
+            score,
+            goal_timer,
+            server,
+            serves_in_turn,
+            rally_hits,
+            last_hit,
+            serve_seed,
+            serves,
+            balls,
+            paddles,
+        })
+    }
+
+    /// Puts the match back into the captured state. Any missing balls are
+    /// respawned and any extra ones removed.
+    pub fn restore(&self, world: &mut World) {
+        let mut score = world.resource_mut::<Score>();
+        [score.left, score.right] = self.score;
+        world.resource_mut::<GoalTimer>().timer = self.goal_timer.clone();
+        let mut serve_direction = world.resource_mut::<ServeDirection>();
+        serve_direction.side = self.server;
+        serve_direction.serves_in_turn = self.serves_in_turn;
+        let mut rally = world.resource_mut::<Rally>();
+        rally.hits = self.rally_hits;
+        rally.last_hit = self.last_hit;
+        world
+            .resource_mut::<ServeRng>()
+            .restore(self.serve_seed, self.serves);
+
+        // Match the number of balls
+        let existing: Vec<Entity> = world
+            .query_filtered::<Entity, With<Ball>>()
+            .iter(world)
+            .collect();
+        for &extra in existing.iter().skip(self.balls.len()) {
+            world.despawn(extra);
+        }
+        for _ in existing.len()..self.balls.len() {
+            let spawned = world.run_system_cached(
+                |mut commands: Commands,
+                 mut meshes: ResMut<Assets<Mesh>>,
+                 mut materials: ResMut<Assets<ColorMaterial>>| {
+                    spawn_ball(&mut commands, &mut meshes, &mut materials);
+                },
+            );
+            if let Err(error) = spawned {
+                warn!("Couldn't respawn ball from snapshot: {error}");
+            }
+        }
+
+        let mut balls = world
+            .query_filtered::<(&mut Transform, &mut Position, &mut LinearVelocity), With<Ball>>();
+        for ((transform, position, velocity), state) in balls.iter_mut(world).zip(&self.balls) {
+            set_body(transform, position, velocity, state);
+        }
+
+        let mut paddles = world.query::<(
+            &Player,
+            &mut Transform,
+            &mut Position,
+            &mut LinearVelocity,
+            &mut PaddleIntent,
+        )>();
+        for (player, transform, position, velocity, mut intent) in paddles.iter_mut(world) {
+            let Some(state) = self.paddles.iter().find(|state| state.side == player.side) else {
+                continue;
+            };
+            set_body(transform, position, velocity, &state.body);
+            intent.0 = state.intent;
+        }
+
+        let current = world
+            .get_resource::<State<GamePhase>>()
+            .map(|state| *state.get());
+        if current != Some(self.phase) {
+            world.resource_mut::<NextState<GamePhase>>().set(self.phase);
+        }
+    }
+}
+
+fn set_body(
+    mut transform: Mut<Transform>,
+    mut position: Mut<Position>,
+    mut velocity: Mut<LinearVelocity>,
+    state: &BodyState,
+) {
+    transform.translation.x = state.position.x;
+    transform.translation.y = state.position.y;
+    position.0 = state.position;
+    velocity.0 = state.velocity;
+}
+
+/// Console command for saving a snapshot and jumping back to it
+#[cfg(feature = "dev")]
+fn snapshot_command(
+    In(args): In<Vec<String>>,
+    world: &mut World,
+    mut saved: Local<Option<GameSnapshot>>,
+) -> ConsoleResult {
+    match args.first().map(String::as_str) {
+        Some("save") => {
+            *saved = Some(GameSnapshot::capture(world).ok_or("not in a match")?);
+            Ok("Snapshot saved".into())
+        }
+        Some("load") => {
+            saved.as_ref().ok_or("no snapshot saved")?.restore(world);
+            Ok("Snapshot restored".into())
+        }
+        _ => Err("usage: snapshot save | load".into()),
+    }
+}
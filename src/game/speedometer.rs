@@ -0,0 +1,129 @@
+//! Ball speed in km/h: an optional live readout at the top of the court, and
+//! the fastest shot of the match and of all time.
+//!
+//! A shot's speed is sampled on the physics step after the paddle hit, once
+//! the solver and any smash have had their say.
+
+use avian2d::prelude::*;
+use bevy::{prelude::*, ui::Val::*};
+
+use super::{
+    ball::Ball,
+    court::CourtSize,
+    hud::InCourtFrame,
+    player::{PaddleHit, PlayerSide},
+    stats::MatchStats,
+};
+use crate::{AppSystems, screens::Screen};
+
+/// Whatever its size, the court stands for a 24m long court
+const COURT_LENGTH: f32 = 24.0; // meters
+
+const SPEEDOMETER_TOP: f32 = 1.0;
+const SPEEDOMETER_FONT_SIZE: f32 = 18.0;
+const SPEEDOMETER_COLOR: Color = Color::srgba(1.0, 1.0, 1.0, 0.6);
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<ShowSpeedometer>();
+    app.init_resource::<ShowSpeedometer>();
+    app.register_type::<ShotSample>();
+    app.register_type::<Speedometer>();
+
+    app.add_observer(mark_shot);
+    app.add_systems(FixedUpdate, sample_shots);
+
+    app.add_systems(OnEnter(Screen::Gameplay), spawn_speedometer);
+    app.add_systems(
+        Update,
+        update_speedometer
+            .in_set(AppSystems::Update)
+            .run_if(in_state(Screen::Gameplay)),
+    );
+}
+
+/// Whether the live ball speed is shown during matches
+#[derive(Resource, Debug, Clone, Copy, Default, Reflect)]
+#[reflect(Resource)]
+pub struct ShowSpeedometer(pub bool);
+
+/// Converts a speed in pixels per second on `court` to whole km/h
+pub fn kmh(pixels_per_second: f32, court: CourtSize) -> u32 {
+    let pixels_per_meter = court.width / COURT_LENGTH;
+    (pixels_per_second / pixels_per_meter * 3.6).round() as u32
+}
+
+/// A ball just hit by `0`'s paddle, waiting for its speed to be sampled
+#[derive(Component, Debug, Clone, Copy, Reflect)]
+#[reflect(Component)]
+struct ShotSample(PlayerSide);
+
+/// The live speed readout
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct Speedometer;
+
+fn mark_shot(trigger: Trigger<PaddleHit>, mut commands: Commands) {
+    let hit = trigger.event();
+    if let Ok(mut ball) = commands.get_entity(hit.ball) {
+        ball.insert(ShotSample(hit.side));
+    }
+}
+
+/// Records each shot's speed against the player who hit it
+fn sample_shots(
+    mut commands: Commands,
+    mut stats: ResMut<MatchStats>,
+    court: Res<CourtSize>,
+    balls: Query<(Entity, &LinearVelocity, &ShotSample), With<Ball>>,
+) {
+    for (ball, velocity, &ShotSample(side)) in &balls {
+        let speed = kmh(velocity.length(), *court);
+        let fastest = &mut stats.get_mut(side).fastest_shot;
+        *fastest = (*fastest).max(speed);
+        commands.entity(ball).remove::<ShotSample>();
+    }
+}
+
+fn spawn_speedometer(mut commands: Commands) {
+    commands.spawn((
+        Name::new("Speedometer"),
+        Speedometer,
+        Text::default(),
+        TextFont {
+            font_size: SPEEDOMETER_FONT_SIZE,
+            ..default()
+        },
+        TextColor(SPEEDOMETER_COLOR),
+        TextLayout::new_with_justify(JustifyText::Center),
+        Node {
+            position_type: PositionType::Absolute,
+            width: Percent(100.0),
+            top: Percent(SPEEDOMETER_TOP),
+            ..default()
+        },
+        Pickable::IGNORE,
+        InCourtFrame,
+        StateScoped(Screen::Gameplay),
+    ));
+}
+
+/// Shows the fastest ball in play, or nothing when turned off
+fn update_speedometer(
+    show: Res<ShowSpeedometer>,
+    court: Res<CourtSize>,
+    balls: Query<&LinearVelocity, With<Ball>>,
+    mut speedometer: Single<(&mut Text, &mut Visibility), With<Speedometer>>,
+) {
+    let (text, visibility) = &mut *speedometer;
+    let fastest = balls
+        .iter()
+        .map(|velocity| velocity.length())
+        .reduce(f32::max);
+    match fastest {
+        Some(speed) if show.0 => {
+            **visibility = Visibility::Inherited;
+            text.0 = format!("{} km/h", kmh(speed, *court));
+        }
+        _ => **visibility = Visibility::Hidden,
+    }
+}
@@ -0,0 +1,313 @@
+//! Per-match statistics shown in the post-match breakdown, and career totals
+//! kept across matches.
+//!
+//! Scoring stats (own goals, clutch points) are counted as goals are scored.
+//! Saves come from predicting each ball's path every physics step: a return
+//! that stops a ball heading for the goal just as it reaches the paddle
+//! counts as a save. Every
+//! ball into a goal is charted by where and how fast it crossed the line.
+//! Each finished match is added to the saved [`CareerStats`].
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    GamePhase,
+    ball::{Ball, Rally},
+    court::CourtSize,
+    player::{PaddleHit, PlayerSide},
+    rules::{CourtLayout, MatchRules},
+    scoring::{PointScored, Score},
+};
+#[cfg(not(target_family = "wasm"))]
+use crate::replay::ReplayPlayback;
+use crate::{arcade::Arcade, screens::Screen, storage};
+
+const CAREER_SAVE_KEY: &str = "career_stats";
+
+/// A return counts as a save if the ball would have passed the defender's
+/// paddle, on its way into the goal, within this many physics steps
+const SAVE_WINDOW_STEPS: f32 = 6.0;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<MatchStats>()
+        .register_type::<PlayerStats>()
+        .register_type::<RallyRecord>()
+        .register_type::<GoalShot>()
+        .register_type::<GoalThreat>()
+        .add_event::<Saved>()
+        .init_resource::<MatchStats>()
+        .add_systems(OnEnter(Screen::Gameplay), reset_stats)
+        .add_systems(
+            FixedUpdate,
+            predict_goal_threats.run_if(in_state(GamePhase::Playing)),
+        )
+        .add_observer(count_returns)
+        .add_observer(count_saves)
+        .add_observer(record_rally);
+
+    app.register_type::<CareerStats>();
+    app.insert_resource(CareerStats::load());
+    // The arcade attract demo doesn't count as playing
+    let record =
+        record_career_stats.run_if(in_state(Screen::Gameplay).and(not(in_state(Arcade::Attract))));
+    // Watching a replay doesn't count as playing
+    #[cfg(not(target_family = "wasm"))]
+    let record = record.run_if(not(resource_exists::<ReplayPlayback>));
+    app.add_systems(OnEnter(GamePhase::GameOver), record);
+}
+
+/// Stats for one player
+#[derive(Debug, Clone, Default, Reflect, Serialize, Deserialize)]
+pub struct PlayerStats {
+    /// Balls sent back off their paddle
+    pub returns: u32,
+    /// Goals conceded off their own paddle
+    pub own_goals: u32,
+    /// Returns of a ball that was about to go in
+    pub saves: u32,
+    /// Points won while facing match point
+    pub clutch_points: u32,
+    /// Fastest ball off their paddle, in km/h
+    pub fastest_shot: u32,
+    /// Serves faked before the real one
+    pub feints: u32,
+}
+
+/// Stats for the current match
+#[derive(Resource, Debug, Clone, Default, Reflect, Serialize, Deserialize)]
+#[reflect(Resource)]
+pub struct MatchStats {
+    pub left: PlayerStats,
+    pub right: PlayerStats,
+    /// Every point that went on the board, in order
+    pub rallies: Vec<RallyRecord>,
+    /// Every ball that went into a goal, in order
+    pub goal_shots: Vec<GoalShot>,
+}
+
+/// How a point was won
+#[derive(Debug, Clone, Copy, Reflect, Serialize, Deserialize)]
+pub struct RallyRecord {
+    /// Paddle hits before it was scored
+    pub hits: u32,
+    pub scorer: PlayerSide,
+}
+
+/// A ball crossing into a goal
+#[derive(Debug, Clone, Copy, Reflect, Serialize, Deserialize)]
+pub struct GoalShot {
+    /// Whose goal it went into
+    pub defender: PlayerSide,
+    /// Height it crossed the goal line at, from the middle
+    pub y: f32,
+    /// In km/h
+    pub speed: u32,
+}
+
+impl MatchStats {
+    pub fn get(&self, side: PlayerSide) -> &PlayerStats {
+        match side {
+            PlayerSide::Left => &self.left,
+            PlayerSide::Right => &self.right,
+        }
+    }
+
+    pub fn get_mut(&mut self, side: PlayerSide) -> &mut PlayerStats {
+        match side {
+            PlayerSide::Left => &mut self.left,
+            PlayerSide::Right => &mut self.right,
+        }
+    }
+
+    /// The most paddle hits in any point
+    pub fn longest_rally(&self) -> u32 {
+        self.rallies
+            .iter()
+            .map(|rally| rally.hits)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Rows for the post-match summary, as `(name, left, right)`
+    pub fn rows(&self) -> [(&'static str, u32, u32); 6] {
+        let row = |name, stat: fn(&PlayerStats) -> u32| (name, stat(&self.left), stat(&self.right));
+        [
+            row("Returns", |stats| stats.returns),
+            row("Saves", |stats| stats.saves),
+            row("Clutch Points", |stats| stats.clutch_points),
+            row("Own Goals", |stats| stats.own_goals),
+            row("Fastest Shot (km/h)", |stats| stats.fastest_shot),
+            row("Serve Feints", |stats| stats.feints),
+        ]
+    }
+}
+
+/// Totals across every match played on this device
+#[derive(Resource, Debug, Clone, Default, Reflect, Serialize, Deserialize)]
+#[reflect(Resource)]
+pub struct CareerStats {
+    pub matches: u32,
+    pub points: u32,
+    pub saves: u32,
+    pub clutch_points: u32,
+    pub own_goals: u32,
+    /// Fastest shot ever, in km/h
+    #[serde(default)]
+    pub fastest_shot: u32,
+}
+
+impl CareerStats {
+    /// Loads the saved totals, or starts from zero
+    pub fn load() -> Self {
+        storage::load(CAREER_SAVE_KEY).unwrap_or_default()
+    }
+
+    pub fn save(&self) {
+        if let Err(error) = storage::save(CAREER_SAVE_KEY, self) {
+            warn!("Couldn't save career stats: {error}");
+        }
+    }
+
+    /// Clears every total, on disk too
+    pub fn reset(&mut self) {
+        *self = Self::default();
+        self.save();
+    }
+
+    /// Rows for display, as `(name, value)`
+    pub fn rows(&self) -> [(&'static str, u32); 6] {
+        [
+            ("Matches Played", self.matches),
+            ("Points Scored", self.points),
+            ("Saves", self.saves),
+            ("Clutch Points", self.clutch_points),
+            ("Own Goals", self.own_goals),
+            ("Fastest Shot (km/h)", self.fastest_shot),
+        ]
+    }
+}
+
+/// Marks a ball predicted to go into `0`'s goal within [`SAVE_WINDOW_STEPS`]
+#[derive(Component, Debug, Clone, Copy, Reflect)]
+#[reflect(Component)]
+struct GoalThreat(PlayerSide);
+
+/// Triggered on a ball when `0`'s paddle saves it from going in
+#[derive(Event, Debug, Clone, Copy, Reflect)]
+pub struct Saved(pub PlayerSide);
+
+fn reset_stats(mut stats: ResMut<MatchStats>) {
+    *stats = MatchStats::default();
+}
+
+/// Flags balls about to cross a goal line, before paddles get a chance to
+/// return them
+fn predict_goal_threats(
+    mut commands: Commands,
+    time: Res<Time<Fixed>>,
+    rules: Res<MatchRules>,
+    size: Res<CourtSize>,
+    balls: Query<(Entity, &Position, &LinearVelocity, Option<&GoalThreat>), With<Ball>>,
+) {
+    let window = time.timestep().as_secs_f32() * SAVE_WINDOW_STEPS;
+
+    for (ball, position, velocity, threat) in &balls {
+        let threatened = goal_threat(position.0, velocity.0, window, rules.court, *size);
+        match (threatened, threat) {
+            (Some(side), _) => {
+                commands.entity(ball).insert(GoalThreat(side));
+            }
+            (None, Some(_)) => {
+                commands.entity(ball).remove::<GoalThreat>();
+            }
+            (None, None) => {}
+        }
+    }
+}
+
+/// The side whose goal the ball is heading into, if it'll get past their
+/// paddle within `window` seconds. It's timed to the paddle rather than the
+/// goal line behind it, so fast balls count while the paddle can still save
+/// them.
+fn goal_threat(
+    position: Vec2,
+    velocity: Vec2,
+    window: f32,
+    court: CourtLayout,
+    size: CourtSize,
+) -> Option<PlayerSide> {
+    let (defender, goal_x) = if velocity.x < 0.0 {
+        (PlayerSide::Left, -size.half_width())
+    } else if velocity.x > 0.0 {
+        (PlayerSide::Right, size.half_width())
+    } else {
+        return None;
+    };
+
+    let time_to_paddle = (defender.paddle_x(size) - position.x) / velocity.x;
+    if !(0.0..=window).contains(&time_to_paddle) {
+        return None;
+    }
+
+    // Walls keep the ball in; an open court lets it leave before the goal
+    let time_to_goal = (goal_x - position.x) / velocity.x;
+    let arrival_y = position.y + velocity.y * time_to_goal;
+    let in_court = arrival_y.abs() <= size.half_height();
+    (court == CourtLayout::Walled || in_court).then_some(defender)
+}
+
+fn count_returns(trigger: Trigger<PaddleHit>, mut stats: ResMut<MatchStats>) {
+    stats.get_mut(trigger.event().side).returns += 1;
+}
+
+/// Remembers how long each scoring rally was, for the post-match graph
+fn record_rally(trigger: Trigger<PointScored>, rally: Res<Rally>, mut stats: ResMut<MatchStats>) {
+    stats.rallies.push(RallyRecord {
+        hits: rally.hits,
+        scorer: trigger.event().side,
+    });
+}
+
+/// A paddle touching a ball headed into its own goal makes a save
+fn count_saves(
+    trigger: Trigger<PaddleHit>,
+    mut commands: Commands,
+    mut stats: ResMut<MatchStats>,
+    balls: Query<&GoalThreat, With<Ball>>,
+) {
+    let &PaddleHit { side, ball, .. } = trigger.event();
+    let Ok(&GoalThreat(defender)) = balls.get(ball) else {
+        return;
+    };
+    if side != defender {
+        return;
+    }
+
+    stats.get_mut(defender).saves += 1;
+    commands.entity(ball).remove::<GoalThreat>();
+    commands.trigger_targets(Saved(defender), ball);
+    info!("Save by {defender:?} player");
+}
+
+fn record_career_stats(
+    mut career: ResMut<CareerStats>,
+    stats: Res<MatchStats>,
+    score: Res<Score>,
+    rules: Res<MatchRules>,
+) {
+    let both = |stat: fn(&PlayerStats) -> u32| stat(&stats.left) + stat(&stats.right);
+    let starting_score = rules.left_handicap.score_offset + rules.right_handicap.score_offset;
+
+    career.matches += 1;
+    career.points += (score.left + score.right).saturating_sub(starting_score);
+    career.saves += both(|stats| stats.saves);
+    career.clutch_points += both(|stats| stats.clutch_points);
+    career.own_goals += both(|stats| stats.own_goals);
+    career.fastest_shot = career
+        .fastest_shot
+        .max(stats.left.fastest_shot)
+        .max(stats.right.fastest_shot);
+    career.save();
+}
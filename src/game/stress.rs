@@ -0,0 +1,259 @@
+//! Stress scenes: hundreds of balls bouncing around a court cluttered with
+//! obstacles, for catching performance regressions.
+//!
+//! [`headless_app`] runs one through the fixed-tick pipeline without a
+//! window or renderer: avian's step, the ball speed clamp, the restitution
+//! the [`GameConfig`] asks for, and the wall bounces fanned out as
+//! [`GameEvent`]s. The benchmarks in `benches/` time it. The `stress` console
+//! command drops the same crowd into a running match, so trails, sounds and
+//! everything else reading the event bus get their share of the load too.
+//! Expect the score to run away while it's on.
+//!
+//! Only built with the `dev` feature, so none of this ships.
+
+use std::time::Duration;
+
+use avian2d::prelude::*;
+use bevy::{prelude::*, state::app::StatesPlugin, time::TimeUpdateStrategy};
+use rand::{prelude::*, rngs::StdRng};
+
+use super::{
+    GamePhase,
+    ball::{Ball, ServeDirection, ServeShot, ball_body, clamp_ball_speed, serve_ball, spawn_ball},
+    config::{self, CourtConfig, GameConfig},
+    court::{BOUNDARY_THICKNESS, Boundary, CourtSize},
+    debug::console::{ConsoleResult, RegisterConsoleCommand},
+    events::{self, GameEvent},
+    physics::{self, BOUNDARY_FRICTION, boundary_layers},
+    player::PlayerSide,
+};
+use crate::screens::Screen;
+
+/// Side of each square obstacle
+const OBSTACLE_SIZE: f32 = 24.0;
+/// Obstacles keep this far from the end lines, clear of the paddles
+const OBSTACLE_MARGIN: f32 = 80.0;
+const OBSTACLE_COLOR: Color = Color::srgb(0.6, 0.6, 0.7);
+/// Fixed ticks per second, matching Bevy's default
+const TICK_RATE: f64 = 64.0;
+/// Most balls the console command adds at once
+const MAX_STRESS_BALLS: usize = 1000;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_console_command(
+        "stress",
+        "stress <balls> [obstacles] - add balls and obstacles to the match",
+        stress_command,
+    );
+}
+
+/// How crowded a stress scene is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StressScene {
+    pub balls: usize,
+    pub obstacles: usize,
+    /// Seeds where everything starts and which way the balls head, so runs
+    /// can be compared
+    pub seed: u64,
+}
+
+impl Default for StressScene {
+    fn default() -> Self {
+        Self {
+            balls: 200,
+            obstacles: 40,
+            seed: 0,
+        }
+    }
+}
+
+/// An app running `scene` mid-rally with no window or renderer. Every
+/// [`App::update`] advances exactly one fixed tick.
+pub fn headless_app(scene: StressScene) -> App {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        AssetPlugin::default(),
+        StatesPlugin,
+        TransformPlugin,
+        bevy::scene::ScenePlugin,
+        bevy::render::mesh::MeshPlugin,
+        PhysicsPlugins::default(),
+    ));
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(
+        1.0 / TICK_RATE,
+    )));
+    app.insert_resource(Time::<Fixed>::from_hz(TICK_RATE));
+
+    app.insert_state(Screen::Gameplay);
+    app.add_sub_state::<GamePhase>();
+    app.add_plugins((config::plugin, events::plugin, physics::plugin));
+    app.add_systems(FixedUpdate, clamp_ball_speed);
+    app.add_systems(Update, drain_game_events);
+    app.init_resource::<CourtSize>();
+
+    app.world_mut()
+        .resource_mut::<NextState<GamePhase>>()
+        .set(GamePhase::Playing);
+    let court = *app.world().resource::<CourtSize>();
+    spawn_walls(&mut app.world_mut().commands(), court);
+    spawn_scene(&mut app.world_mut().commands(), scene, court);
+    app.update();
+    app
+}
+
+/// Reads the event bus the way the effects and sounds do, so fanning
+/// events out is part of what gets measured
+fn drain_game_events(mut game_events: EventReader<GameEvent>) {
+    let bounces = game_events
+        .read()
+        .filter(|event| matches!(event, GameEvent::WallBounce { .. }))
+        .count();
+    if bounces > 0 {
+        trace!("Stress: {bounces} wall bounces");
+    }
+}
+
+/// A closed box around the court, so no ball is lost out of the ends
+fn spawn_walls(commands: &mut Commands, court: CourtSize) {
+    let half_width = court.half_width();
+    let half_height = court.half_height();
+    let walls = [
+        (
+            Vec2::new(0.0, half_height),
+            Vec2::new(court.width, BOUNDARY_THICKNESS),
+        ),
+        (
+            Vec2::new(0.0, -half_height),
+            Vec2::new(court.width, BOUNDARY_THICKNESS),
+        ),
+        (
+            Vec2::new(half_width, 0.0),
+            Vec2::new(BOUNDARY_THICKNESS, court.height),
+        ),
+        (
+            Vec2::new(-half_width, 0.0),
+            Vec2::new(BOUNDARY_THICKNESS, court.height),
+        ),
+    ];
+    for (center, size) in walls {
+        commands.spawn((
+            Name::new("Stress Wall"),
+            obstacle(center, size),
+            StateScoped(Screen::Gameplay),
+        ));
+    }
+}
+
+/// Scatters `scene`'s obstacles and balls over the court, balls already
+/// moving at serve speed in every direction
+fn spawn_scene(commands: &mut Commands, scene: StressScene, court: CourtSize) {
+    let rng = &mut StdRng::seed_from_u64(scene.seed);
+    let config = GameConfig::default();
+    spawn_obstacles(commands, scene.obstacles, court, rng);
+
+    let half_width = court.half_width() - OBSTACLE_MARGIN;
+    let half_height = court.half_height() - BOUNDARY_THICKNESS * 2.0;
+    for _ in 0..scene.balls {
+        let position = Vec2::new(
+            rng.random_range(-half_width..half_width),
+            rng.random_range(-half_height..half_height),
+        );
+        let heading = Vec2::from_angle(rng.random_range(0.0..std::f32::consts::TAU));
+        commands
+            .spawn((
+                Name::new("Stress Ball"),
+                Ball,
+                Transform::from_translation(position.extend(0.0)),
+                ball_body(),
+                StateScoped(Screen::Gameplay),
+            ))
+            .insert(LinearVelocity(heading * config.ball.speed));
+    }
+}
+
+fn spawn_obstacles(commands: &mut Commands, count: usize, court: CourtSize, rng: &mut impl Rng) {
+    let half_width = court.half_width() - OBSTACLE_MARGIN;
+    let half_height = court.half_height() - OBSTACLE_SIZE;
+    for _ in 0..count {
+        let center = Vec2::new(
+            rng.random_range(-half_width..half_width),
+            rng.random_range(-half_height..half_height),
+        );
+        commands.spawn((
+            Name::new("Stress Obstacle"),
+            obstacle(center, Vec2::splat(OBSTACLE_SIZE)),
+            Sprite::from_color(OBSTACLE_COLOR, Vec2::splat(OBSTACLE_SIZE)),
+            StateScoped(Screen::Gameplay),
+        ));
+    }
+}
+
+/// A static block the ball bounces off like a wall. Being a [`Boundary`], its
+/// restitution follows the [`GameConfig`] once spawned.
+fn obstacle(center: Vec2, size: Vec2) -> impl Bundle {
+    (
+        Boundary,
+        RigidBody::Static,
+        Collider::rectangle(size.x, size.y),
+        boundary_layers(),
+        Friction::new(BOUNDARY_FRICTION),
+        Restitution::new(CourtConfig::default().wall_restitution),
+        Transform::from_translation(center.extend(0.0)),
+    )
+}
+
+/// Console command adding balls, and optionally obstacles, to the match.
+/// The balls are real ones, served from the middle at random angles.
+fn stress_command(
+    In(args): In<Vec<String>>,
+    mut commands: Commands,
+    config: Res<GameConfig>,
+    court: Res<CourtSize>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    screen: Res<State<Screen>>,
+) -> ConsoleResult {
+    let usage = || "usage: stress <balls> [obstacles]".to_string();
+    let (balls, obstacles) = match args.as_slice() {
+        [balls] => (balls, None),
+        [balls, obstacles] => (balls, Some(obstacles)),
+        _ => return Err(usage()),
+    };
+    let balls: usize = balls
+        .parse()
+        .map_err(|_| format!("invalid ball count '{balls}'"))?;
+    let obstacles: usize = obstacles
+        .map(|count| count.parse())
+        .transpose()
+        .map_err(|_| usage())?
+        .unwrap_or(0);
+    if *screen.get() != Screen::Gameplay {
+        return Err("start a match first".into());
+    }
+    let balls = balls.min(MAX_STRESS_BALLS);
+
+    // Off the serve generator, so replays of the match don't notice
+    let rng = &mut rand::rng();
+    spawn_obstacles(&mut commands, obstacles, *court, rng);
+    for index in 0..balls {
+        let ball = spawn_ball(&mut commands, &mut meshes, &mut materials);
+        let side = if index % 2 == 0 {
+            PlayerSide::Left
+        } else {
+            PlayerSide::Right
+        };
+        let serve_direction = ServeDirection {
+            side,
+            serves_in_turn: 0,
+        };
+        let angle = config.ball.serve_angle(rng);
+        let shot = ServeShot {
+            angle: if rng.random_bool(0.5) { angle } else { -angle },
+            speed: 1.0,
+        };
+        serve_ball(&mut commands, ball, &serve_direction, shot);
+    }
+
+    Ok(format!("Added {balls} balls and {obstacles} obstacles"))
+}
@@ -0,0 +1,476 @@
+//! The post-match summary: who won, each player's stats side by side, graphs
+//! of every rally and of the match's momentum, where the ball spent the
+//! match and where goals went in, the achievements earned, and what to do
+//! next.
+
+use bevy::{
+    ecs::spawn::SpawnWith, input::common_conditions::input_just_pressed, prelude::*, ui::Val::*,
+};
+
+use super::{
+    GamePhase,
+    court::CourtSize,
+    heatmap::{BallHeatmap, heatmap},
+    hud::InCourtFrame,
+    player::PlayerSide,
+    profile::PlayerProfiles,
+    rules::MatchRules,
+    score_history::{ScoreHistory, timeline},
+    scoring::Score,
+    stats::{GoalShot, MatchStats, RallyRecord},
+};
+use crate::{
+    arcade::Arcade,
+    menus::Menu,
+    screens::{Screen, title::OpenOnTitle, transition::Transition},
+    theme::{
+        prelude::*,
+        tween::{TweenAlpha, TweenOffset},
+    },
+    tournament::Tournament,
+};
+
+const OVERLAY_ALPHA: f32 = 0.8;
+const SLIDE_FROM: f32 = -120.0; // px above its resting place
+const SLIDE_DURATION: f32 = 0.4; // seconds
+
+// Stats table
+const STAT_COLUMN_WIDTH: f32 = 160.0;
+const STAT_FONT_SIZE: f32 = 22.0;
+const STAT_NAME_COLOR: Color = Color::srgb(0.7, 0.7, 0.7);
+
+// Rally graph
+const GRAPH_HEIGHT: f32 = 80.0;
+const GRAPH_MAX_WIDTH: f32 = 360.0;
+const GRAPH_BAR_MAX_WIDTH: f32 = 12.0;
+const GRAPH_BAR_GAP: f32 = 2.0;
+/// Points scored without a hit still get a sliver of a bar
+const GRAPH_BAR_MIN_HEIGHT: f32 = 2.0;
+
+// Shot charts
+const SHOT_CHART_WIDTH: f32 = 60.0;
+const SHOT_CHART_BACKGROUND: Color = Color::srgba(1.0, 1.0, 1.0, 0.05);
+const SHOT_CHART_CAPTION_SIZE: f32 = 14.0;
+const SHOT_DOT_SIZE: f32 = 6.0;
+
+// Achievements
+const BRICK_WALL_SAVES: u32 = 5;
+const MARATHON_HITS: u32 = 20;
+
+pub(super) fn plugin(app: &mut App) {
+    // Arcade mode shows its own result and moves on by itself
+    app.add_systems(
+        OnEnter(GamePhase::GameOver),
+        spawn_summary.run_if(in_state(Arcade::Off)),
+    );
+    app.add_systems(
+        Update,
+        // Tournaments route game over input through the bracket instead
+        return_to_title.run_if(
+            in_state(GamePhase::GameOver)
+                .and(in_state(Transition::Idle))
+                .and(not(resource_exists::<Tournament>))
+                .and(input_just_pressed(KeyCode::Escape)),
+        ),
+    );
+}
+
+/// Something notable a player did this match
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Achievement {
+    /// Won without conceding a point
+    Shutout,
+    /// Made enough saves in one match
+    BrickWall,
+    /// Won a very long rally
+    Marathon,
+    /// Won a point while facing match point
+    Clutch,
+}
+
+impl Achievement {
+    fn name(self) -> &'static str {
+        match self {
+            Achievement::Shutout => "Shutout",
+            Achievement::BrickWall => "Brick Wall",
+            Achievement::Marathon => "Marathon",
+            Achievement::Clutch => "Nerves of Steel",
+        }
+    }
+
+    fn description(self) -> String {
+        match self {
+            Achievement::Shutout => "won without conceding".into(),
+            Achievement::BrickWall => format!("{BRICK_WALL_SAVES}+ saves"),
+            Achievement::Marathon => format!("won a {MARATHON_HITS}+ hit rally"),
+            Achievement::Clutch => "scored facing match point".into(),
+        }
+    }
+
+    /// What `side` earned this match
+    fn earned(side: PlayerSide, winner: PlayerSide, stats: &MatchStats) -> Vec<Self> {
+        let player = stats.get(side);
+        let conceded = stats.rallies.iter().any(|rally| rally.scorer != side);
+        let marathon = stats
+            .rallies
+            .iter()
+            .any(|rally| rally.scorer == side && rally.hits >= MARATHON_HITS);
+
+        let mut earned = Vec::new();
+        if side == winner && !conceded {
+            earned.push(Achievement::Shutout);
+        }
+        if player.saves >= BRICK_WALL_SAVES {
+            earned.push(Achievement::BrickWall);
+        }
+        if marathon {
+            earned.push(Achievement::Marathon);
+        }
+        if player.clutch_points > 0 {
+            earned.push(Achievement::Clutch);
+        }
+        earned
+    }
+}
+
+fn spawn_summary(
+    mut commands: Commands,
+    score: Res<Score>,
+    stats: Res<MatchStats>,
+    history: Res<ScoreHistory>,
+    ball_heatmap: Res<BallHeatmap>,
+    rules: Res<MatchRules>,
+    court: Res<CourtSize>,
+    profiles: Res<PlayerProfiles>,
+    tournament: Option<Res<Tournament>>,
+) {
+    let winner = score.winner(&rules).expect("Game over without winner");
+    let win_type = if score.is_mercy(&rules) {
+        "MERCY WIN!"
+    } else {
+        "VICTORY!"
+    };
+    let winner_name = match winner {
+        PlayerSide::Left => "Left",
+        PlayerSide::Right => "Right",
+    };
+
+    let achievements: Vec<_> = [PlayerSide::Left, PlayerSide::Right]
+        .into_iter()
+        .flat_map(|side| {
+            Achievement::earned(side, winner, &stats)
+                .into_iter()
+                .map(move |achievement| (side, achievement))
+        })
+        .collect();
+
+    commands
+        .spawn((
+            Name::new("Match Summary"),
+            Node {
+                width: Percent(100.0),
+                height: Percent(100.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                flex_direction: FlexDirection::Column,
+                row_gap: Px(14.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, OVERLAY_ALPHA)),
+            // Slides down into place while the backdrop fades in
+            TweenOffset::slide_in(Vec2::new(0.0, SLIDE_FROM), SLIDE_DURATION),
+            TweenAlpha {
+                to: OVERLAY_ALPHA,
+                ..TweenAlpha::fade_in(SLIDE_DURATION)
+            },
+            InCourtFrame,
+            StateScoped(GamePhase::GameOver),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(win_type),
+                TextFont::from_font_size(56.0),
+                TextColor(Color::WHITE),
+            ));
+            parent.spawn((
+                Text::new(format!(
+                    "{winner_name} Player Wins {} - {}",
+                    score.left, score.right
+                )),
+                TextFont::from_font_size(32.0),
+                TextColor(profiles.color(winner)),
+            ));
+
+            parent.spawn(stats_table(&score, &stats, &profiles));
+            parent.spawn(widget::label(format!(
+                "Longest Rally: {} hits",
+                stats.longest_rally()
+            )));
+            // Rally lengths beside the momentum swings
+            parent.spawn((
+                Name::new("Match Graphs"),
+                Node {
+                    align_items: AlignItems::End,
+                    column_gap: Px(30.0),
+                    ..default()
+                },
+                children![
+                    rally_graph(&stats.rallies, &profiles),
+                    timeline(&history, &profiles),
+                ],
+            ));
+            // Where the ball spent the match, between where it went in at
+            // each end
+            parent.spawn((
+                Name::new("Court Maps"),
+                Node {
+                    align_items: AlignItems::Start,
+                    column_gap: Px(16.0),
+                    ..default()
+                },
+                children![
+                    shot_chart(PlayerSide::Left, &stats.goal_shots, *court, &profiles),
+                    heatmap(&ball_heatmap, *court, &profiles),
+                    shot_chart(PlayerSide::Right, &stats.goal_shots, *court, &profiles),
+                ],
+            ));
+
+            for (side, achievement) in achievements {
+                parent.spawn((
+                    Text::new(format!(
+                        "{}: {} ({})",
+                        achievement.name(),
+                        achievement.description(),
+                        match side {
+                            PlayerSide::Left => "Left",
+                            PlayerSide::Right => "Right",
+                        }
+                    )),
+                    TextFont::from_font_size(STAT_FONT_SIZE),
+                    TextColor(profiles.color(side)),
+                ));
+            }
+
+            // The bracket decides what comes next in a tournament
+            if tournament.is_none() {
+                parent.spawn((
+                    Name::new("Summary Buttons"),
+                    Node {
+                        column_gap: Px(20.0),
+                        margin: UiRect::top(Px(10.0)),
+                        ..default()
+                    },
+                    children![
+                        widget::button_medium("Rematch", rematch),
+                        widget::button_medium("Change Mode", change_mode),
+                        widget::button_medium("Title", go_to_title),
+                    ],
+                ));
+            }
+        });
+}
+
+/// Each player's stats in a column either side of the stat names
+fn stats_table(score: &Score, stats: &MatchStats, profiles: &PlayerProfiles) -> impl Bundle {
+    let mut rows = vec![("Points", score.left, score.right)];
+    rows.extend(stats.rows());
+    let (left_color, right_color) = (
+        profiles.color(PlayerSide::Left),
+        profiles.color(PlayerSide::Right),
+    );
+
+    (
+        Name::new("Stats Table"),
+        Node {
+            display: Display::Grid,
+            grid_template_columns: RepeatedGridTrack::px(3, STAT_COLUMN_WIDTH),
+            row_gap: Px(4.0),
+            justify_items: JustifyItems::Center,
+            ..default()
+        },
+        Children::spawn(SpawnWith(move |parent: &mut ChildSpawner| {
+            for (name, left, right) in rows {
+                for (text, color) in [
+                    (left.to_string(), left_color),
+                    (name.to_string(), STAT_NAME_COLOR),
+                    (right.to_string(), right_color),
+                ] {
+                    parent.spawn((
+                        Text::new(text),
+                        TextFont::from_font_size(STAT_FONT_SIZE),
+                        TextColor(color),
+                    ));
+                }
+            }
+        })),
+    )
+}
+
+/// One bar per point in the order they were scored, as tall as the rally was
+/// long and in the scorer's color
+fn rally_graph(rallies: &[RallyRecord], profiles: &PlayerProfiles) -> impl Bundle {
+    let longest = rallies
+        .iter()
+        .map(|rally| rally.hits)
+        .max()
+        .unwrap_or(0)
+        .max(1);
+    let bar_width = (GRAPH_MAX_WIDTH / rallies.len().max(1) as f32 - GRAPH_BAR_GAP)
+        .clamp(1.0, GRAPH_BAR_MAX_WIDTH);
+    let bars: Vec<_> = rallies
+        .iter()
+        .map(|rally| {
+            let height = GRAPH_HEIGHT * rally.hits as f32 / longest as f32;
+            (
+                height.max(GRAPH_BAR_MIN_HEIGHT),
+                profiles.color(rally.scorer),
+            )
+        })
+        .collect();
+
+    (
+        Name::new("Rally Graph"),
+        Node {
+            height: Px(GRAPH_HEIGHT),
+            max_width: Px(GRAPH_MAX_WIDTH),
+            align_items: AlignItems::End,
+            column_gap: Px(GRAPH_BAR_GAP),
+            ..default()
+        },
+        Children::spawn(SpawnWith(move |parent: &mut ChildSpawner| {
+            for (height, color) in bars {
+                parent.spawn((
+                    Name::new("Rally Bar"),
+                    Node {
+                        width: Px(bar_width),
+                        height: Px(height),
+                        ..default()
+                    },
+                    BackgroundColor(color),
+                ));
+            }
+        })),
+    )
+}
+
+/// Every goal `defender` conceded, as a dot at the height it went in and
+/// further from their goal line the faster it was, in the scorer's color
+fn shot_chart(
+    defender: PlayerSide,
+    shots: &[GoalShot],
+    court: CourtSize,
+    profiles: &PlayerProfiles,
+) -> impl Bundle {
+    let fastest = shots
+        .iter()
+        .map(|shot| shot.speed)
+        .max()
+        .unwrap_or(0)
+        .max(1);
+    let color = profiles.color(defender.opponent());
+    let dots: Vec<_> = shots
+        .iter()
+        .filter(|shot| shot.defender == defender)
+        .map(|shot| {
+            let across = shot.speed as f32 / fastest as f32 * 100.0;
+            let left = match defender {
+                PlayerSide::Left => across,
+                PlayerSide::Right => 100.0 - across,
+            };
+            let top = (0.5 - shot.y / court.height).clamp(0.0, 1.0) * 100.0;
+            (left, top)
+        })
+        .collect();
+    let caption = match defender {
+        PlayerSide::Left => "Into Left",
+        PlayerSide::Right => "Into Right",
+    };
+
+    (
+        Name::new("Shot Chart"),
+        Node {
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            row_gap: Px(4.0),
+            ..default()
+        },
+        Pickable::IGNORE,
+        children![
+            (
+                Name::new("Shot Chart Plot"),
+                Node {
+                    width: Px(SHOT_CHART_WIDTH),
+                    height: Px(GRAPH_HEIGHT),
+                    ..default()
+                },
+                BackgroundColor(SHOT_CHART_BACKGROUND),
+                Children::spawn(SpawnWith(move |parent: &mut ChildSpawner| {
+                    for (left, top) in dots {
+                        parent.spawn((
+                            Name::new("Goal Shot"),
+                            Node {
+                                position_type: PositionType::Absolute,
+                                left: Percent(left),
+                                top: Percent(top),
+                                width: Px(SHOT_DOT_SIZE),
+                                height: Px(SHOT_DOT_SIZE),
+                                margin: UiRect::new(
+                                    Px(-SHOT_DOT_SIZE / 2.0),
+                                    Px(0.0),
+                                    Px(-SHOT_DOT_SIZE / 2.0),
+                                    Px(0.0),
+                                ),
+                                ..default()
+                            },
+                            BorderRadius::MAX,
+                            BackgroundColor(color),
+                        ));
+                    }
+                })),
+            ),
+            (
+                Text::new(caption),
+                TextFont::from_font_size(SHOT_CHART_CAPTION_SIZE),
+                TextColor(STAT_NAME_COLOR),
+            ),
+        ],
+    )
+}
+
+fn rematch(
+    _: Trigger<Pointer<Click>>,
+    mut score: ResMut<Score>,
+    mut next_screen: ResMut<NextState<Screen>>,
+) {
+    score.left = 0;
+    score.right = 0;
+    next_screen.set(Screen::Gameplay);
+}
+
+fn change_mode(
+    _: Trigger<Pointer<Click>>,
+    mut commands: Commands,
+    mut score: ResMut<Score>,
+    mut next_screen: ResMut<NextState<Screen>>,
+) {
+    score.left = 0;
+    score.right = 0;
+    commands.insert_resource(OpenOnTitle(Menu::Modes));
+    next_screen.set(Screen::Title);
+}
+
+fn go_to_title(
+    _: Trigger<Pointer<Click>>,
+    mut score: ResMut<Score>,
+    mut next_screen: ResMut<NextState<Screen>>,
+) {
+    score.left = 0;
+    score.right = 0;
+    next_screen.set(Screen::Title);
+}
+
+fn return_to_title(mut score: ResMut<Score>, mut next_screen: ResMut<NextState<Screen>>) {
+    score.left = 0;
+    score.right = 0;
+    next_screen.set(Screen::Title);
+}
@@ -0,0 +1,214 @@
+//! The switch-sides rule: as in real sports, the players change ends once
+//! either is halfway to winning.
+//!
+//! The paddles glide across each other during the pause after the point,
+//! then each takes over the other's side. Everything kept per side (score,
+//! stats, profiles, handicaps, the serve) is swapped to match, and
+//! [`MirroredCourt`] flips so the keyboard follows its player. It's all
+//! swapped back as the match ends, so results are reported for the sides
+//! the players started on.
+
+use avian2d::prelude::*;
+use bevy::{ecs::system::SystemParam, prelude::*};
+
+use super::{
+    GamePhase,
+    animation::facing_court_flipped,
+    ball::ServeDirection,
+    floating_text::floating_text,
+    player::{MirroredCourt, Player},
+    profile::PlayerProfiles,
+    rules::MatchRules,
+    score_history::ScoreHistory,
+    scoring::{GoalTimer, Score},
+    stats::MatchStats,
+};
+use crate::{AppSystems, PausableSystems, screens::Screen};
+
+/// Seconds the paddles take to cross the court
+const SWITCH_DURATION: f32 = 1.5;
+const SWITCH_TEXT_COLOR: Color = Color::srgb(1.0, 0.85, 0.3);
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<SideSwitch>();
+    app.init_resource::<SideSwitch>();
+
+    app.add_systems(OnEnter(Screen::Gameplay), reset_side_switch);
+    app.add_systems(OnEnter(GamePhase::GoalScored), start_switch);
+    app.add_systems(
+        Update,
+        glide_paddles
+            .in_set(AppSystems::Update)
+            .in_set(PausableSystems)
+            .run_if(in_state(GamePhase::GoalScored)),
+    );
+    app.add_systems(OnExit(GamePhase::GoalScored), finish_switch);
+    app.add_systems(OnExit(GamePhase::Playing), switch_back.run_if(match_won));
+    app.add_systems(OnExit(Screen::Gameplay), switch_back);
+}
+
+/// Progress of the current match's switch
+#[derive(Resource, Debug, Default, Reflect)]
+#[reflect(Resource)]
+pub struct SideSwitch {
+    /// Whether the players are at the ends they didn't start at
+    pub switched: bool,
+    /// Whether this match has had its switch, or started past the point for
+    /// one
+    done: bool,
+    /// Paddles on their way across, with where they're coming from and going
+    gliding: Vec<(Entity, f32, f32)>,
+    timer: Timer,
+}
+
+/// Points either player needs before the ends change
+fn halfway(rules: &MatchRules) -> u32 {
+    rules.target_score.div_ceil(2)
+}
+
+fn match_won(score: Res<Score>, rules: Res<MatchRules>) -> bool {
+    score.winner(&rules).is_some()
+}
+
+fn reset_side_switch(mut switch: ResMut<SideSwitch>, rules: Res<MatchRules>) {
+    // A big enough head start skips the switch
+    let head_start = rules
+        .left_handicap
+        .score_offset
+        .max(rules.right_handicap.score_offset);
+    *switch = SideSwitch {
+        done: !rules.switch_sides || head_start >= halfway(&rules),
+        ..default()
+    };
+}
+
+/// Sends the paddles across once someone reaches halfway, holding the next
+/// serve until they arrive
+fn start_switch(
+    mut commands: Commands,
+    mut switch: ResMut<SideSwitch>,
+    score: Res<Score>,
+    rules: Res<MatchRules>,
+    mut goal_timer: ResMut<GoalTimer>,
+    paddles: Query<(Entity, &Position), With<Player>>,
+) {
+    if switch.done || score.left.max(score.right) < halfway(&rules) {
+        return;
+    }
+    let Ok([(first, from), (second, to)]) = <[_; 2]>::try_from(paddles.iter().collect::<Vec<_>>())
+    else {
+        return;
+    };
+    let (from, to) = (from.x, to.x);
+    switch.done = true;
+    switch.gliding = vec![(first, from, to), (second, to, from)];
+    switch.timer = Timer::from_seconds(SWITCH_DURATION, TimerMode::Once);
+
+    // Let the paddles pass through each other and the waiting ball
+    commands.entity(first).insert(ColliderDisabled);
+    commands.entity(second).insert(ColliderDisabled);
+    let pause = goal_timer
+        .timer
+        .duration()
+        .as_secs_f32()
+        .max(SWITCH_DURATION);
+    goal_timer.timer = Timer::from_seconds(pause, TimerMode::Once);
+
+    commands.spawn(floating_text("SWITCH SIDES", Vec2::ZERO, SWITCH_TEXT_COLOR));
+}
+
+fn glide_paddles(
+    time: Res<Time>,
+    mut switch: ResMut<SideSwitch>,
+    mut paddles: Query<&mut Position, With<Player>>,
+) {
+    if switch.gliding.is_empty() {
+        return;
+    }
+    switch.timer.tick(time.delta());
+    let progress = EaseFunction::SmoothStep.sample_clamped(switch.timer.fraction());
+    for &(paddle, from, to) in &switch.gliding {
+        if let Ok(mut position) = paddles.get_mut(paddle) {
+            position.x = from.lerp(to, progress);
+        }
+    }
+}
+
+/// Hands each paddle the side it has arrived on
+fn finish_switch(
+    mut commands: Commands,
+    mut switch: ResMut<SideSwitch>,
+    mut paddles: Query<(&mut Player, &mut Position, &mut Sprite)>,
+    mut sides: SwappedBySide,
+) {
+    if switch.gliding.is_empty() {
+        return;
+    }
+    for (paddle, _, to) in std::mem::take(&mut switch.gliding) {
+        let Ok((mut player, mut position, mut sprite)) = paddles.get_mut(paddle) else {
+            continue;
+        };
+        position.x = to;
+        player.side = player.side.opponent();
+        sprite.flip_x = facing_court_flipped(player.side);
+        commands.entity(paddle).remove::<ColliderDisabled>();
+    }
+    sides.swap();
+    switch.switched = true;
+}
+
+/// Puts everything back on the sides the players started on, so the match
+/// is reported the way it was set up
+fn switch_back(
+    mut switch: ResMut<SideSwitch>,
+    mut paddles: Query<&mut Player>,
+    mut sides: SwappedBySide,
+) {
+    if !switch.switched {
+        return;
+    }
+    for mut player in &mut paddles {
+        player.side = player.side.opponent();
+    }
+    sides.swap();
+    switch.switched = false;
+}
+
+/// Everything kept per side that has to follow the players to their new ends
+#[derive(SystemParam)]
+struct SwappedBySide<'w> {
+    score: ResMut<'w, Score>,
+    stats: ResMut<'w, MatchStats>,
+    history: ResMut<'w, ScoreHistory>,
+    profiles: ResMut<'w, PlayerProfiles>,
+    rules: ResMut<'w, MatchRules>,
+    mirrored: ResMut<'w, MirroredCourt>,
+    serve_direction: ResMut<'w, ServeDirection>,
+}
+
+impl SwappedBySide<'_> {
+    fn swap(&mut self) {
+        let score = &mut *self.score;
+        std::mem::swap(&mut score.left, &mut score.right);
+
+        let stats = &mut *self.stats;
+        std::mem::swap(&mut stats.left, &mut stats.right);
+        for rally in &mut stats.rallies {
+            rally.scorer = rally.scorer.opponent();
+        }
+
+        for point in &mut self.history.points {
+            point.side = point.side.opponent();
+            std::mem::swap(&mut point.left, &mut point.right);
+        }
+
+        let profiles = &mut *self.profiles;
+        std::mem::swap(&mut profiles.left, &mut profiles.right);
+
+        let rules = &mut *self.rules;
+        std::mem::swap(&mut rules.left_handicap, &mut rules.right_handicap);
+
+        self.mirrored.0 = !self.mirrored.0;
+        self.serve_direction.side = self.serve_direction.side.opponent();
+    }
+}
@@ -0,0 +1,160 @@
+//! Tilt control: steer the left paddle by tilting the device.
+//!
+//! Selected with [`InputMode::Tilt`]. The paddle's speed follows how far the
+//! device is tilted from its calibrated neutral angle, ignoring small wobbles
+//! inside a dead zone. Orientation readings come from the browser's
+//! `deviceorientation` events, so only web builds have a sensor for now.
+
+use std::sync::{Arc, Mutex};
+
+use bevy::prelude::*;
+
+use super::player::{MirroredCourt, PaddleIntent, Player};
+use crate::{AppSystems, PausableSystems, screens::Screen};
+
+/// Tilt (degrees either side of neutral) ignored as hand wobble
+const DEAD_ZONE: f32 = 3.0;
+/// Tilt (degrees either side of neutral) for full paddle speed
+const FULL_SPEED_TILT: f32 = 20.0;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<InputMode>();
+    app.register_type::<TiltCalibration>();
+    app.init_resource::<InputMode>();
+    app.init_resource::<TiltCalibration>();
+    app.init_resource::<TiltSensor>();
+
+    app.add_systems(Startup, listen_for_tilt);
+    app.add_systems(
+        Update,
+        steer_with_tilt
+            .in_set(AppSystems::RecordInput)
+            .in_set(PausableSystems)
+            .run_if(in_state(Screen::Gameplay).and(resource_equals(InputMode::Tilt))),
+    );
+}
+
+/// How the left paddle is steered (besides the keyboard, which always works)
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Resource)]
+pub enum InputMode {
+    /// Swiping or on-screen buttons
+    #[default]
+    Touch,
+    /// Tilting the device
+    Tilt,
+}
+
+impl InputMode {
+    pub fn name(self) -> &'static str {
+        match self {
+            InputMode::Touch => "Touch",
+            InputMode::Tilt => "Tilt",
+        }
+    }
+
+    /// The other mode, for toggling in the settings menu
+    pub fn next(self) -> Self {
+        match self {
+            InputMode::Touch => InputMode::Tilt,
+            InputMode::Tilt => InputMode::Touch,
+        }
+    }
+
+    /// The previous mode, for stepping back in the settings menu. With only
+    /// two modes that's the other one too.
+    pub fn previous(self) -> Self {
+        match self {
+            InputMode::Touch => InputMode::Tilt,
+            InputMode::Tilt => InputMode::Touch,
+        }
+    }
+}
+
+/// The angle the device is held at when the paddle should stay still
+#[derive(Resource, Debug, Default, Reflect)]
+#[reflect(Resource)]
+pub struct TiltCalibration {
+    /// Degrees; set from the first reading if the player never calibrates
+    pub neutral: Option<f32>,
+}
+
+/// Latest device tilt in degrees, written by the orientation listener
+#[derive(Resource, Default)]
+pub struct TiltSensor {
+    reading: Arc<Mutex<Option<f32>>>,
+}
+
+impl TiltSensor {
+    /// The current tilt, or `None` if no reading has arrived (e.g. there's no
+    /// sensor)
+    pub fn reading(&self) -> Option<f32> {
+        self.reading.lock().ok().and_then(|reading| *reading)
+    }
+}
+
+impl TiltCalibration {
+    /// Takes the current tilt as neutral
+    pub fn calibrate(&mut self, sensor: &TiltSensor) {
+        if let Some(reading) = sensor.reading() {
+            info!("Tilt calibrated at {reading:.1} degrees");
+            self.neutral = Some(reading);
+        }
+    }
+}
+
+/// Maps tilt away from neutral to a paddle intent, with a dead zone
+fn tilt_intent(offset: f32) -> f32 {
+    let beyond_dead_zone = (offset.abs() - DEAD_ZONE).max(0.0);
+    let strength = (beyond_dead_zone / (FULL_SPEED_TILT - DEAD_ZONE)).min(1.0);
+    strength.copysign(offset)
+}
+
+fn steer_with_tilt(
+    sensor: Res<TiltSensor>,
+    mut calibration: ResMut<TiltCalibration>,
+    mirrored: Res<MirroredCourt>,
+    mut paddles: Query<(&Player, &mut PaddleIntent)>,
+) {
+    let Some(reading) = sensor.reading() else {
+        return;
+    };
+    let neutral = *calibration.neutral.get_or_insert(reading);
+
+    for (player, mut intent) in &mut paddles {
+        if player.side == mirrored.home_side() {
+            intent.0 = tilt_intent(reading - neutral);
+        }
+    }
+}
+
+/// Starts listening for device orientation. In landscape, tilting the top
+/// edge towards or away from the player changes `gamma`.
+#[cfg(target_family = "wasm")]
+fn listen_for_tilt(sensor: Res<TiltSensor>) {
+    use wasm_bindgen::{JsCast, closure::Closure};
+    use web_sys::DeviceOrientationEvent;
+
+    let Some(window) = web_sys::window() else {
+        return;
+    };
+
+    let reading = sensor.reading.clone();
+    let listener =
+        Closure::<dyn FnMut(DeviceOrientationEvent)>::new(move |event: DeviceOrientationEvent| {
+            if let (Some(gamma), Ok(mut reading)) = (event.gamma(), reading.lock()) {
+                *reading = Some(gamma as f32);
+            }
+        });
+    if let Err(error) = window
+        .add_event_listener_with_callback("deviceorientation", listener.as_ref().unchecked_ref())
+    {
+        warn!("Couldn't listen for device tilt: {error:?}");
+    }
+    // The listener stays registered for the lifetime of the page
+    listener.forget();
+}
+
+/// Native builds have no orientation sensor to read yet
+#[cfg(not(target_family = "wasm"))]
+fn listen_for_tilt() {}
@@ -0,0 +1,406 @@
+//! Touch and mouse paddle control, so the game is playable in a browser or on
+//! a touch screen without a keyboard.
+//!
+//! Dragging a finger (or holding the left mouse button) on the left half of
+//! the screen pulls the left paddle towards it. Tapping the other half serves,
+//! so the steering finger never does. Touches and clicks that land on menus
+//! and other UI are left to the UI.
+//!
+//! Players who'd rather not use gestures get on-screen buttons instead: up and
+//! down for each side plus a serve button. They show up automatically once the
+//! device is touched, and replace the gestures while they're shown.
+
+use bevy::{
+    picking::{hover::HoverMap, pointer::PointerId},
+    prelude::*,
+    ui::Val::*,
+    window::PrimaryWindow,
+};
+
+use super::{
+    camera::SplitCamera,
+    input_buffer::{BufferedAction, BufferedPress, InputBuffer},
+    player::{MirroredCourt, PaddleIntent, Player, PlayerSide},
+    tilt::InputMode,
+};
+use crate::{AppSystems, PausableSystems, screens::Screen};
+
+/// Distance from the pointer at which the paddle moves at full speed
+const RESPONSE_DISTANCE: f32 = 40.0; // pixels
+
+// On-screen buttons
+pub const MIN_BUTTON_SIZE: f32 = 48.0;
+pub const MAX_BUTTON_SIZE: f32 = 140.0;
+const DEFAULT_BUTTON_SIZE: f32 = 80.0;
+pub const MIN_BUTTON_OPACITY: f32 = 0.1;
+const DEFAULT_BUTTON_OPACITY: f32 = 0.5;
+const MIN_LABEL_OPACITY: f32 = 0.4; // Labels stay readable on faint buttons
+const BUTTON_BACKGROUND_ALPHA: f32 = 0.35; // Of the chosen opacity
+const BUTTON_GAP: f32 = 16.0;
+const BUTTON_MARGIN: f32 = 20.0;
+const BUTTON_CORNER_RADIUS: f32 = 12.0;
+const BUTTON_FONT_SIZE: f32 = 24.0;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<TouchControls>();
+    app.register_type::<TouchButton>();
+    app.register_type::<TouchButtonsRoot>();
+    app.init_resource::<TouchControls>();
+
+    app.add_systems(OnEnter(Screen::Gameplay), spawn_touch_buttons);
+    app.add_systems(Update, notice_touch);
+    app.add_systems(
+        Update,
+        (
+            style_touch_buttons.run_if(resource_changed::<TouchControls>),
+            press_touch_buttons.run_if(buttons_shown),
+            (
+                steer_with_pointer.run_if(resource_equals(InputMode::Touch)),
+                serve_on_tap,
+            )
+                .run_if(not(buttons_shown)),
+        )
+            .in_set(AppSystems::RecordInput)
+            .in_set(PausableSystems)
+            .run_if(in_state(Screen::Gameplay)),
+    );
+}
+
+/// Whether `position` (in logical pixels) is on `side`'s half of the window
+fn on_half(window: &Window, position: Vec2, side: PlayerSide) -> bool {
+    match side {
+        PlayerSide::Left => position.x < window.width() / 2.0,
+        PlayerSide::Right => position.x >= window.width() / 2.0,
+    }
+}
+
+/// Whether `pointer` is over a UI node, like a menu button, rather than the
+/// court
+fn over_ui(hover_map: &HoverMap, nodes: &Query<(), With<Node>>, pointer: PointerId) -> bool {
+    hover_map
+        .get(&pointer)
+        .is_some_and(|hits| hits.keys().any(|&entity| nodes.contains(entity)))
+}
+
+fn steer_with_pointer(
+    touches: Res<Touches>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    window: Single<&Window, With<PrimaryWindow>>,
+    camera: Single<(&Camera, &GlobalTransform), (With<Camera2d>, Without<SplitCamera>)>,
+    mirrored: Res<MirroredCourt>,
+    hover_map: Res<HoverMap>,
+    nodes: Query<(), With<Node>>,
+    mut paddles: Query<(&Player, &Transform, &mut PaddleIntent)>,
+    mut steering: Local<bool>,
+) {
+    let home = mirrored.home_side();
+    // The first finger on the home paddle's half, or the held mouse button,
+    // that isn't on the UI
+    let held_mouse = mouse
+        .pressed(MouseButton::Left)
+        .then(|| window.cursor_position())
+        .flatten()
+        .map(|position| (PointerId::Mouse, position));
+    let pointer = touches
+        .iter()
+        .map(|touch| (PointerId::Touch(touch.id()), touch.position()))
+        .chain(held_mouse)
+        .find(|&(id, position)| {
+            on_half(&window, position, home) && !over_ui(&hover_map, &nodes, id)
+        })
+        .map(|(_, position)| position);
+
+    let (camera, camera_transform) = *camera;
+    let target =
+        pointer.and_then(|pointer| camera.viewport_to_world_2d(camera_transform, pointer).ok());
+
+    // Only let go of the paddle once, so the keyboard still works otherwise
+    if target.is_none() && !*steering {
+        return;
+    }
+    *steering = target.is_some();
+
+    for (player, transform, mut intent) in &mut paddles {
+        if player.side != home {
+            continue;
+        }
+        intent.0 = match target {
+            Some(target) => {
+                ((target.y - transform.translation.y) / RESPONSE_DISTANCE).clamp(-1.0, 1.0)
+            }
+            None => 0.0,
+        };
+    }
+}
+
+/// Serves on a tap anywhere but the UI and, while steering by touch, the
+/// steering half
+fn serve_on_tap(
+    touches: Res<Touches>,
+    window: Single<&Window, With<PrimaryWindow>>,
+    mode: Res<InputMode>,
+    mirrored: Res<MirroredCourt>,
+    hover_map: Res<HoverMap>,
+    nodes: Query<(), With<Node>>,
+    time: Res<Time<Virtual>>,
+    mut buffer: ResMut<InputBuffer>,
+    mut presses: EventWriter<BufferedPress>,
+) {
+    let steering_half = (*mode == InputMode::Touch).then(|| mirrored.home_side());
+    let tapped = touches.iter_just_pressed().any(|touch| {
+        let steering = steering_half.is_some_and(|side| on_half(&window, touch.position(), side));
+        !steering && !over_ui(&hover_map, &nodes, PointerId::Touch(touch.id()))
+    });
+    if tapped {
+        buffer.press(BufferedAction::Serve, time.elapsed_secs());
+        presses.write(BufferedPress(BufferedAction::Serve));
+    }
+}
+
+/// When the on-screen buttons are shown
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+pub enum TouchButtonsMode {
+    /// Once the device has been touched
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+/// On-screen touch buttons, for players who'd rather not swipe
+#[derive(Resource, Debug, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct TouchControls {
+    pub mode: TouchButtonsMode,
+    /// Button width and height, in logical pixels
+    pub size: f32,
+    /// Background opacity, 0 to 1
+    pub opacity: f32,
+    /// Whether this device has been touched, for [`TouchButtonsMode::Auto`]
+    touch_seen: bool,
+}
+
+impl Default for TouchControls {
+    fn default() -> Self {
+        Self {
+            mode: TouchButtonsMode::default(),
+            size: DEFAULT_BUTTON_SIZE,
+            opacity: DEFAULT_BUTTON_OPACITY,
+            touch_seen: false,
+        }
+    }
+}
+
+impl TouchControls {
+    pub fn shown(&self) -> bool {
+        match self.mode {
+            TouchButtonsMode::Auto => self.touch_seen,
+            TouchButtonsMode::Always => true,
+            TouchButtonsMode::Never => false,
+        }
+    }
+}
+
+impl TouchButtonsMode {
+    pub fn name(self) -> &'static str {
+        match self {
+            TouchButtonsMode::Auto => "Auto",
+            TouchButtonsMode::Always => "On",
+            TouchButtonsMode::Never => "Off",
+        }
+    }
+
+    /// The next mode, for cycling through them in the settings menu
+    pub fn next(self) -> Self {
+        match self {
+            TouchButtonsMode::Auto => TouchButtonsMode::Always,
+            TouchButtonsMode::Always => TouchButtonsMode::Never,
+            TouchButtonsMode::Never => TouchButtonsMode::Auto,
+        }
+    }
+
+    /// The previous mode, for cycling backwards
+    pub fn previous(self) -> Self {
+        match self {
+            TouchButtonsMode::Auto => TouchButtonsMode::Never,
+            TouchButtonsMode::Always => TouchButtonsMode::Auto,
+            TouchButtonsMode::Never => TouchButtonsMode::Always,
+        }
+    }
+}
+
+fn buttons_shown(controls: Res<TouchControls>) -> bool {
+    controls.shown()
+}
+
+fn notice_touch(touches: Res<Touches>, mut controls: ResMut<TouchControls>) {
+    if !controls.touch_seen && touches.any_just_pressed() {
+        controls.touch_seen = true;
+    }
+}
+
+/// What an on-screen button does while held
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+enum TouchButton {
+    Up(PlayerSide),
+    Down(PlayerSide),
+    Serve,
+}
+
+/// Root of the on-screen buttons
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct TouchButtonsRoot;
+
+fn spawn_touch_buttons(mut commands: Commands, controls: Res<TouchControls>) {
+    let column = |side: PlayerSide| {
+        (
+            Name::new(format!("{side:?} Touch Buttons")),
+            Node {
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                row_gap: Px(BUTTON_GAP),
+                ..default()
+            },
+            Pickable::IGNORE,
+            children![
+                touch_button(TouchButton::Up(side), "^", &controls),
+                touch_button(TouchButton::Down(side), "v", &controls),
+            ],
+        )
+    };
+
+    commands.spawn((
+        Name::new("Touch Buttons"),
+        TouchButtonsRoot,
+        Node {
+            position_type: PositionType::Absolute,
+            width: Percent(100.0),
+            height: Percent(100.0),
+            justify_content: JustifyContent::SpaceBetween,
+            align_items: AlignItems::Center,
+            padding: UiRect::all(Px(BUTTON_MARGIN)),
+            ..default()
+        },
+        visibility(&controls),
+        // Buttons are hit-tested by hand so several can be held at once
+        Pickable::IGNORE,
+        StateScoped(Screen::Gameplay),
+        children![
+            column(PlayerSide::Left),
+            (
+                Name::new("Serve Button Row"),
+                Node {
+                    height: Percent(100.0),
+                    align_items: AlignItems::End,
+                    ..default()
+                },
+                Pickable::IGNORE,
+                children![touch_button(TouchButton::Serve, "Serve", &controls)],
+            ),
+            column(PlayerSide::Right),
+        ],
+    ));
+}
+
+fn touch_button(button: TouchButton, text: &str, controls: &TouchControls) -> impl Bundle {
+    (
+        Name::new("Touch Button"),
+        button,
+        button_node(controls),
+        BackgroundColor(button_color(controls)),
+        BorderRadius::all(Px(BUTTON_CORNER_RADIUS)),
+        Pickable::IGNORE,
+        children![(
+            Text::new(text),
+            TextFont::from_font_size(BUTTON_FONT_SIZE),
+            TextColor(Color::WHITE.with_alpha(controls.opacity.max(MIN_LABEL_OPACITY))),
+            Pickable::IGNORE,
+        )],
+    )
+}
+
+fn button_node(controls: &TouchControls) -> Node {
+    Node {
+        width: Px(controls.size),
+        height: Px(controls.size),
+        align_items: AlignItems::Center,
+        justify_content: JustifyContent::Center,
+        ..default()
+    }
+}
+
+fn button_color(controls: &TouchControls) -> Color {
+    Color::WHITE.with_alpha(controls.opacity * BUTTON_BACKGROUND_ALPHA)
+}
+
+fn visibility(controls: &TouchControls) -> Visibility {
+    if controls.shown() {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    }
+}
+
+/// Applies changed settings to buttons that are already on screen
+fn style_touch_buttons(
+    controls: Res<TouchControls>,
+    mut root: Single<&mut Visibility, With<TouchButtonsRoot>>,
+    mut buttons: Query<(&mut Node, &mut BackgroundColor), With<TouchButton>>,
+) {
+    **root = visibility(&controls);
+    for (mut node, mut background) in &mut buttons {
+        *node = button_node(&controls);
+        background.0 = button_color(&controls);
+    }
+}
+
+/// Drives paddles and serves from whichever buttons are under a finger
+fn press_touch_buttons(
+    touches: Res<Touches>,
+    time: Res<Time<Virtual>>,
+    buttons: Query<(&TouchButton, &ComputedNode, &GlobalTransform)>,
+    mut buffer: ResMut<InputBuffer>,
+    mut presses: EventWriter<BufferedPress>,
+    mut paddles: Query<(&Player, &mut PaddleIntent)>,
+    mut held_sides: Local<Vec<PlayerSide>>,
+) {
+    // UI nodes are laid out in physical pixels, touches are logical
+    let button_at = |position: Vec2| {
+        buttons.iter().find_map(|(button, node, transform)| {
+            let rect = Rect::from_center_size(transform.translation().truncate(), node.size());
+            rect.contains(position / node.inverse_scale_factor())
+                .then_some(*button)
+        })
+    };
+
+    let mut intents = [0.0; 2];
+    let mut sides = Vec::new();
+    for touch in touches.iter() {
+        match button_at(touch.position()) {
+            Some(TouchButton::Up(side)) => {
+                intents[side as usize] += 1.0;
+                sides.push(side);
+            }
+            Some(TouchButton::Down(side)) => {
+                intents[side as usize] -= 1.0;
+                sides.push(side);
+            }
+            Some(TouchButton::Serve) if touches.just_pressed(touch.id()) => {
+                buffer.press(BufferedAction::Serve, time.elapsed_secs());
+                presses.write(BufferedPress(BufferedAction::Serve));
+            }
+            _ => {}
+        }
+    }
+
+    // Leave paddles nobody is pressing a button for to the keyboard, but stop
+    // any that were just let go
+    for (player, mut intent) in &mut paddles {
+        if sides.contains(&player.side) || held_sides.contains(&player.side) {
+            intent.0 = intents[player.side as usize].clamp(-1.0, 1.0);
+        }
+    }
+    *held_sides = sides;
+}
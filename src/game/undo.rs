@@ -0,0 +1,237 @@
+//! Undoing the last point in casual matches.
+//!
+//! Friends can take back a glitchy goal a limited number of times per match.
+//! The match goes back to the start of the disputed point, restored from a
+//! [`GameSnapshot`] taken as each point is set up. Tournament matches don't
+//! allow undos.
+
+use bevy::{input::common_conditions::input_just_pressed, prelude::*};
+
+use super::{
+    GamePhase, events::GameEvent, overtime::Overtime, rules::MatchRules, scoring::Score,
+    snapshot::GameSnapshot,
+};
+use crate::{
+    AppSystems, PausableSystems,
+    screens::Screen,
+    theme::tween::{Toast, TweenAlpha, TweenOffset},
+    tournament::Tournament,
+};
+
+const UNDO_KEY: KeyCode = KeyCode::KeyU;
+
+// On-screen notice
+const NOTICE_DURATION: f32 = 1.5; // seconds
+const NOTICE_FONT_SIZE: f32 = 24.0;
+const NOTICE_Y_OFFSET: f32 = 200.0; // Distance from top
+const NOTICE_SLIDE_DISTANCE: f32 = 20.0;
+const NOTICE_FADE_DURATION: f32 = 0.2; // seconds
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<PointUndo>();
+    app.add_event::<PointUndone>();
+    app.add_systems(OnEnter(Screen::Gameplay), reset_undos);
+    app.add_systems(
+        OnEnter(GamePhase::WaitingToServe),
+        remember_point_start.run_if(in_state(Screen::Gameplay)),
+    );
+    app.add_systems(
+        Update,
+        undo_last_point
+            .in_set(AppSystems::RecordInput)
+            .run_if(input_just_pressed(UNDO_KEY).and(not(resource_exists::<Tournament>)))
+            .run_if(in_state(Screen::Gameplay))
+            .in_set(PausableSystems),
+    );
+}
+
+/// Sent after a point has been undone, e.g. for recording replays
+#[derive(Event, Debug, Clone, Copy)]
+pub struct PointUndone;
+
+/// Snapshots of the current and previous point starts
+#[derive(Resource, Default)]
+struct PointUndo {
+    /// Start of the point being played, or about to be
+    current: Option<GameSnapshot>,
+    /// Start of the point before that
+    previous: Option<GameSnapshot>,
+    used: u32,
+}
+
+fn reset_undos(mut undo: ResMut<PointUndo>) {
+    *undo = PointUndo::default();
+}
+
+fn remember_point_start(world: &mut World) {
+    let snapshot = GameSnapshot::capture(world);
+    let mut undo = world.resource_mut::<PointUndo>();
+    undo.previous = undo.current.take();
+    undo.current = snapshot;
+}
+
+fn undo_last_point(world: &mut World) {
+    let rules = world.resource::<MatchRules>();
+    let limit = rules.undo_limit;
+    if limit == 0 {
+        return;
+    }
+    // Points overlap with two balls, so there's no clean point start to go back to
+    if rules.two_balls {
+        spawn_notice(world, "No undos with two balls".into());
+        return;
+    }
+    // Nor is there once the players might have changed ends
+    if rules.switch_sides {
+        spawn_notice(world, "No undos when switching sides".into());
+        return;
+    }
+
+    let Some(phase) = world
+        .get_resource::<State<GamePhase>>()
+        .map(|state| *state.get())
+    else {
+        return;
+    };
+
+    let mut undo = world.resource_mut::<PointUndo>();
+    if undo.used >= limit {
+        spawn_notice(world, "No undos left".into());
+        return;
+    }
+
+    // Once the next point is set up, the disputed one is the previous point.
+    // The start of the undone point is dropped too, so it can't become the
+    // previous point again once the restored one is set up.
+    let snapshot = match phase {
+        GamePhase::WaitingToServe => {
+            let previous = undo.previous.take();
+            if previous.is_some() {
+                undo.current = None;
+            }
+            previous
+        }
+        GamePhase::GoalScored | GamePhase::GameOver => undo.current.take(),
+        GamePhase::Playing => None,
+    };
+    let Some(snapshot) = snapshot else {
+        spawn_notice(world, "Nothing to undo".into());
+        return;
+    };
+
+    // The restored point start becomes the current one again on re-entry
+    undo.previous = None;
+    undo.used += 1;
+    let left = limit - undo.used;
+
+    snapshot.restore(world);
+    world
+        .resource_mut::<NextState<GamePhase>>()
+        .set(GamePhase::WaitingToServe);
+
+    // Leave sudden death if the undone goal is what caused it
+    let still_sudden_death = world
+        .resource::<Score>()
+        .is_sudden_death(world.resource::<MatchRules>());
+    if !still_sudden_death {
+        world
+            .resource_mut::<NextState<Overtime>>()
+            .set(Overtime::Regulation);
+    }
+
+    world.send_event(PointUndone);
+    world.send_event(GameEvent::PointUndone);
+    info!("Point undone, {left} undos left");
+    spawn_notice(world, format!("Point undone - {left} left"));
+}
+
+fn spawn_notice(world: &mut World, message: String) {
+    world.spawn((
+        Name::new("Undo Notice"),
+        Toast::new(NOTICE_DURATION),
+        Text::new(message),
+        TextFont {
+            font_size: NOTICE_FONT_SIZE,
+            ..default()
+        },
+        TextColor(Color::srgb(0.6, 1.0, 0.6)),
+        TextLayout::new_with_justify(JustifyText::Center),
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            top: Val::Px(NOTICE_Y_OFFSET),
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        TweenOffset {
+            to: Vec2::new(0.0, NOTICE_Y_OFFSET),
+            ..TweenOffset::slide_in(
+                Vec2::new(0.0, NOTICE_Y_OFFSET - NOTICE_SLIDE_DISTANCE),
+                NOTICE_FADE_DURATION,
+            )
+        },
+        TweenAlpha::fade_in(NOTICE_FADE_DURATION),
+        StateScoped(Screen::Gameplay),
+    ));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{
+        ball::{Rally, ServeDirection, ServeRng},
+        scoring::GoalTimer,
+    };
+
+    fn match_world() -> World {
+        let mut world = World::new();
+        world.insert_resource(MatchRules {
+            undo_limit: 3,
+            ..default()
+        });
+        world.insert_resource(State::new(GamePhase::WaitingToServe));
+        world.init_resource::<NextState<GamePhase>>();
+        world.init_resource::<NextState<Overtime>>();
+        world.init_resource::<Score>();
+        world.init_resource::<GoalTimer>();
+        world.init_resource::<ServeDirection>();
+        world.init_resource::<Rally>();
+        world.init_resource::<ServeRng>();
+        world.init_resource::<PointUndo>();
+        world.init_resource::<Events<PointUndone>>();
+        world.init_resource::<Events<GameEvent>>();
+        world
+    }
+
+    fn score(world: &World) -> (u32, u32) {
+        let score = world.resource::<Score>();
+        (score.left, score.right)
+    }
+
+    #[test]
+    fn undo_serve_undo_only_takes_back_new_points() {
+        let mut world = match_world();
+        remember_point_start(&mut world);
+
+        // Left scores and the next point is set up
+        world.resource_mut::<Score>().left += 1;
+        remember_point_start(&mut world);
+
+        undo_last_point(&mut world);
+        assert_eq!(score(&world), (0, 0));
+        // The undone point is set up again
+        remember_point_start(&mut world);
+
+        // Pressing undo again must not bring the undone goal back
+        undo_last_point(&mut world);
+        assert_eq!(score(&world), (0, 0));
+        assert_eq!(world.resource::<PointUndo>().used, 1);
+
+        // Serve, Right scores this time, then undo that instead
+        world.resource_mut::<Score>().right += 1;
+        remember_point_start(&mut world);
+        undo_last_point(&mut world);
+        assert_eq!(score(&world), (0, 0));
+        assert_eq!(world.resource::<PointUndo>().used, 2);
+    }
+}
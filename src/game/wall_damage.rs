@@ -0,0 +1,64 @@
+//! Wall damage: the top and bottom walls are built from segments that break
+//! after a few hits, letting the ball out of the court through the gaps.
+//!
+//! An escaped ball is caught by the out-of-bounds sensors behind the walls and
+//! scored as a fault, just like on an open court.
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+
+use super::{ball::Ball, events::GameEvent};
+
+/// Hits a wall segment takes before it breaks
+pub const SEGMENT_HIT_POINTS: u32 = 3;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<WallSegment>();
+    app.add_observer(damage_wall_segment);
+}
+
+/// A breakable piece of the top or bottom wall
+#[derive(Component, Debug, Clone, Copy, Reflect)]
+#[reflect(Component)]
+pub struct WallSegment {
+    pub hit_points: u32,
+}
+
+impl Default for WallSegment {
+    fn default() -> Self {
+        Self {
+            hit_points: SEGMENT_HIT_POINTS,
+        }
+    }
+}
+
+/// Chips a segment each time a ball hits it, fading it as it weakens
+fn damage_wall_segment(
+    trigger: Trigger<OnCollisionStart>,
+    mut commands: Commands,
+    balls: Query<(), With<Ball>>,
+    mut segments: Query<(&mut WallSegment, &MeshMaterial2d<ColorMaterial>)>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut game_events: EventWriter<GameEvent>,
+) {
+    if !balls.contains(trigger.target()) {
+        return;
+    }
+    let segment_entity = trigger.event().collider;
+    let Ok((mut segment, material)) = segments.get_mut(segment_entity) else {
+        return;
+    };
+
+    segment.hit_points = segment.hit_points.saturating_sub(1);
+    if segment.hit_points == 0 {
+        info!("Wall segment broken");
+        game_events.write(GameEvent::WallBroken);
+        commands.entity(segment_entity).despawn();
+        return;
+    }
+
+    if let Some(material) = materials.get_mut(&material.0) {
+        let strength = segment.hit_points as f32 / SEGMENT_HIT_POINTS as f32;
+        material.color = material.color.with_alpha(0.25 + 0.75 * strength);
+    }
+}
@@ -0,0 +1,186 @@
+//! Stuck-ball detection and rescue.
+//!
+//! Physics glitches can leave the ball bouncing vertically forever or push it
+//! outside the court. The watchdog notices either case and, after a grace
+//! period, nudges the ball back into play or re-serves it.
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+
+use super::{
+    GamePhase,
+    ball::{Ball, ServeDirection, ServeRng, serve_ball},
+    config::GameConfig,
+    court::CourtSize,
+    player::PlayerSide,
+};
+use crate::{AppSystems, PausableSystems, screens::Screen, theme::tween::Toast};
+
+// Detection thresholds
+const MIN_HORIZONTAL_SPEED: f32 = 30.0; // pixels per second
+const OUT_OF_BOUNDS_MARGIN: f32 = 20.0; // pixels beyond the court edge
+
+// How long the ball must be stuck before we step in
+const STUCK_GRACE_PERIOD: f32 = 2.0; // seconds
+
+// Angle used when nudging a ball out of a vertical bounce loop
+const NUDGE_ANGLE: f32 = 30.0; // degrees from horizontal
+
+// On-screen notice
+const NOTICE_DURATION: f32 = 1.5; // seconds
+const NOTICE_FONT_SIZE: f32 = 24.0;
+const NOTICE_Y_OFFSET: f32 = 120.0; // Distance from top
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<BallWatchdog>();
+    app.add_systems(
+        Update,
+        rescue_stuck_balls
+            .in_set(AppSystems::Update)
+            .run_if(in_state(GamePhase::Playing))
+            .run_if(in_state(Screen::Gameplay))
+            .in_set(PausableSystems),
+    );
+}
+
+/// Tracks how long a ball has been stuck
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct BallWatchdog {
+    timer: Timer,
+}
+
+impl Default for BallWatchdog {
+    fn default() -> Self {
+        Self {
+            timer: Timer::from_seconds(STUCK_GRACE_PERIOD, TimerMode::Once),
+        }
+    }
+}
+
+/// Why the watchdog had to step in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum StuckReason {
+    /// Horizontal velocity collapsed (endless vertical bouncing)
+    Vertical,
+    /// The ball escaped the court
+    OutOfBounds,
+}
+
+impl StuckReason {
+    fn detect(position: Vec2, velocity: Vec2, court: CourtSize) -> Option<Self> {
+        let half_width = court.half_width() + OUT_OF_BOUNDS_MARGIN;
+        let half_height = court.half_height() + OUT_OF_BOUNDS_MARGIN;
+
+        if position.x.abs() > half_width || position.y.abs() > half_height {
+            Some(Self::OutOfBounds)
+        } else if velocity.x.abs() < MIN_HORIZONTAL_SPEED {
+            Some(Self::Vertical)
+        } else {
+            None
+        }
+    }
+
+    fn notice(self) -> &'static str {
+        match self {
+            Self::Vertical => "Ball nudged back into play",
+            Self::OutOfBounds => "Ball escaped - re-serving",
+        }
+    }
+}
+
+/// Watches every ball and rescues any that stay stuck past the grace period
+fn rescue_stuck_balls(
+    mut commands: Commands,
+    time: Res<Time>,
+    serve_direction: Res<ServeDirection>,
+    config: Res<GameConfig>,
+    court: Res<CourtSize>,
+    mut rng: ResMut<ServeRng>,
+    mut balls: Query<(
+        Entity,
+        &mut Transform,
+        &mut LinearVelocity,
+        &mut BallWatchdog,
+    )>,
+) {
+    for (entity, mut transform, mut velocity, mut watchdog) in &mut balls {
+        let Some(reason) =
+            StuckReason::detect(transform.translation.truncate(), velocity.0, *court)
+        else {
+            watchdog.timer.reset();
+            continue;
+        };
+
+        watchdog.timer.tick(time.delta());
+        if !watchdog.timer.finished() {
+            continue;
+        }
+        watchdog.timer.reset();
+
+        warn!("Ball {entity:?} stuck ({reason:?}), rescuing");
+        match reason {
+            StuckReason::Vertical => {
+                velocity.0 = nudge_velocity(velocity.0, serve_direction.side);
+            }
+            StuckReason::OutOfBounds => {
+                transform.translation.x = 0.0;
+                transform.translation.y = 0.0;
+                velocity.0 = Vec2::ZERO;
+                serve_ball(
+                    &mut commands,
+                    entity,
+                    &serve_direction,
+                    rng.draw_serve(&config.ball),
+                );
+            }
+        }
+
+        spawn_notice(&mut commands, reason.notice());
+    }
+}
+
+/// Redirects a near-vertical velocity into a playable angle, keeping its speed
+fn nudge_velocity(velocity: Vec2, serving_side: PlayerSide) -> Vec2 {
+    let speed = velocity.length().max(MIN_HORIZONTAL_SPEED);
+
+    // Keep drifting the way the ball was already leaning, otherwise send it
+    // toward the receiver like a fresh serve
+    let direction_x = if velocity.x.abs() > f32::EPSILON {
+        velocity.x.signum()
+    } else {
+        match serving_side {
+            PlayerSide::Left => 1.0,
+            PlayerSide::Right => -1.0,
+        }
+    };
+    let direction_y = if velocity.y < 0.0 { -1.0 } else { 1.0 };
+
+    let angle = NUDGE_ANGLE.to_radians();
+    Vec2::new(
+        angle.cos() * speed * direction_x,
+        angle.sin() * speed * direction_y,
+    )
+}
+
+fn spawn_notice(commands: &mut Commands, message: &str) {
+    commands.spawn((
+        Name::new("Watchdog Notice"),
+        Toast::new(NOTICE_DURATION),
+        Text::new(message),
+        TextFont {
+            font_size: NOTICE_FONT_SIZE,
+            ..default()
+        },
+        TextColor(Color::srgb(1.0, 0.8, 0.3)),
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            top: Val::Px(NOTICE_Y_OFFSET),
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        TextLayout::new_with_justify(JustifyText::Center),
+        StateScoped(Screen::Gameplay),
+    ));
+}
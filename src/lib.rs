@@ -0,0 +1,199 @@
+//! Paddlegeddon: Pong, escalated.
+//!
+//! Run the whole game with [`AppPlugin`], or embed it in another Bevy app (as
+//! a minigame, say) with [`PaddlegeddonPlugin`], which leaves the window,
+//! rendering and camera setup to the host. The [`prelude`] has the types
+//! you're most likely to need from outside.
+
+// Support configuring Bevy lints within code.
+#![cfg_attr(bevy_lint, feature(register_tool), register_tool(bevy))]
+
+mod arcade;
+mod asset_tracking;
+mod audio;
+#[cfg(not(target_family = "wasm"))]
+mod capture;
+#[cfg(not(target_family = "wasm"))]
+mod daily;
+#[cfg(feature = "dev")]
+mod dev_tools;
+mod display;
+mod game;
+mod menus;
+#[cfg(not(target_family = "wasm"))]
+mod mods;
+#[cfg(not(target_family = "wasm"))]
+mod replay;
+mod screens;
+mod storage;
+#[cfg(not(target_family = "wasm"))]
+mod streamer;
+mod theme;
+mod tournament;
+
+use avian2d::prelude::*;
+use bevy::{asset::AssetMetaCheck, prelude::*};
+
+use crate::game::{court::CourtSize, rules::MatchRules};
+
+/// The plugins, components and spawn functions for embedding the game
+pub mod prelude {
+    pub use crate::{
+        AppPlugin, PaddlegeddonPlugin,
+        game::{
+            GamePhase,
+            ball::{Ball, spawn_ball},
+            court::{CourtSize, spawn_court},
+            events::GameEvent,
+            level::spawn_level,
+            mutators::{Mutator, Mutators},
+            player::{PaddleHit, Player, PlayerSide, player},
+            rules::MatchRules,
+            scoring::{GoalScored, PointScored, Score},
+        },
+        screens::Screen,
+    };
+}
+
+/// Headless stress scenes, for benchmarks and profiling (dev builds only)
+#[cfg(feature = "dev")]
+pub mod stress {
+    pub use crate::game::stress::{StressScene, headless_app};
+}
+
+/// The complete game in its own window
+pub struct AppPlugin;
+
+impl Plugin for AppPlugin {
+    fn build(&self, app: &mut App) {
+        // Asset sources have to be registered before Bevy's asset plugin.
+        #[cfg(not(target_family = "wasm"))]
+        mods::register_source(app);
+
+        // Add Bevy plugins.
+        app.add_plugins(
+            DefaultPlugins
+                .set(AssetPlugin {
+                    // Wasm builds will check for meta files (that don't exist) if this isn't set.
+                    // This causes errors and even panics on web build on itch.
+                    // See https://github.com/bevyengine/bevy_github_ci_template/issues/48.
+                    meta_check: AssetMetaCheck::Never,
+                    ..default()
+                })
+                .set(WindowPlugin {
+                    primary_window: Window {
+                        title: "Paddlegeddon".to_string(),
+                        fit_canvas_to_parent: true,
+                        ..default()
+                    }
+                    .into(),
+                    ..default()
+                }),
+        );
+
+        app.add_plugins(PaddlegeddonPlugin::default());
+    }
+}
+
+/// The game without the window: everything [`AppPlugin`] adds on top of
+/// Bevy's `DefaultPlugins`, for embedding in another app.
+///
+/// The court is [`court_size`](Self::court_size) world units, 800 by 600
+/// unless the host asks for something else; the game camera zooms to fit it
+/// in whatever the host gives it.
+pub struct PaddlegeddonPlugin {
+    /// Rules for the first match, until the player changes them
+    pub rules: MatchRules,
+    /// Size of the court, in world units
+    pub court_size: CourtSize,
+    /// Spawn a 2D camera at startup. Turn off if the host app brings its own.
+    pub spawn_camera: bool,
+}
+
+impl Default for PaddlegeddonPlugin {
+    fn default() -> Self {
+        Self {
+            rules: MatchRules::default(),
+            court_size: CourtSize::default(),
+            spawn_camera: true,
+        }
+    }
+}
+
+impl Plugin for PaddlegeddonPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins((
+            PhysicsPlugins::default(),
+            arcade::plugin,
+            asset_tracking::plugin,
+            audio::plugin,
+            #[cfg(not(target_family = "wasm"))]
+            capture::plugin,
+            #[cfg(not(target_family = "wasm"))]
+            daily::plugin,
+            game::plugin,
+            #[cfg(feature = "dev")]
+            dev_tools::plugin,
+            display::plugin,
+            menus::plugin,
+            #[cfg(not(target_family = "wasm"))]
+            mods::plugin,
+            #[cfg(not(target_family = "wasm"))]
+            replay::plugin,
+            screens::plugin,
+            #[cfg(not(target_family = "wasm"))]
+            streamer::plugin,
+            theme::plugin,
+            tournament::plugin,
+        ));
+
+        // Order new `AppSystems` variants by adding them here:
+        app.configure_sets(
+            Update,
+            (
+                AppSystems::TickTimers,
+                AppSystems::RecordInput,
+                AppSystems::Update,
+            )
+                .chain(),
+        );
+
+        // Set up the `Pause` state.
+        app.init_state::<Pause>();
+        app.configure_sets(Update, PausableSystems.run_if(in_state(Pause(false))));
+
+        app.insert_resource(self.rules.clone());
+        app.insert_resource(self.court_size);
+
+        // Spawn the main camera.
+        if self.spawn_camera {
+            app.add_systems(Startup, spawn_camera);
+        }
+    }
+}
+
+/// High-level groupings of systems for the app in the `Update` schedule.
+/// When adding a new variant, make sure to order it in the `configure_sets`
+/// call above.
+#[derive(SystemSet, Debug, Clone, Copy, Eq, PartialEq, Hash, PartialOrd, Ord)]
+enum AppSystems {
+    /// Tick timers.
+    TickTimers,
+    /// Record player input.
+    RecordInput,
+    /// Do everything else (consider splitting this into further variants).
+    Update,
+}
+
+/// Whether or not the game is paused.
+#[derive(States, Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+#[states(scoped_entities)]
+struct Pause(pub bool);
+
+/// A system set for systems that shouldn't run while the game is paused.
+#[derive(SystemSet, Copy, Clone, Eq, PartialEq, Hash, Debug)]
+struct PausableSystems;
+
+fn spawn_camera(mut commands: Commands) {
+    commands.spawn((Name::new("Camera"), Camera2d));
+}
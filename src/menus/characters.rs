@@ -0,0 +1,177 @@
+//! The character select menu, where each player picks who they play as.
+
+use bevy::{
+    ecs::spawn::SpawnWith, input::common_conditions::input_just_pressed, prelude::*, ui::Val::*,
+};
+
+use crate::{
+    game::{
+        player::PlayerSide,
+        profile::PlayerProfiles,
+        roster::{Character, CharacterStats},
+    },
+    menus::Menu,
+    theme::prelude::*,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Menu::Characters), spawn_characters_menu);
+    app.add_systems(
+        Update,
+        go_back.run_if(in_state(Menu::Characters).and(input_just_pressed(KeyCode::Escape))),
+    );
+
+    app.register_type::<CharacterLabel>();
+    app.add_systems(
+        Update,
+        update_character_labels.run_if(in_state(Menu::Characters)),
+    );
+}
+
+fn spawn_characters_menu(mut commands: Commands) {
+    commands.spawn((
+        widget::ui_root("Characters Menu"),
+        GlobalZIndex(2),
+        StateScoped(Menu::Characters),
+        children![
+            widget::header("Choose Your Character"),
+            characters_grid(),
+            widget::button("Continue", open_mutators_menu),
+            widget::button("Back", go_back_on_click),
+        ],
+    ));
+}
+
+fn characters_grid() -> impl Bundle {
+    (
+        Name::new("Characters Grid"),
+        Node {
+            display: Display::Grid,
+            row_gap: Px(10.0),
+            column_gap: Px(30.0),
+            grid_template_columns: RepeatedGridTrack::px(2, 400.0),
+            ..default()
+        },
+        Children::spawn(SpawnWith(|parent: &mut ChildSpawner| {
+            for side in [PlayerSide::Left, PlayerSide::Right] {
+                parent.spawn((
+                    widget::label(match side {
+                        PlayerSide::Left => "Left Player",
+                        PlayerSide::Right => "Right Player",
+                    }),
+                    Node {
+                        justify_self: JustifySelf::End,
+                        ..default()
+                    },
+                ));
+                parent.spawn(character_widget(side));
+                // Stats under the picker, in the value column
+                parent.spawn(Node::default());
+                parent.spawn((
+                    widget::label(""),
+                    CharacterLabel {
+                        side,
+                        field: LabelField::Stats,
+                    },
+                ));
+            }
+        })),
+    )
+}
+
+fn character_widget(side: PlayerSide) -> impl Bundle {
+    (
+        Name::new("Character Widget"),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            widget::button_small(
+                "<",
+                move |_: Trigger<Pointer<Click>>, mut profiles: ResMut<PlayerProfiles>| {
+                    let profile = profiles.get_mut(side);
+                    profile.character = profile.character.previous();
+                }
+            ),
+            (
+                Name::new("Current Value"),
+                Node {
+                    padding: UiRect::horizontal(Px(10.0)),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![(
+                    widget::label(""),
+                    CharacterLabel {
+                        side,
+                        field: LabelField::Name,
+                    }
+                )],
+            ),
+            widget::button_small(
+                ">",
+                move |_: Trigger<Pointer<Click>>, mut profiles: ResMut<PlayerProfiles>| {
+                    let profile = profiles.get_mut(side);
+                    profile.character = profile.character.next();
+                }
+            ),
+        ],
+    )
+}
+
+#[derive(Reflect, Clone, Copy, Debug)]
+enum LabelField {
+    Name,
+    Stats,
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct CharacterLabel {
+    side: PlayerSide,
+    field: LabelField,
+}
+
+fn update_character_labels(
+    profiles: Res<PlayerProfiles>,
+    mut labels: Query<(&mut Text, &CharacterLabel)>,
+) {
+    for (mut text, label) in &mut labels {
+        let character = profiles.get(label.side).character;
+        text.0 = match label.field {
+            LabelField::Name => character.name().into(),
+            LabelField::Stats => describe(character),
+        };
+    }
+}
+
+/// E.g. "Size 1.0x, Speed 1.0x, Teleport: blink a short way toward the ball"
+fn describe(character: Character) -> String {
+    let CharacterStats {
+        size_multiplier,
+        speed_multiplier,
+        ability,
+    } = character.stats();
+    let mut description = format!("Size {size_multiplier:.2}x, Speed {speed_multiplier:.2}x");
+    if let Some(ability) = ability {
+        description.push_str(&format!(
+            "\n{} (E or right trigger): {}",
+            ability.name(),
+            ability.description()
+        ));
+    }
+    description
+}
+
+fn open_mutators_menu(_: Trigger<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Mutators);
+}
+
+fn go_back_on_click(_: Trigger<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Main);
+}
+
+fn go_back(mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Main);
+}
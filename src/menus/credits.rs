@@ -1,119 +0,0 @@
-//! The credits menu.
-
-use bevy::{
-    ecs::spawn::SpawnIter, input::common_conditions::input_just_pressed, prelude::*, ui::Val::*,
-};
-
-use crate::{asset_tracking::LoadResource, audio::music, menus::Menu, theme::prelude::*};
-
-pub(super) fn plugin(app: &mut App) {
-    app.add_systems(OnEnter(Menu::Credits), spawn_credits_menu);
-    app.add_systems(
-        Update,
-        go_back.run_if(in_state(Menu::Credits).and(input_just_pressed(KeyCode::Escape))),
-    );
-
-    app.register_type::<CreditsAssets>();
-    app.load_resource::<CreditsAssets>();
-    app.add_systems(OnEnter(Menu::Credits), start_credits_music);
-}
-
-fn spawn_credits_menu(mut commands: Commands) {
-    commands.spawn((
-        widget::ui_root("Credits Menu"),
-        GlobalZIndex(2),
-        StateScoped(Menu::Credits),
-        children![
-            widget::header("Created by"),
-            created_by(),
-            widget::header("Special Thanks"),
-            special_thanks(),
-            widget::header("Assets"),
-            assets(),
-            widget::button("Back", go_back_on_click),
-        ],
-    ));
-}
-
-fn created_by() -> impl Bundle {
-    grid(vec![[
-        "Jack Ford",
-        "Turned procrastination into a game mechanic",
-    ]])
-}
-
-fn special_thanks() -> impl Bundle {
-    grid(vec![[
-        "The Pattycake Mafia",
-        "Endured pre-alpha trauma with suspicious enthusiasm",
-    ]])
-}
-
-fn assets() -> impl Bundle {
-    grid(vec![
-        ["Ducky sprite", "CC0 by Caz Creates Games"],
-        ["Button SFX", "CC0 by Jaszunio15"],
-        ["Music", "CC BY 3.0 by Kevin MacLeod"],
-        ["Bevy logo", "All rights reserved by the Bevy Foundation"],
-    ])
-}
-
-fn grid(content: Vec<[&'static str; 2]>) -> impl Bundle {
-    (
-        Name::new("Grid"),
-        Node {
-            display: Display::Grid,
-            row_gap: Px(10.0),
-            column_gap: Px(30.0),
-            grid_template_columns: RepeatedGridTrack::px(2, 400.0),
-            ..default()
-        },
-        Children::spawn(SpawnIter(content.into_iter().flatten().enumerate().map(
-            |(i, text)| {
-                (
-                    widget::label(text),
-                    Node {
-                        justify_self: if i % 2 == 0 {
-                            JustifySelf::End
-                        } else {
-                            JustifySelf::Start
-                        },
-                        ..default()
-                    },
-                )
-            },
-        ))),
-    )
-}
-
-fn go_back_on_click(_: Trigger<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
-    next_menu.set(Menu::Main);
-}
-
-fn go_back(mut next_menu: ResMut<NextState<Menu>>) {
-    next_menu.set(Menu::Main);
-}
-
-#[derive(Resource, Asset, Clone, Reflect)]
-#[reflect(Resource)]
-struct CreditsAssets {
-    #[dependency]
-    music: Handle<AudioSource>,
-}
-
-impl FromWorld for CreditsAssets {
-    fn from_world(world: &mut World) -> Self {
-        let assets = world.resource::<AssetServer>();
-        Self {
-            music: assets.load("audio/music/Monkeys Spinning Monkeys.ogg"),
-        }
-    }
-}
-
-fn start_credits_music(mut commands: Commands, credits_music: Res<CreditsAssets>) {
-    commands.spawn((
-        Name::new("Credits Music"),
-        StateScoped(Menu::Credits),
-        music(credits_music.music.clone()),
-    ));
-}
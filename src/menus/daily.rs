@@ -0,0 +1,76 @@
+//! The daily challenge menu, showing today's setup and best result.
+
+use bevy::{input::common_conditions::input_just_pressed, prelude::*};
+
+use crate::{
+    asset_tracking::ResourceHandles,
+    daily::{DailyBest, DailyChallenge, DailySetup},
+    game::{
+        mutators::Mutators,
+        player::{MirroredCourt, PlayerSide},
+        rules::MatchRules,
+    },
+    menus::Menu,
+    screens::Screen,
+    theme::prelude::*,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Menu::Daily), spawn_daily_menu);
+    app.add_systems(
+        Update,
+        go_back.run_if(in_state(Menu::Daily).and(input_just_pressed(KeyCode::Escape))),
+    );
+}
+
+fn spawn_daily_menu(mut commands: Commands, mirrored: Res<MirroredCourt>) {
+    let setup = DailySetup::today();
+    let best = DailyBest::load(setup.day).map_or("No result yet today".to_string(), |best| {
+        format!("Today's best: {:+}", best.score)
+    });
+
+    commands.spawn((
+        widget::ui_root("Daily Challenge Menu"),
+        GlobalZIndex(2),
+        StateScoped(Menu::Daily),
+        children![
+            widget::header("Daily Challenge"),
+            widget::label(setup.describe()),
+            widget::label(match mirrored.home_side() {
+                PlayerSide::Left => "You play left. Scored on point difference.",
+                PlayerSide::Right => "You play right. Scored on point difference.",
+            }),
+            widget::label(best),
+            widget::button("Play", start_daily_challenge),
+            widget::button("Back", go_back_on_click),
+        ],
+    ));
+}
+
+fn start_daily_challenge(
+    _: Trigger<Pointer<Click>>,
+    mut commands: Commands,
+    mut rules: ResMut<MatchRules>,
+    mut mutators: ResMut<Mutators>,
+    resource_handles: Res<ResourceHandles>,
+    mut next_screen: ResMut<NextState<Screen>>,
+) {
+    commands.insert_resource(DailyChallenge::start(
+        DailySetup::today(),
+        &mut rules,
+        &mut mutators,
+    ));
+    next_screen.set(if resource_handles.is_all_done() {
+        Screen::Gameplay
+    } else {
+        Screen::Loading
+    });
+}
+
+fn go_back_on_click(_: Trigger<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Modes);
+}
+
+fn go_back(mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Modes);
+}
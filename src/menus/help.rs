@@ -0,0 +1,146 @@
+//! The help overlay, explaining how the current match is played.
+//!
+//! Everything here is generated from the live [`MatchRules`] and [`Mutators`],
+//! so it always describes the match as it's actually set up.
+
+use bevy::{
+    ecs::spawn::SpawnIter, input::common_conditions::input_just_pressed, prelude::*, ui::Val::*,
+};
+
+use crate::{
+    game::{
+        ball_kind::BallKind,
+        mutators::Mutators,
+        rules::{CourtLayout, MatchRules, SERVES_PER_TURN, ScoringSystem, ServeRotation},
+    },
+    menus::Menu,
+    screens::Screen,
+    theme::prelude::*,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Menu::Help), spawn_help_menu);
+    app.add_systems(
+        Update,
+        go_back.run_if(in_state(Menu::Help).and(input_just_pressed(KeyCode::Escape))),
+    );
+}
+
+fn spawn_help_menu(mut commands: Commands, rules: Res<MatchRules>, mutators: Res<Mutators>) {
+    let lines = help_lines(&rules, &mutators);
+
+    commands.spawn((
+        widget::ui_root("Help Menu"),
+        GlobalZIndex(2),
+        StateScoped(Menu::Help),
+        children![
+            widget::header("How to Play"),
+            (
+                Name::new("Help Lines"),
+                Node {
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    row_gap: Px(6.0),
+                    ..default()
+                },
+                Children::spawn(SpawnIter(lines.into_iter().map(widget::label))),
+            ),
+            widget::button("Back", go_back_on_click),
+        ],
+    ));
+}
+
+/// One sentence per rule that matters for this match
+fn help_lines(rules: &MatchRules, mutators: &Mutators) -> Vec<String> {
+    let mut lines = Vec::new();
+
+    lines.push(if rules.win_by_two {
+        format!(
+            "First to {} wins, but you must win by 2.",
+            rules.target_score
+        )
+    } else {
+        format!("First to {} wins.", rules.target_score)
+    });
+    lines.push(format!(
+        "Reach {} while your opponent has none and it's over early.",
+        rules.mercy_score
+    ));
+    if rules.sudden_death && !rules.win_by_two && rules.shrinking_court {
+        lines.push("At match point all, the walls close in.".into());
+    }
+
+    lines.push(match rules.scoring {
+        ScoringSystem::Rally => "Every rally scores a point.".into(),
+        ScoringSystem::SideOut => {
+            "Only the server scores. Win a rally as receiver to take the serve.".into()
+        }
+    });
+    lines.push(match rules.effective_serve_rotation() {
+        ServeRotation::LoserServes => "Whoever concedes serves next.".into(),
+        ServeRotation::WinnerServes => "Whoever scores serves next.".into(),
+        ServeRotation::Alternate => format!("Serve changes every {SERVES_PER_TURN} points."),
+    });
+    if rules.let_serves {
+        lines.push("A serve that clips a wall is a let and is served again.".into());
+    }
+
+    lines.push(match rules.court {
+        CourtLayout::Walled if rules.wall_damage => {
+            "Walls crack and break after repeated hits.".into()
+        }
+        CourtLayout::Walled => "The ball bounces off the top and bottom walls.".into(),
+        CourtLayout::Open => {
+            "There are no walls: knock the ball out the top or bottom and it's a fault.".into()
+        }
+    });
+    if rules.moving_goals {
+        lines.push("Goals only cover part of each end and sweep up and down.".into());
+    }
+    if rules.bricks {
+        lines.push("Break the bricks mid-court for bonus points.".into());
+    }
+    lines.push(if rules.own_goals {
+        "Own goals count for your opponent.".into()
+    } else {
+        "Own goals are replayed.".into()
+    });
+    if rules.ball != BallKind::Classic {
+        lines.push(format!("Playing with the {} ball.", rules.ball.name()));
+    }
+    if rules.two_balls {
+        lines.push("Two balls stay in play all match.".into());
+    }
+    if rules.undo_limit > 0 {
+        lines.push(format!(
+            "You can undo up to {} points a match.",
+            rules.undo_limit
+        ));
+    }
+
+    for mutator in mutators.iter() {
+        lines.push(format!("{}: {}.", mutator.name(), mutator.description()));
+    }
+
+    lines
+}
+
+fn go_back_on_click(
+    _: Trigger<Pointer<Click>>,
+    screen: Res<State<Screen>>,
+    mut next_menu: ResMut<NextState<Menu>>,
+) {
+    next_menu.set(if screen.get() == &Screen::Title {
+        Menu::Modes
+    } else {
+        Menu::Pause
+    });
+}
+
+fn go_back(screen: Res<State<Screen>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(if screen.get() == &Screen::Title {
+        Menu::Modes
+    } else {
+        Menu::Pause
+    });
+}
@@ -0,0 +1,67 @@
+//! The arcade high-score table.
+
+use bevy::{
+    ecs::spawn::SpawnIter, input::common_conditions::input_just_pressed, prelude::*, ui::Val::*,
+};
+
+use crate::{arcade::ArcadeHighScores, menus::Menu, theme::prelude::*};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Menu::HighScores), spawn_high_scores_menu);
+    app.add_systems(
+        Update,
+        go_back.run_if(in_state(Menu::HighScores).and(input_just_pressed(KeyCode::Escape))),
+    );
+}
+
+fn spawn_high_scores_menu(mut commands: Commands, high_scores: Res<ArcadeHighScores>) {
+    commands.spawn((
+        widget::ui_root("High Scores Menu"),
+        GlobalZIndex(2),
+        StateScoped(Menu::HighScores),
+        children![
+            widget::header("Arcade High Scores"),
+            high_score_grid(&high_scores),
+            widget::button("Back", go_back_on_click),
+        ],
+    ));
+}
+
+fn high_score_grid(high_scores: &ArcadeHighScores) -> impl Bundle {
+    let cells = if high_scores.entries.is_empty() {
+        vec![widget::label("No high scores yet")]
+    } else {
+        high_scores
+            .entries
+            .iter()
+            .enumerate()
+            .flat_map(|(rank, entry)| {
+                [
+                    widget::label(format!("{}.", rank + 1)),
+                    widget::label(entry.initials.clone()),
+                    widget::label(entry.score.to_string()),
+                ]
+            })
+            .collect()
+    };
+
+    (
+        Name::new("High Score Grid"),
+        Node {
+            display: Display::Grid,
+            row_gap: Px(10.0),
+            column_gap: Px(40.0),
+            grid_template_columns: RepeatedGridTrack::auto(3),
+            ..default()
+        },
+        Children::spawn(SpawnIter(cells.into_iter())),
+    )
+}
+
+fn go_back_on_click(_: Trigger<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Stats);
+}
+
+fn go_back(mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Stats);
+}
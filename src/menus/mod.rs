@@ -1,9 +1,19 @@
 //! The game's menus and transitions between them.
 
-mod credits;
+mod characters;
+#[cfg(not(target_family = "wasm"))]
+mod daily;
+mod help;
+mod high_scores;
 mod main;
+mod modes;
+mod mutators;
 mod pause;
+mod rules;
 mod settings;
+mod stats;
+mod still_there;
+mod tournament;
 
 use bevy::prelude::*;
 
@@ -11,10 +21,20 @@ pub(super) fn plugin(app: &mut App) {
     app.init_state::<Menu>();
 
     app.add_plugins((
-        credits::plugin,
+        #[cfg(not(target_family = "wasm"))]
+        daily::plugin,
+        characters::plugin,
+        help::plugin,
+        high_scores::plugin,
         main::plugin,
+        modes::plugin,
+        mutators::plugin,
         settings::plugin,
+        stats::plugin,
+        still_there::plugin,
         pause::plugin,
+        rules::plugin,
+        tournament::plugin,
     ));
 }
 
@@ -24,7 +44,16 @@ pub enum Menu {
     #[default]
     None,
     Main,
-    Credits,
+    Characters,
+    Modes,
+    Mutators,
+    Daily,
+    Help,
     Settings,
+    Stats,
+    HighScores,
     Pause,
+    StillThere,
+    Rules,
+    Tournament,
 }
@@ -0,0 +1,109 @@
+//! The modes menu, for everything beyond a quick match.
+
+use bevy::{input::common_conditions::input_just_pressed, prelude::*};
+
+use crate::{
+    arcade::{self, Arcade},
+    asset_tracking::ResourceHandles,
+    game::{coach::Practice, mutators::Mutators, rules::MatchRules},
+    menus::Menu,
+    screens::Screen,
+    theme::widget,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Menu::Modes), spawn_modes_menu);
+    app.add_systems(
+        Update,
+        go_back.run_if(in_state(Menu::Modes).and(input_just_pressed(KeyCode::Escape))),
+    );
+}
+
+fn spawn_modes_menu(mut commands: Commands) {
+    commands.spawn((
+        widget::ui_root("Modes Menu"),
+        GlobalZIndex(2),
+        StateScoped(Menu::Modes),
+        #[cfg(not(target_family = "wasm"))]
+        children![
+            widget::header("Modes"),
+            widget::button("Practice", start_practice),
+            widget::button("Arcade", start_arcade),
+            widget::button("Daily Challenge", open_daily_menu),
+            widget::button("Tournament", open_tournament_menu),
+            widget::button("Match Rules", open_rules_menu),
+            widget::button("Help", open_help_menu),
+            widget::button("Back", go_back_on_click),
+        ],
+        #[cfg(target_family = "wasm")]
+        children![
+            widget::header("Modes"),
+            widget::button("Practice", start_practice),
+            widget::button("Arcade", start_arcade),
+            widget::button("Tournament", open_tournament_menu),
+            widget::button("Match Rules", open_rules_menu),
+            widget::button("Help", open_help_menu),
+            widget::button("Back", go_back_on_click),
+        ],
+    ));
+}
+
+/// A match with the coach's hints, under the current rules
+fn start_practice(
+    _: Trigger<Pointer<Click>>,
+    mut commands: Commands,
+    resource_handles: Res<ResourceHandles>,
+    mut next_screen: ResMut<NextState<Screen>>,
+) {
+    commands.insert_resource(Practice);
+    next_screen.set(if resource_handles.is_all_done() {
+        Screen::Gameplay
+    } else {
+        Screen::Loading
+    });
+}
+
+/// Hands the game over to arcade mode's attract demo
+fn start_arcade(
+    _: Trigger<Pointer<Click>>,
+    mut commands: Commands,
+    mut rules: ResMut<MatchRules>,
+    mut mutators: ResMut<Mutators>,
+    resource_handles: Res<ResourceHandles>,
+    mut next_arcade: ResMut<NextState<Arcade>>,
+    mut next_screen: ResMut<NextState<Screen>>,
+) {
+    arcade::enter_arcade(
+        &mut commands,
+        &mut rules,
+        &mut mutators,
+        &resource_handles,
+        &mut next_arcade,
+        &mut next_screen,
+    );
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn open_daily_menu(_: Trigger<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Daily);
+}
+
+fn open_tournament_menu(_: Trigger<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Tournament);
+}
+
+fn open_help_menu(_: Trigger<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Help);
+}
+
+fn open_rules_menu(_: Trigger<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Rules);
+}
+
+fn go_back_on_click(_: Trigger<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Main);
+}
+
+fn go_back(mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Main);
+}
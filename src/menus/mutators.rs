@@ -0,0 +1,210 @@
+//! The pre-match menu, where mutators are toggled before the match starts.
+
+use bevy::{
+    ecs::spawn::SpawnWith, input::common_conditions::input_just_pressed, prelude::*, ui::Val::*,
+};
+
+#[cfg(all(feature = "scripting", not(target_family = "wasm")))]
+use crate::game::scripting::ScriptedMutators;
+use crate::{
+    asset_tracking::ResourceHandles,
+    game::mutators::{Mutator, Mutators},
+    menus::Menu,
+    screens::Screen,
+    theme::prelude::*,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Menu::Mutators), spawn_mutators_menu);
+    app.add_systems(
+        Update,
+        go_back.run_if(in_state(Menu::Mutators).and(input_just_pressed(KeyCode::Escape))),
+    );
+
+    app.register_type::<MutatorLabel>();
+    app.add_systems(
+        Update,
+        update_mutator_labels.run_if(in_state(Menu::Mutators)),
+    );
+    #[cfg(all(feature = "scripting", not(target_family = "wasm")))]
+    {
+        app.register_type::<ScriptLabel>();
+        app.add_systems(
+            Update,
+            update_script_labels.run_if(in_state(Menu::Mutators)),
+        );
+    }
+}
+
+fn spawn_mutators_menu(
+    mut commands: Commands,
+    #[cfg(all(feature = "scripting", not(target_family = "wasm")))] scripts: Res<ScriptedMutators>,
+) {
+    #[cfg(all(feature = "scripting", not(target_family = "wasm")))]
+    let grid = mutators_grid(scripts.names().map(String::from).collect());
+    #[cfg(not(all(feature = "scripting", not(target_family = "wasm"))))]
+    let grid = mutators_grid();
+
+    commands.spawn((
+        widget::ui_root("Mutators Menu"),
+        GlobalZIndex(2),
+        StateScoped(Menu::Mutators),
+        children![
+            widget::header("Mutators"),
+            grid,
+            widget::button("Start Match", enter_loading_or_gameplay_screen),
+            widget::button("Back", go_back_on_click),
+        ],
+    ));
+}
+
+/// The built-in mutators, then any scripted ones
+fn mutators_grid(
+    #[cfg(all(feature = "scripting", not(target_family = "wasm")))] scripts: Vec<String>,
+) -> impl Bundle {
+    (
+        Name::new("Mutators Grid"),
+        Node {
+            display: Display::Grid,
+            row_gap: Px(10.0),
+            column_gap: Px(30.0),
+            grid_template_columns: RepeatedGridTrack::px(2, 400.0),
+            ..default()
+        },
+        Children::spawn(SpawnWith(move |parent: &mut ChildSpawner| {
+            for mutator in Mutator::ALL {
+                parent.spawn((
+                    widget::label(mutator.name()),
+                    Node {
+                        justify_self: JustifySelf::End,
+                        ..default()
+                    },
+                ));
+                parent.spawn(mutator_widget(mutator));
+            }
+            #[cfg(all(feature = "scripting", not(target_family = "wasm")))]
+            for name in scripts {
+                parent.spawn((
+                    widget::label(name.clone()),
+                    Node {
+                        justify_self: JustifySelf::End,
+                        ..default()
+                    },
+                ));
+                parent.spawn(script_widget(name));
+            }
+        })),
+    )
+}
+
+fn mutator_widget(mutator: Mutator) -> impl Bundle {
+    let toggle = move |_: Trigger<Pointer<Click>>, mut mutators: ResMut<Mutators>| {
+        mutators.toggle(mutator);
+    };
+
+    (
+        Name::new("Mutator Widget"),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            widget::button_small("-", toggle),
+            (
+                Name::new("Current Value"),
+                Node {
+                    padding: UiRect::horizontal(Px(10.0)),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![(widget::label(""), MutatorLabel(mutator))],
+            ),
+            widget::button_small("+", toggle),
+        ],
+    )
+}
+
+#[cfg(all(feature = "scripting", not(target_family = "wasm")))]
+fn script_widget(name: String) -> impl Bundle {
+    let toggle = {
+        let name = name.clone();
+        move |_: Trigger<Pointer<Click>>, mut scripts: ResMut<ScriptedMutators>| {
+            scripts.toggle(&name);
+        }
+    };
+
+    (
+        Name::new("Script Widget"),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            widget::button_small("-", toggle.clone()),
+            (
+                Name::new("Current Value"),
+                Node {
+                    padding: UiRect::horizontal(Px(10.0)),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![(widget::label(""), ScriptLabel(name))],
+            ),
+            widget::button_small("+", toggle),
+        ],
+    )
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct MutatorLabel(Mutator);
+
+/// Shows whether the named scripted mutator is on
+#[cfg(all(feature = "scripting", not(target_family = "wasm")))]
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct ScriptLabel(String);
+
+fn update_mutator_labels(mutators: Res<Mutators>, mut labels: Query<(&mut Text, &MutatorLabel)>) {
+    for (mut text, label) in &mut labels {
+        text.0 = if mutators.is_active(label.0) {
+            "On".into()
+        } else {
+            "Off".into()
+        };
+    }
+}
+
+#[cfg(all(feature = "scripting", not(target_family = "wasm")))]
+fn update_script_labels(
+    scripts: Res<ScriptedMutators>,
+    mut labels: Query<(&mut Text, &ScriptLabel)>,
+) {
+    for (mut text, label) in &mut labels {
+        text.0 = if scripts.is_enabled(&label.0) {
+            "On".into()
+        } else {
+            "Off".into()
+        };
+    }
+}
+
+fn enter_loading_or_gameplay_screen(
+    _: Trigger<Pointer<Click>>,
+    resource_handles: Res<ResourceHandles>,
+    mut next_screen: ResMut<NextState<Screen>>,
+) {
+    if resource_handles.is_all_done() {
+        next_screen.set(Screen::Gameplay);
+    } else {
+        next_screen.set(Screen::Loading);
+    }
+}
+
+fn go_back_on_click(_: Trigger<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Characters);
+}
+
+fn go_back(mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Characters);
+}
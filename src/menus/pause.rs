@@ -2,7 +2,15 @@
 
 use bevy::{input::common_conditions::input_just_pressed, prelude::*};
 
-use crate::{menus::Menu, screens::Screen, theme::widget};
+use crate::{
+    game::{
+        profile::PlayerProfiles,
+        score_history::{ScoreHistory, timeline},
+    },
+    menus::Menu,
+    screens::Screen,
+    theme::prelude::*,
+};
 
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(OnEnter(Menu::Pause), spawn_pause_menu);
@@ -12,18 +20,31 @@ pub(super) fn plugin(app: &mut App) {
     );
 }
 
-fn spawn_pause_menu(mut commands: Commands) {
-    commands.spawn((
+fn spawn_pause_menu(
+    mut commands: Commands,
+    history: Res<ScoreHistory>,
+    profiles: Res<PlayerProfiles>,
+) {
+    let mut menu = commands.spawn((
         widget::ui_root("Pause Menu"),
         GlobalZIndex(2),
         StateScoped(Menu::Pause),
         children![
             widget::header("Game paused"),
             widget::button("Continue", close_menu),
+            widget::button("Help", open_help_menu),
             widget::button("Settings", open_settings_menu),
-            widget::button("Quit to title", quit_to_title),
+            widget::button("Quit to title", confirm_quit_to_title),
         ],
     ));
+    // How the match has gone so far, once there's something to show
+    if !history.points.is_empty() {
+        menu.with_child(timeline(&history, &profiles));
+    }
+}
+
+fn open_help_menu(_: Trigger<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Help);
 }
 
 fn open_settings_menu(_: Trigger<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
@@ -34,6 +55,17 @@ fn close_menu(_: Trigger<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>
     next_menu.set(Menu::None);
 }
 
+fn confirm_quit_to_title(_: Trigger<Pointer<Click>>, mut commands: Commands) {
+    commands.spawn((
+        confirm_dialog(
+            "Quit this match?\nIt won't be saved.",
+            "Quit",
+            quit_to_title,
+        ),
+        StateScoped(Menu::Pause),
+    ));
+}
+
 fn quit_to_title(_: Trigger<Pointer<Click>>, mut next_screen: ResMut<NextState<Screen>>) {
     next_screen.set(Screen::Title);
 }
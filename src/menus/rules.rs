@@ -0,0 +1,350 @@
+//! The match rules menu, where scoring rules and handicaps are set before a match.
+
+use bevy::{
+    ecs::spawn::SpawnWith, input::common_conditions::input_just_pressed, prelude::*, ui::Val::*,
+};
+
+use crate::{
+    game::{
+        ball_kind::BallKind,
+        player::PlayerSide,
+        rules::{
+            CourtLayout, MAX_SCORE_OFFSET, MAX_SERVE_CLOCK, MAX_SIZE_MULTIPLIER,
+            MAX_SPEED_MULTIPLIER, MAX_TARGET_SCORE, MAX_UNDO_LIMIT, MIN_SIZE_MULTIPLIER,
+            MIN_SPEED_MULTIPLIER, MIN_TARGET_SCORE, MatchRules, SERVE_CLOCK_STEP, SERVES_PER_TURN,
+            ScoringSystem, ServeClockPenalty, ServeRotation,
+        },
+    },
+    menus::Menu,
+    theme::prelude::*,
+};
+
+const MULTIPLIER_STEP: f32 = 0.1;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Menu::Rules), spawn_rules_menu);
+    app.add_systems(
+        Update,
+        go_back.run_if(in_state(Menu::Rules).and(input_just_pressed(KeyCode::Escape))),
+    );
+
+    app.register_type::<RuleLabel>();
+    app.add_systems(Update, update_rule_labels.run_if(in_state(Menu::Rules)));
+}
+
+fn spawn_rules_menu(mut commands: Commands) {
+    commands.spawn((
+        widget::ui_root("Rules Menu"),
+        GlobalZIndex(2),
+        StateScoped(Menu::Rules),
+        children![
+            widget::header("Match Rules"),
+            rules_grid(),
+            widget::button("Back", go_back_on_click),
+        ],
+    ));
+}
+
+/// A rule that can be adjusted from this menu
+#[derive(Reflect, Clone, Copy, Debug)]
+enum Rule {
+    TargetScore,
+    WinByTwo,
+    SuddenDeath,
+    ShrinkingCourt,
+    Scoring,
+    ServeRotation,
+    LetServes,
+    ServeClock,
+    ServeClockPenalty,
+    ServeFeints,
+    Court,
+    WallDamage,
+    MovingGoals,
+    Bricks,
+    UndoLimit,
+    Ball,
+    TwoBalls,
+    OwnGoals,
+    SwitchSides,
+    ScoreOffset(PlayerSide),
+    Speed(PlayerSide),
+    Size(PlayerSide),
+}
+
+impl Rule {
+    const ALL: [Rule; 25] = [
+        Rule::TargetScore,
+        Rule::WinByTwo,
+        Rule::SuddenDeath,
+        Rule::ShrinkingCourt,
+        Rule::Scoring,
+        Rule::ServeRotation,
+        Rule::LetServes,
+        Rule::ServeClock,
+        Rule::ServeClockPenalty,
+        Rule::ServeFeints,
+        Rule::Court,
+        Rule::WallDamage,
+        Rule::MovingGoals,
+        Rule::Bricks,
+        Rule::UndoLimit,
+        Rule::Ball,
+        Rule::TwoBalls,
+        Rule::OwnGoals,
+        Rule::SwitchSides,
+        Rule::ScoreOffset(PlayerSide::Left),
+        Rule::Speed(PlayerSide::Left),
+        Rule::Size(PlayerSide::Left),
+        Rule::ScoreOffset(PlayerSide::Right),
+        Rule::Speed(PlayerSide::Right),
+        Rule::Size(PlayerSide::Right),
+    ];
+
+    fn name(self) -> String {
+        let (side, setting) = match self {
+            Rule::TargetScore => return "Points to Win".into(),
+            Rule::WinByTwo => return "Win by 2".into(),
+            Rule::SuddenDeath => return "Sudden Death".into(),
+            Rule::ShrinkingCourt => return "Overtime Court".into(),
+            Rule::Scoring => return "Scoring".into(),
+            Rule::ServeRotation => return "Serve Rotation".into(),
+            Rule::LetServes => return "Let Serves".into(),
+            Rule::ServeClock => return "Serve Clock".into(),
+            Rule::ServeClockPenalty => return "Serve Clock Penalty".into(),
+            Rule::ServeFeints => return "Serve Feints".into(),
+            Rule::Court => return "Court".into(),
+            Rule::WallDamage => return "Breakable Walls".into(),
+            Rule::Bricks => return "Bricks".into(),
+            Rule::MovingGoals => return "Goals".into(),
+            Rule::UndoLimit => return "Point Undos".into(),
+            Rule::Ball => return "Ball".into(),
+            Rule::TwoBalls => return "Balls".into(),
+            Rule::OwnGoals => return "Own Goals".into(),
+            Rule::SwitchSides => return "Switch Sides".into(),
+            Rule::ScoreOffset(side) => (side, "Starting Score"),
+            Rule::Speed(side) => (side, "Paddle Speed"),
+            Rule::Size(side) => (side, "Paddle Size"),
+        };
+        let side = match side {
+            PlayerSide::Left => "Left",
+            PlayerSide::Right => "Right",
+        };
+        format!("{side} {setting}")
+    }
+
+    /// Steps the rule up (`step > 0`) or down (`step < 0`) within its limits
+    fn adjust(self, rules: &mut MatchRules, step: i32) {
+        match self {
+            Rule::TargetScore => {
+                rules.target_score = rules
+                    .target_score
+                    .saturating_add_signed(step)
+                    .clamp(MIN_TARGET_SCORE, MAX_TARGET_SCORE);
+            }
+            Rule::WinByTwo => rules.win_by_two = !rules.win_by_two,
+            Rule::SuddenDeath => rules.sudden_death = !rules.sudden_death,
+            Rule::ShrinkingCourt => rules.shrinking_court = !rules.shrinking_court,
+            Rule::LetServes => rules.let_serves = !rules.let_serves,
+            Rule::ServeFeints => rules.serve_feints = !rules.serve_feints,
+            Rule::WallDamage => rules.wall_damage = !rules.wall_damage,
+            Rule::Bricks => rules.bricks = !rules.bricks,
+            Rule::MovingGoals => rules.moving_goals = !rules.moving_goals,
+            Rule::TwoBalls => rules.two_balls = !rules.two_balls,
+            Rule::OwnGoals => rules.own_goals = !rules.own_goals,
+            Rule::SwitchSides => rules.switch_sides = !rules.switch_sides,
+            Rule::Court => {
+                rules.court = match rules.court {
+                    CourtLayout::Walled => CourtLayout::Open,
+                    CourtLayout::Open => CourtLayout::Walled,
+                };
+            }
+            Rule::Scoring => {
+                rules.scoring = match rules.scoring {
+                    ScoringSystem::Rally => ScoringSystem::SideOut,
+                    ScoringSystem::SideOut => ScoringSystem::Rally,
+                };
+            }
+            // Side-out scoring decides who serves, so there's nothing to set
+            Rule::ServeRotation if rules.scoring == ScoringSystem::SideOut => {}
+            Rule::ServeRotation if step < 0 => {
+                rules.serve_rotation = rules.serve_rotation.previous();
+            }
+            Rule::ServeRotation => rules.serve_rotation = rules.serve_rotation.next(),
+            Rule::ServeClock => {
+                rules.serve_clock = rules
+                    .serve_clock
+                    .saturating_add_signed(step * SERVE_CLOCK_STEP as i32)
+                    .min(MAX_SERVE_CLOCK);
+            }
+            Rule::ServeClockPenalty => {
+                rules.serve_clock_penalty = match rules.serve_clock_penalty {
+                    ServeClockPenalty::LoseServe => ServeClockPenalty::LosePoint,
+                    ServeClockPenalty::LosePoint => ServeClockPenalty::LoseServe,
+                };
+            }
+            Rule::UndoLimit => {
+                rules.undo_limit = rules
+                    .undo_limit
+                    .saturating_add_signed(step)
+                    .min(MAX_UNDO_LIMIT);
+            }
+            Rule::Ball if step < 0 => rules.ball = rules.ball.previous(),
+            Rule::Ball => rules.ball = rules.ball.next(),
+            Rule::ScoreOffset(side) => {
+                let handicap = rules.handicap_mut(side);
+                handicap.score_offset = handicap
+                    .score_offset
+                    .saturating_add_signed(step)
+                    .min(MAX_SCORE_OFFSET);
+            }
+            Rule::Speed(side) => {
+                let handicap = rules.handicap_mut(side);
+                handicap.speed_multiplier = (handicap.speed_multiplier
+                    + step as f32 * MULTIPLIER_STEP)
+                    .clamp(MIN_SPEED_MULTIPLIER, MAX_SPEED_MULTIPLIER);
+            }
+            Rule::Size(side) => {
+                let handicap = rules.handicap_mut(side);
+                handicap.size_multiplier = (handicap.size_multiplier
+                    + step as f32 * MULTIPLIER_STEP)
+                    .clamp(MIN_SIZE_MULTIPLIER, MAX_SIZE_MULTIPLIER);
+            }
+        }
+    }
+
+    fn value(self, rules: &MatchRules) -> String {
+        match self {
+            Rule::TargetScore => rules.target_score.to_string(),
+            Rule::WinByTwo if rules.win_by_two => "On".into(),
+            Rule::WinByTwo => "Off".into(),
+            Rule::SuddenDeath if rules.win_by_two => "-".into(),
+            Rule::SuddenDeath if rules.sudden_death => "On".into(),
+            Rule::SuddenDeath => "Off".into(),
+            Rule::ShrinkingCourt if !rules.sudden_death || rules.win_by_two => "-".into(),
+            Rule::ShrinkingCourt if rules.shrinking_court => "Shrinking".into(),
+            Rule::ShrinkingCourt => "Normal".into(),
+            Rule::LetServes if rules.let_serves => "On".into(),
+            Rule::LetServes => "Off".into(),
+            Rule::ServeClock if rules.serve_clock == 0 => "Off".into(),
+            Rule::ServeClock => format!("{}s", rules.serve_clock),
+            Rule::ServeClockPenalty if rules.serve_clock == 0 => "-".into(),
+            Rule::ServeClockPenalty => match rules.serve_clock_penalty {
+                ServeClockPenalty::LoseServe => "Lose Serve".into(),
+                ServeClockPenalty::LosePoint => "Lose Point".into(),
+            },
+            Rule::ServeFeints if rules.serve_feints => "Once per Point".into(),
+            Rule::ServeFeints => "Off".into(),
+            Rule::Court => match rules.court {
+                CourtLayout::Walled => "Walled".into(),
+                CourtLayout::Open => "Open".into(),
+            },
+            Rule::Scoring => match rules.scoring {
+                ScoringSystem::Rally => "Rally".into(),
+                ScoringSystem::SideOut => "Side-out".into(),
+            },
+            Rule::ServeRotation if rules.scoring == ScoringSystem::SideOut => "-".into(),
+            Rule::ServeRotation => match rules.serve_rotation {
+                ServeRotation::LoserServes => "Loser".into(),
+                ServeRotation::WinnerServes => "Winner".into(),
+                ServeRotation::Alternate => format!("Every {SERVES_PER_TURN}"),
+            },
+            Rule::WallDamage if rules.court == CourtLayout::Open => "No Walls".into(),
+            Rule::WallDamage if rules.wall_damage => "On".into(),
+            Rule::WallDamage => "Off".into(),
+            Rule::MovingGoals if rules.moving_goals => "Moving".into(),
+            Rule::MovingGoals => "Fixed".into(),
+            Rule::Bricks if rules.bricks => "On".into(),
+            Rule::Bricks => "Off".into(),
+            Rule::UndoLimit if rules.undo_limit == 0 => "Off".into(),
+            Rule::UndoLimit => rules.undo_limit.to_string(),
+            Rule::Ball => rules.ball.name().into(),
+            Rule::TwoBalls if rules.two_balls => "2 (Chaos)".into(),
+            Rule::TwoBalls => "1".into(),
+            Rule::OwnGoals if rules.own_goals => "Count".into(),
+            Rule::OwnGoals => "Replay".into(),
+            Rule::SwitchSides if rules.two_balls => "-".into(),
+            Rule::SwitchSides if rules.switch_sides => "At Halfway".into(),
+            Rule::SwitchSides => "Off".into(),
+            Rule::ScoreOffset(side) => rules.handicap(side).score_offset.to_string(),
+            Rule::Speed(side) => format!("{:.1}x", rules.handicap(side).speed_multiplier),
+            Rule::Size(side) => format!("{:.1}x", rules.handicap(side).size_multiplier),
+        }
+    }
+}
+
+fn rules_grid() -> impl Bundle {
+    (
+        Name::new("Rules Grid"),
+        Node {
+            display: Display::Grid,
+            row_gap: Px(10.0),
+            column_gap: Px(30.0),
+            grid_template_columns: RepeatedGridTrack::px(2, 400.0),
+            ..default()
+        },
+        Children::spawn(SpawnWith(|parent: &mut ChildSpawner| {
+            for rule in Rule::ALL {
+                parent.spawn((
+                    widget::label(rule.name()),
+                    Node {
+                        justify_self: JustifySelf::End,
+                        ..default()
+                    },
+                ));
+                parent.spawn(rule_widget(rule));
+            }
+        })),
+    )
+}
+
+fn rule_widget(rule: Rule) -> impl Bundle {
+    (
+        Name::new("Rule Widget"),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            widget::button_small(
+                "-",
+                move |_: Trigger<Pointer<Click>>, mut rules: ResMut<MatchRules>| {
+                    rule.adjust(&mut rules, -1);
+                }
+            ),
+            (
+                Name::new("Current Value"),
+                Node {
+                    padding: UiRect::horizontal(Px(10.0)),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![(widget::label(""), RuleLabel(rule))],
+            ),
+            widget::button_small(
+                "+",
+                move |_: Trigger<Pointer<Click>>, mut rules: ResMut<MatchRules>| {
+                    rule.adjust(&mut rules, 1);
+                }
+            ),
+        ],
+    )
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct RuleLabel(Rule);
+
+fn update_rule_labels(rules: Res<MatchRules>, mut labels: Query<(&mut Text, &RuleLabel)>) {
+    for (mut text, label) in &mut labels {
+        text.0 = label.0.value(&rules);
+    }
+}
+
+fn go_back_on_click(_: Trigger<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Modes);
+}
+
+fn go_back(mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Modes);
+}
@@ -2,9 +2,36 @@
 //!
 //! Additional settings and accessibility options should go here.
 
-use bevy::{audio::Volume, input::common_conditions::input_just_pressed, prelude::*, ui::Val::*};
+use bevy::{
+    audio::Volume, ecs::system::IntoObserverSystem, input::common_conditions::input_just_pressed,
+    prelude::*, ui::Val::*,
+};
 
-use crate::{menus::Menu, screens::Screen, theme::prelude::*};
+use crate::{
+    display::DisplaySettings,
+    game::{
+        accessibility::{AlwaysShowBall, ReducedMotion},
+        arena::BackgroundDetail,
+        camera::CameraMode,
+        coach::CoachHints,
+        court_sounds::SoundStyle,
+        fx::FxQuality,
+        idle::{IdleTimeout, PauseOnFocusLoss},
+        player::{AimAssist, MirroredCourt, MovementFeel, PlayerSide},
+        power_serve::PowerServe,
+        profile::PlayerProfiles,
+        speedometer::ShowSpeedometer,
+        tilt::{InputMode, TiltCalibration, TiltSensor},
+        touch::{MAX_BUTTON_SIZE, MIN_BUTTON_OPACITY, MIN_BUTTON_SIZE, TouchControls},
+    },
+    menus::Menu,
+    screens::Screen,
+    theme::prelude::*,
+};
+#[cfg(not(target_family = "wasm"))]
+use crate::{
+    game::court_theme::CourtThemeChoices, mods::InstalledMods, replay::telemetry::ExportTelemetry,
+};
 
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(OnEnter(Menu::Settings), spawn_settings_menu);
@@ -14,17 +41,78 @@ pub(super) fn plugin(app: &mut App) {
     );
 
     app.register_type::<GlobalVolumeLabel>();
+    app.register_type::<MovementFeelLabel>();
+    app.register_type::<CameraModeLabel>();
+    app.register_type::<BackgroundDetailLabel>();
+    app.register_type::<FxQualityLabel>();
+    app.register_type::<SoundStyleLabel>();
+    app.register_type::<TouchControlsLabel>();
+    app.register_type::<InputModeLabel>();
+    app.register_type::<TiltCalibrationLabel>();
+    app.register_type::<DisplaySettingsLabel>();
+    app.register_type::<ReducedMotionLabel>();
+    app.register_type::<AlwaysShowBallLabel>();
+    app.register_type::<SpeedometerLabel>();
+    app.register_type::<CoachHintsLabel>();
+    app.register_type::<AimAssistLabel>();
+    app.register_type::<PowerServeLabel>();
+    app.register_type::<IdleTimeoutLabel>();
+    app.register_type::<FocusPauseLabel>();
+    app.register_type::<MirroredCourtLabel>();
+    app.register_type::<PlayerColorLabel>();
     app.add_systems(
         Update,
-        update_global_volume_label.run_if(in_state(Menu::Settings)),
+        (
+            update_global_volume_label,
+            update_movement_feel_label,
+            update_camera_mode_label,
+            update_background_detail_label,
+            update_fx_quality_label,
+            update_sound_style_label,
+            update_touch_controls_labels,
+            update_input_mode_label,
+            update_tilt_calibration_label,
+            update_display_settings_labels,
+            update_reduced_motion_label,
+            update_always_show_ball_label,
+            update_speedometer_label,
+            update_coach_hints_label,
+            update_aim_assist_label,
+            update_power_serve_label,
+            update_idle_timeout_label,
+            update_focus_pause_label,
+            update_mirrored_court_label,
+            update_player_color_labels,
+        )
+            .run_if(in_state(Menu::Settings)),
+    );
+    #[cfg(not(target_family = "wasm"))]
+    app.register_type::<ExportTelemetryLabel>();
+    #[cfg(not(target_family = "wasm"))]
+    app.register_type::<CourtThemeLabel>();
+    #[cfg(not(target_family = "wasm"))]
+    app.add_systems(
+        Update,
+        (update_export_telemetry_label, update_court_theme_label).run_if(in_state(Menu::Settings)),
     );
 }
 
-fn spawn_settings_menu(mut commands: Commands) {
+fn spawn_settings_menu(
+    mut commands: Commands,
+    #[cfg(not(target_family = "wasm"))] mods: Res<InstalledMods>,
+) {
     commands.spawn((
         widget::ui_root("Settings Menu"),
         GlobalZIndex(2),
         StateScoped(Menu::Settings),
+        #[cfg(not(target_family = "wasm"))]
+        children![
+            widget::header("Settings"),
+            settings_grid(),
+            native_settings_grid(&mods),
+            widget::button("Back", go_back_on_click),
+        ],
+        #[cfg(target_family = "wasm")]
         children![
             widget::header("Settings"),
             settings_grid(),
@@ -40,7 +128,8 @@ fn settings_grid() -> impl Bundle {
             display: Display::Grid,
             row_gap: Px(10.0),
             column_gap: Px(30.0),
-            grid_template_columns: RepeatedGridTrack::px(2, 400.0),
+            // Two label/widget pairs per row, so every setting fits on screen
+            grid_template_columns: RepeatedGridTrack::px(4, 260.0),
             ..default()
         },
         children![
@@ -52,6 +141,248 @@ fn settings_grid() -> impl Bundle {
                 }
             ),
             global_volume_widget(),
+            (
+                widget::label("Paddle Feel"),
+                Node {
+                    justify_self: JustifySelf::End,
+                    ..default()
+                }
+            ),
+            movement_feel_widget(),
+            (
+                widget::label("Your Side"),
+                Node {
+                    justify_self: JustifySelf::End,
+                    ..default()
+                }
+            ),
+            mirrored_court_widget(),
+            (
+                widget::label("Camera"),
+                Node {
+                    justify_self: JustifySelf::End,
+                    ..default()
+                }
+            ),
+            camera_mode_widget(),
+            (
+                widget::label("Background"),
+                Node {
+                    justify_self: JustifySelf::End,
+                    ..default()
+                }
+            ),
+            background_detail_widget(),
+            (
+                widget::label("Effects"),
+                Node {
+                    justify_self: JustifySelf::End,
+                    ..default()
+                }
+            ),
+            fx_quality_widget(),
+            (
+                widget::label("Sound Style"),
+                Node {
+                    justify_self: JustifySelf::End,
+                    ..default()
+                }
+            ),
+            sound_style_widget(),
+            (
+                widget::label("Touch Buttons"),
+                Node {
+                    justify_self: JustifySelf::End,
+                    ..default()
+                }
+            ),
+            touch_controls_widget(
+                TouchControlsLabel::Mode,
+                previous_touch_buttons_mode,
+                next_touch_buttons_mode
+            ),
+            (
+                widget::label("Button Size"),
+                Node {
+                    justify_self: JustifySelf::End,
+                    ..default()
+                }
+            ),
+            touch_controls_widget(
+                TouchControlsLabel::Size,
+                shrink_touch_buttons,
+                grow_touch_buttons
+            ),
+            (
+                widget::label("Button Opacity"),
+                Node {
+                    justify_self: JustifySelf::End,
+                    ..default()
+                }
+            ),
+            touch_controls_widget(
+                TouchControlsLabel::Opacity,
+                fade_touch_buttons,
+                unfade_touch_buttons
+            ),
+            (
+                widget::label("Paddle Control"),
+                Node {
+                    justify_self: JustifySelf::End,
+                    ..default()
+                }
+            ),
+            input_mode_widget(),
+            (
+                widget::label("Tilt Neutral"),
+                Node {
+                    justify_self: JustifySelf::End,
+                    ..default()
+                }
+            ),
+            tilt_calibration_widget(),
+            (
+                widget::label("VSync"),
+                Node {
+                    justify_self: JustifySelf::End,
+                    ..default()
+                }
+            ),
+            display_settings_widget(DisplaySettingsLabel::Vsync, toggle_vsync, toggle_vsync),
+            (
+                widget::label("FPS Cap"),
+                Node {
+                    justify_self: JustifySelf::End,
+                    ..default()
+                }
+            ),
+            display_settings_widget(DisplaySettingsLabel::FpsCap, previous_fps_cap, next_fps_cap),
+            (
+                widget::label("Reduced Motion"),
+                Node {
+                    justify_self: JustifySelf::End,
+                    ..default()
+                }
+            ),
+            reduced_motion_widget(),
+            (
+                widget::label("Always Show Ball"),
+                Node {
+                    justify_self: JustifySelf::End,
+                    ..default()
+                }
+            ),
+            always_show_ball_widget(),
+            (
+                widget::label("Ball Speed"),
+                Node {
+                    justify_self: JustifySelf::End,
+                    ..default()
+                }
+            ),
+            speedometer_widget(),
+            (
+                widget::label("Coach Hints"),
+                Node {
+                    justify_self: JustifySelf::End,
+                    ..default()
+                }
+            ),
+            coach_hints_widget(),
+            (
+                widget::label("Aim Assist"),
+                Node {
+                    justify_self: JustifySelf::End,
+                    ..default()
+                }
+            ),
+            aim_assist_widget(),
+            (
+                widget::label("Power Serve"),
+                Node {
+                    justify_self: JustifySelf::End,
+                    ..default()
+                }
+            ),
+            power_serve_widget(),
+            (
+                widget::label("Idle Pause"),
+                Node {
+                    justify_self: JustifySelf::End,
+                    ..default()
+                }
+            ),
+            idle_timeout_widget(),
+            (
+                widget::label("Pause When Away"),
+                Node {
+                    justify_self: JustifySelf::End,
+                    ..default()
+                }
+            ),
+            focus_pause_widget(),
+            (
+                widget::label("Left Color"),
+                Node {
+                    justify_self: JustifySelf::End,
+                    ..default()
+                }
+            ),
+            player_color_widget(PlayerSide::Left),
+            (
+                widget::label("Right Color"),
+                Node {
+                    justify_self: JustifySelf::End,
+                    ..default()
+                }
+            ),
+            player_color_widget(PlayerSide::Right),
+        ],
+    )
+}
+
+/// Settings for features that need a filesystem
+#[cfg(not(target_family = "wasm"))]
+fn native_settings_grid(mods: &InstalledMods) -> impl Bundle {
+    (
+        Name::new("Native Settings Grid"),
+        Node {
+            display: Display::Grid,
+            column_gap: Px(30.0),
+            grid_template_columns: RepeatedGridTrack::px(2, 260.0),
+            ..default()
+        },
+        children![
+            (
+                widget::label("Export Match Data"),
+                Node {
+                    justify_self: JustifySelf::End,
+                    ..default()
+                }
+            ),
+            export_telemetry_widget(),
+            (
+                widget::label("Court Theme"),
+                Node {
+                    justify_self: JustifySelf::End,
+                    ..default()
+                }
+            ),
+            court_theme_widget(),
+            (
+                widget::label("Mods"),
+                Node {
+                    justify_self: JustifySelf::End,
+                    ..default()
+                }
+            ),
+            (
+                widget::label(mods.summary()),
+                Node {
+                    justify_self: JustifySelf::Start,
+                    ..default()
+                }
+            ),
         ],
     )
 }
@@ -79,29 +410,949 @@ fn global_volume_widget() -> impl Bundle {
     )
 }
 
-const MIN_VOLUME: f32 = 0.0;
-const MAX_VOLUME: f32 = 3.0;
-
-fn lower_global_volume(_: Trigger<Pointer<Click>>, mut global_volume: ResMut<GlobalVolume>) {
-    let linear = (global_volume.volume.to_linear() - 0.1).max(MIN_VOLUME);
-    global_volume.volume = Volume::Linear(linear);
+fn movement_feel_widget() -> impl Bundle {
+    (
+        Name::new("Movement Feel Widget"),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            widget::button_small("<", previous_movement_feel),
+            (
+                Name::new("Current Feel"),
+                Node {
+                    padding: UiRect::horizontal(Px(10.0)),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![(widget::label(""), MovementFeelLabel)],
+            ),
+            widget::button_small(">", next_movement_feel),
+        ],
+    )
 }
 
-fn raise_global_volume(_: Trigger<Pointer<Click>>, mut global_volume: ResMut<GlobalVolume>) {
-    let linear = (global_volume.volume.to_linear() + 0.1).min(MAX_VOLUME);
-    global_volume.volume = Volume::Linear(linear);
+fn camera_mode_widget() -> impl Bundle {
+    (
+        Name::new("Camera Mode Widget"),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            widget::button_small("<", previous_camera_mode),
+            (
+                Name::new("Current Camera Mode"),
+                Node {
+                    padding: UiRect::horizontal(Px(10.0)),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![(widget::label(""), CameraModeLabel)],
+            ),
+            widget::button_small(">", next_camera_mode),
+        ],
+    )
 }
 
-#[derive(Component, Reflect)]
-#[reflect(Component)]
-struct GlobalVolumeLabel;
+fn background_detail_widget() -> impl Bundle {
+    (
+        Name::new("Background Detail Widget"),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            widget::button_small("<", toggle_background_detail),
+            (
+                Name::new("Current Background Detail"),
+                Node {
+                    padding: UiRect::horizontal(Px(10.0)),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![(widget::label(""), BackgroundDetailLabel)],
+            ),
+            widget::button_small(">", toggle_background_detail),
+        ],
+    )
+}
 
-fn update_global_volume_label(
-    global_volume: Res<GlobalVolume>,
-    mut label: Single<&mut Text, With<GlobalVolumeLabel>>,
-) {
-    let percent = 100.0 * global_volume.volume.to_linear();
-    label.0 = format!("{percent:3.0}%");
+fn fx_quality_widget() -> impl Bundle {
+    (
+        Name::new("Effects Quality Widget"),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            widget::button_small("<", previous_fx_quality),
+            (
+                Name::new("Current Effects Quality"),
+                Node {
+                    padding: UiRect::horizontal(Px(10.0)),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![(widget::label(""), FxQualityLabel)],
+            ),
+            widget::button_small(">", next_fx_quality),
+        ],
+    )
+}
+
+fn sound_style_widget() -> impl Bundle {
+    (
+        Name::new("Sound Style Widget"),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            widget::button_small("<", previous_sound_style),
+            (
+                Name::new("Current Sound Style"),
+                Node {
+                    padding: UiRect::horizontal(Px(10.0)),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![(widget::label(""), SoundStyleLabel)],
+            ),
+            widget::button_small(">", next_sound_style),
+        ],
+    )
+}
+
+fn touch_controls_widget<M1, M2>(
+    label: TouchControlsLabel,
+    lower: impl IntoObserverSystem<Pointer<Click>, (), M1>,
+    raise: impl IntoObserverSystem<Pointer<Click>, (), M2>,
+) -> impl Bundle {
+    (
+        Name::new("Touch Controls Widget"),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            widget::button_small("-", lower),
+            (
+                Name::new("Current Value"),
+                Node {
+                    padding: UiRect::horizontal(Px(10.0)),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![(widget::label(""), label)],
+            ),
+            widget::button_small("+", raise),
+        ],
+    )
+}
+
+fn input_mode_widget() -> impl Bundle {
+    (
+        Name::new("Input Mode Widget"),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            widget::button_small("<", previous_input_mode),
+            (
+                Name::new("Current Input Mode"),
+                Node {
+                    padding: UiRect::horizontal(Px(10.0)),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![(widget::label(""), InputModeLabel)],
+            ),
+            widget::button_small(">", next_input_mode),
+        ],
+    )
+}
+
+/// Shows the neutral angle, with a button to take the current tilt as neutral
+fn tilt_calibration_widget() -> impl Bundle {
+    (
+        Name::new("Tilt Calibration Widget"),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            (
+                Name::new("Current Tilt Neutral"),
+                Node {
+                    padding: UiRect::horizontal(Px(10.0)),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![(widget::label(""), TiltCalibrationLabel)],
+            ),
+            widget::button_small("*", calibrate_tilt),
+        ],
+    )
+}
+
+fn display_settings_widget<M1, M2>(
+    label: DisplaySettingsLabel,
+    previous: impl IntoObserverSystem<Pointer<Click>, (), M1>,
+    next: impl IntoObserverSystem<Pointer<Click>, (), M2>,
+) -> impl Bundle {
+    (
+        Name::new("Display Settings Widget"),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            widget::button_small("<", previous),
+            (
+                Name::new("Current Value"),
+                Node {
+                    padding: UiRect::horizontal(Px(10.0)),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![(widget::label(""), label)],
+            ),
+            widget::button_small(">", next),
+        ],
+    )
+}
+
+fn reduced_motion_widget() -> impl Bundle {
+    (
+        Name::new("Reduced Motion Widget"),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            widget::button_small("<", toggle_reduced_motion),
+            (
+                Name::new("Current Reduced Motion"),
+                Node {
+                    padding: UiRect::horizontal(Px(10.0)),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![(widget::label(""), ReducedMotionLabel)],
+            ),
+            widget::button_small(">", toggle_reduced_motion),
+        ],
+    )
+}
+
+fn always_show_ball_widget() -> impl Bundle {
+    (
+        Name::new("Always Show Ball Widget"),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            widget::button_small("<", toggle_always_show_ball),
+            (
+                Name::new("Current Always Show Ball"),
+                Node {
+                    padding: UiRect::horizontal(Px(10.0)),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![(widget::label(""), AlwaysShowBallLabel)],
+            ),
+            widget::button_small(">", toggle_always_show_ball),
+        ],
+    )
+}
+
+fn speedometer_widget() -> impl Bundle {
+    (
+        Name::new("Speedometer Widget"),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            widget::button_small("<", toggle_speedometer),
+            (
+                Name::new("Current Speedometer"),
+                Node {
+                    padding: UiRect::horizontal(Px(10.0)),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![(widget::label(""), SpeedometerLabel)],
+            ),
+            widget::button_small(">", toggle_speedometer),
+        ],
+    )
+}
+
+fn coach_hints_widget() -> impl Bundle {
+    (
+        Name::new("Coach Hints Widget"),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            widget::button_small("<", toggle_coach_hints),
+            (
+                Name::new("Current Coach Hints"),
+                Node {
+                    padding: UiRect::horizontal(Px(10.0)),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![(widget::label(""), CoachHintsLabel)],
+            ),
+            widget::button_small(">", toggle_coach_hints),
+        ],
+    )
+}
+
+fn mirrored_court_widget() -> impl Bundle {
+    (
+        Name::new("Mirrored Court Widget"),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            widget::button_small("<", toggle_mirrored_court),
+            (
+                Name::new("Current Side"),
+                Node {
+                    padding: UiRect::horizontal(Px(10.0)),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![(widget::label(""), MirroredCourtLabel)],
+            ),
+            widget::button_small(">", toggle_mirrored_court),
+        ],
+    )
+}
+
+fn aim_assist_widget() -> impl Bundle {
+    (
+        Name::new("Aim Assist Widget"),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            widget::button_small("-", weaken_aim_assist),
+            (
+                Name::new("Current Aim Assist"),
+                Node {
+                    padding: UiRect::horizontal(Px(10.0)),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![(widget::label(""), AimAssistLabel)],
+            ),
+            widget::button_small("+", strengthen_aim_assist),
+        ],
+    )
+}
+
+fn power_serve_widget() -> impl Bundle {
+    (
+        Name::new("Power Serve Widget"),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            widget::button_small("<", toggle_power_serve),
+            (
+                Name::new("Current Power Serve"),
+                Node {
+                    padding: UiRect::horizontal(Px(10.0)),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![(widget::label(""), PowerServeLabel)],
+            ),
+            widget::button_small(">", toggle_power_serve),
+        ],
+    )
+}
+
+fn idle_timeout_widget() -> impl Bundle {
+    (
+        Name::new("Idle Timeout Widget"),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            widget::button_small("<", previous_idle_timeout),
+            (
+                Name::new("Current Idle Timeout"),
+                Node {
+                    padding: UiRect::horizontal(Px(10.0)),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![(widget::label(""), IdleTimeoutLabel)],
+            ),
+            widget::button_small(">", next_idle_timeout),
+        ],
+    )
+}
+
+fn focus_pause_widget() -> impl Bundle {
+    (
+        Name::new("Focus Pause Widget"),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            widget::button_small("<", toggle_focus_pause),
+            (
+                Name::new("Current Focus Pause"),
+                Node {
+                    padding: UiRect::horizontal(Px(10.0)),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![(widget::label(""), FocusPauseLabel)],
+            ),
+            widget::button_small(">", toggle_focus_pause),
+        ],
+    )
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn export_telemetry_widget() -> impl Bundle {
+    (
+        Name::new("Export Telemetry Widget"),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            widget::button_small("<", toggle_export_telemetry),
+            (
+                Name::new("Current Export Telemetry"),
+                Node {
+                    padding: UiRect::horizontal(Px(10.0)),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![(widget::label(""), ExportTelemetryLabel)],
+            ),
+            widget::button_small(">", toggle_export_telemetry),
+        ],
+    )
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn court_theme_widget() -> impl Bundle {
+    (
+        Name::new("Court Theme Widget"),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            widget::button_small("<", previous_court_theme),
+            (
+                Name::new("Current Court Theme"),
+                Node {
+                    padding: UiRect::horizontal(Px(10.0)),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![(widget::label(""), CourtThemeLabel)],
+            ),
+            widget::button_small(">", next_court_theme),
+        ],
+    )
+}
+
+fn player_color_widget(side: PlayerSide) -> impl Bundle {
+    let previous = move |_: Trigger<Pointer<Click>>, mut profiles: ResMut<PlayerProfiles>| {
+        let profile = profiles.get_mut(side);
+        profile.color = profile.color.previous();
+    };
+    let next = move |_: Trigger<Pointer<Click>>, mut profiles: ResMut<PlayerProfiles>| {
+        let profile = profiles.get_mut(side);
+        profile.color = profile.color.next();
+    };
+    (
+        Name::new("Player Color Widget"),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            widget::button_small("<", previous),
+            (
+                Name::new("Current Player Color"),
+                Node {
+                    padding: UiRect::horizontal(Px(10.0)),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![(widget::label(""), PlayerColorLabel(side))],
+            ),
+            widget::button_small(">", next),
+        ],
+    )
+}
+
+const MIN_VOLUME: f32 = 0.0;
+const MAX_VOLUME: f32 = 3.0;
+
+fn lower_global_volume(_: Trigger<Pointer<Click>>, mut global_volume: ResMut<GlobalVolume>) {
+    let linear = (global_volume.volume.to_linear() - 0.1).max(MIN_VOLUME);
+    global_volume.volume = Volume::Linear(linear);
+}
+
+fn raise_global_volume(_: Trigger<Pointer<Click>>, mut global_volume: ResMut<GlobalVolume>) {
+    let linear = (global_volume.volume.to_linear() + 0.1).min(MAX_VOLUME);
+    global_volume.volume = Volume::Linear(linear);
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct GlobalVolumeLabel;
+
+fn update_global_volume_label(
+    global_volume: Res<GlobalVolume>,
+    mut label: Single<&mut Text, With<GlobalVolumeLabel>>,
+) {
+    let percent = 100.0 * global_volume.volume.to_linear();
+    label.0 = format!("{percent:3.0}%");
+}
+
+fn previous_movement_feel(_: Trigger<Pointer<Click>>, mut feel: ResMut<MovementFeel>) {
+    *feel = feel.previous();
+}
+
+fn next_movement_feel(_: Trigger<Pointer<Click>>, mut feel: ResMut<MovementFeel>) {
+    *feel = feel.next();
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct MovementFeelLabel;
+
+fn update_movement_feel_label(
+    feel: Res<MovementFeel>,
+    mut label: Single<&mut Text, With<MovementFeelLabel>>,
+) {
+    label.0 = feel.name().to_string();
+}
+
+fn previous_camera_mode(_: Trigger<Pointer<Click>>, mut mode: ResMut<CameraMode>) {
+    *mode = mode.previous();
+}
+
+fn next_camera_mode(_: Trigger<Pointer<Click>>, mut mode: ResMut<CameraMode>) {
+    *mode = mode.next();
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct CameraModeLabel;
+
+fn update_camera_mode_label(
+    mode: Res<CameraMode>,
+    mut label: Single<&mut Text, With<CameraModeLabel>>,
+) {
+    label.0 = mode.name().to_string();
+}
+
+fn toggle_background_detail(_: Trigger<Pointer<Click>>, mut detail: ResMut<BackgroundDetail>) {
+    *detail = detail.next();
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct BackgroundDetailLabel;
+
+fn update_background_detail_label(
+    detail: Res<BackgroundDetail>,
+    mut label: Single<&mut Text, With<BackgroundDetailLabel>>,
+) {
+    label.0 = detail.name().to_string();
+}
+
+fn previous_fx_quality(_: Trigger<Pointer<Click>>, mut quality: ResMut<FxQuality>) {
+    *quality = quality.previous();
+}
+
+fn next_fx_quality(_: Trigger<Pointer<Click>>, mut quality: ResMut<FxQuality>) {
+    *quality = quality.next();
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct FxQualityLabel;
+
+fn update_fx_quality_label(
+    quality: Res<FxQuality>,
+    mut label: Single<&mut Text, With<FxQualityLabel>>,
+) {
+    label.0 = quality.name().to_string();
+}
+
+fn previous_sound_style(_: Trigger<Pointer<Click>>, mut style: ResMut<SoundStyle>) {
+    *style = style.previous();
+}
+
+fn next_sound_style(_: Trigger<Pointer<Click>>, mut style: ResMut<SoundStyle>) {
+    *style = style.next();
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct SoundStyleLabel;
+
+fn update_sound_style_label(
+    style: Res<SoundStyle>,
+    mut label: Single<&mut Text, With<SoundStyleLabel>>,
+) {
+    label.0 = style.name().to_string();
+}
+
+const TOUCH_BUTTON_SIZE_STEP: f32 = 8.0;
+const TOUCH_BUTTON_OPACITY_STEP: f32 = 0.1;
+
+fn previous_touch_buttons_mode(_: Trigger<Pointer<Click>>, mut controls: ResMut<TouchControls>) {
+    controls.mode = controls.mode.previous();
+}
+
+fn next_touch_buttons_mode(_: Trigger<Pointer<Click>>, mut controls: ResMut<TouchControls>) {
+    controls.mode = controls.mode.next();
+}
+
+fn shrink_touch_buttons(_: Trigger<Pointer<Click>>, mut controls: ResMut<TouchControls>) {
+    controls.size = (controls.size - TOUCH_BUTTON_SIZE_STEP).max(MIN_BUTTON_SIZE);
+}
+
+fn grow_touch_buttons(_: Trigger<Pointer<Click>>, mut controls: ResMut<TouchControls>) {
+    controls.size = (controls.size + TOUCH_BUTTON_SIZE_STEP).min(MAX_BUTTON_SIZE);
+}
+
+fn fade_touch_buttons(_: Trigger<Pointer<Click>>, mut controls: ResMut<TouchControls>) {
+    controls.opacity = (controls.opacity - TOUCH_BUTTON_OPACITY_STEP).max(MIN_BUTTON_OPACITY);
+}
+
+fn unfade_touch_buttons(_: Trigger<Pointer<Click>>, mut controls: ResMut<TouchControls>) {
+    controls.opacity = (controls.opacity + TOUCH_BUTTON_OPACITY_STEP).min(1.0);
+}
+
+#[derive(Component, Reflect, Clone, Copy)]
+#[reflect(Component)]
+enum TouchControlsLabel {
+    Mode,
+    Size,
+    Opacity,
+}
+
+fn update_touch_controls_labels(
+    controls: Res<TouchControls>,
+    mut labels: Query<(&mut Text, &TouchControlsLabel)>,
+) {
+    for (mut text, label) in &mut labels {
+        text.0 = match label {
+            TouchControlsLabel::Mode => controls.mode.name().to_string(),
+            TouchControlsLabel::Size => format!("{:.0}px", controls.size),
+            TouchControlsLabel::Opacity => format!("{:.0}%", controls.opacity * 100.0),
+        };
+    }
+}
+
+fn previous_input_mode(_: Trigger<Pointer<Click>>, mut mode: ResMut<InputMode>) {
+    *mode = mode.previous();
+}
+
+fn next_input_mode(_: Trigger<Pointer<Click>>, mut mode: ResMut<InputMode>) {
+    *mode = mode.next();
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct InputModeLabel;
+
+fn update_input_mode_label(
+    mode: Res<InputMode>,
+    mut label: Single<&mut Text, With<InputModeLabel>>,
+) {
+    label.0 = mode.name().to_string();
+}
+
+fn calibrate_tilt(
+    _: Trigger<Pointer<Click>>,
+    sensor: Res<TiltSensor>,
+    mut calibration: ResMut<TiltCalibration>,
+) {
+    calibration.calibrate(&sensor);
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct TiltCalibrationLabel;
+
+fn update_tilt_calibration_label(
+    sensor: Res<TiltSensor>,
+    calibration: Res<TiltCalibration>,
+    mut label: Single<&mut Text, With<TiltCalibrationLabel>>,
+) {
+    label.0 = match (sensor.reading(), calibration.neutral) {
+        (None, _) => "No sensor".into(),
+        (Some(_), None) => "Not set".into(),
+        (Some(_), Some(neutral)) => format!("{neutral:.0} deg"),
+    };
+}
+
+fn toggle_vsync(_: Trigger<Pointer<Click>>, mut display: ResMut<DisplaySettings>) {
+    display.vsync = !display.vsync;
+}
+
+fn previous_fps_cap(_: Trigger<Pointer<Click>>, mut display: ResMut<DisplaySettings>) {
+    display.fps_cap = display.previous_fps_cap();
+}
+
+fn next_fps_cap(_: Trigger<Pointer<Click>>, mut display: ResMut<DisplaySettings>) {
+    display.fps_cap = display.next_fps_cap();
+}
+
+#[derive(Component, Reflect, Clone, Copy)]
+#[reflect(Component)]
+enum DisplaySettingsLabel {
+    Vsync,
+    FpsCap,
+}
+
+fn update_display_settings_labels(
+    display: Res<DisplaySettings>,
+    mut labels: Query<(&mut Text, &DisplaySettingsLabel)>,
+) {
+    for (mut text, label) in &mut labels {
+        text.0 = match label {
+            DisplaySettingsLabel::Vsync if display.vsync => "On".into(),
+            DisplaySettingsLabel::Vsync => "Off".into(),
+            DisplaySettingsLabel::FpsCap => {
+                display.fps_cap.map_or("Off".into(), |cap| cap.to_string())
+            }
+        };
+    }
+}
+
+fn toggle_reduced_motion(_: Trigger<Pointer<Click>>, mut reduced_motion: ResMut<ReducedMotion>) {
+    reduced_motion.0 = !reduced_motion.0;
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct ReducedMotionLabel;
+
+fn update_reduced_motion_label(
+    reduced_motion: Res<ReducedMotion>,
+    mut label: Single<&mut Text, With<ReducedMotionLabel>>,
+) {
+    label.0 = if reduced_motion.0 { "On" } else { "Off" }.to_string();
+}
+
+fn toggle_always_show_ball(_: Trigger<Pointer<Click>>, mut always_show: ResMut<AlwaysShowBall>) {
+    always_show.0 = !always_show.0;
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct AlwaysShowBallLabel;
+
+fn update_always_show_ball_label(
+    always_show: Res<AlwaysShowBall>,
+    mut label: Single<&mut Text, With<AlwaysShowBallLabel>>,
+) {
+    label.0 = if always_show.0 { "On" } else { "Off" }.to_string();
+}
+
+fn toggle_speedometer(_: Trigger<Pointer<Click>>, mut show: ResMut<ShowSpeedometer>) {
+    show.0 = !show.0;
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct SpeedometerLabel;
+
+fn update_speedometer_label(
+    show: Res<ShowSpeedometer>,
+    mut label: Single<&mut Text, With<SpeedometerLabel>>,
+) {
+    label.0 = if show.0 { "On" } else { "Off" }.to_string();
+}
+
+fn toggle_mirrored_court(_: Trigger<Pointer<Click>>, mut mirrored: ResMut<MirroredCourt>) {
+    mirrored.0 = !mirrored.0;
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct MirroredCourtLabel;
+
+fn update_mirrored_court_label(
+    mirrored: Res<MirroredCourt>,
+    mut label: Single<&mut Text, With<MirroredCourtLabel>>,
+) {
+    label.0 = match mirrored.home_side() {
+        PlayerSide::Left => "Left",
+        PlayerSide::Right => "Right",
+    }
+    .to_string();
+}
+
+fn weaken_aim_assist(_: Trigger<Pointer<Click>>, mut aim_assist: ResMut<AimAssist>) {
+    aim_assist.weaker();
+}
+
+fn strengthen_aim_assist(_: Trigger<Pointer<Click>>, mut aim_assist: ResMut<AimAssist>) {
+    aim_assist.stronger();
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct AimAssistLabel;
+
+fn update_aim_assist_label(
+    aim_assist: Res<AimAssist>,
+    mut label: Single<&mut Text, With<AimAssistLabel>>,
+) {
+    label.0 = aim_assist.label();
+}
+
+fn toggle_power_serve(_: Trigger<Pointer<Click>>, mut power_serve: ResMut<PowerServe>) {
+    power_serve.0 = !power_serve.0;
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct PowerServeLabel;
+
+fn update_power_serve_label(
+    power_serve: Res<PowerServe>,
+    mut label: Single<&mut Text, With<PowerServeLabel>>,
+) {
+    label.0 = if power_serve.0 { "On" } else { "Off" }.to_string();
+}
+
+fn previous_idle_timeout(_: Trigger<Pointer<Click>>, mut timeout: ResMut<IdleTimeout>) {
+    *timeout = timeout.previous();
+}
+
+fn next_idle_timeout(_: Trigger<Pointer<Click>>, mut timeout: ResMut<IdleTimeout>) {
+    *timeout = timeout.next();
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct IdleTimeoutLabel;
+
+fn update_idle_timeout_label(
+    timeout: Res<IdleTimeout>,
+    mut label: Single<&mut Text, With<IdleTimeoutLabel>>,
+) {
+    label.0 = timeout.label();
+}
+
+fn toggle_focus_pause(_: Trigger<Pointer<Click>>, mut setting: ResMut<PauseOnFocusLoss>) {
+    setting.0 = !setting.0;
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct FocusPauseLabel;
+
+fn update_focus_pause_label(
+    setting: Res<PauseOnFocusLoss>,
+    mut label: Single<&mut Text, With<FocusPauseLabel>>,
+) {
+    label.0 = if setting.0 { "On" } else { "Off" }.to_string();
+}
+
+fn toggle_coach_hints(_: Trigger<Pointer<Click>>, mut hints: ResMut<CoachHints>) {
+    hints.0 = !hints.0;
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct CoachHintsLabel;
+
+fn update_coach_hints_label(
+    hints: Res<CoachHints>,
+    mut label: Single<&mut Text, With<CoachHintsLabel>>,
+) {
+    label.0 = if hints.0 { "On" } else { "Off" }.to_string();
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn toggle_export_telemetry(_: Trigger<Pointer<Click>>, mut export: ResMut<ExportTelemetry>) {
+    export.0 = !export.0;
+}
+
+#[cfg(not(target_family = "wasm"))]
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct ExportTelemetryLabel;
+
+#[cfg(not(target_family = "wasm"))]
+fn update_export_telemetry_label(
+    export: Res<ExportTelemetry>,
+    mut label: Single<&mut Text, With<ExportTelemetryLabel>>,
+) {
+    label.0 = if export.0 { "On" } else { "Off" }.to_string();
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn previous_court_theme(_: Trigger<Pointer<Click>>, mut choices: ResMut<CourtThemeChoices>) {
+    choices.previous();
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn next_court_theme(_: Trigger<Pointer<Click>>, mut choices: ResMut<CourtThemeChoices>) {
+    choices.next();
+}
+
+#[cfg(not(target_family = "wasm"))]
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct CourtThemeLabel;
+
+#[cfg(not(target_family = "wasm"))]
+fn update_court_theme_label(
+    choices: Res<CourtThemeChoices>,
+    mut label: Single<&mut Text, With<CourtThemeLabel>>,
+) {
+    label.0 = choices.selected().to_string();
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct PlayerColorLabel(PlayerSide);
+
+fn update_player_color_labels(
+    profiles: Res<PlayerProfiles>,
+    mut labels: Query<(&mut Text, &mut TextColor, &PlayerColorLabel)>,
+) {
+    for (mut text, mut color, label) in &mut labels {
+        let player_color = profiles.get(label.0).color;
+        text.0 = player_color.name().to_string();
+        color.0 = player_color.color();
+    }
 }
 
 fn go_back_on_click(
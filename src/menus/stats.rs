@@ -0,0 +1,107 @@
+//! The career stats menu.
+
+use bevy::{
+    ecs::spawn::SpawnIter, input::common_conditions::input_just_pressed, prelude::*, ui::Val::*,
+};
+
+use crate::{game::stats::CareerStats, menus::Menu, theme::prelude::*};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<StatsMenu>();
+
+    app.add_systems(OnEnter(Menu::Stats), spawn_stats_menu);
+    app.add_systems(
+        Update,
+        go_back.run_if(in_state(Menu::Stats).and(input_just_pressed(KeyCode::Escape))),
+    );
+}
+
+fn spawn_stats_menu(mut commands: Commands, career: Res<CareerStats>) {
+    commands.spawn((
+        widget::ui_root("Stats Menu"),
+        StatsMenu,
+        GlobalZIndex(2),
+        StateScoped(Menu::Stats),
+        children![
+            widget::header("Career Stats"),
+            stats_grid(&career),
+            widget::button("High Scores", open_high_scores_menu),
+            widget::button("Reset Stats", confirm_reset_stats),
+            widget::button("Back", go_back_on_click),
+        ],
+    ));
+}
+
+/// The stats menu's root, rebuilt when the stats are reset
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct StatsMenu;
+
+fn stats_grid(career: &CareerStats) -> impl Bundle {
+    let cells = career
+        .rows()
+        .into_iter()
+        .flat_map(|(name, value)| {
+            [
+                (
+                    widget::label(name),
+                    Node {
+                        justify_self: JustifySelf::End,
+                        ..default()
+                    },
+                ),
+                (
+                    widget::label(value.to_string()),
+                    Node {
+                        justify_self: JustifySelf::Start,
+                        ..default()
+                    },
+                ),
+            ]
+        })
+        .collect::<Vec<_>>();
+
+    (
+        Name::new("Stats Grid"),
+        Node {
+            display: Display::Grid,
+            row_gap: Px(10.0),
+            column_gap: Px(30.0),
+            grid_template_columns: RepeatedGridTrack::px(2, 400.0),
+            ..default()
+        },
+        Children::spawn(SpawnIter(cells.into_iter())),
+    )
+}
+
+fn confirm_reset_stats(_: Trigger<Pointer<Click>>, mut commands: Commands) {
+    commands.spawn((
+        confirm_dialog("Reset all career stats?", "Reset", reset_stats),
+        StateScoped(Menu::Stats),
+    ));
+}
+
+fn reset_stats(
+    _: Trigger<Pointer<Click>>,
+    mut commands: Commands,
+    mut career: ResMut<CareerStats>,
+    menu: Query<Entity, With<StatsMenu>>,
+) {
+    career.reset();
+    for entity in &menu {
+        commands.entity(entity).despawn();
+    }
+    commands.run_system_cached(spawn_stats_menu);
+}
+
+fn open_high_scores_menu(_: Trigger<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::HighScores);
+}
+
+fn go_back_on_click(_: Trigger<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Main);
+}
+
+fn go_back(mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Main);
+}
@@ -0,0 +1,46 @@
+//! The prompt shown when the match pauses itself because nobody's moved.
+
+use bevy::prelude::*;
+
+use crate::{menus::Menu, screens::Screen, theme::prelude::*};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Menu::StillThere), spawn_still_there_menu);
+    app.add_systems(
+        Update,
+        resume.run_if(in_state(Menu::StillThere).and(any_key_just_pressed)),
+    );
+}
+
+fn spawn_still_there_menu(mut commands: Commands) {
+    commands.spawn((
+        widget::ui_root("Still There Menu"),
+        GlobalZIndex(2),
+        StateScoped(Menu::StillThere),
+        children![
+            widget::header("Are you still there?"),
+            widget::label("Press any key to carry on"),
+            widget::button("I'm here", resume_on_click),
+            widget::button("Quit to title", quit_to_title),
+        ],
+    ));
+}
+
+fn any_key_just_pressed(keyboard: Res<ButtonInput<KeyCode>>, gamepads: Query<&Gamepad>) -> bool {
+    keyboard.get_just_pressed().next().is_some()
+        || gamepads
+            .iter()
+            .any(|gamepad| gamepad.get_just_pressed().next().is_some())
+}
+
+fn resume_on_click(_: Trigger<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::None);
+}
+
+fn resume(mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::None);
+}
+
+fn quit_to_title(_: Trigger<Pointer<Click>>, mut next_screen: ResMut<NextState<Screen>>) {
+    next_screen.set(Screen::Title);
+}
@@ -0,0 +1,140 @@
+//! The tournament setup menu.
+
+use bevy::{input::common_conditions::input_just_pressed, prelude::*, ui::Val::*};
+
+use crate::{
+    menus::Menu,
+    screens::Screen,
+    theme::prelude::*,
+    tournament::{MAX_ENTRANTS, MIN_ENTRANTS, Tournament, entrant_names},
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<TournamentSetup>();
+    app.add_systems(OnEnter(Menu::Tournament), spawn_tournament_menu);
+    app.add_systems(
+        Update,
+        go_back.run_if(in_state(Menu::Tournament).and(input_just_pressed(KeyCode::Escape))),
+    );
+
+    app.register_type::<EntrantCountLabel>();
+    app.add_systems(
+        Update,
+        update_entrant_count_label.run_if(in_state(Menu::Tournament)),
+    );
+}
+
+/// Options chosen before starting a tournament
+#[derive(Resource)]
+struct TournamentSetup {
+    entrants: usize,
+}
+
+impl Default for TournamentSetup {
+    fn default() -> Self {
+        Self {
+            entrants: MIN_ENTRANTS,
+        }
+    }
+}
+
+fn spawn_tournament_menu(mut commands: Commands) {
+    let saved = Tournament::load().is_some();
+
+    commands
+        .spawn((
+            widget::ui_root("Tournament Menu"),
+            GlobalZIndex(2),
+            StateScoped(Menu::Tournament),
+            children![
+                widget::header("Tournament"),
+                (
+                    Name::new("Entrants"),
+                    Node {
+                        column_gap: Px(30.0),
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    children![widget::label("Players"), entrant_count_widget()],
+                ),
+                widget::button("Start", start_tournament),
+            ],
+        ))
+        .with_children(|parent| {
+            if saved {
+                parent.spawn(widget::button("Resume", resume_tournament));
+            }
+            parent.spawn(widget::button("Back", go_back_on_click));
+        });
+}
+
+fn entrant_count_widget() -> impl Bundle {
+    (
+        Name::new("Entrant Count Widget"),
+        Node::default(),
+        children![
+            widget::button_small("-", remove_entrant),
+            (
+                Name::new("Current Entrants"),
+                Node {
+                    padding: UiRect::horizontal(Px(10.0)),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![(widget::label(""), EntrantCountLabel)],
+            ),
+            widget::button_small("+", add_entrant),
+        ],
+    )
+}
+
+fn remove_entrant(_: Trigger<Pointer<Click>>, mut setup: ResMut<TournamentSetup>) {
+    setup.entrants = (setup.entrants - 1).max(MIN_ENTRANTS);
+}
+
+fn add_entrant(_: Trigger<Pointer<Click>>, mut setup: ResMut<TournamentSetup>) {
+    setup.entrants = (setup.entrants + 1).min(MAX_ENTRANTS);
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct EntrantCountLabel;
+
+fn update_entrant_count_label(
+    setup: Res<TournamentSetup>,
+    mut label: Single<&mut Text, With<EntrantCountLabel>>,
+) {
+    label.0 = setup.entrants.to_string();
+}
+
+fn start_tournament(
+    _: Trigger<Pointer<Click>>,
+    mut commands: Commands,
+    setup: Res<TournamentSetup>,
+    mut next_screen: ResMut<NextState<Screen>>,
+) {
+    let tournament = Tournament::new(entrant_names(setup.entrants));
+    tournament.save();
+    commands.insert_resource(tournament);
+    next_screen.set(Screen::Bracket);
+}
+
+fn resume_tournament(
+    _: Trigger<Pointer<Click>>,
+    mut commands: Commands,
+    mut next_screen: ResMut<NextState<Screen>>,
+) {
+    let Some(tournament) = Tournament::load() else {
+        return;
+    };
+    commands.insert_resource(tournament);
+    next_screen.set(Screen::Bracket);
+}
+
+fn go_back_on_click(_: Trigger<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Modes);
+}
+
+fn go_back(mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Modes);
+}
@@ -0,0 +1,196 @@
+//! Asset packs installed in a `mods/` folder next to the game.
+//!
+//! Each pack is a folder with a `mod.json` manifest listing what it adds,
+//! with paths relative to the pack's folder:
+//!
+//! ```json
+//! {
+//!     "name": "Neon Nights",
+//!     "version": "1.0",
+//!     "themes": [{ "name": "Neon", "file": "neon.theme.json" }]
+//! }
+//! ```
+//!
+//! Packs are checked once at startup. Anything wrong with one (a missing
+//! file, a name another pack already took, content this version of the game
+//! can't use) is skipped and reported in the log and the settings menu, and
+//! the rest of the pack still loads. Court themes from packs can be picked in
+//! the settings menu.
+
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Component, Path},
+};
+
+use bevy::{asset::io::AssetSourceBuilder, prelude::*};
+use serde::Deserialize;
+
+use crate::game::court_theme::CourtThemeChoices;
+
+/// Where packs are installed, relative to the working directory
+const MODS_DIR: &str = "mods";
+/// The asset source packs are loaded through, as in `mods://pack/file`
+const MODS_SOURCE: &str = "mods";
+const MANIFEST: &str = "mod.json";
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<InstalledMods>();
+    app.add_systems(Startup, load_mods);
+}
+
+/// Lets assets load from `mods/`. Has to be called before Bevy's
+/// `AssetPlugin` is added, and does nothing without a `mods/` folder.
+pub fn register_source(app: &mut App) {
+    if Path::new(MODS_DIR).is_dir() {
+        app.register_asset_source(
+            MODS_SOURCE,
+            AssetSourceBuilder::platform_default(MODS_DIR, None),
+        );
+    }
+}
+
+/// A pack's `mod.json`
+#[derive(Deserialize, Debug)]
+struct ModManifest {
+    name: String,
+    #[serde(default)]
+    version: String,
+    #[serde(default)]
+    themes: Vec<ModTheme>,
+    /// Anything else, which this version of the game doesn't know how to use
+    #[serde(flatten)]
+    unsupported: BTreeMap<String, serde_json::Value>,
+}
+
+/// A court theme in a pack
+#[derive(Deserialize, Debug)]
+struct ModTheme {
+    name: String,
+    file: String,
+}
+
+/// Packs that loaded, and everything wrong with the ones found
+#[derive(Resource, Debug, Default)]
+pub struct InstalledMods {
+    /// Name and version of each pack
+    pub packs: Vec<(String, String)>,
+    pub problems: Vec<String>,
+}
+
+impl InstalledMods {
+    /// A one-line summary, e.g. "2 loaded, 1 problem"
+    pub fn summary(&self) -> String {
+        let problems = match self.problems.len() {
+            0 => String::new(),
+            1 => ", 1 problem".into(),
+            count => format!(", {count} problems"),
+        };
+        format!("{} loaded{problems}", self.packs.len())
+    }
+}
+
+fn load_mods(
+    asset_server: Res<AssetServer>,
+    mut installed: ResMut<InstalledMods>,
+    mut themes: ResMut<CourtThemeChoices>,
+) {
+    if asset_server.get_source(MODS_SOURCE).is_err() {
+        return;
+    }
+    let Ok(entries) = fs::read_dir(MODS_DIR) else {
+        return;
+    };
+    // Sorted, so the first of two conflicting packs always wins
+    let mut folders: Vec<_> = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .collect();
+    folders.sort();
+
+    for folder in folders {
+        let folder_name = folder.file_name().unwrap_or_default().to_string_lossy();
+        let manifest = match fs::read_to_string(folder.join(MANIFEST))
+            .map_err(|error| error.to_string())
+            .and_then(|json| {
+                serde_json::from_str::<ModManifest>(&json).map_err(|error| error.to_string())
+            }) {
+            Ok(manifest) => manifest,
+            Err(error) => {
+                installed
+                    .problems
+                    .push(format!("{folder_name}: couldn't read {MANIFEST}: {error}"));
+                continue;
+            }
+        };
+
+        let name = manifest.name.trim().to_string();
+        if name.is_empty() {
+            installed
+                .problems
+                .push(format!("{folder_name}: {MANIFEST} has no name"));
+            continue;
+        }
+        if installed.packs.iter().any(|(other, _)| *other == name) {
+            installed.problems.push(format!(
+                "{folder_name}: a pack called \"{name}\" is already loaded"
+            ));
+            continue;
+        }
+
+        for kind in manifest.unsupported.keys() {
+            installed
+                .problems
+                .push(format!("{name}: \"{kind}\" isn't supported, skipped"));
+        }
+
+        for theme in manifest.themes {
+            if theme.name.trim().is_empty() {
+                installed
+                    .problems
+                    .push(format!("{name}: a theme has no name ({})", theme.file));
+                continue;
+            }
+            if let Err(problem) = check_file(&folder, &theme.file, ".theme.json") {
+                installed
+                    .problems
+                    .push(format!("{name}: theme \"{}\" {problem}", theme.name));
+                continue;
+            }
+            let path = format!("{MODS_SOURCE}://{folder_name}/{}", theme.file);
+            if !themes.add(theme.name.clone(), asset_server.load(path)) {
+                installed.problems.push(format!(
+                    "{name}: theme \"{}\" clashes with one already loaded",
+                    theme.name
+                ));
+            }
+        }
+
+        installed.packs.push((name, manifest.version));
+    }
+
+    for problem in &installed.problems {
+        warn!("Mod problem: {problem}");
+    }
+    info!("Mods: {}", installed.summary());
+}
+
+/// Checks that `file` is inside the pack's `folder`, exists, and has the
+/// right extension
+fn check_file(folder: &Path, file: &str, extension: &str) -> Result<(), String> {
+    let path = Path::new(file);
+    let inside = path
+        .components()
+        .all(|component| matches!(component, Component::Normal(_)));
+    if !inside {
+        return Err(format!("points outside the pack ({file})"));
+    }
+    if !file.ends_with(extension) {
+        return Err(format!("isn't a {extension} file ({file})"));
+    }
+    if !folder.join(path).is_file() {
+        return Err(format!("is missing ({file})"));
+    }
+    Ok(())
+}
@@ -0,0 +1,162 @@
+//! Playback controls shown while watching a replay: pause, speed, frame step
+//! and a marker per goal to jump straight to it.
+
+use bevy::{app::FixedMain, ecs::spawn::SpawnIter, prelude::*, ui::Val::*};
+
+use super::{ReplayClock, ReplayPlayback};
+use crate::{screens::Screen, theme::prelude::*};
+
+/// Playback speeds the speed buttons step through
+const SPEEDS: [f32; 5] = [0.25, 0.5, 1.0, 2.0, 4.0];
+const NORMAL_SPEED: usize = 2;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<PlaybackStatusLabel>();
+    app.add_systems(
+        OnEnter(Screen::Gameplay),
+        spawn_control_bar.run_if(resource_exists::<ReplayPlayback>),
+    );
+    app.add_systems(
+        Update,
+        update_status_label
+            .run_if(in_state(Screen::Gameplay).and(resource_exists::<ReplayPlayback>)),
+    );
+}
+
+fn spawn_control_bar(mut commands: Commands, playback: Res<ReplayPlayback>) {
+    // Every checkpoint after the first one starts the point after a goal
+    let markers: Vec<_> = playback
+        .replay
+        .checkpoints
+        .iter()
+        .enumerate()
+        .skip(1)
+        .map(|(index, _)| {
+            widget::button_small(
+                index.to_string(),
+                move |_: Trigger<Pointer<Click>>,
+                      mut playback: ResMut<ReplayPlayback>,
+                      mut next_screen: ResMut<NextState<Screen>>| {
+                    // Rebuild the match, then jump once the point is set up
+                    playback.pending_seek = Some(index);
+                    next_screen.set(Screen::Gameplay);
+                },
+            )
+        })
+        .collect();
+
+    commands.spawn((
+        Name::new("Replay Controls"),
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Px(10.0),
+            width: Percent(100.0),
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            row_gap: Px(8.0),
+            ..default()
+        },
+        StateScoped(Screen::Gameplay),
+        children![
+            (
+                Name::new("Transport"),
+                Node {
+                    column_gap: Px(10.0),
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                children![
+                    widget::button_small("<<", slow_down),
+                    widget::button_small("||", toggle_pause),
+                    widget::button_small(">|", step_tick),
+                    widget::button_small(">>", speed_up),
+                    (widget::label(""), PlaybackStatusLabel),
+                ],
+            ),
+            (
+                Name::new("Goal Markers"),
+                Node {
+                    column_gap: Px(6.0),
+                    row_gap: Px(6.0),
+                    flex_wrap: FlexWrap::Wrap,
+                    justify_content: JustifyContent::Center,
+                    max_width: Percent(80.0),
+                    ..default()
+                },
+                Children::spawn(SpawnIter(markers.into_iter())),
+            ),
+        ],
+    ));
+}
+
+fn current_speed_index(time: &Time<Virtual>) -> usize {
+    SPEEDS
+        .iter()
+        .position(|&speed| speed == time.relative_speed())
+        .unwrap_or(NORMAL_SPEED)
+}
+
+fn slow_down(_: Trigger<Pointer<Click>>, mut time: ResMut<Time<Virtual>>) {
+    let index = current_speed_index(&time).saturating_sub(1);
+    time.set_relative_speed(SPEEDS[index]);
+}
+
+fn speed_up(_: Trigger<Pointer<Click>>, mut time: ResMut<Time<Virtual>>) {
+    let index = (current_speed_index(&time) + 1).min(SPEEDS.len() - 1);
+    time.set_relative_speed(SPEEDS[index]);
+}
+
+fn toggle_pause(_: Trigger<Pointer<Click>>, mut time: ResMut<Time<Virtual>>) {
+    if time.is_paused() {
+        time.unpause();
+    } else {
+        time.pause();
+    }
+}
+
+/// Runs a single fixed tick while paused, the same way the fixed main loop
+/// would: advance `Time<Fixed>` and expose it as the generic `Time`.
+fn step_tick(_: Trigger<Pointer<Click>>, mut commands: Commands, time: Res<Time<Virtual>>) {
+    if !time.is_paused() {
+        return;
+    }
+
+    commands.queue(|world: &mut World| {
+        let timestep = world.resource::<Time<Fixed>>().timestep();
+        world.resource_mut::<Time<Fixed>>().advance_by(timestep);
+
+        let fixed = world.resource::<Time<Fixed>>().as_generic();
+        let frame = std::mem::replace(&mut *world.resource_mut::<Time>(), fixed);
+        world.run_schedule(FixedMain);
+        *world.resource_mut::<Time>() = frame;
+    });
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct PlaybackStatusLabel;
+
+fn update_status_label(
+    time: Res<Time<Virtual>>,
+    clock: Res<ReplayClock>,
+    playback: Res<ReplayPlayback>,
+    mut label: Single<&mut Text, With<PlaybackStatusLabel>>,
+) {
+    let header = &playback.replay.header;
+    let elapsed = (clock.tick as f64 / header.tick_rate) as u64;
+    let total = header.duration_secs() as u64;
+    let state = if time.is_paused() {
+        "Paused"
+    } else {
+        "Playing"
+    };
+
+    label.0 = format!(
+        "{state} {}x  {}:{:02} / {}:{:02}",
+        time.relative_speed(),
+        elapsed / 60,
+        elapsed % 60,
+        total / 60,
+        total % 60
+    );
+}
@@ -0,0 +1,667 @@
+//! Match recording and playback.
+//!
+//! Every match is recorded as a [`Replay`]: a header with everything needed to
+//! rebuild the match (rules, mutators, serve seed, movement feel, power serve,
+//! characters, player names)
+//! followed by the input stream, stamped with fixed-timestep ticks. Completed
+//! matches are saved to `replays/` and can be watched again from the Replays screen.
+//!
+//! Paddle movement and buffered presses (serves, smashes, abilities) are
+//! sampled at the start of each fixed tick and applied on exactly the recorded
+//! tick. A [`Checkpoint`] taken at the start of every point resyncs
+//! playback as each point is set up, and lets the viewer jump straight to any
+//! goal.
+
+mod controls;
+pub mod telemetry;
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use avian2d::prelude::*;
+use bevy::{ecs::system::SystemParam, prelude::*};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    AppSystems,
+    arcade::Arcade,
+    daily::{DailyChallenge, seed_daily_match},
+    game::{
+        GamePhase,
+        ball::{Ball, ServeDirection, ServeRng, spawn_ball},
+        input_buffer::{BufferedAction, BufferedPress, InputBuffer},
+        mutators::Mutators,
+        overtime::Overtime,
+        player::{MirroredCourt, MovementFeel, PaddleIntent, Player, PlayerSide},
+        power_serve::PowerServe,
+        profile::PlayerProfiles,
+        roster::Character,
+        rules::MatchRules,
+        scoring::Score,
+        stats::MatchStats,
+        undo::PointUndone,
+    },
+    screens::Screen,
+    tournament::Tournament,
+};
+
+/// Bumped whenever the file layout changes; older files are skipped
+pub const REPLAY_VERSION: u32 = 20;
+
+const REPLAY_DIR: &str = "replays";
+const REPLAY_EXTENSION: &str = "json";
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_plugins((controls::plugin, telemetry::plugin));
+
+    app.register_type::<ReplayClock>();
+    app.init_resource::<ReplayClock>();
+
+    // Live input is ignored while a replay drives the match
+    app.configure_sets(
+        Update,
+        AppSystems::RecordInput.run_if(not(resource_exists::<ReplayPlayback>)),
+    );
+
+    app.add_systems(
+        OnEnter(Screen::Gameplay),
+        (
+            reset_clock,
+            start_recording
+                .after(seed_daily_match)
+                .run_if(not(resource_exists::<ReplayPlayback>).and(in_state(Arcade::Off))),
+            (restart_playback, spawn_replay_banner).run_if(resource_exists::<ReplayPlayback>),
+        ),
+    );
+    app.add_systems(
+        FixedPreUpdate,
+        (
+            tick_clock,
+            (record_movement, record_presses).run_if(resource_exists::<ReplayRecorder>),
+            drive_playback.run_if(resource_exists::<ReplayPlayback>),
+        )
+            .chain()
+            .run_if(in_state(Screen::Gameplay)),
+    );
+    app.add_systems(
+        Update,
+        record_undos
+            .in_set(AppSystems::Update)
+            .run_if(in_state(Screen::Gameplay).and(resource_exists::<ReplayRecorder>)),
+    );
+    app.add_systems(
+        OnEnter(GamePhase::WaitingToServe),
+        (
+            record_checkpoint.run_if(resource_exists::<ReplayRecorder>),
+            apply_next_checkpoint.run_if(resource_exists::<ReplayPlayback>),
+        )
+            .run_if(in_state(Screen::Gameplay)),
+    );
+    app.add_systems(
+        OnEnter(GamePhase::GameOver),
+        save_replay.run_if(resource_exists::<ReplayRecorder>),
+    );
+    app.add_systems(OnExit(Screen::Gameplay), stop_recording);
+    app.add_systems(OnEnter(Screen::Title), stop_playback);
+}
+
+/// A recorded match
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Replay {
+    pub header: ReplayHeader,
+    pub inputs: Vec<ReplayInput>,
+    /// Match state each time a point was set up, in order
+    pub checkpoints: Vec<Checkpoint>,
+}
+
+/// Everything needed to set a match up again, plus a summary for browsing
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ReplayHeader {
+    pub version: u32,
+    /// Seconds since the Unix epoch
+    pub recorded_at: u64,
+    /// Seed for serve angles
+    pub seed: u64,
+    pub rules: MatchRules,
+    pub mutators: Mutators,
+    pub feel: MovementFeel,
+    /// Whether the recording player served with the power meter
+    pub power_serve: PowerServe,
+    /// Whether the recording player was on the right
+    pub mirrored: MirroredCourt,
+    /// Left and right characters
+    pub characters: [Character; 2],
+    /// Left and right player names
+    pub players: [String; 2],
+    /// Left and right final score
+    pub final_score: [u32; 2],
+    /// Length of the match in fixed ticks
+    pub ticks: u32,
+    /// Fixed timestep rate the match was recorded at
+    pub tick_rate: f64,
+}
+
+impl ReplayHeader {
+    /// Match length in seconds
+    pub fn duration_secs(&self) -> f64 {
+        self.ticks as f64 / self.tick_rate
+    }
+}
+
+/// One input, applied on `tick`
+#[derive(Serialize, Deserialize, Clone, Copy, Debug)]
+pub struct ReplayInput {
+    pub tick: u32,
+    pub action: ReplayAction,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub enum ReplayAction {
+    /// The keyboard paddle's movement intent changed
+    Move(f32),
+    Serve,
+    Feint,
+    Smash,
+    Lob,
+    DropShot,
+    Ability,
+    /// The last point was undone
+    Undo,
+}
+
+/// Match state as a point is about to be served
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Checkpoint {
+    pub tick: u32,
+    /// Left and right score
+    pub score: [u32; 2],
+    /// Whether the match had gone to sudden death
+    pub sudden_death: bool,
+    /// Stats for the match so far
+    pub stats: MatchStats,
+    pub server: PlayerSide,
+    pub serves_in_turn: u32,
+    /// Serves drawn from the serve RNG so far
+    pub serves: u32,
+    /// Left and right paddle heights
+    pub paddles: [f32; 2],
+    /// The keyboard paddle's movement intent
+    pub intent: f32,
+}
+
+impl Replay {
+    /// Loads a replay, skipping files from other format versions
+    pub fn load(path: &Path) -> Option<Self> {
+        let json = fs::read_to_string(path).ok()?;
+        let replay: Self = serde_json::from_str(&json)
+            .inspect_err(|error| warn!("Ignoring unreadable replay {path:?}: {error}"))
+            .ok()?;
+        if replay.header.version != REPLAY_VERSION {
+            warn!(
+                "Ignoring replay {path:?} from format version {}",
+                replay.header.version
+            );
+            return None;
+        }
+        Some(replay)
+    }
+
+    /// Saves the replay under a new timestamped file name
+    pub fn save(&self) {
+        let path = PathBuf::from(REPLAY_DIR).join(format!(
+            "replay-{}.{REPLAY_EXTENSION}",
+            self.header.recorded_at
+        ));
+        let result = fs::create_dir_all(REPLAY_DIR).and_then(|_| {
+            let json = serde_json::to_string(self).map_err(std::io::Error::other)?;
+            fs::write(&path, json)
+        });
+        match result {
+            Ok(()) => info!("Saved replay to {path:?}"),
+            Err(error) => warn!("Couldn't save replay: {error}"),
+        }
+    }
+}
+
+/// Deletes a saved replay file
+pub fn delete_replay(path: &Path) {
+    match fs::remove_file(path) {
+        Ok(()) => info!("Deleted replay {path:?}"),
+        Err(error) => warn!("Couldn't delete replay {path:?}: {error}"),
+    }
+}
+
+/// Lists saved replays, newest first
+pub fn saved_replays() -> Vec<(PathBuf, ReplayHeader)> {
+    let Ok(entries) = fs::read_dir(REPLAY_DIR) else {
+        return Vec::new();
+    };
+
+    let mut replays: Vec<_> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == REPLAY_EXTENSION))
+        .filter_map(|path| Replay::load(&path).map(|replay| (path, replay.header)))
+        .collect();
+    replays.sort_by_key(|(_, header)| std::cmp::Reverse(header.recorded_at));
+    replays
+}
+
+/// Fixed ticks since the match started
+#[derive(Resource, Default, Reflect)]
+#[reflect(Resource)]
+pub struct ReplayClock {
+    pub tick: u32,
+}
+
+/// Collects inputs while a match is being played
+#[derive(Resource)]
+struct ReplayRecorder {
+    seed: u64,
+    players: [String; 2],
+    inputs: Vec<ReplayInput>,
+    checkpoints: Vec<Checkpoint>,
+    last_intent: f32,
+}
+
+/// A replay being watched
+#[derive(Resource)]
+pub struct ReplayPlayback {
+    pub replay: Replay,
+    /// Next input to apply
+    cursor: usize,
+    /// The keyboard paddle's current recorded intent
+    intent: f32,
+    /// Checkpoint to apply when the next point is set up
+    next_checkpoint: usize,
+    /// Checkpoint to start from when gameplay is next entered
+    pending_seek: Option<usize>,
+    /// Settings to put back once the replay is over
+    previous_rules: MatchRules,
+    previous_mutators: Mutators,
+    previous_feel: MovementFeel,
+    previous_power_serve: PowerServe,
+    previous_mirrored: MirroredCourt,
+    previous_characters: [Character; 2],
+}
+
+impl ReplayPlayback {
+    /// Sets the match up the way it was recorded. Call before entering gameplay.
+    pub fn start(
+        replay: Replay,
+        rules: &mut MatchRules,
+        mutators: &mut Mutators,
+        feel: &mut MovementFeel,
+        power_serve: &mut PowerServe,
+        mirrored: &mut MirroredCourt,
+        profiles: &mut PlayerProfiles,
+    ) -> ReplayPlayback {
+        let previous_rules = std::mem::replace(rules, replay.header.rules.clone());
+        let previous_mutators = std::mem::replace(mutators, replay.header.mutators.clone());
+        let previous_feel = std::mem::replace(feel, replay.header.feel);
+        let previous_power_serve = std::mem::replace(power_serve, replay.header.power_serve);
+        let previous_mirrored = std::mem::replace(mirrored, replay.header.mirrored);
+        let [left, right] = replay.header.characters;
+        let previous_characters = [
+            std::mem::replace(&mut profiles.left.character, left),
+            std::mem::replace(&mut profiles.right.character, right),
+        ];
+        ReplayPlayback {
+            replay,
+            cursor: 0,
+            intent: 0.0,
+            next_checkpoint: 0,
+            pending_seek: None,
+            previous_rules,
+            previous_mutators,
+            previous_feel,
+            previous_power_serve,
+            previous_mirrored,
+            previous_characters,
+        }
+    }
+}
+
+fn reset_clock(mut clock: ResMut<ReplayClock>) {
+    clock.tick = 0;
+}
+
+fn tick_clock(mut clock: ResMut<ReplayClock>) {
+    clock.tick += 1;
+}
+
+fn start_recording(
+    mut commands: Commands,
+    mut rng: ResMut<ServeRng>,
+    tournament: Option<Res<Tournament>>,
+    daily: Option<Res<DailyChallenge>>,
+) {
+    // The daily challenge has already seeded its serves
+    let seed = if daily.is_some() {
+        rng.seed()
+    } else {
+        let seed = rand::random();
+        rng.reseed(seed);
+        seed
+    };
+
+    let players = tournament
+        .as_deref()
+        .and_then(Tournament::current_players)
+        .map_or_else(
+            || ["Left".into(), "Right".into()],
+            |names| names.map(String::from),
+        );
+
+    commands.insert_resource(ReplayRecorder {
+        seed,
+        players,
+        inputs: Vec::new(),
+        checkpoints: Vec::new(),
+        last_intent: 0.0,
+    });
+}
+
+fn stop_recording(mut commands: Commands) {
+    commands.remove_resource::<ReplayRecorder>();
+}
+
+fn record_movement(
+    clock: Res<ReplayClock>,
+    mut recorder: ResMut<ReplayRecorder>,
+    mirrored: Res<MirroredCourt>,
+    paddles: Query<(&Player, &PaddleIntent)>,
+) {
+    let Some(intent) = paddles
+        .iter()
+        .find(|(player, _)| player.takes_keyboard_input(&mirrored))
+        .map(|(_, intent)| intent.0)
+    else {
+        return;
+    };
+
+    if intent != recorder.last_intent {
+        recorder.last_intent = intent;
+        recorder.inputs.push(ReplayInput {
+            tick: clock.tick,
+            action: ReplayAction::Move(intent),
+        });
+    }
+}
+
+/// Stamps presses with the first tick that sees them, which is the tick they
+/// are consumed on
+fn record_presses(
+    clock: Res<ReplayClock>,
+    mut recorder: ResMut<ReplayRecorder>,
+    mut presses: EventReader<BufferedPress>,
+) {
+    for BufferedPress(action) in presses.read() {
+        recorder.inputs.push(ReplayInput {
+            tick: clock.tick,
+            action: match action {
+                BufferedAction::Serve => ReplayAction::Serve,
+                BufferedAction::Feint => ReplayAction::Feint,
+                BufferedAction::Smash => ReplayAction::Smash,
+                BufferedAction::Lob => ReplayAction::Lob,
+                BufferedAction::DropShot => ReplayAction::DropShot,
+                BufferedAction::Ability => ReplayAction::Ability,
+            },
+        });
+    }
+}
+
+fn record_undos(
+    clock: Res<ReplayClock>,
+    mut recorder: ResMut<ReplayRecorder>,
+    mut undos: EventReader<PointUndone>,
+) {
+    for PointUndone in undos.read() {
+        recorder.inputs.push(ReplayInput {
+            tick: clock.tick,
+            action: ReplayAction::Undo,
+        });
+    }
+}
+
+/// Everything that goes into the header of the match being recorded
+#[derive(SystemParam)]
+struct MatchSetup<'w> {
+    recorder: Res<'w, ReplayRecorder>,
+    clock: Res<'w, ReplayClock>,
+    score: Res<'w, Score>,
+    rules: Res<'w, MatchRules>,
+    mutators: Res<'w, Mutators>,
+    feel: Res<'w, MovementFeel>,
+    power_serve: Res<'w, PowerServe>,
+    mirrored: Res<'w, MirroredCourt>,
+    profiles: Res<'w, PlayerProfiles>,
+    fixed_time: Res<'w, Time<Fixed>>,
+}
+
+impl MatchSetup<'_> {
+    /// The header for the match as it stands, stamped with the current time
+    fn header(&self) -> ReplayHeader {
+        let recorded_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs());
+
+        ReplayHeader {
+            version: REPLAY_VERSION,
+            recorded_at,
+            seed: self.recorder.seed,
+            rules: self.rules.clone(),
+            mutators: self.mutators.clone(),
+            feel: *self.feel,
+            power_serve: *self.power_serve,
+            mirrored: *self.mirrored,
+            characters: [self.profiles.left.character, self.profiles.right.character],
+            players: self.recorder.players.clone(),
+            final_score: [self.score.left, self.score.right],
+            ticks: self.clock.tick,
+            tick_rate: 1.0 / self.fixed_time.timestep().as_secs_f64(),
+        }
+    }
+}
+
+fn save_replay(setup: MatchSetup) {
+    Replay {
+        header: setup.header(),
+        inputs: setup.recorder.inputs.clone(),
+        checkpoints: setup.recorder.checkpoints.clone(),
+    }
+    .save();
+}
+
+fn record_checkpoint(
+    clock: Res<ReplayClock>,
+    score: Res<Score>,
+    overtime: Res<State<Overtime>>,
+    stats: Res<MatchStats>,
+    serve_direction: Res<ServeDirection>,
+    rng: Res<ServeRng>,
+    mut recorder: ResMut<ReplayRecorder>,
+    paddles: Query<(&Player, &Transform)>,
+) {
+    let mut heights = [0.0; 2];
+    for (player, transform) in &paddles {
+        heights[player.side as usize] = transform.translation.y;
+    }
+
+    let intent = recorder.last_intent;
+    recorder.checkpoints.push(Checkpoint {
+        tick: clock.tick,
+        score: [score.left, score.right],
+        sudden_death: *overtime.get() == Overtime::SuddenDeath,
+        stats: stats.clone(),
+        server: serve_direction.side,
+        serves_in_turn: serve_direction.serves_in_turn,
+        serves: rng.serves(),
+        paddles: heights,
+        intent,
+    });
+}
+
+/// Rewinds the replay to the start (or to a seek target), including when
+/// watching it again. The checkpoint is applied once the serve is set up.
+fn restart_playback(mut playback: ResMut<ReplayPlayback>, mut rng: ResMut<ServeRng>) {
+    playback.cursor = 0;
+    playback.intent = 0.0;
+    playback.next_checkpoint = playback.pending_seek.take().unwrap_or(0);
+    rng.reseed(playback.replay.header.seed);
+}
+
+/// Puts the match into the state recorded as this point was set up
+fn apply_next_checkpoint(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut playback: ResMut<ReplayPlayback>,
+    mut clock: ResMut<ReplayClock>,
+    mut score: ResMut<Score>,
+    mut stats: ResMut<MatchStats>,
+    overtime: Res<State<Overtime>>,
+    mut next_overtime: ResMut<NextState<Overtime>>,
+    mut serve_direction: ResMut<ServeDirection>,
+    mut rng: ResMut<ServeRng>,
+    mirrored: Res<MirroredCourt>,
+    mut paddles: Query<(
+        &Player,
+        &mut Transform,
+        Option<&mut Position>,
+        &mut LinearVelocity,
+        &mut PaddleIntent,
+    )>,
+    balls: Query<(), With<Ball>>,
+) {
+    let playback = &mut *playback;
+    let Some(checkpoint) = playback.replay.checkpoints.get(playback.next_checkpoint) else {
+        return;
+    };
+    playback.next_checkpoint += 1;
+
+    clock.tick = checkpoint.tick;
+    playback.cursor = playback
+        .replay
+        .inputs
+        .partition_point(|input| input.tick <= checkpoint.tick);
+    playback.intent = checkpoint.intent;
+
+    [score.left, score.right] = checkpoint.score;
+    *stats = checkpoint.stats.clone();
+    // Seeking back out of sudden death has to leave it too
+    let recorded_overtime = if checkpoint.sudden_death {
+        Overtime::SuddenDeath
+    } else {
+        Overtime::Regulation
+    };
+    if *overtime.get() != recorded_overtime {
+        next_overtime.set(recorded_overtime);
+    }
+    serve_direction.side = checkpoint.server;
+    serve_direction.serves_in_turn = checkpoint.serves_in_turn;
+    rng.restore(playback.replay.header.seed, checkpoint.serves);
+
+    // A point undone from game over has no ball left to serve
+    if balls.is_empty() {
+        spawn_ball(&mut commands, &mut meshes, &mut materials);
+    }
+
+    for (player, mut transform, position, mut velocity, mut intent) in &mut paddles {
+        let y = checkpoint.paddles[player.side as usize];
+        transform.translation.y = y;
+        if let Some(mut position) = position {
+            position.y = y;
+        }
+        velocity.y = 0.0;
+        intent.0 = if player.takes_keyboard_input(&mirrored) {
+            checkpoint.intent
+        } else {
+            0.0
+        };
+    }
+}
+
+/// Feeds recorded inputs into the match as their ticks come up
+fn drive_playback(
+    clock: Res<ReplayClock>,
+    time: Res<Time<Virtual>>,
+    mut playback: ResMut<ReplayPlayback>,
+    mut buffer: ResMut<InputBuffer>,
+    mut next_phase: ResMut<NextState<GamePhase>>,
+    mirrored: Res<MirroredCourt>,
+    mut paddles: Query<(&Player, &mut PaddleIntent)>,
+) {
+    let playback = &mut *playback;
+    while let Some(input) = playback.replay.inputs.get(playback.cursor) {
+        if input.tick > clock.tick {
+            break;
+        }
+        match input.action {
+            ReplayAction::Move(intent) => playback.intent = intent,
+            ReplayAction::Serve => buffer.press(BufferedAction::Serve, time.elapsed_secs()),
+            ReplayAction::Feint => buffer.press(BufferedAction::Feint, time.elapsed_secs()),
+            ReplayAction::Smash => buffer.press(BufferedAction::Smash, time.elapsed_secs()),
+            ReplayAction::Lob => buffer.press(BufferedAction::Lob, time.elapsed_secs()),
+            ReplayAction::DropShot => buffer.press(BufferedAction::DropShot, time.elapsed_secs()),
+            ReplayAction::Ability => buffer.press(BufferedAction::Ability, time.elapsed_secs()),
+            // Setting the point up again applies the restored checkpoint
+            ReplayAction::Undo => next_phase.set(GamePhase::WaitingToServe),
+        }
+        playback.cursor += 1;
+    }
+
+    // Overwrite whatever the keyboard did since the last tick
+    for (player, mut intent) in &mut paddles {
+        if player.takes_keyboard_input(&mirrored) {
+            intent.0 = playback.intent;
+        }
+    }
+}
+
+fn spawn_replay_banner(mut commands: Commands) {
+    commands.spawn((
+        Name::new("Replay Banner"),
+        Text::new("REPLAY"),
+        TextFont {
+            font_size: 24.0,
+            ..default()
+        },
+        TextColor(Color::srgb(1.0, 0.3, 0.3)),
+        Node {
+            position_type: PositionType::Absolute,
+            left: Val::Px(20.0),
+            top: Val::Px(20.0),
+            ..default()
+        },
+        StateScoped(Screen::Gameplay),
+    ));
+}
+
+/// Leaving to the title ends playback and restores the player's own settings
+fn stop_playback(
+    mut commands: Commands,
+    playback: Option<Res<ReplayPlayback>>,
+    mut rules: ResMut<MatchRules>,
+    mut mutators: ResMut<Mutators>,
+    mut feel: ResMut<MovementFeel>,
+    mut power_serve: ResMut<PowerServe>,
+    mut mirrored: ResMut<MirroredCourt>,
+    mut profiles: ResMut<PlayerProfiles>,
+    mut time: ResMut<Time<Virtual>>,
+) {
+    let Some(playback) = playback else {
+        return;
+    };
+    time.set_relative_speed(1.0);
+    time.unpause();
+    *rules = playback.previous_rules.clone();
+    *mutators = playback.previous_mutators.clone();
+    *feel = playback.previous_feel;
+    *power_serve = playback.previous_power_serve;
+    *mirrored = playback.previous_mirrored;
+    [profiles.left.character, profiles.right.character] = playback.previous_characters;
+    commands.remove_resource::<ReplayPlayback>();
+}
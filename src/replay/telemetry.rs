@@ -0,0 +1,204 @@
+//! Match telemetry export, for players who want to dig into their games.
+//!
+//! With the option on, each finished match is written to `telemetry/` as a
+//! JSON file: the same header a replay gets (rules, mutators, seed, players),
+//! then every game event stamped with its tick, both players' stats, the
+//! score history and the ball heatmap.
+
+use std::{fs, path::PathBuf};
+
+use bevy::prelude::*;
+use serde::Serialize;
+
+use super::{MatchSetup, ReplayClock, ReplayHeader, ReplayPlayback, ReplayRecorder};
+use crate::{
+    AppSystems,
+    game::{
+        GamePhase, events::GameEvent, heatmap::BallHeatmap, player::PlayerSide,
+        score_history::ScoreHistory, stats::MatchStats,
+    },
+    screens::Screen,
+};
+
+const TELEMETRY_DIR: &str = "telemetry";
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<ExportTelemetry>();
+    app.init_resource::<ExportTelemetry>();
+    app.init_resource::<TelemetryLog>();
+
+    app.add_systems(OnEnter(Screen::Gameplay), clear_log);
+    // An undo can take the match out of game over; export again if it ends again
+    app.add_systems(OnExit(GamePhase::GameOver), clear_exported);
+    app.add_systems(
+        Update,
+        (
+            log_events,
+            // After logging, so the winning goal makes it in
+            export_telemetry.run_if(
+                in_state(GamePhase::GameOver)
+                    .and(resource_exists::<ReplayRecorder>)
+                    .and(|export: Res<ExportTelemetry>, log: Res<TelemetryLog>| {
+                        export.0 && !log.exported
+                    }),
+            ),
+        )
+            .chain()
+            .in_set(AppSystems::Update)
+            .run_if(in_state(Screen::Gameplay).and(not(resource_exists::<ReplayPlayback>))),
+    );
+}
+
+/// Whether to write telemetry for each finished match
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Resource)]
+pub struct ExportTelemetry(pub bool);
+
+/// A finished match, as exported
+#[derive(Serialize, Debug)]
+pub struct Telemetry<'a> {
+    pub header: ReplayHeader,
+    pub events: &'a [TelemetryEvent],
+    pub stats: &'a MatchStats,
+    pub score_history: &'a ScoreHistory,
+    pub heatmap: &'a BallHeatmap,
+}
+
+/// A [`GameEvent`] flattened for export, without entity IDs
+#[derive(Serialize, Debug, Clone)]
+pub struct TelemetryEvent {
+    pub tick: u32,
+    pub kind: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub side: Option<PlayerSide>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub points: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub own_goal: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub position: Option<[f32; 2]>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub phase: Option<String>,
+}
+
+impl TelemetryEvent {
+    pub fn new(tick: u32, event: &GameEvent) -> Self {
+        let mut exported = Self {
+            tick,
+            kind: "",
+            side: None,
+            points: None,
+            own_goal: None,
+            position: None,
+            phase: None,
+        };
+        match *event {
+            GameEvent::Serve { side, .. } => {
+                exported.kind = "serve";
+                exported.side = Some(side);
+            }
+            GameEvent::ServeFeint { side } => {
+                exported.kind = "serve_feint";
+                exported.side = Some(side);
+            }
+            GameEvent::PaddleHit { side, position, .. } => {
+                exported.kind = "paddle_hit";
+                exported.side = Some(side);
+                exported.position = Some(position.to_array());
+            }
+            GameEvent::WallBounce { position, .. } => {
+                exported.kind = "wall_bounce";
+                exported.position = Some(position.to_array());
+            }
+            GameEvent::Goal {
+                side,
+                points,
+                own_goal,
+            } => {
+                exported.kind = "goal";
+                exported.side = Some(side);
+                exported.points = Some(points);
+                exported.own_goal = Some(own_goal);
+            }
+            GameEvent::SideOut { side } => {
+                exported.kind = "side_out";
+                exported.side = Some(side);
+            }
+            GameEvent::PointReplayed => exported.kind = "point_replayed",
+            GameEvent::LetServe { server } => {
+                exported.kind = "let_serve";
+                exported.side = Some(server);
+            }
+            GameEvent::ServeClockExpired { server, .. } => {
+                exported.kind = "serve_clock_expired";
+                exported.side = Some(server);
+            }
+            GameEvent::BrickBroken { side, points } => {
+                exported.kind = "brick_broken";
+                exported.side = Some(side);
+                exported.points = Some(points);
+            }
+            GameEvent::WallBroken => exported.kind = "wall_broken",
+            GameEvent::SuddenDeath => exported.kind = "sudden_death",
+            GameEvent::PointUndone => exported.kind = "point_undone",
+            GameEvent::PhaseChanged { phase } => {
+                exported.kind = "phase_changed";
+                exported.phase = Some(format!("{phase:?}"));
+            }
+        }
+        exported
+    }
+}
+
+/// Game events seen so far this match
+#[derive(Resource, Default)]
+struct TelemetryLog {
+    events: Vec<TelemetryEvent>,
+    /// Already written out for this game over
+    exported: bool,
+}
+
+fn clear_log(mut log: ResMut<TelemetryLog>) {
+    *log = TelemetryLog::default();
+}
+
+fn clear_exported(mut log: ResMut<TelemetryLog>) {
+    log.exported = false;
+}
+
+fn log_events(
+    clock: Res<ReplayClock>,
+    mut events: EventReader<GameEvent>,
+    mut log: ResMut<TelemetryLog>,
+) {
+    for event in events.read() {
+        log.events.push(TelemetryEvent::new(clock.tick, event));
+    }
+}
+
+fn export_telemetry(
+    setup: MatchSetup,
+    mut log: ResMut<TelemetryLog>,
+    stats: Res<MatchStats>,
+    history: Res<ScoreHistory>,
+    heatmap: Res<BallHeatmap>,
+) {
+    let telemetry = Telemetry {
+        header: setup.header(),
+        events: &log.events,
+        stats: &stats,
+        score_history: &history,
+        heatmap: &heatmap,
+    };
+    let path =
+        PathBuf::from(TELEMETRY_DIR).join(format!("match-{}.json", telemetry.header.recorded_at));
+    let result = fs::create_dir_all(TELEMETRY_DIR).and_then(|_| {
+        let json = serde_json::to_string_pretty(&telemetry).map_err(std::io::Error::other)?;
+        fs::write(&path, json)
+    });
+    match result {
+        Ok(()) => info!("Exported match telemetry to {path:?}"),
+        Err(error) => warn!("Couldn't export match telemetry: {error}"),
+    }
+    log.exported = true;
+}
@@ -0,0 +1,123 @@
+//! The tournament bracket screen shown between matches.
+
+use bevy::{
+    ecs::spawn::SpawnIter, input::common_conditions::input_just_pressed, prelude::*, ui::Val::*,
+};
+
+use crate::{
+    asset_tracking::ResourceHandles, screens::Screen, theme::prelude::*, tournament::Tournament,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Screen::Bracket), spawn_bracket_screen);
+    app.add_systems(
+        Update,
+        (
+            continue_tournament.run_if(input_just_pressed(KeyCode::Space)),
+            leave_bracket.run_if(input_just_pressed(KeyCode::Escape)),
+        )
+            .run_if(in_state(Screen::Bracket)),
+    );
+}
+
+fn spawn_bracket_screen(mut commands: Commands, tournament: Option<Res<Tournament>>) {
+    let Some(tournament) = tournament else {
+        warn!("Entered the bracket screen without a tournament");
+        return;
+    };
+
+    let footer = match (tournament.champion(), tournament.current_match()) {
+        (Some(champion), _) => format!("{champion} is the champion! Press SPACE to finish"),
+        (None, Some((round, index))) => {
+            let next = tournament.rounds[round][index];
+            format!(
+                "Next: {} (Left) vs {} (Right) - press SPACE to play",
+                tournament.name(next.left),
+                tournament.name(next.right)
+            )
+        }
+        (None, None) => "Press SPACE to finish".into(),
+    };
+
+    commands.spawn((
+        widget::ui_root("Bracket Screen"),
+        StateScoped(Screen::Bracket),
+        children![
+            widget::header("Tournament"),
+            bracket(&tournament),
+            widget::label(footer),
+            widget::label("ESC to save and quit"),
+        ],
+    ));
+}
+
+/// One column per round, listing each match and its winner
+fn bracket(tournament: &Tournament) -> impl Bundle {
+    let columns: Vec<_> = tournament
+        .rounds
+        .iter()
+        .enumerate()
+        .map(|(round, matches)| {
+            let lines: Vec<String> = std::iter::once(tournament.round_name(round))
+                .chain(matches.iter().map(|m| {
+                    let result = m
+                        .winner
+                        .map(|winner| format!(" -> {}", tournament.name(Some(winner))))
+                        .unwrap_or_default();
+                    format!(
+                        "{} vs {}{result}",
+                        tournament.name(m.left),
+                        tournament.name(m.right)
+                    )
+                }))
+                .collect();
+            (
+                Name::new("Round"),
+                Node {
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Px(10.0),
+                    ..default()
+                },
+                Children::spawn(SpawnIter(lines.into_iter().map(widget::label))),
+            )
+        })
+        .collect();
+
+    (
+        Name::new("Bracket"),
+        Node {
+            column_gap: Px(40.0),
+            ..default()
+        },
+        Children::spawn(SpawnIter(columns.into_iter())),
+    )
+}
+
+fn continue_tournament(
+    mut commands: Commands,
+    tournament: Option<Res<Tournament>>,
+    resource_handles: Res<ResourceHandles>,
+    mut next_screen: ResMut<NextState<Screen>>,
+) {
+    let Some(tournament) = tournament else {
+        next_screen.set(Screen::Title);
+        return;
+    };
+
+    if tournament.current_match().is_some() {
+        next_screen.set(if resource_handles.is_all_done() {
+            Screen::Gameplay
+        } else {
+            Screen::Loading
+        });
+    } else {
+        // Tournament is over
+        Tournament::delete_save();
+        commands.remove_resource::<Tournament>();
+        next_screen.set(Screen::Title);
+    }
+}
+
+fn leave_bracket(mut next_screen: ResMut<NextState<Screen>>) {
+    next_screen.set(Screen::Title);
+}
@@ -0,0 +1,233 @@
+//! The credits screen, scrolling through everyone who made the game.
+//!
+//! The names come from `assets/credits.json`, so adding an attribution doesn't
+//! need a code change. Any key, click or gamepad button skips back to the
+//! title.
+
+use bevy::{
+    asset::{AssetLoader, LoadContext, io::Reader},
+    ecs::spawn::SpawnIter,
+    prelude::*,
+    ui::Val::*,
+};
+use serde::Deserialize;
+
+use crate::{
+    asset_tracking::LoadResource,
+    audio::music,
+    screens::{Screen, transition::Transition},
+    theme::prelude::*,
+};
+
+const SCROLL_SPEED: f32 = 60.0; // px/s
+const SECTION_GAP: f32 = 60.0;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_asset::<Credits>();
+    app.register_asset_loader(CreditsLoader);
+    app.register_type::<CreditsAssets>();
+    app.register_type::<CreditsScroll>();
+    app.load_resource::<CreditsAssets>();
+
+    app.add_systems(
+        OnEnter(Screen::Credits),
+        (spawn_credits_screen, start_credits_music),
+    );
+    app.add_systems(
+        Update,
+        (
+            scroll_credits,
+            skip_credits.run_if(in_state(Transition::Idle)),
+        )
+            .run_if(in_state(Screen::Credits)),
+    );
+}
+
+/// Everyone to credit, in sections
+#[derive(Asset, TypePath, Deserialize, Debug, Clone)]
+pub struct Credits {
+    sections: Vec<CreditsSection>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+struct CreditsSection {
+    title: String,
+    /// `[who, what]` pairs
+    entries: Vec<[String; 2]>,
+}
+
+#[derive(Default)]
+struct CreditsLoader;
+
+impl AssetLoader for CreditsLoader {
+    type Asset = Credits;
+    type Settings = ();
+    type Error = Box<dyn std::error::Error + Send + Sync>;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Credits, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        Ok(serde_json::from_slice(&bytes)?)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["json"]
+    }
+}
+
+#[derive(Resource, Asset, Clone, Reflect)]
+#[reflect(Resource)]
+struct CreditsAssets {
+    #[dependency]
+    credits: Handle<Credits>,
+    #[dependency]
+    music: Handle<AudioSource>,
+}
+
+impl FromWorld for CreditsAssets {
+    fn from_world(world: &mut World) -> Self {
+        let assets = world.resource::<AssetServer>();
+        Self {
+            credits: assets.load("credits.json"),
+            music: assets.load("audio/music/Monkeys Spinning Monkeys.ogg"),
+        }
+    }
+}
+
+/// The column of credits rolling up the screen
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct CreditsScroll {
+    /// Distance scrolled from just below the window
+    offset: f32,
+}
+
+fn spawn_credits_screen(
+    mut commands: Commands,
+    credits_assets: Res<CreditsAssets>,
+    credits: Res<Assets<Credits>>,
+) {
+    let sections = credits
+        .get(&credits_assets.credits)
+        .map(|credits| credits.sections.clone())
+        .unwrap_or_default();
+
+    commands.spawn((
+        Name::new("Credits Screen"),
+        Node {
+            position_type: PositionType::Absolute,
+            width: Percent(100.0),
+            height: Percent(100.0),
+            justify_content: JustifyContent::Center,
+            overflow: Overflow::clip(),
+            ..default()
+        },
+        Pickable::IGNORE,
+        StateScoped(Screen::Credits),
+        children![(
+            Name::new("Credits Scroll"),
+            CreditsScroll { offset: 0.0 },
+            Node {
+                position_type: PositionType::Absolute,
+                top: Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                row_gap: Px(SECTION_GAP),
+                ..default()
+            },
+            Children::spawn(SpawnIter(sections.into_iter().map(section))),
+        )],
+    ));
+}
+
+fn section(section: CreditsSection) -> impl Bundle {
+    (
+        Name::new("Credits Section"),
+        Node {
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            row_gap: Px(20.0),
+            ..default()
+        },
+        children![widget::header(section.title), grid(section.entries)],
+    )
+}
+
+fn grid(entries: Vec<[String; 2]>) -> impl Bundle {
+    (
+        Name::new("Grid"),
+        Node {
+            display: Display::Grid,
+            row_gap: Px(10.0),
+            column_gap: Px(30.0),
+            grid_template_columns: RepeatedGridTrack::px(2, 400.0),
+            ..default()
+        },
+        Children::spawn(SpawnIter(entries.into_iter().flatten().enumerate().map(
+            |(i, text)| {
+                (
+                    widget::label(text),
+                    Node {
+                        justify_self: if i % 2 == 0 {
+                            JustifySelf::End
+                        } else {
+                            JustifySelf::Start
+                        },
+                        ..default()
+                    },
+                )
+            },
+        ))),
+    )
+}
+
+fn start_credits_music(mut commands: Commands, credits_music: Res<CreditsAssets>) {
+    commands.spawn((
+        Name::new("Credits Music"),
+        StateScoped(Screen::Credits),
+        music(credits_music.music.clone()),
+    ));
+}
+
+/// Rolls the credits up from below the window, returning to the title once
+/// they've gone off the top
+fn scroll_credits(
+    time: Res<Time>,
+    mut next_screen: ResMut<NextState<Screen>>,
+    mut scrolls: Query<(&mut CreditsScroll, &mut Node, &ComputedNode)>,
+    windows: Query<&Window>,
+) {
+    let Ok(window) = windows.single() else {
+        return;
+    };
+    for (mut scroll, mut node, computed) in &mut scrolls {
+        scroll.offset += SCROLL_SPEED * time.delta_secs();
+        node.top = Px(window.height() - scroll.offset);
+
+        let height = computed.size().y * computed.inverse_scale_factor();
+        if scroll.offset > window.height() + height {
+            next_screen.set(Screen::Title);
+        }
+    }
+}
+
+fn skip_credits(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    gamepads: Query<&Gamepad>,
+    mut next_screen: ResMut<NextState<Screen>>,
+) {
+    let pressed = keyboard.get_just_pressed().next().is_some()
+        || mouse.get_just_pressed().next().is_some()
+        || gamepads
+            .iter()
+            .any(|gamepad| gamepad.get_just_pressed().next().is_some());
+    if pressed {
+        next_screen.set(Screen::Title);
+    }
+}
@@ -1,9 +1,14 @@
 //! The game's main screen states and transitions between them.
 
+mod bracket;
+mod credits;
 mod gameplay;
 mod loading;
+#[cfg(not(target_family = "wasm"))]
+mod replays;
 mod splash;
-mod title;
+pub mod title;
+pub mod transition;
 
 use bevy::prelude::*;
 
@@ -11,10 +16,15 @@ pub(super) fn plugin(app: &mut App) {
     app.init_state::<Screen>();
 
     app.add_plugins((
+        bracket::plugin,
+        credits::plugin,
         gameplay::plugin,
         loading::plugin,
+        #[cfg(not(target_family = "wasm"))]
+        replays::plugin,
         splash::plugin,
         title::plugin,
+        transition::plugin,
     ));
 }
 
@@ -27,4 +37,8 @@ pub enum Screen {
     Title,
     Loading,
     Gameplay,
+    Bracket,
+    Credits,
+    #[cfg(not(target_family = "wasm"))]
+    Replays,
 }
@@ -0,0 +1,190 @@
+//! The replay browser, listing saved matches to watch again.
+
+use std::path::PathBuf;
+
+use bevy::{
+    ecs::spawn::SpawnIter, input::common_conditions::input_just_pressed, prelude::*, ui::Val::*,
+};
+
+use crate::{
+    asset_tracking::ResourceHandles,
+    game::{
+        mutators::Mutators,
+        player::{MirroredCourt, MovementFeel},
+        power_serve::PowerServe,
+        profile::PlayerProfiles,
+        rules::MatchRules,
+    },
+    replay::{Replay, ReplayHeader, ReplayPlayback, delete_replay, saved_replays},
+    screens::Screen,
+    theme::prelude::*,
+};
+
+/// Most replays listed at once
+const MAX_LISTED: usize = 8;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<ReplaysScreen>();
+
+    app.add_systems(OnEnter(Screen::Replays), spawn_replays_screen);
+    app.add_systems(
+        Update,
+        leave_replays.run_if(in_state(Screen::Replays).and(input_just_pressed(KeyCode::Escape))),
+    );
+}
+
+fn spawn_replays_screen(mut commands: Commands) {
+    let replays = saved_replays();
+    let summary = if replays.is_empty() {
+        "No replays yet - finish a match to record one".to_string()
+    } else {
+        format!("{} saved", replays.len())
+    };
+
+    commands.spawn((
+        widget::ui_root("Replays Screen"),
+        ReplaysScreen,
+        StateScoped(Screen::Replays),
+        children![
+            widget::header("Replays"),
+            widget::label(summary),
+            replay_list(replays),
+            widget::button("Back", leave_replays_on_click),
+        ],
+    ));
+}
+
+/// The replay browser's root, rebuilt when a replay is deleted
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct ReplaysScreen;
+
+/// One row per replay: its summary, a button to watch it and one to delete it
+fn replay_list(replays: Vec<(PathBuf, ReplayHeader)>) -> impl Bundle {
+    let rows: Vec<_> = replays
+        .into_iter()
+        .take(MAX_LISTED)
+        .map(|(path, header)| {
+            let delete_path = path.clone();
+            (
+                Name::new("Replay Row"),
+                Node {
+                    column_gap: Px(20.0),
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                children![
+                    widget::label(describe(&header)),
+                    widget::button_small(
+                        ">",
+                        move |_: Trigger<Pointer<Click>>,
+                              mut commands: Commands,
+                              mut rules: ResMut<MatchRules>,
+                              mut mutators: ResMut<Mutators>,
+                              mut feel: ResMut<MovementFeel>,
+                              mut power_serve: ResMut<PowerServe>,
+                              mut mirrored: ResMut<MirroredCourt>,
+                              mut profiles: ResMut<PlayerProfiles>,
+                              resource_handles: Res<ResourceHandles>,
+                              mut next_screen: ResMut<NextState<Screen>>| {
+                            let Some(replay) = Replay::load(&path) else {
+                                return;
+                            };
+                            commands.insert_resource(ReplayPlayback::start(
+                                replay,
+                                &mut rules,
+                                &mut mutators,
+                                &mut feel,
+                                &mut power_serve,
+                                &mut mirrored,
+                                &mut profiles,
+                            ));
+                            next_screen.set(if resource_handles.is_all_done() {
+                                Screen::Gameplay
+                            } else {
+                                Screen::Loading
+                            });
+                        }
+                    ),
+                    widget::button_small(
+                        "x",
+                        move |_: Trigger<Pointer<Click>>, mut commands: Commands| {
+                            let path = delete_path.clone();
+                            commands.spawn((
+                                confirm_dialog(
+                                    "Delete this replay?",
+                                    "Delete",
+                                    move |_: Trigger<Pointer<Click>>,
+                                          mut commands: Commands,
+                                          screen: Query<Entity, With<ReplaysScreen>>| {
+                                        delete_replay(&path);
+                                        for entity in &screen {
+                                            commands.entity(entity).despawn();
+                                        }
+                                        commands.run_system_cached(spawn_replays_screen);
+                                    },
+                                ),
+                                StateScoped(Screen::Replays),
+                            ));
+                        }
+                    ),
+                ],
+            )
+        })
+        .collect();
+
+    (
+        Name::new("Replay List"),
+        Node {
+            flex_direction: FlexDirection::Column,
+            row_gap: Px(10.0),
+            ..default()
+        },
+        Children::spawn(SpawnIter(rows.into_iter())),
+    )
+}
+
+/// e.g. "2025-06-01 14:05  Left 11 - 7 Right  (3:42)"
+fn describe(header: &ReplayHeader) -> String {
+    let [left, right] = &header.players;
+    let [left_score, right_score] = header.final_score;
+    let duration = header.duration_secs() as u64;
+    format!(
+        "{}  {left} {left_score} - {right_score} {right}  ({}:{:02})",
+        format_date(header.recorded_at),
+        duration / 60,
+        duration % 60
+    )
+}
+
+/// Formats Unix seconds as a UTC "YYYY-MM-DD HH:MM" timestamp
+fn format_date(unix_secs: u64) -> String {
+    let days = (unix_secs / 86_400) as i64;
+    let minutes = unix_secs % 86_400 / 60;
+
+    // Civil-from-days, see http://howardhinnant.github.io/date_algorithms.html
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{year}-{month:02}-{day:02} {:02}:{:02}",
+        minutes / 60,
+        minutes % 60
+    )
+}
+
+fn leave_replays_on_click(_: Trigger<Pointer<Click>>, mut next_screen: ResMut<NextState<Screen>>) {
+    next_screen.set(Screen::Title);
+}
+
+fn leave_replays(mut next_screen: ResMut<NextState<Screen>>) {
+    next_screen.set(Screen::Title);
+}
@@ -9,8 +9,22 @@ pub(super) fn plugin(app: &mut App) {
     app.add_systems(OnExit(Screen::Title), close_menu);
 }
 
-fn open_main_menu(mut next_menu: ResMut<NextState<Menu>>) {
-    next_menu.set(Menu::Main);
+/// A menu to open instead of the main menu next time the title screen comes up
+#[derive(Resource, Debug, Clone, Copy)]
+pub struct OpenOnTitle(pub Menu);
+
+fn open_main_menu(
+    mut commands: Commands,
+    open_on_title: Option<Res<OpenOnTitle>>,
+    mut next_menu: ResMut<NextState<Menu>>,
+) {
+    match open_on_title {
+        Some(open_on_title) => {
+            next_menu.set(open_on_title.0);
+            commands.remove_resource::<OpenOnTitle>();
+        }
+        None => next_menu.set(Menu::Main),
+    }
 }
 
 fn close_menu(mut next_menu: ResMut<NextState<Menu>>) {
@@ -0,0 +1,156 @@
+//! Animated transitions: the screen fades in from black whenever [`Screen`]
+//! changes, and the game over screen is wiped open from the center line.
+//!
+//! The [`Transition`] state tracks whether one is running. Input handlers
+//! should only run while it's [`Transition::Idle`], and the transition's cover
+//! sits above all UI so it swallows clicks until it's gone.
+
+use bevy::{prelude::*, ui::Val::*};
+
+use crate::{
+    game::{GamePhase, accessibility::ReducedMotion},
+    screens::Screen,
+};
+
+const FADE_DURATION: f32 = 0.35; // seconds
+const WIPE_DURATION: f32 = 0.45; // seconds
+const COVER_Z: i32 = 10; // Above every menu and overlay
+const COVER_COLOR: Color = Color::BLACK;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_state::<Transition>();
+    app.register_type::<TransitionCover>();
+
+    app.add_systems(Update, start_fade);
+    app.add_systems(OnEnter(GamePhase::GameOver), start_wipe);
+    app.add_systems(
+        Update,
+        (animate_fade, animate_wipe).run_if(not(in_state(Transition::Idle))),
+    );
+}
+
+/// Whether a transition is playing
+#[derive(States, Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub enum Transition {
+    #[default]
+    Idle,
+    /// Fading in from black after a screen change
+    Fading,
+    /// Wiping open from the center line into the game over screen
+    Wiping,
+}
+
+/// A piece of a transition's cover, and how long until it's gone
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct TransitionCover {
+    timer: Timer,
+}
+
+/// Which half of the wipe a panel is
+#[derive(Component)]
+enum WipePanel {
+    Left,
+    Right,
+}
+
+fn start_fade(
+    mut commands: Commands,
+    mut transitions: EventReader<StateTransitionEvent<Screen>>,
+    mut next_transition: ResMut<NextState<Transition>>,
+) {
+    // Nothing to fade from when the app starts
+    if !transitions
+        .read()
+        .any(|transition| transition.exited.is_some() && transition.exited != transition.entered)
+    {
+        return;
+    }
+
+    commands.spawn((
+        Name::new("Fade Cover"),
+        TransitionCover {
+            timer: Timer::from_seconds(FADE_DURATION, TimerMode::Once),
+        },
+        Node {
+            position_type: PositionType::Absolute,
+            width: Percent(100.0),
+            height: Percent(100.0),
+            ..default()
+        },
+        BackgroundColor(COVER_COLOR),
+        GlobalZIndex(COVER_Z),
+    ));
+    next_transition.set(Transition::Fading);
+}
+
+fn start_wipe(
+    mut commands: Commands,
+    reduced_motion: Res<ReducedMotion>,
+    mut next_transition: ResMut<NextState<Transition>>,
+) {
+    if reduced_motion.0 {
+        return;
+    }
+
+    for (panel, name) in [
+        (WipePanel::Left, "Left Wipe Panel"),
+        (WipePanel::Right, "Right Wipe Panel"),
+    ] {
+        let mut node = Node {
+            position_type: PositionType::Absolute,
+            width: Percent(50.0),
+            height: Percent(100.0),
+            ..default()
+        };
+        match panel {
+            WipePanel::Left => node.left = Percent(0.0),
+            WipePanel::Right => node.right = Percent(0.0),
+        }
+        commands.spawn((
+            Name::new(name),
+            panel,
+            TransitionCover {
+                timer: Timer::from_seconds(WIPE_DURATION, TimerMode::Once),
+            },
+            node,
+            BackgroundColor(COVER_COLOR),
+            GlobalZIndex(COVER_Z),
+        ));
+    }
+    next_transition.set(Transition::Wiping);
+}
+
+fn animate_fade(
+    mut commands: Commands,
+    time: Res<Time<Real>>,
+    mut covers: Query<(Entity, &mut TransitionCover, &mut BackgroundColor), Without<WipePanel>>,
+    mut next_transition: ResMut<NextState<Transition>>,
+) {
+    for (entity, mut cover, mut background) in &mut covers {
+        cover.timer.tick(time.delta());
+        background.0 = COVER_COLOR.with_alpha(cover.timer.fraction_remaining());
+        if cover.timer.finished() {
+            commands.entity(entity).despawn();
+            next_transition.set(Transition::Idle);
+        }
+    }
+}
+
+/// Shrinks each panel toward its outer edge, opening from the center line
+fn animate_wipe(
+    mut commands: Commands,
+    time: Res<Time<Real>>,
+    mut panels: Query<(Entity, &mut TransitionCover, &mut Node), With<WipePanel>>,
+    mut next_transition: ResMut<NextState<Transition>>,
+) {
+    for (entity, mut cover, mut node) in &mut panels {
+        cover.timer.tick(time.delta());
+        let remaining = cover.timer.fraction_remaining();
+        node.width = Percent(50.0 * remaining * remaining);
+        if cover.timer.finished() {
+            commands.entity(entity).despawn();
+            next_transition.set(Transition::Idle);
+        }
+    }
+}
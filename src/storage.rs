@@ -0,0 +1,97 @@
+//! Small key-value persistence that works on every platform.
+//!
+//! Native builds keep each key as a JSON file under `saves/`. Web builds have
+//! no filesystem, so the same keys go to the browser's `localStorage` instead.
+
+use std::io;
+
+use bevy::prelude::*;
+use serde::{Serialize, de::DeserializeOwned};
+
+/// Reads the value saved under `key`, if there is a readable one
+pub fn load<T: DeserializeOwned>(key: &str) -> Option<T> {
+    let json = backend::read(key)?;
+    serde_json::from_str(&json)
+        .inspect_err(|error| warn!("Ignoring unreadable save \"{key}\": {error}"))
+        .ok()
+}
+
+/// Saves `value` under `key`, replacing anything saved there before
+pub fn save<T: Serialize>(key: &str, value: &T) -> io::Result<()> {
+    let json = serde_json::to_string_pretty(value).map_err(io::Error::other)?;
+    backend::write(key, &json)
+}
+
+/// Removes whatever is saved under `key`
+pub fn remove(key: &str) {
+    backend::remove(key);
+}
+
+#[cfg(not(target_family = "wasm"))]
+mod backend {
+    use std::{
+        fs, io,
+        path::{Path, PathBuf},
+    };
+
+    const SAVE_DIR: &str = "saves";
+
+    fn path(key: &str) -> PathBuf {
+        Path::new(SAVE_DIR).join(format!("{key}.json"))
+    }
+
+    pub fn read(key: &str) -> Option<String> {
+        fs::read_to_string(path(key)).ok()
+    }
+
+    pub fn write(key: &str, contents: &str) -> io::Result<()> {
+        fs::create_dir_all(SAVE_DIR)?;
+        fs::write(path(key), contents)
+    }
+
+    pub fn remove(key: &str) {
+        let _ = fs::remove_file(path(key));
+    }
+}
+
+#[cfg(target_family = "wasm")]
+mod backend {
+    use std::{fmt::Debug, io};
+
+    use web_sys::Storage;
+
+    /// Keeps our keys apart from anything else on the same origin (e.g. itch.io)
+    const KEY_PREFIX: &str = "paddlegeddon/";
+
+    fn local_storage() -> io::Result<Storage> {
+        web_sys::window()
+            .ok_or_else(|| io::Error::other("no browser window"))?
+            .local_storage()
+            .map_err(js_error)?
+            .ok_or_else(|| io::Error::other("localStorage is unavailable"))
+    }
+
+    fn js_error(error: impl Debug) -> io::Error {
+        io::Error::other(format!("{error:?}"))
+    }
+
+    pub fn read(key: &str) -> Option<String> {
+        local_storage()
+            .ok()?
+            .get_item(&format!("{KEY_PREFIX}{key}"))
+            .ok()
+            .flatten()
+    }
+
+    pub fn write(key: &str, contents: &str) -> io::Result<()> {
+        local_storage()?
+            .set_item(&format!("{KEY_PREFIX}{key}"), contents)
+            .map_err(js_error)
+    }
+
+    pub fn remove(key: &str) {
+        if let Ok(storage) = local_storage() {
+            let _ = storage.remove_item(&format!("{KEY_PREFIX}{key}"));
+        }
+    }
+}
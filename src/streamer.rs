@@ -0,0 +1,133 @@
+//! Streamer mode: exports live match state for OBS overlays.
+//!
+//! Set `PADDLEGEDDON_OVERLAY_FILE` to a file path and the current score, player
+//! names, and game phase are written there as JSON whenever they change. Use a
+//! `.txt` extension to get a single plain-text line instead. Players are named
+//! after their tournament entrants while a tournament is running, and after
+//! their characters otherwise.
+
+use std::{fs, path::PathBuf};
+
+use bevy::{prelude::*, tasks::IoTaskPool};
+use serde::Serialize;
+
+use crate::{
+    game::{GamePhase, player::PlayerSide, profile::PlayerProfiles, scoring::Score},
+    screens::Screen,
+    tournament::Tournament,
+};
+
+const OVERLAY_FILE_ENV: &str = "PADDLEGEDDON_OVERLAY_FILE";
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<StreamerMode>();
+    app.add_systems(
+        Last,
+        export_overlay_state.run_if(|mode: Res<StreamerMode>| mode.output.is_some()),
+    );
+}
+
+/// Where to export overlay data, if anywhere
+#[derive(Resource)]
+pub struct StreamerMode {
+    pub output: Option<PathBuf>,
+    last_written: Option<OverlayState>,
+}
+
+impl Default for StreamerMode {
+    fn default() -> Self {
+        let output = std::env::var_os(OVERLAY_FILE_ENV).map(PathBuf::from);
+        if let Some(path) = &output {
+            info!(
+                "Streamer mode: exporting overlay state to {}",
+                path.display()
+            );
+        }
+        Self {
+            output,
+            last_written: None,
+        }
+    }
+}
+
+#[derive(Serialize, Clone, PartialEq)]
+struct OverlayState {
+    left: PlayerOverlay,
+    right: PlayerOverlay,
+    screen: String,
+    phase: Option<String>,
+}
+
+#[derive(Serialize, Clone, PartialEq)]
+struct PlayerOverlay {
+    name: String,
+    score: u32,
+}
+
+impl OverlayState {
+    fn to_text(&self) -> String {
+        format!(
+            "{} {} - {} {} | {}",
+            self.left.name,
+            self.left.score,
+            self.right.score,
+            self.right.name,
+            self.phase.as_deref().unwrap_or(&self.screen)
+        )
+    }
+}
+
+fn export_overlay_state(
+    mut mode: ResMut<StreamerMode>,
+    score: Res<Score>,
+    profiles: Res<PlayerProfiles>,
+    tournament: Option<Res<Tournament>>,
+    screen: Res<State<Screen>>,
+    phase: Option<Res<State<GamePhase>>>,
+) {
+    let entrants = tournament.as_deref().and_then(Tournament::current_players);
+    let name = |side: PlayerSide| match entrants {
+        Some(names) => names[side as usize].to_string(),
+        None => profiles.get(side).character.name().to_string(),
+    };
+    let state = OverlayState {
+        left: PlayerOverlay {
+            name: name(PlayerSide::Left),
+            score: score.left,
+        },
+        right: PlayerOverlay {
+            name: name(PlayerSide::Right),
+            score: score.right,
+        },
+        screen: format!("{:?}", screen.get()),
+        phase: phase.map(|phase| format!("{:?}", phase.get())),
+    };
+
+    if mode.last_written.as_ref() == Some(&state) {
+        return;
+    }
+    let Some(path) = mode.output.clone() else {
+        return;
+    };
+
+    let contents = if path.extension().is_some_and(|extension| extension == "txt") {
+        state.to_text()
+    } else {
+        match serde_json::to_string_pretty(&state) {
+            Ok(json) => json,
+            Err(error) => {
+                warn!("Couldn't serialize overlay state: {error}");
+                return;
+            }
+        }
+    };
+    mode.last_written = Some(state);
+
+    IoTaskPool::get()
+        .spawn(async move {
+            if let Err(error) = fs::write(&path, contents) {
+                warn!("Couldn't write overlay file {}: {error}", path.display());
+            }
+        })
+        .detach();
+}
@@ -0,0 +1,151 @@
+//! A modal confirmation dialog for actions that can't be undone.
+//!
+//! Spawn [`confirm_dialog`] from a button action. The dialog covers the
+//! window so nothing behind it can be clicked, keeps keyboard/gamepad focus
+//! on its own buttons, and closes itself on either choice. Escape or the
+//! gamepad's east button cancels.
+
+use bevy::{ecs::system::IntoObserverSystem, input::InputSystem, prelude::*, ui::Val::*};
+
+use crate::theme::{palette::*, widget};
+
+/// Dims whatever is behind the dialog
+const BACKDROP_COLOR: Color = Color::srgba(0.0, 0.0, 0.0, 0.7);
+const PANEL_COLOR: Color = Color::srgb(0.12, 0.12, 0.18);
+/// Above menus, below screen transitions
+const DIALOG_Z: i32 = 5;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<Modal>();
+    app.register_type::<DialogChoice>();
+
+    app.add_observer(close_on_choice);
+    app.add_systems(PreUpdate, cancel_on_back.after(InputSystem));
+}
+
+/// The root of a modal dialog. While one exists, navigation stays inside it.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct Modal;
+
+/// A dialog button; clicking it closes the dialog after its action runs
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct DialogChoice;
+
+/// A modal asking to confirm `message`, running `action` if the player picks
+/// `confirm_text`.
+pub fn confirm_dialog<E, B, M, I>(
+    message: impl Into<String>,
+    confirm_text: impl Into<String>,
+    action: I,
+) -> impl Bundle
+where
+    E: Event,
+    B: Bundle,
+    I: IntoObserverSystem<E, B, M>,
+{
+    (
+        Name::new("Confirm Dialog"),
+        Modal,
+        Node {
+            position_type: PositionType::Absolute,
+            width: Percent(100.0),
+            height: Percent(100.0),
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        // Blocks clicks to everything behind the dialog
+        BackgroundColor(BACKDROP_COLOR),
+        GlobalZIndex(DIALOG_Z),
+        children![(
+            Name::new("Dialog Panel"),
+            Node {
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                row_gap: Px(30.0),
+                padding: UiRect::all(Px(40.0)),
+                border: UiRect::all(Px(2.0)),
+                ..default()
+            },
+            BackgroundColor(PANEL_COLOR),
+            BorderColor(LABEL_TEXT),
+            BorderRadius::all(Px(12.0)),
+            children![
+                (
+                    widget::header(message),
+                    TextLayout::new_with_justify(JustifyText::Center),
+                ),
+                (
+                    Name::new("Dialog Buttons"),
+                    Node {
+                        column_gap: Px(30.0),
+                        ..default()
+                    },
+                    children![
+                        // Closing is handled for every choice by `close_on_choice`
+                        (
+                            widget::button("Cancel", |_: Trigger<Pointer<Click>>| {}),
+                            DialogChoice
+                        ),
+                        (widget::button(confirm_text, action), DialogChoice),
+                    ],
+                ),
+            ],
+        )],
+    )
+}
+
+/// Whether `entity` can be navigated to: anything when no dialog is open,
+/// otherwise only what's inside the dialog
+pub fn outside_modal(
+    entity: Entity,
+    modals: &Query<(), With<Modal>>,
+    parents: &Query<&ChildOf>,
+) -> bool {
+    !modals.is_empty()
+        && !parents
+            .iter_ancestors(entity)
+            .any(|ancestor| modals.contains(ancestor))
+}
+
+/// Button clicks bubble up to the dialog choice after the button's own action
+fn close_on_choice(
+    trigger: Trigger<Pointer<Click>>,
+    mut commands: Commands,
+    choices: Query<(), With<DialogChoice>>,
+    modals: Query<Entity, With<Modal>>,
+    parents: Query<&ChildOf>,
+) {
+    let choice = trigger.target();
+    if !choices.contains(choice) {
+        return;
+    }
+    if let Some(modal) = parents
+        .iter_ancestors(choice)
+        .find(|&ancestor| modals.contains(ancestor))
+    {
+        commands.entity(modal).despawn();
+    }
+}
+
+/// Closes the newest dialog, consuming Escape so menus behind it don't also
+/// go back
+fn cancel_on_back(
+    mut commands: Commands,
+    mut keyboard: ResMut<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+    modals: Query<Entity, With<Modal>>,
+) {
+    let Some(modal) = modals.iter().max_by_key(|modal| modal.index()) else {
+        return;
+    };
+    let pressed = keyboard.clear_just_pressed(KeyCode::Escape)
+        || gamepads
+            .iter()
+            .any(|gamepad| gamepad.just_pressed(GamepadButton::East));
+    if pressed {
+        commands.entity(modal).despawn();
+    }
+}
@@ -3,17 +3,27 @@
 // Unused utilities may trigger this lints undesirably.
 #![allow(dead_code)]
 
+pub mod dialog;
 pub mod interaction;
+pub mod navigation;
 pub mod palette;
+pub mod tween;
 pub mod widget;
 
 #[allow(unused_imports)]
 pub mod prelude {
-    pub use super::{interaction::InteractionPalette, palette as ui_palette, widget};
+    pub use super::{
+        dialog::confirm_dialog, interaction::InteractionPalette, palette as ui_palette, widget,
+    };
 }
 
 use bevy::prelude::*;
 
 pub(super) fn plugin(app: &mut App) {
-    app.add_plugins(interaction::plugin);
+    app.add_plugins((
+        dialog::plugin,
+        interaction::plugin,
+        navigation::plugin,
+        tween::plugin,
+    ));
 }
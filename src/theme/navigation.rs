@@ -0,0 +1,155 @@
+//! Keyboard and gamepad navigation for any screen built from [`widget`] buttons.
+//!
+//! Arrow keys or the d-pad move focus between the visible buttons in reading
+//! order, and Enter, Space or the gamepad's south button presses the focused
+//! one. Pressing sends the same [`Pointer<Click>`] a mouse would, so button
+//! actions don't need to know how they were activated. While a [`Modal`]
+//! dialog is open, focus stays on the dialog's buttons.
+//!
+//! [`widget`]: super::widget
+
+use std::time::Duration;
+
+use bevy::{
+    picking::{
+        backend::HitData,
+        pointer::{Location, PointerButton, PointerId},
+    },
+    prelude::*,
+    render::camera::NormalizedRenderTarget,
+    ui::Val::*,
+    window::{PrimaryWindow, WindowRef},
+};
+
+use super::dialog::{Modal, outside_modal};
+use crate::{menus::Menu, screens::Screen};
+
+const FOCUS_OUTLINE_WIDTH: f32 = 4.0;
+const FOCUS_OUTLINE_COLOR: Color = Color::srgb(1.0, 0.9, 0.4);
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<Focused>();
+    app.add_systems(
+        Update,
+        (move_focus, press_focused)
+            .chain()
+            // In a match the arrow keys and Space belong to the players
+            .run_if(not(in_state(Screen::Gameplay)).or(not(in_state(Menu::None)))),
+    );
+}
+
+/// Marks the button that has keyboard/gamepad focus
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct Focused;
+
+/// Which way a navigation input moves focus through the buttons
+fn focus_step(keyboard: &ButtonInput<KeyCode>, gamepads: &Query<&Gamepad>) -> Option<isize> {
+    let pressed = |key: KeyCode, button: GamepadButton| {
+        keyboard.just_pressed(key) || gamepads.iter().any(|gamepad| gamepad.just_pressed(button))
+    };
+
+    if pressed(KeyCode::ArrowUp, GamepadButton::DPadUp)
+        || pressed(KeyCode::ArrowLeft, GamepadButton::DPadLeft)
+    {
+        Some(-1)
+    } else if pressed(KeyCode::ArrowDown, GamepadButton::DPadDown)
+        || pressed(KeyCode::ArrowRight, GamepadButton::DPadRight)
+    {
+        Some(1)
+    } else {
+        None
+    }
+}
+
+fn move_focus(
+    mut commands: Commands,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+    buttons: Query<(Entity, &GlobalTransform, &InheritedVisibility, Has<Focused>), With<Button>>,
+    modals: Query<(), With<Modal>>,
+    parents: Query<&ChildOf>,
+) {
+    let Some(step) = focus_step(&keyboard, &gamepads) else {
+        return;
+    };
+
+    // Reading order: top to bottom, then left to right
+    let mut visible = buttons
+        .iter()
+        .filter(|(entity, _, visibility, _)| {
+            visibility.get() && !outside_modal(*entity, &modals, &parents)
+        })
+        .map(|(entity, transform, _, focused)| {
+            (entity, transform.translation().truncate(), focused)
+        })
+        .collect::<Vec<_>>();
+    if visible.is_empty() {
+        return;
+    }
+    visible.sort_by(|(_, a, _), (_, b, _)| a.y.total_cmp(&b.y).then(a.x.total_cmp(&b.x)));
+
+    // Clear focus everywhere, including a button left behind a dialog
+    for (entity, ..) in buttons.iter().filter(|(.., focused)| *focused) {
+        commands.entity(entity).remove::<(Focused, Outline)>();
+    }
+    let next = match visible.iter().position(|(_, _, focused)| *focused) {
+        Some(current) => (current as isize + step).rem_euclid(visible.len() as isize) as usize,
+        // Nothing focused yet: start from the top
+        None => 0,
+    };
+    commands.entity(visible[next].0).insert((
+        Focused,
+        Outline::new(Px(FOCUS_OUTLINE_WIDTH), Px(0.0), FOCUS_OUTLINE_COLOR),
+    ));
+}
+
+/// Clicks the focused button
+fn press_focused(
+    mut commands: Commands,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+    focused: Query<(Entity, &GlobalTransform, &InheritedVisibility), (With<Button>, With<Focused>)>,
+    window: Query<Entity, With<PrimaryWindow>>,
+    camera: Query<Entity, With<Camera>>,
+    modals: Query<(), With<Modal>>,
+    parents: Query<&ChildOf>,
+) {
+    let pressed = keyboard.any_just_pressed([KeyCode::Enter, KeyCode::Space])
+        || gamepads
+            .iter()
+            .any(|gamepad| gamepad.just_pressed(GamepadButton::South));
+    if !pressed {
+        return;
+    }
+    let Ok((button, transform, visibility)) = focused.single() else {
+        return;
+    };
+    if !visibility.get() || outside_modal(button, &modals, &parents) {
+        return;
+    }
+    let (Ok(window), Some(camera)) = (window.single(), camera.iter().next()) else {
+        return;
+    };
+    let Some(target) = WindowRef::Primary.normalize(Some(window)) else {
+        return;
+    };
+
+    let position = transform.translation().truncate();
+    commands.trigger_targets(
+        Pointer::new(
+            PointerId::Mouse,
+            Location {
+                target: NormalizedRenderTarget::Window(target),
+                position,
+            },
+            button,
+            Click {
+                button: PointerButton::Primary,
+                hit: HitData::new(camera, 0.0, Some(position.extend(0.0)), None),
+                duration: Duration::ZERO,
+            },
+        ),
+        button,
+    );
+}
@@ -0,0 +1,295 @@
+//! Lightweight tweens for UI polish.
+//!
+//! Add a tween component to an entity and it animates from `from` to `to`
+//! over its duration with the chosen easing, then removes itself, leaving the
+//! end value in place. Position and scale tweens count as decorative motion
+//! and jump straight to their end value with [`ReducedMotion`] on.
+//!
+//! A [`Toast`] is a transient message: it stays up for its lifetime, fades
+//! out at the end of it and despawns, so one-off notices don't each need
+//! their own timer and cleanup system.
+
+use bevy::{
+    math::curve::{Curve, EaseFunction},
+    prelude::*,
+};
+
+use crate::{AppSystems, PausableSystems, game::accessibility::ReducedMotion};
+
+/// How long a toast takes to fade out at the end of its lifetime
+const TOAST_FADE_OUT: f32 = 0.25; // seconds
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<TweenOffset>();
+    app.register_type::<TweenScale>();
+    app.register_type::<TweenAlpha>();
+    app.register_type::<Pulse>();
+    app.register_type::<Toast>();
+    app.add_systems(
+        Update,
+        (
+            animate_offsets,
+            animate_scales,
+            animate_alphas,
+            animate_pulses,
+            tick_toasts
+                .in_set(AppSystems::TickTimers)
+                .in_set(PausableSystems),
+        ),
+    );
+}
+
+/// Eased progress from 0 to 1 over a fixed duration
+#[derive(Debug, Clone, Reflect)]
+pub struct Tween {
+    timer: Timer,
+    ease: EaseFunction,
+}
+
+impl Tween {
+    pub fn new(secs: f32, ease: EaseFunction) -> Self {
+        Self {
+            timer: Timer::from_seconds(secs, TimerMode::Once),
+            ease,
+        }
+    }
+
+    /// Advances the tween and returns the eased progress
+    pub fn tick(&mut self, time: &Time) -> f32 {
+        self.timer.tick(time.delta());
+        self.ease.sample_clamped(self.timer.fraction())
+    }
+
+    pub fn finished(&self) -> bool {
+        self.timer.finished()
+    }
+}
+
+/// Slides a UI node by tweening its `left`/`top` offsets, in pixels
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component)]
+pub struct TweenOffset {
+    pub tween: Tween,
+    pub from: Vec2,
+    pub to: Vec2,
+}
+
+impl TweenOffset {
+    /// Slides in from `from` to where the node would normally sit
+    pub fn slide_in(from: Vec2, secs: f32) -> Self {
+        Self {
+            tween: Tween::new(secs, EaseFunction::CubicOut),
+            from,
+            to: Vec2::ZERO,
+        }
+    }
+}
+
+/// Scales an entity uniformly
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component)]
+pub struct TweenScale {
+    pub tween: Tween,
+    pub from: f32,
+    pub to: f32,
+}
+
+impl TweenScale {
+    /// Pops up to `scale` and springs back to normal size
+    pub fn punch(scale: f32, secs: f32) -> Self {
+        Self {
+            tween: Tween::new(secs, EaseFunction::ElasticOut),
+            from: scale,
+            to: 1.0,
+        }
+    }
+}
+
+/// Fades an entity's text and background alpha
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component)]
+pub struct TweenAlpha {
+    pub tween: Tween,
+    pub from: f32,
+    pub to: f32,
+}
+
+impl TweenAlpha {
+    pub fn fade_in(secs: f32) -> Self {
+        Self {
+            tween: Tween::new(secs, EaseFunction::QuadraticOut),
+            from: 0.0,
+            to: 1.0,
+        }
+    }
+}
+
+/// Flashes text to `color` and fades it back to `rest`
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component)]
+pub struct Pulse {
+    pub tween: Tween,
+    pub color: Color,
+    pub rest: Color,
+}
+
+impl Pulse {
+    pub fn new(color: Color, rest: Color, secs: f32) -> Self {
+        Self {
+            tween: Tween::new(secs, EaseFunction::QuadraticIn),
+            color,
+            rest,
+        }
+    }
+}
+
+/// Despawns an entity after `secs`, fading its text and background out
+/// over the last moment
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component)]
+pub struct Toast {
+    lifetime: Timer,
+    fade_out: f32,
+    fading: bool,
+}
+
+impl Toast {
+    pub fn new(secs: f32) -> Self {
+        Self {
+            lifetime: Timer::from_seconds(secs, TimerMode::Once),
+            fade_out: TOAST_FADE_OUT.min(secs),
+            fading: false,
+        }
+    }
+
+    /// Shows the toast for its full lifetime again, e.g. after its message changed
+    pub fn restart(&mut self) {
+        self.lifetime.reset();
+    }
+
+    /// Seconds since the toast was shown or restarted
+    pub fn age(&self) -> f32 {
+        self.lifetime.elapsed_secs()
+    }
+
+    /// Whether the toast has started fading out
+    pub fn is_fading(&self) -> bool {
+        self.fading
+    }
+}
+
+fn animate_offsets(
+    mut commands: Commands,
+    time: Res<Time>,
+    reduced_motion: Res<ReducedMotion>,
+    mut offsets: Query<(Entity, &mut TweenOffset, &mut Node)>,
+) {
+    for (entity, mut offset, mut node) in &mut offsets {
+        let progress = if reduced_motion.0 {
+            1.0
+        } else {
+            offset.tween.tick(&time)
+        };
+        let position = offset.from.lerp(offset.to, progress);
+        node.left = Val::Px(position.x);
+        node.top = Val::Px(position.y);
+        if reduced_motion.0 || offset.tween.finished() {
+            commands.entity(entity).remove::<TweenOffset>();
+        }
+    }
+}
+
+fn animate_scales(
+    mut commands: Commands,
+    time: Res<Time>,
+    reduced_motion: Res<ReducedMotion>,
+    mut scales: Query<(Entity, &mut TweenScale, &mut Transform)>,
+) {
+    for (entity, mut scale, mut transform) in &mut scales {
+        let progress = if reduced_motion.0 {
+            1.0
+        } else {
+            scale.tween.tick(&time)
+        };
+        transform.scale = Vec3::splat(scale.from + (scale.to - scale.from) * progress);
+        if reduced_motion.0 || scale.tween.finished() {
+            commands.entity(entity).remove::<TweenScale>();
+        }
+    }
+}
+
+fn animate_alphas(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut alphas: Query<(
+        Entity,
+        &mut TweenAlpha,
+        Option<&mut TextColor>,
+        Option<&mut BackgroundColor>,
+    )>,
+) {
+    for (entity, mut alpha, text_color, background) in &mut alphas {
+        let progress = alpha.tween.tick(&time);
+        let value = alpha.from + (alpha.to - alpha.from) * progress;
+        if let Some(mut text_color) = text_color {
+            text_color.0.set_alpha(value);
+        }
+        if let Some(mut background) = background {
+            background.0.set_alpha(value);
+        }
+        if alpha.tween.finished() {
+            commands.entity(entity).remove::<TweenAlpha>();
+        }
+    }
+}
+
+fn animate_pulses(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut pulses: Query<(Entity, &mut Pulse, &mut TextColor)>,
+) {
+    for (entity, mut pulse, mut text_color) in &mut pulses {
+        let progress = pulse.tween.tick(&time);
+        text_color.0 = pulse.color.mix(&pulse.rest, progress);
+        if pulse.tween.finished() {
+            commands.entity(entity).remove::<Pulse>();
+        }
+    }
+}
+
+fn tick_toasts(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut toasts: Query<(
+        Entity,
+        &mut Toast,
+        Option<&mut TextColor>,
+        Option<&mut BackgroundColor>,
+    )>,
+) {
+    for (entity, mut toast, text_color, background) in &mut toasts {
+        toast.lifetime.tick(time.delta());
+        if toast.lifetime.finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        let remaining = toast.lifetime.remaining_secs();
+        let alpha = if remaining < toast.fade_out {
+            toast.fading = true;
+            remaining / toast.fade_out
+        } else if toast.fading {
+            // Restarted part-way through fading out
+            toast.fading = false;
+            1.0
+        } else {
+            continue;
+        };
+        if let Some(mut text_color) = text_color {
+            text_color.0.set_alpha(alpha);
+        }
+        if let Some(mut background) = background {
+            background.0.set_alpha(alpha);
+        }
+    }
+}
@@ -0,0 +1,225 @@
+//! Single-elimination tournaments for local parties.
+//!
+//! A [`Tournament`] resource holds the bracket while one is running. Matches are
+//! played one after another on the regular gameplay screen, with the bracket
+//! screen shown in between. The bracket is saved after every result so a
+//! tournament survives quitting halfway through.
+
+use bevy::{input::common_conditions::input_just_pressed, prelude::*};
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    game::{GamePhase, player::PlayerSide, rules::MatchRules, scoring::Score},
+    screens::{Screen, transition::Transition},
+    storage,
+};
+
+pub const MIN_ENTRANTS: usize = 4;
+pub const MAX_ENTRANTS: usize = 8;
+
+const SAVE_KEY: &str = "tournament";
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        OnEnter(GamePhase::GameOver),
+        record_match_result.run_if(resource_exists::<Tournament>),
+    );
+    app.add_systems(
+        Update,
+        (
+            return_to_bracket.run_if(input_just_pressed(KeyCode::Space)),
+            leave_tournament.run_if(input_just_pressed(KeyCode::Escape)),
+        )
+            .run_if(
+                in_state(GamePhase::GameOver)
+                    .and(in_state(Transition::Idle))
+                    .and(resource_exists::<Tournament>),
+            ),
+    );
+    // Quitting to the title from anywhere (e.g. the pause menu) leaves the
+    // tournament; it stays saved and can be resumed from the menu.
+    app.add_systems(OnEnter(Screen::Title), forget_tournament);
+}
+
+/// A running single-elimination bracket
+#[derive(Resource, Serialize, Deserialize, Clone, Debug)]
+pub struct Tournament {
+    /// Entrant names, indexed by the ids stored in each match
+    pub entrants: Vec<String>,
+    /// Matches per round, from the first round to the final
+    pub rounds: Vec<Vec<BracketMatch>>,
+}
+
+/// One match in the bracket. `None` means the slot is still undecided, or a
+/// bye in the first round.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default)]
+pub struct BracketMatch {
+    pub left: Option<usize>,
+    pub right: Option<usize>,
+    pub winner: Option<usize>,
+}
+
+impl Tournament {
+    /// Builds a randomly seeded bracket. Entrant counts that aren't a power of
+    /// two get byes, each paired against a real entrant.
+    pub fn new(entrants: Vec<String>) -> Self {
+        let size = entrants.len().next_power_of_two().max(2);
+
+        let mut seats: Vec<Option<usize>> = (0..entrants.len()).map(Some).collect();
+        seats.shuffle(&mut rand::rng());
+        seats.resize(size, None);
+
+        let mut rounds = vec![
+            (0..size / 2)
+                .map(|i| BracketMatch {
+                    left: seats[i],
+                    right: seats[size - 1 - i],
+                    winner: None,
+                })
+                .collect::<Vec<_>>(),
+        ];
+        let mut matches = size / 4;
+        while matches > 0 {
+            rounds.push(vec![BracketMatch::default(); matches]);
+            matches /= 2;
+        }
+
+        let mut tournament = Self { entrants, rounds };
+        tournament.advance_byes();
+        tournament
+    }
+
+    fn advance_byes(&mut self) {
+        for index in 0..self.rounds[0].len() {
+            let bracket_match = self.rounds[0][index];
+            if let (Some(entrant), None) | (None, Some(entrant)) =
+                (bracket_match.left, bracket_match.right)
+            {
+                self.set_winner(0, index, entrant);
+            }
+        }
+    }
+
+    fn set_winner(&mut self, round: usize, index: usize, entrant: usize) {
+        self.rounds[round][index].winner = Some(entrant);
+
+        if let Some(next_round) = self.rounds.get_mut(round + 1) {
+            let next = &mut next_round[index / 2];
+            if index % 2 == 0 {
+                next.left = Some(entrant);
+            } else {
+                next.right = Some(entrant);
+            }
+        }
+    }
+
+    /// The next match to be played, as `(round, index)`
+    pub fn current_match(&self) -> Option<(usize, usize)> {
+        self.rounds.iter().enumerate().find_map(|(round, matches)| {
+            matches
+                .iter()
+                .position(|m| m.left.is_some() && m.right.is_some() && m.winner.is_none())
+                .map(|index| (round, index))
+        })
+    }
+
+    /// Left and right entrant names in the match being played
+    pub fn current_players(&self) -> Option<[&str; 2]> {
+        let (round, index) = self.current_match()?;
+        let bracket_match = self.rounds[round][index];
+        Some([
+            self.name(bracket_match.left),
+            self.name(bracket_match.right),
+        ])
+    }
+
+    /// Records the result of the current match from the winning side
+    pub fn record_result(&mut self, winner: PlayerSide) {
+        let Some((round, index)) = self.current_match() else {
+            return;
+        };
+        let bracket_match = self.rounds[round][index];
+        let entrant = match winner {
+            PlayerSide::Left => bracket_match.left,
+            PlayerSide::Right => bracket_match.right,
+        };
+        if let Some(entrant) = entrant {
+            self.set_winner(round, index, entrant);
+        }
+    }
+
+    /// The tournament winner, once the final has been played
+    pub fn champion(&self) -> Option<&str> {
+        self.rounds
+            .last()
+            .and_then(|round| round[0].winner)
+            .map(|entrant| self.name(Some(entrant)))
+    }
+
+    /// Display name for a bracket slot
+    pub fn name(&self, entrant: Option<usize>) -> &str {
+        entrant.map_or("-", |entrant| self.entrants[entrant].as_str())
+    }
+
+    /// Human-friendly round title, e.g. "Semifinal"
+    pub fn round_name(&self, round: usize) -> String {
+        match self.rounds.len() - round {
+            1 => "Final".into(),
+            2 => "Semifinals".into(),
+            3 => "Quarterfinals".into(),
+            _ => format!("Round {}", round + 1),
+        }
+    }
+
+    /// Loads the saved tournament, if there is one
+    pub fn load() -> Option<Self> {
+        storage::load(SAVE_KEY)
+    }
+
+    /// Saves the tournament so it can be resumed later
+    pub fn save(&self) {
+        if let Err(error) = storage::save(SAVE_KEY, self) {
+            warn!("Couldn't save tournament: {error}");
+        }
+    }
+
+    /// Removes the saved tournament once it's finished
+    pub fn delete_save() {
+        storage::remove(SAVE_KEY);
+    }
+}
+
+/// Default entrant names for `count` local players
+pub fn entrant_names(count: usize) -> Vec<String> {
+    (1..=count).map(|i| format!("Player {i}")).collect()
+}
+
+fn record_match_result(
+    mut tournament: ResMut<Tournament>,
+    score: Res<Score>,
+    rules: Res<MatchRules>,
+) {
+    let Some(winner) = score.winner(&rules) else {
+        return;
+    };
+    tournament.record_result(winner);
+    tournament.save();
+}
+
+fn return_to_bracket(mut score: ResMut<Score>, mut next_screen: ResMut<NextState<Screen>>) {
+    score.left = 0;
+    score.right = 0;
+    next_screen.set(Screen::Bracket);
+}
+
+/// Leaves the tournament for the title screen; it stays saved for later
+fn leave_tournament(mut score: ResMut<Score>, mut next_screen: ResMut<NextState<Screen>>) {
+    score.left = 0;
+    score.right = 0;
+    next_screen.set(Screen::Title);
+}
+
+fn forget_tournament(mut commands: Commands) {
+    commands.remove_resource::<Tournament>();
+}